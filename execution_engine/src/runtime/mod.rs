@@ -6,6 +6,8 @@ mod externals;
 mod handle_payment_internal;
 mod host_function_flag;
 mod mint_internal;
+#[cfg(feature = "test-support")]
+pub mod print_capture;
 pub mod stack;
 mod utils;
 pub(crate) mod wasm_prep;
@@ -3930,6 +3932,7 @@ where
     fn print(&mut self, text_ptr: u32, text_size: u32) -> Result<(), Trap> {
         let text = self.string_from_mem(text_ptr, text_size)?;
         println!("{}", text); // this println! is intentional
+        print_capture::record(text);
         Ok(())
     }
 
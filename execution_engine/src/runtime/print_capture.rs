@@ -0,0 +1,17 @@
+//! Capture of text printed by contracts through the `casper_print` host function.
+use std::cell::RefCell;
+
+thread_local! {
+    static PRINTED_LINES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records a line printed by a contract executing on the current thread.
+pub(crate) fn record(line: String) {
+    PRINTED_LINES.with(|lines| lines.borrow_mut().push(line));
+}
+
+/// Returns the lines printed by contracts executing on the current thread since the last call,
+/// clearing the captured lines.
+pub fn take_printed_lines() -> Vec<String> {
+    PRINTED_LINES.with(|lines| lines.take())
+}
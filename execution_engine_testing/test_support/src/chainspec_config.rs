@@ -1,12 +1,14 @@
 use std::{
+    collections::BTreeSet,
     convert::TryFrom,
+    fmt::{self, Display, Formatter},
     fs, io,
     path::{Path, PathBuf},
 };
 
 use log::error;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::engine_state::{EngineConfig, EngineConfigBuilder};
 use casper_storage::data_access_layer::GenesisRequest;
@@ -41,13 +43,31 @@ pub enum Error {
         error: io::Error,
     },
     FailedToParseChainspec(toml::de::Error),
+    FailedToSerializeChainspec(toml::ser::Error),
     Validation,
 }
 
+/// A single field whose value differs between two [`ChainspecConfig`]s.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FieldDiff {
+    /// Dotted path of the field as it appears in the chainspec, e.g. `core.auction_delay`.
+    pub path: String,
+    /// Value of the field in the config on which [`ChainspecConfig::diff`] was called.
+    pub left: String,
+    /// Value of the field in the config passed to [`ChainspecConfig::diff`].
+    pub right: String,
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} != {}", self.path, self.left, self.right)
+    }
+}
+
 /// This struct can be parsed from a TOML-encoded chainspec file.  It means that as the
 /// chainspec format changes over versions, as long as we maintain the core config in this form
 /// in the chainspec file, it can continue to be parsed as an `ChainspecConfig`.
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct ChainspecConfig {
     /// CoreConfig
     #[serde(rename = "core")]
@@ -244,6 +264,24 @@ impl ChainspecConfig {
         self.core_config.max_associated_keys
     }
 
+    /// Returns every field whose value differs between `self` and `other`.
+    ///
+    /// Fields are reported by their dotted chainspec path, ordered alphabetically. Ratios are
+    /// compared in their reduced form, so e.g. `[2, 4]` and `[1, 2]` are equal.
+    pub fn diff(&self, other: &ChainspecConfig) -> Result<Vec<FieldDiff>, Error> {
+        let to_toml = |config: &ChainspecConfig| {
+            let mut config = config.clone();
+            config.core_config.round_seigniorage_rate =
+                config.core_config.round_seigniorage_rate.reduced();
+            toml::Value::try_from(config).map_err(Error::FailedToSerializeChainspec)
+        };
+        let left = to_toml(self)?;
+        let right = to_toml(other)?;
+        let mut diffs = Vec::new();
+        collect_field_diffs("", Some(&left), Some(&right), &mut diffs);
+        Ok(diffs)
+    }
+
     /// Returns an engine config.
     pub fn engine_config(&self) -> EngineConfig {
         EngineConfigBuilder::new()
@@ -267,6 +305,38 @@ impl ChainspecConfig {
     }
 }
 
+fn collect_field_diffs(
+    path: &str,
+    left: Option<&toml::Value>,
+    right: Option<&toml::Value>,
+    diffs: &mut Vec<FieldDiff>,
+) {
+    match (left, right) {
+        (Some(toml::Value::Table(left)), Some(toml::Value::Table(right))) => {
+            let keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_field_diffs(&path, left.get(key), right.get(key), diffs);
+            }
+        }
+        (left, right) if left != right => {
+            let format_value = |value: Option<&toml::Value>| {
+                value.map_or_else(|| "<missing>".to_string(), toml::Value::to_string)
+            };
+            diffs.push(FieldDiff {
+                path: path.to_string(),
+                left: format_value(left),
+                right: format_value(right),
+            });
+        }
+        _ => {}
+    }
+}
+
 impl From<ChainspecConfig> for EngineConfig {
     fn from(chainspec_config: ChainspecConfig) -> Self {
         EngineConfigBuilder::new()
@@ -320,9 +390,10 @@ mod tests {
     use std::{convert::TryFrom, path::PathBuf};
 
     use casper_types::GenesisConfig;
+    use num_rational::Ratio;
     use once_cell::sync::Lazy;
 
-    use super::{ChainspecConfig, CHAINSPEC_NAME};
+    use super::{ChainspecConfig, FieldDiff, CHAINSPEC_NAME, CHAINSPEC_SYMLINK};
 
     pub static LOCAL_PATH: Lazy<PathBuf> =
         Lazy::new(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../resources/local/"));
@@ -342,4 +413,55 @@ mod tests {
         let config = GenesisConfig::try_from(chainspec_config).unwrap();
         assert_eq!(config.auction_delay(), 1)
     }
+
+    #[test]
+    fn should_report_every_differing_field() {
+        let chainspec_config = ChainspecConfig::from_chainspec_path(&*CHAINSPEC_SYMLINK).unwrap();
+        assert_eq!(chainspec_config.diff(&chainspec_config).unwrap(), vec![]);
+
+        let mut other = chainspec_config
+            .clone()
+            .with_minimum_delegation_amount(42)
+            .with_wasm_max_stack_height(1234);
+        other.core_config.auction_delay = 5;
+
+        let diffs = chainspec_config.diff(&other).unwrap();
+        let expected = vec![
+            FieldDiff {
+                path: "core.auction_delay".to_string(),
+                left: chainspec_config.core_config.auction_delay.to_string(),
+                right: "5".to_string(),
+            },
+            FieldDiff {
+                path: "core.minimum_delegation_amount".to_string(),
+                left: chainspec_config
+                    .core_config
+                    .minimum_delegation_amount
+                    .to_string(),
+                right: "42".to_string(),
+            },
+            FieldDiff {
+                path: "wasm.v1.max_stack_height".to_string(),
+                left: chainspec_config
+                    .wasm_config
+                    .v1()
+                    .max_stack_height()
+                    .to_string(),
+                right: "1234".to_string(),
+            },
+        ];
+        assert_eq!(diffs, expected);
+    }
+
+    #[test]
+    fn should_compare_reduced_round_seigniorage_rate() {
+        let chainspec_config = ChainspecConfig::from_chainspec_path(&*CHAINSPEC_SYMLINK).unwrap();
+        let rate = chainspec_config.core_config.round_seigniorage_rate;
+
+        let mut unreduced = chainspec_config.clone();
+        unreduced.core_config.round_seigniorage_rate =
+            Ratio::new_raw(rate.numer() * 2, rate.denom() * 2);
+
+        assert_eq!(chainspec_config.diff(&unreduced).unwrap(), vec![]);
+    }
 }
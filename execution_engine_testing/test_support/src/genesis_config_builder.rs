@@ -1,14 +1,16 @@
 //! A builder for an [`GenesisConfig`].
 use casper_execution_engine::engine_state::engine_config::DEFAULT_ENABLE_ENTITY;
 use casper_types::{
-    GenesisAccount, GenesisConfig, HoldBalanceHandling, StorageCosts, SystemConfig, WasmConfig,
+    system::auction::DelegationRate, GenesisAccount, GenesisConfig, GenesisValidator,
+    HoldBalanceHandling, Motes, PublicKey, StorageCosts, SystemConfig, WasmConfig,
 };
 use num_rational::Ratio;
 
 use crate::{
-    DEFAULT_AUCTION_DELAY, DEFAULT_GAS_HOLD_BALANCE_HANDLING, DEFAULT_GAS_HOLD_INTERVAL_MILLIS,
-    DEFAULT_GENESIS_TIMESTAMP_MILLIS, DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
-    DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS,
+    DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_AUCTION_DELAY, DEFAULT_GAS_HOLD_BALANCE_HANDLING,
+    DEFAULT_GAS_HOLD_INTERVAL_MILLIS, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_UNBONDING_DELAY,
+    DEFAULT_VALIDATOR_SLOTS,
 };
 
 /// A builder for an [`GenesisConfig`].
@@ -39,11 +41,53 @@ impl GenesisConfigBuilder {
     }
 
     /// Sets the genesis accounts.
+    ///
+    /// Replaces any accounts added so far, including validators and delegators.
     pub fn with_accounts(mut self, accounts: Vec<GenesisAccount>) -> Self {
         self.accounts = Some(accounts);
         self
     }
 
+    /// Adds a genesis validator account bonding `bonded_amount` at `delegation_rate`.
+    ///
+    /// The account is funded with [`DEFAULT_ACCOUNT_INITIAL_BALANCE`] on top of its stake.
+    pub fn with_validator(
+        mut self,
+        public_key: PublicKey,
+        bonded_amount: Motes,
+        delegation_rate: DelegationRate,
+    ) -> Self {
+        let validator = GenesisValidator::new(bonded_amount, delegation_rate);
+        self.accounts
+            .get_or_insert_with(Vec::new)
+            .push(GenesisAccount::account(
+                public_key,
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE),
+                Some(validator),
+            ));
+        self
+    }
+
+    /// Adds a genesis delegator account delegating `amount` to `validator`.
+    ///
+    /// The account is funded with [`DEFAULT_ACCOUNT_INITIAL_BALANCE`] on top of its delegation.
+    pub fn with_delegator(
+        mut self,
+        validator: PublicKey,
+        delegator: PublicKey,
+        amount: Motes,
+    ) -> Self {
+        self.accounts
+            .get_or_insert_with(Vec::new)
+            .push(GenesisAccount::delegator(
+                validator,
+                delegator,
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE),
+                amount,
+            ));
+        self
+    }
+
     /// Sets the Wasm config options.
     pub fn with_wasm_config(mut self, wasm_config: WasmConfig) -> Self {
         self.wasm_config = Some(wasm_config);
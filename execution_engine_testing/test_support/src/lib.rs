@@ -30,13 +30,13 @@ use casper_types::{
     SystemConfig, WasmConfig, WasmV1Config, U512,
 };
 
-pub use chainspec_config::{ChainspecConfig, CHAINSPEC_SYMLINK};
+pub use chainspec_config::{ChainspecConfig, FieldDiff, CHAINSPEC_SYMLINK};
 pub use deploy_item_builder::DeployItemBuilder;
 pub use execute_request_builder::{ExecuteRequest, ExecuteRequestBuilder};
 pub use step_request_builder::StepRequestBuilder;
 pub use transfer_request_builder::TransferRequestBuilder;
 pub use upgrade_request_builder::UpgradeRequestBuilder;
-pub use wasm_test_builder::{EntityWithNamedKeys, LmdbWasmTestBuilder, WasmTestBuilder};
+pub use wasm_test_builder::{EntityWithNamedKeys, GasLedger, LmdbWasmTestBuilder, WasmTestBuilder};
 
 /// Default number of validator slots.
 pub const DEFAULT_VALIDATOR_SLOTS: u32 = 5;
@@ -195,49 +195,39 @@ pub static SYSTEM_ADDR: Lazy<AccountHash> = Lazy::new(|| PublicKey::System.to_ac
 #[cfg(test)]
 mod tests {
     use super::*;
-    use casper_types::MessageLimits;
+    use casper_types::{MessageLimits, TimeDiff};
 
     #[test]
     fn defaults_should_match_production_chainspec_values() {
         let production = ChainspecConfig::from_chainspec_path(&*CHAINSPEC_SYMLINK).unwrap();
+
+        let mut defaults = production.clone();
         // No need to test `CoreConfig::validator_slots`.
-        assert_eq!(production.core_config.auction_delay, DEFAULT_AUCTION_DELAY);
-        assert_eq!(
-            production.core_config.locked_funds_period.millis(),
-            DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS
-        );
-        assert_eq!(
-            production.core_config.unbonding_delay,
-            DEFAULT_UNBONDING_DELAY
-        );
-        assert_eq!(
-            production.core_config.round_seigniorage_rate.reduced(),
-            DEFAULT_ROUND_SEIGNIORAGE_RATE.reduced()
-        );
-        assert_eq!(
-            production.core_config.max_associated_keys,
-            DEFAULT_MAX_ASSOCIATED_KEYS
-        );
-        assert_eq!(
-            production.core_config.max_runtime_call_stack_height,
-            DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT
+        defaults.core_config.auction_delay = DEFAULT_AUCTION_DELAY;
+        defaults.core_config.locked_funds_period =
+            TimeDiff::from_millis(DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS);
+        defaults.core_config.unbonding_delay = DEFAULT_UNBONDING_DELAY;
+        defaults.core_config.round_seigniorage_rate = DEFAULT_ROUND_SEIGNIORAGE_RATE;
+        defaults.core_config.max_associated_keys = DEFAULT_MAX_ASSOCIATED_KEYS;
+        defaults.core_config.max_runtime_call_stack_height = DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT;
+        defaults.core_config.minimum_delegation_amount = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+        defaults.core_config.maximum_delegation_amount = DEFAULT_MAXIMUM_DELEGATION_AMOUNT;
+        defaults.wasm_config = WasmConfig::new(
+            MessageLimits::default(),
+            WasmV1Config::default(),
+            *production.wasm_config.v2(),
         );
-        assert_eq!(
-            production.core_config.minimum_delegation_amount,
-            DEFAULT_MINIMUM_DELEGATION_AMOUNT
+        defaults.system_costs_config = SystemConfig::default();
+
+        let diffs = production.diff(&defaults).unwrap();
+        assert!(
+            diffs.is_empty(),
+            "defaults differ from production chainspec values (production != default):\n{}",
+            diffs
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
         );
-        assert_eq!(
-            production.core_config.maximum_delegation_amount,
-            DEFAULT_MAXIMUM_DELEGATION_AMOUNT
-        );
-
-        assert_eq!(
-            production.wasm_config.messages_limits(),
-            MessageLimits::default()
-        );
-
-        assert_eq!(production.wasm_config.v1(), &WasmV1Config::default());
-
-        assert_eq!(production.system_costs_config, SystemConfig::default());
     }
 }
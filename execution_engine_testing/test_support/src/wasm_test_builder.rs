@@ -16,8 +16,12 @@ use num_rational::Ratio;
 use num_traits::{CheckedMul, Zero};
 use tempfile::TempDir;
 
-use casper_execution_engine::engine_state::{
-    EngineConfig, Error, ExecutionEngineV1, WasmV1Request, WasmV1Result, DEFAULT_MAX_QUERY_DEPTH,
+use casper_execution_engine::{
+    engine_state::{
+        EngineConfig, Error, ExecutionEngineV1, WasmV1Request, WasmV1Result,
+        DEFAULT_MAX_QUERY_DEPTH,
+    },
+    runtime::print_capture,
 };
 use casper_storage::{
     data_access_layer::{
@@ -51,7 +55,7 @@ use casper_types::{
     addressable_entity::{EntityKindTag, MessageTopics, NamedKeyAddr},
     bytesrepr::{self, FromBytes},
     contracts::{ContractHash, NamedKeys},
-    execution::Effects,
+    execution::{Effects, TransformKindV2},
     global_state::TrieMerkleProof,
     runtime_args,
     system::{
@@ -64,17 +68,18 @@ use casper_types::{
         AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
     AccessRights, Account, AddressableEntity, AddressableEntityHash, AuctionCosts, BlockGlobalAddr,
-    BlockTime, ByteCode, ByteCodeAddr, ByteCodeHash, CLTyped, CLValue, Contract, Digest,
-    EntityAddr, EntryPoints, EraId, FeeHandling, Gas, HandlePaymentCosts, HoldBalanceHandling,
-    InitiatorAddr, Key, KeyTag, MintCosts, Motes, Package, PackageHash, Phase,
+    BlockTime, ByteCode, ByteCodeAddr, ByteCodeHash, CLTyped, CLValue, Contract, DictionaryItemKey,
+    Digest, EntityAddr, EntryPoints, EraId, FeeHandling, Gas, GenesisAccount, HandlePaymentCosts,
+    HoldBalanceHandling, InitiatorAddr, Key, KeyTag, MintCosts, Motes, Package, PackageHash, Phase,
     ProtocolUpgradeConfig, ProtocolVersion, PublicKey, RefundHandling, StoredValue,
-    SystemHashRegistry, TransactionHash, TransactionV1Hash, URef, OS_PAGE_SIZE, U512,
+    SystemHashRegistry, TransactionHash, TransactionV1Hash, URef, WasmConfig, OS_PAGE_SIZE, U512,
 };
 
 use crate::{
     chainspec_config::{ChainspecConfig, CHAINSPEC_SYMLINK},
-    ExecuteRequest, ExecuteRequestBuilder, StepRequestBuilder, DEFAULT_GAS_PRICE,
-    DEFAULT_PROPOSER_ADDR, DEFAULT_PROTOCOL_VERSION, SYSTEM_ADDR,
+    ExecuteRequest, ExecuteRequestBuilder, StepRequestBuilder, DEFAULT_ACCOUNTS,
+    DEFAULT_BLOCK_TIME, DEFAULT_GAS_PRICE, DEFAULT_PROPOSER_ADDR, DEFAULT_PROTOCOL_VERSION,
+    SYSTEM_ADDR, TIMESTAMP_MILLIS_INCREMENT,
 };
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
@@ -88,6 +93,20 @@ pub(crate) const DEFAULT_MAX_READERS: u32 = 512;
 /// This is appended to the data dir path provided to the `LmdbWasmTestBuilder`".
 const GLOBAL_STATE_DIR: &str = "global_state";
 
+/// Evaluates `$body` with `$state` bound to the scratch global state while the builder is in
+/// scratch mode, or to the builder's data access layer otherwise.
+macro_rules! with_active_state {
+    ($builder:expr, |$state:ident| $body:expr) => {
+        match $builder.active_scratch_state() {
+            Some($state) => $body,
+            None => {
+                let $state = $builder.data_access_layer.as_ref();
+                $body
+            }
+        }
+    };
+}
+
 /// A wrapper structure that groups an entity alongside its namedkeys.
 #[derive(Debug)]
 pub struct EntityWithNamedKeys {
@@ -117,6 +136,39 @@ impl EntityWithNamedKeys {
     }
 }
 
+/// Running total of the gas consumed by the executions run through a [`WasmTestBuilder`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasLedger {
+    total_gas_spent: Gas,
+    exec_count: usize,
+}
+
+impl GasLedger {
+    /// Adds the gas consumed by a single execution.
+    pub fn record(&mut self, gas: Gas) {
+        self.total_gas_spent = self
+            .total_gas_spent
+            .checked_add(gas)
+            .expect("total gas spent should not overflow");
+        self.exec_count += 1;
+    }
+
+    /// Returns the total gas consumed by the recorded executions.
+    pub fn total_gas_spent(&self) -> Gas {
+        self.total_gas_spent
+    }
+
+    /// Returns the number of recorded executions.
+    pub fn exec_count(&self) -> usize {
+        self.exec_count
+    }
+
+    /// Forgets all recorded executions.
+    pub fn reset(&mut self) {
+        *self = GasLedger::default();
+    }
+}
+
 /// Builder for simple WASM test
 pub struct WasmTestBuilder<S> {
     /// Data access layer.
@@ -127,6 +179,8 @@ pub struct WasmTestBuilder<S> {
     /// The chainspec.
     chainspec: ChainspecConfig,
     exec_results: Vec<WasmV1Result>,
+    /// Lines printed by contracts during the last execution.
+    last_execution_logs: Vec<String>,
     upgrade_results: Vec<ProtocolUpgradeResult>,
     prune_results: Vec<PruneResult>,
     genesis_hash: Option<Digest>,
@@ -137,14 +191,23 @@ pub struct WasmTestBuilder<S> {
     effects: Vec<Effects>,
     /// Genesis effects.
     genesis_effects: Option<Effects>,
+    /// Accounts seeded by the last genesis run.
+    genesis_accounts: Vec<GenesisAccount>,
     /// Cached system account.
     system_account: Option<AddressableEntity>,
     /// Scratch global state used for in-memory execution and commit optimization.
     scratch_global_state: Option<ScratchGlobalState>,
+    /// Whether executions and commits are kept in the scratch global state until
+    /// [`WasmTestBuilder::persist`] is called.
+    scratch_mode: bool,
     /// Global state dir, for implementations that define one.
     global_state_dir: Option<PathBuf>,
     /// Temporary directory, for implementation that uses one.
     temp_dir: Option<Rc<TempDir>>,
+    /// Block time last written to global state by this builder.
+    block_time: Option<BlockTime>,
+    /// Gas consumed by the executions run since the ledger was last reset.
+    gas_ledger: GasLedger,
 }
 
 impl<S: ScratchProvider> WasmTestBuilder<S> {
@@ -160,6 +223,53 @@ impl<S: ScratchProvider> WasmTestBuilder<S> {
         }
         self
     }
+
+    /// Keeps the effects of subsequent executions in memory, in a scratch global state, instead
+    /// of writing every commit to the database.
+    ///
+    /// Queries made through the builder see the scratch state, while the post state hash stays
+    /// unchanged until [`Self::persist`] is called.
+    pub fn with_scratch_state(&mut self) -> &mut Self {
+        if self.scratch_global_state.is_none() {
+            self.scratch_global_state = Some(self.data_access_layer.get_scratch_global_state());
+        }
+        self.scratch_mode = true;
+        self
+    }
+
+    /// Writes the effects committed in scratch mode to the database and updates the post state
+    /// hash. The builder stays in scratch mode.
+    pub fn persist(&mut self) -> &mut Self {
+        self.write_scratch_to_db();
+        if self.scratch_mode {
+            self.scratch_global_state = Some(self.data_access_layer.get_scratch_global_state());
+        }
+        self
+    }
+
+    /// Returns the current post state hash, which can later be passed to [`Self::restore`] to
+    /// roll the builder back to this state.
+    ///
+    /// In scratch mode, effects not yet written by [`Self::persist`] are not part of the
+    /// checkpoint.
+    pub fn checkpoint(&self) -> Digest {
+        self.post_state_hash.expect("Should have post-state hash.")
+    }
+
+    /// Repositions the builder at `state_hash`, typically one returned by [`Self::checkpoint`].
+    ///
+    /// Subsequent executions and commits build on that state, leaving any states committed in
+    /// the meantime untouched. Unpersisted effects held in scratch mode are discarded.
+    pub fn restore(&mut self, state_hash: Digest) -> &mut Self {
+        self.post_state_hash = Some(state_hash);
+        self.scratch_global_state = if self.scratch_mode {
+            Some(self.data_access_layer.get_scratch_global_state())
+        } else {
+            None
+        };
+        self
+    }
+
     /// Flushes the LMDB environment to disk.
     pub fn flush_environment(&self) {
         let request = FlushRequest::new();
@@ -185,7 +295,9 @@ impl<S: ScratchProvider> WasmTestBuilder<S> {
         exec_request.block_info.with_state_hash(state_hash);
 
         // First execute the request against our scratch global state.
+        print_capture::take_printed_lines();
         let execution_result = self.execution_engine.execute(cached_state, exec_request);
+        self.last_execution_logs = print_capture::take_printed_lines();
         let _post_state_hash = cached_state
             .commit_effects(
                 self.post_state_hash.expect("requires a post_state_hash"),
@@ -195,6 +307,7 @@ impl<S: ScratchProvider> WasmTestBuilder<S> {
 
         // Save transforms and execution results for WasmTestBuilder.
         self.effects.push(execution_result.effects().clone());
+        self.gas_ledger.record(execution_result.consumed());
         self.exec_results.push(execution_result);
         self
     }
@@ -207,16 +320,21 @@ impl<S> Clone for WasmTestBuilder<S> {
             execution_engine: Rc::clone(&self.execution_engine),
             chainspec: self.chainspec.clone(),
             exec_results: self.exec_results.clone(),
+            last_execution_logs: self.last_execution_logs.clone(),
             upgrade_results: self.upgrade_results.clone(),
             prune_results: self.prune_results.clone(),
             genesis_hash: self.genesis_hash,
             post_state_hash: self.post_state_hash,
             effects: self.effects.clone(),
             genesis_effects: self.genesis_effects.clone(),
+            genesis_accounts: self.genesis_accounts.clone(),
             system_account: self.system_account.clone(),
             scratch_global_state: None,
+            scratch_mode: false,
             global_state_dir: self.global_state_dir.clone(),
             temp_dir: self.temp_dir.clone(),
+            block_time: self.block_time,
+            gas_ledger: self.gas_ledger,
         }
     }
 }
@@ -330,6 +448,7 @@ impl LmdbWasmTestBuilder {
             execution_engine: Rc::new(engine_state),
             chainspec,
             exec_results: Vec::new(),
+            last_execution_logs: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: None,
@@ -337,9 +456,13 @@ impl LmdbWasmTestBuilder {
             effects: Vec::new(),
             system_account: None,
             genesis_effects: None,
+            genesis_accounts: Vec::new(),
             scratch_global_state: None,
+            scratch_mode: false,
             global_state_dir: Some(global_state_dir),
             temp_dir: None,
+            block_time: None,
+            gas_ledger: GasLedger::default(),
         }
     }
 
@@ -412,16 +535,21 @@ impl LmdbWasmTestBuilder {
             execution_engine: Rc::new(engine_state),
             chainspec,
             exec_results: Vec::new(),
+            last_execution_logs: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: None,
             post_state_hash,
             effects: Vec::new(),
             genesis_effects: None,
+            genesis_accounts: Vec::new(),
             system_account: None,
             scratch_global_state: None,
+            scratch_mode: false,
             global_state_dir: Some(global_state_dir.as_ref().to_path_buf()),
             temp_dir: None,
+            block_time: None,
+            gas_ledger: GasLedger::default(),
         };
 
         builder
@@ -568,13 +696,13 @@ impl LmdbWasmTestBuilder {
     pub fn transfer_and_commit(&mut self, mut transfer_request: TransferRequest) -> &mut Self {
         let pre_state_hash = self.post_state_hash.expect("expected post_state_hash");
         transfer_request.set_state_hash_and_config(pre_state_hash, self.native_runtime_config());
-        let transfer_result = self.data_access_layer.transfer(transfer_request);
+        let transfer_result = with_active_state!(self, |state| state.transfer(transfer_request));
         let gas = Gas::new(self.chainspec.system_costs_config.mint_costs().transfer);
         let execution_result = WasmV1Result::from_transfer_result(transfer_result, gas)
             .expect("transfer result should map to wasm v1 result");
         let effects = execution_result.effects().clone();
         self.effects.push(effects.clone());
-        self.exec_results.push(execution_result);
+        self.push_exec_result(execution_result);
         self.commit_transforms(pre_state_hash, effects);
         self
     }
@@ -584,8 +712,24 @@ impl<S> WasmTestBuilder<S>
 where
     S: StateProvider + CommitProvider,
 {
+    /// Records the result of an execution, adding the gas it consumed to the gas ledger.
+    fn push_exec_result(&mut self, exec_result: WasmV1Result) {
+        self.gas_ledger.record(exec_result.consumed());
+        self.exec_results.push(exec_result);
+    }
+
+    /// Returns the scratch global state if the builder is in scratch mode.
+    fn active_scratch_state(&self) -> Option<&ScratchGlobalState> {
+        if self.scratch_mode {
+            self.scratch_global_state.as_ref()
+        } else {
+            None
+        }
+    }
+
     /// Takes a [`GenesisRequest`], executes the request and returns Self.
     pub fn run_genesis(&mut self, request: GenesisRequest) -> &mut Self {
+        let genesis_accounts = request.config().accounts().to_vec();
         match self.data_access_layer.genesis(request) {
             GenesisResult::Fatal(msg) => {
                 panic!("{}", msg);
@@ -601,11 +745,26 @@ where
                 self.post_state_hash = Some(post_state_hash);
                 self.system_account = self.get_entity_by_account_hash(*SYSTEM_ADDR);
                 self.genesis_effects = Some(effects);
+                self.genesis_accounts = genesis_accounts;
             }
         }
         self
     }
 
+    /// Runs genesis for the default accounts with `wasm_config` swapped into the builder's
+    /// chainspec.
+    ///
+    /// The execution engine is reconfigured as well, so subsequent requests are charged according
+    /// to `wasm_config`.
+    pub fn run_genesis_with_wasm_config(&mut self, wasm_config: WasmConfig) -> &mut Self {
+        let chainspec = self.chainspec.clone().with_wasm_config(wasm_config);
+        let request = chainspec
+            .create_genesis_request(DEFAULT_ACCOUNTS.clone(), DEFAULT_PROTOCOL_VERSION)
+            .expect("must create the request");
+        self.with_chainspec(chainspec);
+        self.run_genesis(request)
+    }
+
     fn query_system_entity_registry(
         &self,
         post_state_hash: Option<Digest>,
@@ -632,6 +791,10 @@ where
             .or(self.post_state_hash)
             .expect("builder must have a post-state hash");
 
+        if let Some(scratch_state) = self.active_scratch_state() {
+            return Self::query_scratch_state(scratch_state, post_state, base_key, path);
+        }
+
         let query_request = QueryRequest::new(post_state, base_key, path.to_vec());
 
         let query_result = self.data_access_layer.query(query_request);
@@ -642,6 +805,60 @@ where
         Err(format!("{:?}", query_result))
     }
 
+    /// Queries the scratch global state for a [`StoredValue`].
+    ///
+    /// The scratch state can't provide proofs over its cached values, so the path is resolved
+    /// through the named keys of accounts, contracts and addressable entities rather than with a
+    /// regular query.
+    fn query_scratch_state(
+        scratch_state: &ScratchGlobalState,
+        post_state: Digest,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<StoredValue, String> {
+        let reader = scratch_state
+            .checkout(post_state)
+            .map_err(|error| format!("{:?}", error))?
+            .ok_or_else(|| format!("root not found: {}", post_state))?;
+        let read = |key: Key| {
+            reader
+                .read(&key)
+                .map_err(|error| format!("{:?}", error))?
+                .ok_or_else(|| format!("value not found under {}", key))
+        };
+
+        let mut key = base_key;
+        let mut value = read(key)?;
+        for name in path {
+            let named_key_not_found = || format!("named key not found: {}", name);
+            key = match (&value, key) {
+                (StoredValue::Account(account), _) => *account
+                    .named_keys()
+                    .get(name)
+                    .ok_or_else(named_key_not_found)?,
+                (StoredValue::Contract(contract), _) => *contract
+                    .named_keys()
+                    .get(name)
+                    .ok_or_else(named_key_not_found)?,
+                // Named keys of addressable entities are stored under their own
+                // `Key::NamedKey` entries.
+                (StoredValue::AddressableEntity(_), Key::AddressableEntity(entity_addr)) => {
+                    let named_key_addr = NamedKeyAddr::new_from_string(entity_addr, name.clone())
+                        .map_err(|error| format!("{:?}", error))?;
+                    match read(Key::NamedKey(named_key_addr)).map_err(|_| named_key_not_found())? {
+                        StoredValue::NamedKey(named_key_value) => named_key_value
+                            .get_key()
+                            .map_err(|error| format!("{:?}", error))?,
+                        other => return Err(format!("expected named key, got {:?}", other)),
+                    }
+                }
+                (other, _) => return Err(format!("can't resolve {} in {:?}", name, other)),
+            };
+            value = read(key)?;
+        }
+        Ok(value)
+    }
+
     /// Retrieves the message topics for the given hash addr.
     pub fn message_topics(
         &self,
@@ -653,7 +870,7 @@ where
             .expect("builder must have a post-state hash");
 
         let request = MessageTopicsRequest::new(post_state, entity_addr);
-        let result = self.data_access_layer.message_topics(request);
+        let result = with_active_state!(self, |state| state.message_topics(request));
         if let MessageTopicsResult::Success { message_topics } = result {
             return Ok(message_topics);
         }
@@ -697,14 +914,18 @@ where
     }
 
     /// Queries state for a dictionary item.
+    ///
+    /// Returns an error if `dictionary_item_key` is longer than
+    /// [`casper_types::DICTIONARY_ITEM_KEY_MAX_LENGTH`].
     pub fn query_dictionary_item(
         &self,
         maybe_post_state: Option<Digest>,
         dictionary_seed_uref: URef,
         dictionary_item_key: &str,
     ) -> Result<StoredValue, String> {
-        let dictionary_address =
-            Key::dictionary(dictionary_seed_uref, dictionary_item_key.as_bytes());
+        let dictionary_address = DictionaryItemKey::new(dictionary_item_key)
+            .map_err(|error| error.to_string())?
+            .to_key(dictionary_seed_uref);
         let empty_path: Vec<String> = vec![];
         self.query(maybe_post_state, dictionary_address, &empty_path)
     }
@@ -724,7 +945,7 @@ where
 
         let query_request = QueryRequest::new(post_state, base_key, path_vec);
 
-        let query_result = self.data_access_layer.query(query_request);
+        let query_result = with_active_state!(self, |state| state.query(query_request));
 
         if let QueryResult::Success { value, proofs } = query_result {
             return Ok((value.deref().clone(), proofs));
@@ -744,9 +965,8 @@ where
         let post_state = maybe_post_state
             .or(self.post_state_hash)
             .expect("builder must have a post-state hash");
-        let result = self
-            .data_access_layer
-            .total_supply(TotalSupplyRequest::new(post_state, protocol_version));
+        let result = with_active_state!(self, |state| state
+            .total_supply(TotalSupplyRequest::new(post_state, protocol_version)));
         if let TotalSupplyResult::Success { total_supply } = result {
             total_supply
         } else {
@@ -765,12 +985,9 @@ where
         let post_state = maybe_post_state
             .or(self.post_state_hash)
             .expect("builder must have a post-state hash");
-        let result =
-            self.data_access_layer
-                .round_seigniorage_rate(RoundSeigniorageRateRequest::new(
-                    post_state,
-                    protocol_version,
-                ));
+        let result = with_active_state!(self, |state| state.round_seigniorage_rate(
+            RoundSeigniorageRateRequest::new(post_state, protocol_version)
+        ));
         if let RoundSeigniorageRateResult::Success { rate } = result {
             rate
         } else {
@@ -856,7 +1073,7 @@ where
             authorization_keys,
             auction_method,
         );
-        self.data_access_layer().bidding(bidding_req)
+        with_active_state!(self, |state| state.bidding(bidding_req))
     }
 
     /// Runs an optional custom payment [`WasmV1Request`] and a session `WasmV1Request`.
@@ -866,11 +1083,12 @@ where
     pub fn exec_wasm_v1(&mut self, mut request: WasmV1Request) -> &mut Self {
         let state_hash = self.post_state_hash.expect("expected post_state_hash");
         request.block_info.with_state_hash(state_hash);
-        let result = self
-            .execution_engine
-            .execute(self.data_access_layer.as_ref(), request);
+        print_capture::take_printed_lines();
+        let result =
+            with_active_state!(self, |state| self.execution_engine.execute(state, request));
+        self.last_execution_logs = print_capture::take_printed_lines();
         let effects = result.effects().clone();
-        self.exec_results.push(result);
+        self.push_exec_result(result);
         self.effects.push(effects);
         self
     }
@@ -878,35 +1096,70 @@ where
     /// Runs an [`ExecuteRequest`].
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
         let mut effects = Effects::new();
+        // Discard lines printed outside of this execution.
+        print_capture::take_printed_lines();
         if let Some(mut payment) = exec_request.custom_payment {
             let state_hash = self.post_state_hash.expect("expected post_state_hash");
             payment.block_info.with_state_hash(state_hash);
-            let payment_result = self
-                .execution_engine
-                .execute(self.data_access_layer.as_ref(), payment);
+            let payment_result =
+                with_active_state!(self, |state| self.execution_engine.execute(state, payment));
             // If executing payment code failed, record this and exit without attempting session
             // execution.
             effects = payment_result.effects().clone();
             let payment_failed = payment_result.error().is_some();
-            self.exec_results.push(payment_result);
+            self.push_exec_result(payment_result);
             if payment_failed {
                 self.effects.push(effects);
+                self.last_execution_logs = print_capture::take_printed_lines();
                 return self;
             }
         }
         let state_hash = self.post_state_hash.expect("expected post_state_hash");
         exec_request.session.block_info.with_state_hash(state_hash);
 
-        let session_result = self
+        let session_result = with_active_state!(self, |state| self
             .execution_engine
-            .execute(self.data_access_layer.as_ref(), exec_request.session);
+            .execute(state, exec_request.session));
         // Cache transformations
         effects.append(session_result.effects().clone());
         self.effects.push(effects);
-        self.exec_results.push(session_result);
+        self.push_exec_result(session_result);
+        self.last_execution_logs = print_capture::take_printed_lines();
         self
     }
 
+    /// Writes `block_time` to global state, then runs an execute request with its block info
+    /// (including that of any custom payment) set to the same block time.
+    pub fn exec_with_block_time(
+        &mut self,
+        mut exec_request: ExecuteRequest,
+        block_time: u64,
+    ) -> &mut Self {
+        let block_time = BlockTime::new(block_time);
+        exec_request.session.block_info.block_time = block_time;
+        if let Some(payment) = exec_request.custom_payment.as_mut() {
+            payment.block_info.block_time = block_time;
+        }
+        self.with_block_time(block_time);
+        self.exec(exec_request)
+    }
+
+    /// Runs an execute request via [`Self::exec_with_block_time`], using a block time
+    /// [`TIMESTAMP_MILLIS_INCREMENT`] later than the last one set on this builder (or than
+    /// [`DEFAULT_BLOCK_TIME`] if none has been set yet).
+    pub fn exec_advancing_time(&mut self, exec_request: ExecuteRequest) -> &mut Self {
+        let block_time = self
+            .block_time
+            .map_or(DEFAULT_BLOCK_TIME, |block_time| block_time.value())
+            + TIMESTAMP_MILLIS_INCREMENT;
+        self.exec_with_block_time(exec_request, block_time)
+    }
+
+    /// Returns the block time last written to global state by this builder, if any.
+    pub fn block_time(&self) -> Option<BlockTime> {
+        self.block_time
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self.post_state_hash.expect("Should have genesis hash");
@@ -919,10 +1172,13 @@ where
     /// Runs a commit request, expects a successful response, and
     /// overwrites existing cached post state hash with a new one.
     pub fn commit_transforms(&mut self, pre_state_hash: Digest, effects: Effects) -> &mut Self {
-        let post_state_hash = self
-            .data_access_layer
-            .commit_effects(pre_state_hash, effects)
-            .expect("should commit");
+        let post_state_hash = match self.active_scratch_state() {
+            Some(scratch_state) => scratch_state.commit_effects(pre_state_hash, effects),
+            None => self
+                .data_access_layer
+                .commit_effects(pre_state_hash, effects),
+        }
+        .expect("should commit");
         self.post_state_hash = Some(post_state_hash);
         self
     }
@@ -934,7 +1190,7 @@ where
 
         let req = ProtocolUpgradeRequest::new(upgrade_config.clone());
 
-        let result = self.data_access_layer.protocol_upgrade(req);
+        let result = with_active_state!(self, |state| state.protocol_upgrade(req));
 
         if let ProtocolUpgradeResult::Success {
             post_state_hash, ..
@@ -972,7 +1228,7 @@ where
 
     /// Increments engine state.
     pub fn step(&mut self, step_request: StepRequest) -> StepResult {
-        let step_result = self.data_access_layer.step(step_request);
+        let step_result = with_active_state!(self, |state| state.step(step_request));
 
         if let StepResult::Success {
             post_state_hash, ..
@@ -1033,7 +1289,7 @@ where
             protocol_version,
             block_time.into(),
         );
-        let fee_result = self.data_access_layer.distribute_fees(fee_req);
+        let fee_result = with_active_state!(self, |state| state.distribute_fees(fee_req));
 
         if let FeeResult::Success {
             post_state_hash, ..
@@ -1093,7 +1349,8 @@ where
             transaction_hash,
             handle_fee_mode,
         );
-        let handle_fee_result = self.data_access_layer.handle_fee(handle_fee_request);
+        let handle_fee_result =
+            with_active_state!(self, |state| state.handle_fee(handle_fee_request));
         if let HandleFeeResult::Success { effects, .. } = &handle_fee_result {
             self.commit_transforms(pre_state_hash, effects.clone());
         }
@@ -1163,6 +1420,58 @@ where
         self.effects.clone()
     }
 
+    /// Returns the purses created by the last execution.
+    ///
+    /// A purse is considered newly created if the last effects write both a fresh `Key::URef` and
+    /// a `Key::Balance` under the same address. The returned URefs carry
+    /// [`AccessRights::READ_ADD_WRITE`], as granted to the creator of a purse.
+    pub fn new_purses(&self) -> Vec<URef> {
+        let effects = match self.effects.last() {
+            Some(effects) => effects,
+            None => return Vec::new(),
+        };
+
+        let written_urefs: BTreeSet<_> = effects
+            .transforms()
+            .iter()
+            .filter(|transform| matches!(transform.kind(), TransformKindV2::Write(_)))
+            .filter_map(|transform| match transform.key() {
+                Key::URef(uref) => Some(uref.addr()),
+                _ => None,
+            })
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        effects
+            .transforms()
+            .iter()
+            .filter(|transform| matches!(transform.kind(), TransformKindV2::Write(_)))
+            .filter_map(|transform| match transform.key() {
+                Key::Balance(addr) if written_urefs.contains(addr) && seen.insert(*addr) => {
+                    Some(URef::new(*addr, AccessRights::READ_ADD_WRITE))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the transforms of the last execution keyed by the `Key` they apply to.
+    ///
+    /// Where the effects hold several transforms for the same key, only the last one recorded is
+    /// kept.
+    pub fn last_exec_transforms(&self) -> BTreeMap<Key, TransformKindV2> {
+        self.effects
+            .last()
+            .map(|effects| {
+                effects
+                    .transforms()
+                    .iter()
+                    .map(|transform| (*transform.key(), transform.kind().clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Gets genesis account (if present)
     pub fn get_genesis_account(&self) -> &AddressableEntity {
         self.system_account
@@ -1210,6 +1519,22 @@ where
             .expect("should have genesis transforms")
     }
 
+    /// Returns the account hash and initial balance of every account seeded at genesis.
+    ///
+    /// The virtual system account is not included.
+    pub fn genesis_accounts(&self) -> Vec<(AccountHash, U512)> {
+        self.genesis_accounts
+            .iter()
+            .filter(|genesis_account| !genesis_account.is_system_account())
+            .map(|genesis_account| {
+                (
+                    genesis_account.account_hash(),
+                    genesis_account.balance().value(),
+                )
+            })
+            .collect()
+    }
+
     /// Returns the genesis hash, panics if it can't be found.
     pub fn get_genesis_hash(&self) -> Digest {
         self.genesis_hash
@@ -1242,18 +1567,21 @@ where
     /// Sets blocktime into global state.
     pub fn with_block_time(&mut self, block_time: BlockTime) -> &mut Self {
         if let Some(state_root_hash) = self.post_state_hash {
-            let mut tracking_copy = self
-                .data_access_layer
-                .tracking_copy(state_root_hash)
-                .expect("should not error on checkout")
-                .expect("should checkout tracking copy");
-
-            let cl_value = CLValue::from_t(block_time.value()).expect("should get cl value");
-            tracking_copy.write(
-                Key::BlockGlobal(BlockGlobalAddr::BlockTime),
-                StoredValue::CLValue(cl_value),
-            );
-            self.commit_transforms(state_root_hash, tracking_copy.effects());
+            let effects = with_active_state!(self, |state| {
+                let mut tracking_copy = state
+                    .tracking_copy(state_root_hash)
+                    .expect("should not error on checkout")
+                    .expect("should checkout tracking copy");
+
+                let cl_value = CLValue::from_t(block_time.value()).expect("should get cl value");
+                tracking_copy.write(
+                    Key::BlockGlobal(BlockGlobalAddr::BlockTime),
+                    StoredValue::CLValue(cl_value),
+                );
+                tracking_copy.effects()
+            });
+            self.commit_transforms(state_root_hash, effects);
+            self.block_time = Some(block_time);
         }
 
         self
@@ -1265,17 +1593,19 @@ where
         data: impl Iterator<Item = (Key, StoredValue)>,
     ) -> &mut Self {
         if let Some(state_root_hash) = self.post_state_hash {
-            let mut tracking_copy = self
-                .data_access_layer
-                .tracking_copy(state_root_hash)
-                .expect("should not error on checkout")
-                .expect("should checkout tracking copy");
-
-            for (key, val) in data {
-                tracking_copy.write(key, val);
-            }
+            let effects = with_active_state!(self, |state| {
+                let mut tracking_copy = state
+                    .tracking_copy(state_root_hash)
+                    .expect("should not error on checkout")
+                    .expect("should checkout tracking copy");
+
+                for (key, val) in data {
+                    tracking_copy.write(key, val);
+                }
 
-            self.commit_transforms(state_root_hash, tracking_copy.effects());
+                tracking_copy.effects()
+            });
+            self.commit_transforms(state_root_hash, effects);
         }
         self
     }
@@ -1287,57 +1617,59 @@ where
         interval: u64,
     ) -> &mut Self {
         if let Some(state_root_hash) = self.post_state_hash {
-            let mut tracking_copy = self
-                .data_access_layer
-                .tracking_copy(state_root_hash)
-                .expect("should not error on checkout")
-                .expect("should checkout tracking copy");
-
-            let registry = tracking_copy
-                .get_system_entity_registry()
-                .expect("should have registry");
-            let mint = *registry.get("mint").expect("should have mint");
-            let mint_addr = EntityAddr::new_system(mint);
-            let named_keys = tracking_copy
-                .get_named_keys(mint_addr)
-                .expect("should have named keys");
-
-            let mut address_generator =
-                AddressGenerator::new(state_root_hash.as_ref(), Phase::System);
-
-            // gas handling
-            let uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
-            let stored_value = StoredValue::CLValue(
-                CLValue::from_t(handling.tag()).expect("should turn handling tag into CLValue"),
-            );
-
-            tracking_copy
-                .upsert_uref_to_named_keys(
-                    mint_addr,
-                    MINT_GAS_HOLD_HANDLING_KEY,
-                    &named_keys,
-                    uref,
-                    stored_value,
-                )
-                .expect("should upsert gas handling");
-
-            // gas interval
-            let uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
-            let stored_value = StoredValue::CLValue(
-                CLValue::from_t(interval).expect("should turn gas interval into CLValue"),
-            );
-
-            tracking_copy
-                .upsert_uref_to_named_keys(
-                    mint_addr,
-                    MINT_GAS_HOLD_INTERVAL_KEY,
-                    &named_keys,
-                    uref,
-                    stored_value,
-                )
-                .expect("should upsert gas interval");
-
-            self.commit_transforms(state_root_hash, tracking_copy.effects());
+            let effects = with_active_state!(self, |state| {
+                let mut tracking_copy = state
+                    .tracking_copy(state_root_hash)
+                    .expect("should not error on checkout")
+                    .expect("should checkout tracking copy");
+
+                let registry = tracking_copy
+                    .get_system_entity_registry()
+                    .expect("should have registry");
+                let mint = *registry.get("mint").expect("should have mint");
+                let mint_addr = EntityAddr::new_system(mint);
+                let named_keys = tracking_copy
+                    .get_named_keys(mint_addr)
+                    .expect("should have named keys");
+
+                let mut address_generator =
+                    AddressGenerator::new(state_root_hash.as_ref(), Phase::System);
+
+                // gas handling
+                let uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
+                let stored_value = StoredValue::CLValue(
+                    CLValue::from_t(handling.tag()).expect("should turn handling tag into CLValue"),
+                );
+
+                tracking_copy
+                    .upsert_uref_to_named_keys(
+                        mint_addr,
+                        MINT_GAS_HOLD_HANDLING_KEY,
+                        &named_keys,
+                        uref,
+                        stored_value,
+                    )
+                    .expect("should upsert gas handling");
+
+                // gas interval
+                let uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
+                let stored_value = StoredValue::CLValue(
+                    CLValue::from_t(interval).expect("should turn gas interval into CLValue"),
+                );
+
+                tracking_copy
+                    .upsert_uref_to_named_keys(
+                        mint_addr,
+                        MINT_GAS_HOLD_INTERVAL_KEY,
+                        &named_keys,
+                        uref,
+                        stored_value,
+                    )
+                    .expect("should upsert gas interval");
+
+                tracking_copy.effects()
+            });
+            self.commit_transforms(state_root_hash, effects);
         }
         self
     }
@@ -1357,6 +1689,12 @@ where
         self.exec_results.last().cloned()
     }
 
+    /// Returns the lines printed by contracts through the `print` host function during the last
+    /// execution, including its custom payment if any.
+    pub fn last_execution_logs(&self) -> Vec<String> {
+        self.last_execution_logs.clone()
+    }
+
     /// Returns the owned results of a specific exec.
     pub fn get_exec_result_owned(&self, index: usize) -> Option<WasmV1Result> {
         self.exec_results.get(index).cloned()
@@ -1444,6 +1782,48 @@ where
             .expect("should parse balance into a U512")
     }
 
+    /// Returns the balance of the main purse of the account under `account_hash`, as of
+    /// `state_hash` or the latest post state hash if `None`.
+    ///
+    /// Panics if the account doesn't exist or its main purse balance can't be read.
+    #[track_caller]
+    pub fn account_balance(&self, account_hash: AccountHash, state_hash: Option<Digest>) -> U512 {
+        let main_purse = match self.query(state_hash, Key::Account(account_hash), &[]) {
+            Ok(StoredValue::Account(account)) => account.main_purse(),
+            Ok(StoredValue::CLValue(cl_value)) => {
+                let entity_key = CLValue::into_t::<Key>(cl_value).expect("must have entity key");
+                match self.query(state_hash, entity_key, &[]) {
+                    Ok(StoredValue::AddressableEntity(entity)) => entity.main_purse(),
+                    Ok(other) => panic!(
+                        "expected an addressable entity for account {}, found {}",
+                        account_hash,
+                        other.type_name()
+                    ),
+                    Err(error) => panic!(
+                        "unable to query entity of account {}: {}",
+                        account_hash, error
+                    ),
+                }
+            }
+            Ok(other) => panic!(
+                "expected an account under {}, found {}",
+                account_hash,
+                other.type_name()
+            ),
+            Err(error) => panic!("account {} does not exist: {}", account_hash, error),
+        };
+
+        self.query(state_hash, Key::Balance(main_purse.addr()), &[])
+            .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
+            .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "unable to read main purse balance of account {}: {}",
+                    account_hash, error
+                )
+            })
+    }
+
     /// Returns a `BalanceResult` for a purse, panics if the balance can't be found.
     pub fn get_purse_balance_result_with_proofs(
         &self,
@@ -1460,7 +1840,7 @@ where
             balance_handling,
             proof_handling,
         );
-        self.data_access_layer.balance(request)
+        with_active_state!(self, |state| state.balance(request))
     }
 
     /// Returns a `BalanceResult` for a purse using a `PublicKey`.
@@ -1479,7 +1859,7 @@ where
             balance_handling,
             proof_handling,
         );
-        self.data_access_layer.balance(request)
+        with_active_state!(self, |state| state.balance(request))
     }
 
     /// Gets the purse balance of a proposer.
@@ -1654,6 +2034,25 @@ where
             .unwrap()
     }
 
+    /// Returns the ledger of gas consumed by executions since it was last reset.
+    pub fn gas_ledger(&self) -> &GasLedger {
+        &self.gas_ledger
+    }
+
+    /// Returns the total gas consumed by executions since the gas ledger was last reset.
+    ///
+    /// Custom payment code run as part of an exec counts as a separate execution.
+    pub fn total_gas_spent(&self) -> Gas {
+        self.gas_ledger.total_gas_spent()
+    }
+
+    /// Resets the gas ledger, so that [`Self::total_gas_spent`] only accounts for subsequent
+    /// executions.
+    pub fn reset_gas_ledger(&mut self) -> &mut Self {
+        self.gas_ledger.reset();
+        self
+    }
+
     /// Assert that last error is the expected one.
     ///
     /// NOTE: we're using string-based representation for checking equality
@@ -1669,7 +2068,7 @@ where
     pub fn get_era_validators(&mut self) -> EraValidators {
         let state_hash = self.get_post_state_hash();
         let request = EraValidatorsRequest::new(state_hash);
-        let result = self.data_access_layer.era_validators(request);
+        let result = with_active_state!(self, |state| state.era_validators(request));
 
         if let EraValidatorsResult::Success { era_validators } = result {
             era_validators
@@ -1688,7 +2087,7 @@ where
     pub fn get_bids(&mut self) -> Vec<BidKind> {
         let get_bids_request = BidsRequest::new(self.get_post_state_hash());
 
-        let get_bids_result = self.data_access_layer.bids(get_bids_request);
+        let get_bids_result = with_active_state!(self, |state| state.bids(get_bids_request));
 
         get_bids_result.into_option().unwrap()
     }
@@ -1714,135 +2113,129 @@ where
     pub fn get_named_keys(&self, entity_addr: EntityAddr) -> NamedKeys {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        tracking_copy
-            .get_named_keys(entity_addr)
-            .expect("should have named keys")
+            tracking_copy
+                .get_named_keys(entity_addr)
+                .expect("should have named keys")
+        })
     }
 
     /// Gets [`BTreeMap<UnbondKind, Unbond>`].
     pub fn get_unbonds(&mut self) -> BTreeMap<UnbondKind, Vec<Unbond>> {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        let reader = tracking_copy.reader();
+            let reader = tracking_copy.reader();
 
-        let unbond_keys = reader
-            .keys_with_prefix(&[KeyTag::BidAddr as u8])
-            .unwrap_or_default();
+            let unbond_keys = reader
+                .keys_with_prefix(&[KeyTag::BidAddr as u8])
+                .unwrap_or_default();
 
-        let mut ret = BTreeMap::new();
+            let mut ret = BTreeMap::new();
 
-        for key in unbond_keys.into_iter() {
-            if let Ok(Some(StoredValue::BidKind(BidKind::Unbond(unbond)))) = reader.read(&key) {
-                let unbond_kind = unbond.unbond_kind();
-                match ret.get_mut(unbond_kind) {
-                    None => {
-                        let _ = ret.insert(unbond_kind.clone(), vec![*unbond]);
-                    }
-                    Some(unbonds) => unbonds.push(*unbond),
-                };
+            for key in unbond_keys.into_iter() {
+                if let Ok(Some(StoredValue::BidKind(BidKind::Unbond(unbond)))) = reader.read(&key) {
+                    let unbond_kind = unbond.unbond_kind();
+                    match ret.get_mut(unbond_kind) {
+                        None => {
+                            let _ = ret.insert(unbond_kind.clone(), vec![*unbond]);
+                        }
+                        Some(unbonds) => unbonds.push(*unbond),
+                    };
+                }
             }
-        }
 
-        ret
+            ret
+        })
     }
 
     /// Retrieve the bid for a validator by their public key.
     pub fn get_validator_bid(&mut self, validator_public_key: PublicKey) -> Option<ValidatorBid> {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        let reader = tracking_copy.reader();
+            let reader = tracking_copy.reader();
 
-        let validator_keys = reader
-            .keys_with_prefix(&[KeyTag::BidAddr as u8, BidAddrTag::Validator as u8])
-            .unwrap_or_default();
+            let validator_keys = reader
+                .keys_with_prefix(&[KeyTag::BidAddr as u8, BidAddrTag::Validator as u8])
+                .unwrap_or_default();
 
-        for key in validator_keys.into_iter() {
-            if let Ok(Some(StoredValue::BidKind(BidKind::Validator(bid)))) = reader.read(&key) {
-                if bid.validator_public_key() == &validator_public_key {
-                    return Some(*bid);
+            for key in validator_keys.into_iter() {
+                if let Ok(Some(StoredValue::BidKind(BidKind::Validator(bid)))) = reader.read(&key) {
+                    if bid.validator_public_key() == &validator_public_key {
+                        return Some(*bid);
+                    }
                 }
             }
-        }
 
-        None
+            None
+        })
     }
 
     /// Gets [`BTreeMap<AccountHash, Vec<UnbondingPurse>>`].
     pub fn get_unbonding_purses(&mut self) -> BTreeMap<AccountHash, Vec<UnbondingPurse>> {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        let reader = tracking_copy.reader();
+            let reader = tracking_copy.reader();
 
-        let unbond_keys = reader
-            .keys_with_prefix(&[KeyTag::Unbond as u8])
-            .unwrap_or_default();
+            let unbond_keys = reader
+                .keys_with_prefix(&[KeyTag::Unbond as u8])
+                .unwrap_or_default();
 
-        let mut ret = BTreeMap::new();
+            let mut ret = BTreeMap::new();
 
-        for key in unbond_keys.into_iter() {
-            let read_result = reader.read(&key);
-            if let (Key::Unbond(account_hash), Ok(Some(StoredValue::Unbonding(unbonding_purses)))) =
-                (key, read_result)
-            {
-                ret.insert(account_hash, unbonding_purses);
+            for key in unbond_keys.into_iter() {
+                let read_result = reader.read(&key);
+                if let (
+                    Key::Unbond(account_hash),
+                    Ok(Some(StoredValue::Unbonding(unbonding_purses))),
+                ) = (key, read_result)
+                {
+                    ret.insert(account_hash, unbonding_purses);
+                }
             }
-        }
 
-        ret
+            ret
+        })
     }
 
     /// Gets [`WithdrawPurses`].
     pub fn get_withdraw_purses(&mut self) -> WithdrawPurses {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        let reader = tracking_copy.reader();
+            let reader = tracking_copy.reader();
 
-        let withdraws_keys = reader
-            .keys_with_prefix(&[KeyTag::Withdraw as u8])
-            .unwrap_or_default();
+            let withdraws_keys = reader
+                .keys_with_prefix(&[KeyTag::Withdraw as u8])
+                .unwrap_or_default();
 
-        let mut ret = BTreeMap::new();
+            let mut ret = BTreeMap::new();
 
-        for key in withdraws_keys.into_iter() {
-            let read_result = reader.read(&key);
-            if let (Key::Withdraw(account_hash), Ok(Some(StoredValue::Withdraw(withdraw_purses)))) =
-                (key, read_result)
-            {
-                ret.insert(account_hash, withdraw_purses);
+            for key in withdraws_keys.into_iter() {
+                let read_result = reader.read(&key);
+                if let (
+                    Key::Withdraw(account_hash),
+                    Ok(Some(StoredValue::Withdraw(withdraw_purses))),
+                ) = (key, read_result)
+                {
+                    ret.insert(account_hash, withdraw_purses);
+                }
             }
-        }
 
-        ret
+            ret
+        })
     }
 
     /// Gets all `[Key::Balance]`s in global state.
@@ -1857,30 +2250,26 @@ where
     ) -> Result<Vec<Key>, casper_storage::global_state::error::Error> {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        let reader = tracking_copy.reader();
+            let reader = tracking_copy.reader();
 
-        reader.keys_with_prefix(&[tag as u8])
+            reader.keys_with_prefix(&[tag as u8])
+        })
     }
 
     /// Gets all entry points for a given entity
     pub fn get_entry_points(&self, entity_addr: EntityAddr) -> EntryPoints {
         let state_root_hash = self.get_post_state_hash();
 
-        let tracking_copy = self
-            .data_access_layer
-            .tracking_copy(state_root_hash)
-            .unwrap()
-            .unwrap();
+        with_active_state!(self, |state| {
+            let tracking_copy = state.tracking_copy(state_root_hash).unwrap().unwrap();
 
-        tracking_copy
-            .get_v1_entry_points(entity_addr)
-            .expect("must get entry points")
+            tracking_copy
+                .get_v1_entry_points(entity_addr)
+                .expect("must get entry points")
+        })
     }
 
     /// Gets a stored value from a contract's named keys.
@@ -1922,7 +2311,7 @@ where
     }
 
     fn system_entity_key(&self, request: SystemEntityRegistryRequest) -> Key {
-        let result = self.data_access_layer.system_entity_registry(request);
+        let result = with_active_state!(self, |state| state.system_entity_registry(request));
         if let SystemEntityRegistryResult::Success { payload, .. } = result {
             match payload {
                 SystemEntityRegistryPayload::All(_) => {
@@ -1982,9 +2371,10 @@ where
             .expect("should downcast")
     }
 
-    /// Resets the `exec_results`, `upgrade_results` and `transform` fields.
+    /// Resets the `exec_results`, `last_execution_logs`, `upgrade_results` and `transform` fields.
     pub fn clear_results(&mut self) -> &mut Self {
         self.exec_results = Vec::new();
+        self.last_execution_logs = Vec::new();
         self.upgrade_results = Vec::new();
         self.effects = Vec::new();
         self
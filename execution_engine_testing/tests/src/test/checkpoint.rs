@@ -0,0 +1,74 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, LOCAL_GENESIS_REQUEST,
+};
+use casper_types::{runtime_args, Key};
+
+use super::scratch_state::put_key_bytes;
+
+fn put_key(builder: &mut LmdbWasmTestBuilder, name: &str, byte: u8) {
+    let exec_request = ExecuteRequestBuilder::module_bytes(
+        *DEFAULT_ACCOUNT_ADDR,
+        put_key_bytes(name, byte),
+        runtime_args! {},
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn named_key(builder: &LmdbWasmTestBuilder, name: &str) -> Option<Key> {
+    builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(name)
+        .copied()
+}
+
+#[ignore]
+#[test]
+fn should_fork_execution_from_checkpoint() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+    let fork_point = builder.checkpoint();
+
+    put_key(&mut builder, "branch", 1);
+    let first_branch = builder.checkpoint();
+    assert_ne!(first_branch, fork_point);
+    assert_eq!(named_key(&builder, "branch"), Some(Key::Hash([1; 32])));
+
+    builder.restore(fork_point);
+    assert_eq!(builder.checkpoint(), fork_point);
+    assert_eq!(named_key(&builder, "branch"), None);
+
+    put_key(&mut builder, "branch", 2);
+    let second_branch = builder.checkpoint();
+    assert_ne!(second_branch, first_branch);
+    assert_eq!(named_key(&builder, "branch"), Some(Key::Hash([2; 32])));
+
+    // Each branch keeps its own state.
+    builder.restore(first_branch);
+    assert_eq!(named_key(&builder, "branch"), Some(Key::Hash([1; 32])));
+    builder.restore(second_branch);
+    assert_eq!(named_key(&builder, "branch"), Some(Key::Hash([2; 32])));
+}
+
+#[ignore]
+#[test]
+fn should_discard_unpersisted_scratch_effects_on_restore() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder
+        .run_genesis(LOCAL_GENESIS_REQUEST.clone())
+        .with_scratch_state();
+    let fork_point = builder.checkpoint();
+
+    put_key(&mut builder, "branch", 1);
+    assert_eq!(named_key(&builder, "branch"), Some(Key::Hash([1; 32])));
+
+    builder.restore(fork_point);
+    assert_eq!(named_key(&builder, "branch"), None);
+
+    put_key(&mut builder, "branch", 2);
+    builder.persist();
+    assert_ne!(builder.checkpoint(), fork_point);
+    assert_eq!(named_key(&builder, "branch"), Some(Key::Hash([2; 32])));
+}
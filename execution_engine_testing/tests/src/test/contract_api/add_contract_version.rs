@@ -2,13 +2,16 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use crate::lmdb_fixture;
 use casper_engine_test_support::{
-    utils, ExecuteRequestBuilder, LmdbWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNT_ADDR,
-    DEFAULT_ACCOUNT_SECRET_KEY, LOCAL_GENESIS_REQUEST,
+    genesis_config_builder::GenesisConfigBuilder, utils, ChainspecConfig, ExecuteRequestBuilder,
+    LmdbWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_SECRET_KEY, DEFAULT_CHAINSPEC_REGISTRY, DEFAULT_GENESIS_CONFIG_HASH,
+    DEFAULT_PROTOCOL_VERSION, LOCAL_GENESIS_REQUEST,
 };
 use casper_execution_engine::{
     engine_state::{Error as StateError, SessionDataV1, SessionInputData},
     execution::ExecError,
 };
+use casper_storage::data_access_layer::GenesisRequest;
 use casper_types::{
     bytesrepr::{Bytes, ToBytes},
     ApiError, BlockTime, Digest, EraId, InitiatorAddr, Key, PricingMode, ProtocolVersion,
@@ -39,6 +42,49 @@ fn should_allow_add_contract_version_via_deploy() {
     builder.exec(deploy_request).expect_success().commit();
 }
 
+#[ignore]
+#[test]
+fn should_report_main_purse_of_installed_contract_as_new_purse() {
+    let genesis_config = GenesisConfigBuilder::new()
+        .with_accounts(DEFAULT_ACCOUNTS.clone())
+        .with_enable_addressable_entity(true)
+        .build();
+    let genesis_request = GenesisRequest::new(
+        DEFAULT_GENESIS_CONFIG_HASH,
+        DEFAULT_PROTOCOL_VERSION,
+        genesis_config,
+        DEFAULT_CHAINSPEC_REGISTRY.clone(),
+    );
+    let mut builder = LmdbWasmTestBuilder::new_temporary_with_config(
+        ChainspecConfig::default().with_enable_addressable_entity(true),
+    );
+    builder.run_genesis(genesis_request);
+
+    let deploy_request =
+        ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, CONTRACT, RuntimeArgs::new())
+            .build();
+    builder.exec(deploy_request).expect_success().commit();
+
+    let contract_hash = builder
+        .get_named_keys_by_account_hash(*DEFAULT_ACCOUNT_ADDR)
+        .get("do_nothing_hash")
+        .expect("should have contract hash")
+        .into_entity_hash()
+        .expect("should be an entity hash");
+    let contract = builder
+        .get_addressable_entity(contract_hash)
+        .expect("should have contract");
+
+    let new_purses = builder.new_purses();
+    assert!(
+        new_purses
+            .iter()
+            .any(|purse| purse.addr() == contract.main_purse().addr()),
+        "{:?} should contain the contract's main purse",
+        new_purses
+    );
+}
+
 fn try_add_contract_version(
     is_install_upgrade: bool,
     should_succeed: bool,
@@ -13,6 +13,8 @@ use casper_execution_engine::{
 use casper_storage::address_generator::ADDRESS_LENGTH;
 use casper_types::runtime_args;
 
+use crate::wasm_utils;
+
 const ARG_BYTES: &str = "bytes";
 const ARG_AMOUNT: &str = "amount";
 
@@ -131,3 +133,39 @@ fn should_hash() {
         assert_eq!(digest, expected_digest);
     }
 }
+
+#[ignore]
+#[test]
+fn should_capture_printed_lines() {
+    // Prints a length-prefixed string, as serialized by the contract API.
+    let wat = r#"(module
+        (import "env" "casper_print" (func $print (param i32 i32)))
+        (func $call (call $print (i32.const 0) (i32.const 22)))
+        (export "call" (func $call))
+        (memory $memory 1)
+        (data (i32.const 0) "\12\00\00\00hello from session")
+      )"#;
+    let module_bytes = wat::parse_str(wat).unwrap();
+
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let exec_request =
+        ExecuteRequestBuilder::module_bytes(*DEFAULT_ACCOUNT_ADDR, module_bytes, runtime_args! {})
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+    assert_eq!(
+        builder.last_execution_logs(),
+        vec!["hello from session".to_string()]
+    );
+
+    // Lines are captured per execution.
+    let exec_request = ExecuteRequestBuilder::module_bytes(
+        *DEFAULT_ACCOUNT_ADDR,
+        wasm_utils::do_minimum_bytes(),
+        runtime_args! {},
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    assert!(builder.last_execution_logs().is_empty());
+}
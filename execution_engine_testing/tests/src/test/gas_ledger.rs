@@ -0,0 +1,49 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, LOCAL_GENESIS_REQUEST,
+};
+use casper_types::{runtime_args, Gas};
+
+/// Session code which spins in a loop `iterations` times, so each deploy consumes a different
+/// amount of gas.
+fn loop_bytes(iterations: u32) -> Vec<u8> {
+    let wat = format!(
+        r#"(module
+            (func $call (local $i i32)
+                (loop $continue
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br_if $continue (i32.lt_u (local.get $i) (i32.const {iterations})))))
+            (export "call" (func $call))
+            (memory $memory 1)
+        )"#
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+#[ignore]
+#[test]
+fn should_sum_gas_spent_across_deploys() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+    assert_eq!(builder.total_gas_spent(), Gas::zero());
+
+    let mut expected_total = Gas::zero();
+    for iterations in [1, 10, 100] {
+        let exec_request = ExecuteRequestBuilder::module_bytes(
+            *DEFAULT_ACCOUNT_ADDR,
+            loop_bytes(iterations),
+            runtime_args! {},
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+        expected_total = expected_total
+            .checked_add(builder.last_exec_gas_consumed())
+            .unwrap();
+    }
+
+    assert_eq!(builder.gas_ledger().exec_count(), 3);
+    assert_eq!(builder.total_gas_spent(), expected_total);
+
+    builder.reset_gas_ledger();
+    assert_eq!(builder.total_gas_spent(), Gas::zero());
+    assert_eq!(builder.gas_ledger().exec_count(), 0);
+}
@@ -1,17 +1,28 @@
 use once_cell::sync::Lazy;
 
 use casper_engine_test_support::{
-    LmdbWasmTestBuilder, TransferRequestBuilder, LOCAL_GENESIS_REQUEST,
+    ExecuteRequest, ExecuteRequestBuilder, LmdbWasmTestBuilder, TransferRequestBuilder,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_BLOCK_TIME,
+    DEFAULT_GAS_HOLD_INTERVAL_MILLIS, LOCAL_GENESIS_REQUEST, TIMESTAMP_MILLIS_INCREMENT,
 };
 use casper_storage::{
-    data_access_layer::BalanceIdentifier,
+    data_access_layer::{
+        balance::BalanceHandling, BalanceHoldRequest, BalanceHoldResult, BalanceIdentifier,
+        BalanceRequest, HoldsBreakdownHandling, InsufficientBalanceHandling, ProofHandling,
+        PruneResult,
+    },
+    global_state::state::{CommitProvider, StateProvider},
     tracking_copy::{self, ValidationError},
 };
 use casper_types::{
-    account::AccountHash, AccessRights, Digest, Key, ProtocolVersion, PublicKey, SecretKey, URef,
+    account::AccountHash,
+    system::mint::{BalanceHoldAddr, BalanceHoldAddrTag},
+    AccessRights, BlockTime, Digest, Key, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, URef,
     U512,
 };
 
+use crate::wasm_utils;
+
 static ALICE_KEY: Lazy<PublicKey> = Lazy::new(|| {
     let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
     PublicKey::from(&secret_key)
@@ -19,6 +30,50 @@ static ALICE_KEY: Lazy<PublicKey> = Lazy::new(|| {
 static ALICE_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*ALICE_KEY));
 
 static TRANSFER_AMOUNT_1: Lazy<U512> = Lazy::new(|| U512::from(100_000_000));
+static GAS_HOLD_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(1_000));
+
+fn do_nothing_request() -> ExecuteRequest {
+    ExecuteRequestBuilder::module_bytes(
+        *DEFAULT_ACCOUNT_ADDR,
+        wasm_utils::do_nothing_bytes(),
+        RuntimeArgs::default(),
+    )
+    .build()
+}
+
+/// Funds Alice, advances the block time once and places a gas hold of `GAS_HOLD_AMOUNT` on her
+/// account. Returns the builder together with the block time the hold was placed at.
+fn setup_with_hold(protocol_version: ProtocolVersion) -> (LmdbWasmTestBuilder, u64) {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let transfer_request = TransferRequestBuilder::new(*TRANSFER_AMOUNT_1, *ALICE_ADDR).build();
+    builder
+        .transfer_and_commit(transfer_request)
+        .expect_success();
+
+    builder
+        .exec_advancing_time(do_nothing_request())
+        .expect_success()
+        .commit();
+    let hold_block_time = DEFAULT_BLOCK_TIME + TIMESTAMP_MILLIS_INCREMENT;
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let hold_request = BalanceHoldRequest::new_gas_hold(
+        pre_state_hash,
+        protocol_version,
+        BalanceIdentifier::Account(*ALICE_ADDR),
+        *GAS_HOLD_AMOUNT,
+        InsufficientBalanceHandling::Noop,
+    );
+    let effects = match builder.data_access_layer().balance_hold(hold_request) {
+        BalanceHoldResult::Success { effects, .. } => *effects,
+        other => panic!("should place balance hold: {:?}", other),
+    };
+    builder.commit_transforms(pre_state_hash, effects);
+
+    (builder, hold_block_time)
+}
 
 #[ignore]
 #[test]
@@ -117,6 +172,29 @@ fn get_balance_should_work() {
     );
 }
 
+#[ignore]
+#[test]
+fn transfer_to_new_account_should_report_its_main_purse_as_new() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let transfer_request = TransferRequestBuilder::new(*TRANSFER_AMOUNT_1, *ALICE_ADDR).build();
+    builder
+        .transfer_and_commit(transfer_request)
+        .expect_success();
+
+    let alice_main_purse = builder
+        .get_entity_by_account_hash(*ALICE_ADDR)
+        .expect("should have Alice's account")
+        .main_purse();
+
+    assert_eq!(
+        builder.new_purses(),
+        vec![alice_main_purse],
+        "transfer should have created Alice's main purse"
+    );
+}
+
 #[ignore]
 #[test]
 fn get_balance_using_public_key_should_work() {
@@ -211,3 +289,322 @@ fn get_balance_using_public_key_should_work() {
         Err(ValidationError::UnexpectedValue)
     );
 }
+
+#[ignore]
+#[test]
+fn get_balance_should_break_down_holds_by_kind() {
+    let protocol_version = ProtocolVersion::V2_0_0;
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let block_time = 1_000_000;
+    let transfer_request = TransferRequestBuilder::new(*TRANSFER_AMOUNT_1, *ALICE_ADDR)
+        .with_block_time(block_time)
+        .build();
+
+    builder
+        .transfer_and_commit(transfer_request)
+        .expect_success();
+
+    let gas_hold_amount = U512::from(1_000);
+    let processing_hold_amount = U512::from(2_500);
+
+    for (hold_kind, hold_amount) in [
+        (BalanceHoldAddrTag::Gas, gas_hold_amount),
+        (BalanceHoldAddrTag::Processing, processing_hold_amount),
+    ] {
+        let pre_state_hash = builder.get_post_state_hash();
+        let identifier = BalanceIdentifier::Account(*ALICE_ADDR);
+        let insufficient_handling = InsufficientBalanceHandling::Noop;
+        let hold_request = match hold_kind {
+            BalanceHoldAddrTag::Gas => BalanceHoldRequest::new_gas_hold(
+                pre_state_hash,
+                protocol_version,
+                identifier,
+                hold_amount,
+                insufficient_handling,
+            ),
+            BalanceHoldAddrTag::Processing => BalanceHoldRequest::new_processing_hold(
+                pre_state_hash,
+                protocol_version,
+                identifier,
+                hold_amount,
+                insufficient_handling,
+            ),
+        };
+        let effects = match builder.data_access_layer().balance_hold(hold_request) {
+            BalanceHoldResult::Success { effects, .. } => *effects,
+            other => panic!("should place balance hold: {:?}", other),
+        };
+        builder.commit_transforms(pre_state_hash, effects);
+    }
+
+    let balance_request = |holds_breakdown_handling| {
+        BalanceRequest::from_account_hash(
+            builder.get_post_state_hash(),
+            protocol_version,
+            *ALICE_ADDR,
+            BalanceHandling::Available,
+            ProofHandling::NoProofs,
+        )
+        .with_holds_breakdown_handling(holds_breakdown_handling)
+    };
+
+    let balance_result = builder
+        .data_access_layer()
+        .balance(balance_request(HoldsBreakdownHandling::NoBreakdown));
+    assert_eq!(balance_result.holds_breakdown(), None);
+
+    let balance_result = builder
+        .data_access_layer()
+        .balance(balance_request(HoldsBreakdownHandling::Breakdown));
+    assert_eq!(
+        balance_result.total_balance().cloned(),
+        Some(*TRANSFER_AMOUNT_1)
+    );
+    assert_eq!(
+        balance_result.available_balance().cloned(),
+        Some(*TRANSFER_AMOUNT_1 - gas_hold_amount - processing_hold_amount)
+    );
+
+    let holds_breakdown = balance_result
+        .holds_breakdown()
+        .expect("should have holds breakdown");
+    assert_eq!(holds_breakdown.len(), 2);
+    assert_eq!(
+        holds_breakdown.get(&BalanceHoldAddrTag::Gas),
+        Some(&gas_hold_amount)
+    );
+    assert_eq!(
+        holds_breakdown.get(&BalanceHoldAddrTag::Processing),
+        Some(&processing_hold_amount)
+    );
+}
+
+#[ignore]
+#[test]
+fn get_balance_breakdown_should_skip_expired_holds() {
+    let protocol_version = ProtocolVersion::V2_0_0;
+    let (mut builder, hold_block_time) = setup_with_hold(protocol_version);
+
+    let holds_breakdown = |builder: &LmdbWasmTestBuilder, proof_handling| {
+        let balance_request = BalanceRequest::from_account_hash(
+            builder.get_post_state_hash(),
+            protocol_version,
+            *ALICE_ADDR,
+            BalanceHandling::Available,
+            proof_handling,
+        )
+        .with_holds_breakdown_handling(HoldsBreakdownHandling::Breakdown);
+        builder
+            .data_access_layer()
+            .balance(balance_request)
+            .holds_breakdown()
+            .cloned()
+            .expect("should have holds breakdown")
+    };
+
+    for proof_handling in [ProofHandling::NoProofs, ProofHandling::Proofs] {
+        assert_eq!(
+            holds_breakdown(&builder, proof_handling).get(&BalanceHoldAddrTag::Gas),
+            Some(&*GAS_HOLD_AMOUNT)
+        );
+    }
+
+    // Past the hold interval, the expired hold is left out of the breakdown.
+    builder
+        .exec_with_block_time(
+            do_nothing_request(),
+            hold_block_time + DEFAULT_GAS_HOLD_INTERVAL_MILLIS + TIMESTAMP_MILLIS_INCREMENT,
+        )
+        .expect_success()
+        .commit();
+
+    for proof_handling in [ProofHandling::NoProofs, ProofHandling::Proofs] {
+        assert_eq!(
+            holds_breakdown(&builder, proof_handling).get(&BalanceHoldAddrTag::Gas),
+            None
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn gas_hold_should_expire_after_advancing_block_time() {
+    let protocol_version = ProtocolVersion::V2_0_0;
+    let (mut builder, hold_block_time) = setup_with_hold(protocol_version);
+    assert_eq!(builder.block_time(), Some(BlockTime::new(hold_block_time)));
+
+    let available_balance = |builder: &LmdbWasmTestBuilder| {
+        let balance_request = BalanceRequest::from_account_hash(
+            builder.get_post_state_hash(),
+            protocol_version,
+            *ALICE_ADDR,
+            BalanceHandling::Available,
+            ProofHandling::NoProofs,
+        );
+        builder
+            .data_access_layer()
+            .balance(balance_request)
+            .available_balance()
+            .cloned()
+            .expect("should have available balance")
+    };
+
+    assert_eq!(
+        available_balance(&builder),
+        *TRANSFER_AMOUNT_1 - *GAS_HOLD_AMOUNT
+    );
+
+    // Still within the hold interval, so the hold remains in force.
+    builder
+        .exec_advancing_time(do_nothing_request())
+        .expect_success()
+        .commit();
+    assert_eq!(
+        builder.block_time(),
+        Some(BlockTime::new(hold_block_time + TIMESTAMP_MILLIS_INCREMENT))
+    );
+    assert_eq!(
+        available_balance(&builder),
+        *TRANSFER_AMOUNT_1 - *GAS_HOLD_AMOUNT
+    );
+
+    // Past the hold interval, the hold no longer reduces the available balance.
+    builder
+        .exec_with_block_time(
+            do_nothing_request(),
+            hold_block_time + DEFAULT_GAS_HOLD_INTERVAL_MILLIS + TIMESTAMP_MILLIS_INCREMENT,
+        )
+        .expect_success()
+        .commit();
+    assert_eq!(available_balance(&builder), *TRANSFER_AMOUNT_1);
+}
+
+#[ignore]
+#[test]
+fn historical_balance_should_apply_holds_as_of_the_historical_block_time() {
+    let protocol_version = ProtocolVersion::V2_0_0;
+    let (mut builder, hold_block_time) = setup_with_hold(protocol_version);
+    let held_state_hash = builder.get_post_state_hash();
+
+    // Past the hold interval, the hold no longer applies at the latest state root.
+    builder
+        .exec_with_block_time(
+            do_nothing_request(),
+            hold_block_time + DEFAULT_GAS_HOLD_INTERVAL_MILLIS + TIMESTAMP_MILLIS_INCREMENT,
+        )
+        .expect_success()
+        .commit();
+    let latest_state_hash = builder.get_post_state_hash();
+
+    let available_balance_at = |state_hash| {
+        let balance_request = BalanceRequest::from_account_hash(
+            state_hash,
+            protocol_version,
+            *ALICE_ADDR,
+            BalanceHandling::Available,
+            ProofHandling::NoProofs,
+        );
+        builder
+            .data_access_layer()
+            .balance(balance_request)
+            .available_balance()
+            .cloned()
+            .expect("should have available balance")
+    };
+
+    assert_eq!(
+        available_balance_at(held_state_hash),
+        *TRANSFER_AMOUNT_1 - *GAS_HOLD_AMOUNT
+    );
+    assert_eq!(available_balance_at(latest_state_hash), *TRANSFER_AMOUNT_1);
+}
+
+#[ignore]
+#[test]
+fn account_balance_should_return_default_account_balance_after_genesis() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+    let genesis_hash = builder.get_post_state_hash();
+
+    let expected = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE);
+    assert_eq!(
+        builder.account_balance(*DEFAULT_ACCOUNT_ADDR, None),
+        expected
+    );
+
+    let transfer_request = TransferRequestBuilder::new(*TRANSFER_AMOUNT_1, *ALICE_ADDR).build();
+    builder
+        .transfer_and_commit(transfer_request)
+        .expect_success();
+
+    // Querying the genesis state is unaffected by later executions.
+    assert_eq!(
+        builder.account_balance(*DEFAULT_ACCOUNT_ADDR, Some(genesis_hash)),
+        expected
+    );
+    assert_eq!(
+        builder.account_balance(*ALICE_ADDR, None),
+        *TRANSFER_AMOUNT_1
+    );
+}
+
+#[ignore]
+#[test]
+#[should_panic(expected = "does not exist")]
+fn account_balance_should_panic_for_unknown_account() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    builder.account_balance(*ALICE_ADDR, None);
+}
+
+#[ignore]
+#[test]
+fn prune_expired_holds_should_remove_hold_past_interval() {
+    let protocol_version = ProtocolVersion::V2_0_0;
+    let (builder, hold_block_time) = setup_with_hold(protocol_version);
+
+    let alice_purse = builder
+        .get_entity_by_account_hash(*ALICE_ADDR)
+        .expect("should have Alice's account")
+        .main_purse();
+    let hold_key = Key::BalanceHold(BalanceHoldAddr::new_gas(
+        alice_purse.addr(),
+        BlockTime::new(hold_block_time),
+    ));
+    assert!(builder.query(None, hold_key, &[]).is_ok());
+
+    // Still within the hold interval, so nothing is pruned.
+    let state_hash = builder.get_post_state_hash();
+    match builder
+        .data_access_layer()
+        .prune_expired_holds(state_hash, BlockTime::new(hold_block_time))
+    {
+        PruneResult::Success {
+            post_state_hash, ..
+        } => assert_eq!(post_state_hash, state_hash),
+        other => panic!("should prune expired holds: {:?}", other),
+    }
+
+    let past_interval = BlockTime::new(
+        hold_block_time + DEFAULT_GAS_HOLD_INTERVAL_MILLIS + TIMESTAMP_MILLIS_INCREMENT,
+    );
+    let post_state_hash = match builder
+        .data_access_layer()
+        .prune_expired_holds(state_hash, past_interval)
+    {
+        PruneResult::Success {
+            post_state_hash, ..
+        } => post_state_hash,
+        other => panic!("should prune expired holds: {:?}", other),
+    };
+    assert_ne!(post_state_hash, state_hash);
+
+    assert!(builder.query(Some(post_state_hash), hold_key, &[]).is_err());
+    assert_eq!(
+        builder.account_balance(*ALICE_ADDR, Some(post_state_hash)),
+        *TRANSFER_AMOUNT_1
+    );
+}
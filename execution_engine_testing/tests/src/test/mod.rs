@@ -1,17 +1,20 @@
 mod chainspec_registry;
 mod check_transfer_success;
+mod checkpoint;
 mod contract_api;
 mod contract_context;
 mod contract_messages;
 mod counter_factory;
 mod deploy;
 mod explorer;
+mod gas_ledger;
 mod get_balance;
 mod groups;
 mod host_function_costs;
 mod manage_groups;
 mod private_chain;
 mod regression;
+mod scratch_state;
 mod stack_overflow;
 mod step;
 mod storage_costs;
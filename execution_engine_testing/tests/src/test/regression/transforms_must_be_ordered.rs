@@ -131,3 +131,69 @@ fn contract_transforms_should_be_ordered_in_the_effects() {
         })
         .eq(operations.into_iter()));
 }
+
+#[ignore]
+#[test]
+fn last_exec_transforms_should_keep_last_transform_per_key() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let execution_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        "ordered-transforms.wasm",
+        runtime_args! { "n" => 1u32 },
+    )
+    .build();
+    builder.exec(execution_request).expect_success().commit();
+
+    let contract_hash = match builder
+        .get_entity_with_named_keys_by_account_hash(*DEFAULT_ACCOUNT_ADDR)
+        .unwrap()
+        .named_keys()
+        .get("ordered-transforms-contract-hash")
+        .unwrap()
+    {
+        Key::AddressableEntity(entity_addr) => AddressableEntityHash::new(entity_addr.value()),
+        _ => panic!("Couldn't find ordered-transforms contract."),
+    };
+
+    // Write the same URef twice.
+    let operations: Vec<(u8, u32, i32)> = vec![(1, 0, 1), (1, 0, 7)];
+    builder
+        .exec(
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                "perform_operations",
+                runtime_args! { "operations" => operations },
+            )
+            .build(),
+        )
+        .expect_success()
+        .commit();
+
+    let uref = match builder
+        .get_entity_with_named_keys_by_entity_hash(contract_hash)
+        .unwrap()
+        .named_keys()
+        .get("uref-0")
+        .unwrap()
+    {
+        Key::URef(uref) => uref.remove_access_rights(),
+        _ => panic!("Expected a URef."),
+    };
+
+    let transforms = builder.last_exec_transforms();
+    match transforms.get(&Key::URef(uref)) {
+        Some(TransformKindV2::Write(stored_value)) => {
+            let value: i32 = stored_value
+                .as_cl_value()
+                .unwrap()
+                .clone()
+                .into_t()
+                .unwrap();
+            assert_eq!(value, 7);
+        }
+        other => panic!("unexpected transform: {:?}", other),
+    }
+}
@@ -0,0 +1,89 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, LOCAL_GENESIS_REQUEST,
+};
+use casper_storage::{
+    data_access_layer::{QueryRequest, QueryResult},
+    global_state::state::StateProvider,
+};
+use casper_types::{account::Account, runtime_args, Key, StoredValue};
+
+const NAMED_KEYS: [(&str, u8); 3] = [("first", 1), ("second", 2), ("third", 3)];
+
+/// Session code which puts a named key `name` pointing at `Key::Hash([byte; 32])`.
+pub(crate) fn put_key_bytes(name: &str, byte: u8) -> Vec<u8> {
+    let key_hash = format!("\\{byte:02x}").repeat(32);
+    let wat = format!(
+        r#"(module
+            (import "env" "casper_put_key" (func $put_key (param i32 i32 i32 i32)))
+            (func $call (call $put_key (i32.const 0) (i32.const {name_size}) (i32.const 64) (i32.const 33)))
+            (export "call" (func $call))
+            (memory $memory 1)
+            (data (i32.const 0) "\{name_len:02x}\00\00\00{name}")
+            (data (i32.const 64) "\01{key_hash}")
+        )"#,
+        name_size = name.len() + 4,
+        name_len = name.len(),
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+fn read_account_from_disk(builder: &LmdbWasmTestBuilder) -> Account {
+    let request = QueryRequest::new(
+        builder.get_post_state_hash(),
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+        vec![],
+    );
+    match builder.data_access_layer().query(request) {
+        QueryResult::Success { value, .. } => match *value {
+            StoredValue::Account(account) => account,
+            other => panic!("expected account, got {other:?}"),
+        },
+        other => panic!("expected account, got {other:?}"),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_read_prior_writes_from_scratch_state() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder
+        .run_genesis(LOCAL_GENESIS_REQUEST.clone())
+        .with_scratch_state();
+    let genesis_hash = builder.get_post_state_hash();
+
+    for (index, (name, byte)) in NAMED_KEYS.into_iter().enumerate() {
+        let exec_request = ExecuteRequestBuilder::module_bytes(
+            *DEFAULT_ACCOUNT_ADDR,
+            put_key_bytes(name, byte),
+            runtime_args! {},
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+
+        // Every key written so far is visible through the builder.
+        let account = builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account");
+        for (name, byte) in &NAMED_KEYS[..=index] {
+            assert_eq!(
+                account.named_keys().get(name),
+                Some(&Key::Hash([*byte; 32]))
+            );
+        }
+    }
+
+    // None of the commits reached the database.
+    assert_eq!(builder.get_post_state_hash(), genesis_hash);
+    let account = read_account_from_disk(&builder);
+    for (name, _) in NAMED_KEYS {
+        assert!(!account.named_keys().contains(name));
+    }
+
+    builder.persist();
+
+    assert_ne!(builder.get_post_state_hash(), genesis_hash);
+    let account = read_account_from_disk(&builder);
+    for (name, byte) in NAMED_KEYS {
+        assert_eq!(account.named_keys().get(name), Some(&Key::Hash([byte; 32])));
+    }
+}
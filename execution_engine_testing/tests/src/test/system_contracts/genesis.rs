@@ -3,15 +3,21 @@ use once_cell::sync::Lazy;
 
 use casper_engine_test_support::{
     genesis_config_builder::GenesisConfigBuilder, ChainspecConfig, LmdbWasmTestBuilder,
-    DEFAULT_AUCTION_DELAY, DEFAULT_CHAINSPEC_REGISTRY, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_AUCTION_DELAY,
+    DEFAULT_CHAINSPEC_REGISTRY, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
     DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_PROTOCOL_VERSION, DEFAULT_ROUND_SEIGNIORAGE_RATE,
     DEFAULT_STORAGE_COSTS, DEFAULT_SYSTEM_CONFIG, DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS,
-    DEFAULT_WASM_CONFIG,
+    DEFAULT_WASM_CONFIG, LOCAL_GENESIS_REQUEST,
+};
+use casper_storage::{
+    data_access_layer::{GenesisRequest, RoundSeigniorageRateRequest},
+    global_state::state::StateProvider,
 };
-use casper_storage::data_access_layer::GenesisRequest;
 use casper_types::{
-    account::AccountHash, system::auction::DelegationRate, GenesisAccount, GenesisValidator, Key,
-    Motes, ProtocolVersion, PublicKey, SecretKey, StoredValue, U512,
+    account::AccountHash,
+    system::auction::{BidsExt, DelegationRate, DelegatorKind},
+    Digest, GenesisAccount, GenesisValidator, Key, Motes, ProtocolVersion, PublicKey, SecretKey,
+    StoredValue, U512,
 };
 
 const GENESIS_CONFIG_HASH: [u8; 32] = [127; 32];
@@ -167,3 +173,129 @@ fn should_track_total_token_supply_in_mint() {
         "unexpected total supply"
     )
 }
+
+#[ignore]
+#[test]
+fn should_read_genesis_round_seigniorage_rate() {
+    let config = GenesisConfigBuilder::default()
+        .with_accounts(DEFAULT_ACCOUNTS.clone())
+        .with_wasm_config(*DEFAULT_WASM_CONFIG)
+        .with_system_config(*DEFAULT_SYSTEM_CONFIG)
+        .with_validator_slots(DEFAULT_VALIDATOR_SLOTS)
+        .with_auction_delay(DEFAULT_AUCTION_DELAY)
+        .with_locked_funds_period_millis(DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS)
+        .with_round_seigniorage_rate(DEFAULT_ROUND_SEIGNIORAGE_RATE)
+        .with_unbonding_delay(DEFAULT_UNBONDING_DELAY)
+        .with_genesis_timestamp_millis(DEFAULT_GENESIS_TIMESTAMP_MILLIS)
+        .with_storage_costs(*DEFAULT_STORAGE_COSTS)
+        .build();
+    let genesis_request = GenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        DEFAULT_PROTOCOL_VERSION,
+        config,
+        DEFAULT_CHAINSPEC_REGISTRY.clone(),
+    );
+
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(genesis_request);
+
+    let rate = builder
+        .data_access_layer()
+        .round_seigniorage_rate_ratio(RoundSeigniorageRateRequest::new(
+            builder.get_post_state_hash(),
+            DEFAULT_PROTOCOL_VERSION,
+        ))
+        .expect("should read round seigniorage rate");
+    assert_eq!(rate, DEFAULT_ROUND_SEIGNIORAGE_RATE);
+
+    assert_eq!(
+        builder
+            .data_access_layer()
+            .round_seigniorage_rate_ratio(RoundSeigniorageRateRequest::new(
+                Digest::hash(b"missing root"),
+                DEFAULT_PROTOCOL_VERSION,
+            )),
+        None
+    );
+}
+
+#[ignore]
+#[test]
+fn should_seed_every_default_account_with_initial_balance() {
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let genesis_accounts = builder.genesis_accounts();
+    assert_eq!(genesis_accounts.len(), DEFAULT_ACCOUNTS.len());
+
+    for (account_hash, initial_balance) in genesis_accounts {
+        assert_eq!(
+            initial_balance,
+            U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE),
+            "unexpected initial balance for {}",
+            account_hash
+        );
+
+        let entity = builder
+            .get_entity_by_account_hash(account_hash)
+            .expect("seeded account should exist");
+        assert_eq!(
+            builder.get_purse_balance(entity.main_purse()),
+            initial_balance,
+            "unexpected main purse balance for {}",
+            account_hash
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_seed_validator_and_delegator_bids_at_genesis() {
+    const DELEGATION_RATE: DelegationRate = 10;
+
+    let config = GenesisConfigBuilder::default()
+        .with_accounts(DEFAULT_ACCOUNTS.clone())
+        .with_validator(
+            ACCOUNT_1_PUBLIC_KEY.clone(),
+            Motes::new(ACCOUNT_1_BONDED_AMOUNT),
+            DELEGATION_RATE,
+        )
+        .with_delegator(
+            ACCOUNT_1_PUBLIC_KEY.clone(),
+            ACCOUNT_2_PUBLIC_KEY.clone(),
+            Motes::new(ACCOUNT_2_BONDED_AMOUNT),
+        )
+        .with_storage_costs(*DEFAULT_STORAGE_COSTS)
+        .build();
+    let genesis_request = GenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        DEFAULT_PROTOCOL_VERSION,
+        config,
+        DEFAULT_CHAINSPEC_REGISTRY.clone(),
+    );
+
+    let mut builder = LmdbWasmTestBuilder::default();
+    builder.run_genesis(genesis_request);
+
+    let bids = builder.get_bids();
+
+    let validator_bid = bids
+        .validator_bid(&ACCOUNT_1_PUBLIC_KEY)
+        .expect("should have validator bid");
+    assert_eq!(
+        validator_bid.staked_amount(),
+        U512::from(ACCOUNT_1_BONDED_AMOUNT)
+    );
+    assert_eq!(*validator_bid.delegation_rate(), DELEGATION_RATE);
+
+    let delegator_bid = bids
+        .delegator_by_kind(
+            &ACCOUNT_1_PUBLIC_KEY,
+            &DelegatorKind::PublicKey(ACCOUNT_2_PUBLIC_KEY.clone()),
+        )
+        .expect("should have delegator bid");
+    assert_eq!(
+        delegator_bid.staked_amount(),
+        U512::from(ACCOUNT_2_BONDED_AMOUNT)
+    );
+}
@@ -23,8 +23,9 @@ use casper_types::{
         },
         mint::ROUND_SEIGNIORAGE_RATE_KEY,
     },
-    Account, AddressableEntityHash, CLValue, CoreConfig, EntityAddr, EraId, Key, ProtocolVersion,
-    StorageCosts, StoredValue, SystemHashRegistry, U256, U512,
+    Account, AddressableEntityHash, CLValue, CoreConfig, EntityAddr, EraId,
+    GlobalStateUpdateBuilder, Key, ProtocolVersion, StorageCosts, StoredValue, SystemHashRegistry,
+    U256, U512,
 };
 use rand::Rng;
 
@@ -592,6 +593,43 @@ fn should_apply_global_state_upgrade() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_apply_global_state_update_built_from_entries() {
+    let mut builder = LmdbWasmTestBuilder::default();
+
+    builder.run_genesis(LOCAL_GENESIS_REQUEST.clone());
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let overridden_key = Key::Hash([7; 32]);
+    let overridden_value = StoredValue::from(CLValue::from_t(42u64).expect("should create"));
+    assert!(builder.query(None, overridden_key, &[]).is_err());
+
+    let global_state_update = GlobalStateUpdateBuilder::new()
+        .with_entry(overridden_key, overridden_value.clone())
+        .build_update_mapping()
+        .expect("should serialize entries");
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_global_state_update(global_state_update)
+        .build();
+
+    builder
+        .upgrade(&mut upgrade_request)
+        .expect_upgrade_success();
+
+    let stored_value = builder
+        .query(None, overridden_key, &[])
+        .expect("should have overridden value");
+    assert_eq!(stored_value, overridden_value);
+}
+
 #[ignore]
 #[test]
 fn should_increase_max_associated_keys_after_upgrade() {
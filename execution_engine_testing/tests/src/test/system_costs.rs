@@ -933,3 +933,47 @@ fn should_verify_wasm_add_bid_wasm_cost_is_not_recursive() {
         Gas::new(expected_call_cost)
     );
 }
+
+#[ignore]
+#[test]
+fn should_charge_more_gas_with_higher_opcode_costs() {
+    let gas_consumed_with = |wasm_config: WasmConfig| {
+        let mut builder = LmdbWasmTestBuilder::default();
+        builder.run_genesis_with_wasm_config(wasm_config);
+
+        let exec_request = ExecuteRequestBuilder::module_bytes(
+            *DEFAULT_ACCOUNT_ADDR,
+            wasm_utils::do_minimum_bytes(),
+            RuntimeArgs::default(),
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+        builder.last_exec_gas_consumed()
+    };
+
+    let default_wasm_config = LmdbWasmTestBuilder::default().chainspec().wasm_config;
+    let default_v1_config = *default_wasm_config.v1();
+    let default_opcode_costs = default_v1_config.opcode_costs();
+    let expensive_opcode_costs = OpcodeCosts {
+        nop: default_opcode_costs.nop + 10_000,
+        ..default_opcode_costs
+    };
+    let expensive_wasm_config = WasmConfig::new(
+        default_wasm_config.messages_limits(),
+        WasmV1Config::new(
+            default_v1_config.max_memory(),
+            default_v1_config.max_stack_height(),
+            expensive_opcode_costs,
+            default_v1_config.take_host_function_costs(),
+        ),
+        *default_wasm_config.v2(),
+    );
+
+    let default_gas = gas_consumed_with(default_wasm_config);
+    let expensive_gas = gas_consumed_with(expensive_wasm_config);
+    // `do_minimum_bytes` executes exactly one `nop`.
+    assert_eq!(
+        expensive_gas.value(),
+        default_gas.value() + U512::from(10_000)
+    );
+}
@@ -43,6 +43,42 @@ pub fn compute_next_contract_hash_version(
     hasher.finalize().into()
 }
 
+/// Compute the selector of an entry point.
+///
+/// The selector is the first 4 bytes of the blake2b256 hash of the entry point name, interpreted
+/// as a little-endian `u32`.
+#[must_use]
+pub fn compute_selector(entry_point: &str) -> u32 {
+    let hash: [u8; 32] = Blake2b::<U32>::digest(entry_point).into();
+    u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Maximum number of pseudo-random bytes that can be requested at once.
+pub const MAX_RANDOM_BYTES: usize = 1024;
+
+/// Fill `output` with pseudo-random bytes derived from `seed`, the `address` of the requesting
+/// entity and `counter`.
+///
+/// Each 32-byte block of the output is the blake2b256 hash of the seed, the address, the counter
+/// and the index of the block, so the same inputs always produce the same bytes. This is not a
+/// source of secure randomness.
+pub fn fill_pseudo_random_bytes(
+    seed: [u8; 32],
+    address: [u8; 32],
+    counter: u64,
+    output: &mut [u8],
+) {
+    for (index, chunk) in output.chunks_mut(32).enumerate() {
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update(seed);
+        hasher.update(address);
+        hasher.update(counter.to_le_bytes());
+        hasher.update((index as u64).to_le_bytes());
+        let hash: [u8; 32] = hasher.finalize().into();
+        chunk.copy_from_slice(&hash[..chunk.len()]);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     const SEED: [u8; 32] = [1u8; 32];
@@ -1,3 +1,5 @@
+use crate::flags::EntryPointFlags;
+
 /// The caller must cover cost.
 ///
 /// This is the default mode in VM2 runtime.
@@ -6,3 +8,92 @@ pub const ENTRY_POINT_PAYMENT_CALLER: u8 = 0;
 pub const ENTRY_POINT_PAYMENT_DIRECT_INVOCATION_ONLY: u8 = 1;
 /// will cover cost to execute self and the cost of any subsequent invoked contracts
 pub const ENTRY_POINT_PAYMENT_SELF_ONWARD: u8 = 2;
+
+/// Returns `true` if the caller covers the cost of an entry point with the given payment mode.
+pub const fn pays_caller(entry_point_payment: u8) -> bool {
+    entry_point_payment == ENTRY_POINT_PAYMENT_CALLER
+}
+
+/// Returns `true` if the contract covers the cost of an entry point with the given payment mode,
+/// at least when it is directly invoked.
+pub const fn pays_self(entry_point_payment: u8) -> bool {
+    matches!(
+        entry_point_payment,
+        ENTRY_POINT_PAYMENT_DIRECT_INVOCATION_ONLY | ENTRY_POINT_PAYMENT_SELF_ONWARD
+    )
+}
+
+/// Name of the custom Wasm section holding the manifest of a contract.
+///
+/// The manifest lists the [`EntryPointFlags`] of each entry point. It is a concatenation of
+/// records made of the flags (`u32`, little endian), the length of the entry point name (`u32`,
+/// little endian) and the name itself.
+pub const MANIFEST_SECTION: &str = "casper_manifest";
+
+/// Encodes the manifest record of a single entry point.
+pub fn encode_manifest_record(name: &str, flags: EntryPointFlags) -> Vec<u8> {
+    let mut record = Vec::with_capacity(8 + name.len());
+    record.extend_from_slice(&flags.bits().to_le_bytes());
+    record.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    record.extend_from_slice(name.as_bytes());
+    record
+}
+
+/// Looks up the flags of the entry point called `name` in the contents of a manifest section.
+///
+/// Returns `None` if the entry point is not listed, or if the manifest is malformed.
+pub fn find_entry_point_flags(mut manifest: &[u8], name: &str) -> Option<EntryPointFlags> {
+    while !manifest.is_empty() {
+        let (flags, rest) = manifest.split_first_chunk::<4>()?;
+        let (name_len, rest) = rest.split_first_chunk::<4>()?;
+        let name_len = usize::try_from(u32::from_le_bytes(*name_len)).ok()?;
+        if rest.len() < name_len {
+            return None;
+        }
+        let (record_name, rest) = rest.split_at(name_len);
+        if record_name == name.as_bytes() {
+            return Some(EntryPointFlags::from_bits_retain(u32::from_le_bytes(
+                *flags,
+            )));
+        }
+        manifest = rest;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_point_payment_predicates() {
+        assert!(pays_caller(ENTRY_POINT_PAYMENT_CALLER));
+        assert!(!pays_self(ENTRY_POINT_PAYMENT_CALLER));
+
+        assert!(!pays_caller(ENTRY_POINT_PAYMENT_DIRECT_INVOCATION_ONLY));
+        assert!(pays_self(ENTRY_POINT_PAYMENT_DIRECT_INVOCATION_ONLY));
+
+        assert!(!pays_caller(ENTRY_POINT_PAYMENT_SELF_ONWARD));
+        assert!(pays_self(ENTRY_POINT_PAYMENT_SELF_ONWARD));
+
+        assert!(!pays_caller(u8::MAX));
+        assert!(!pays_self(u8::MAX));
+    }
+
+    #[test]
+    fn should_find_entry_point_flags_in_manifest() {
+        let mut manifest = encode_manifest_record("new", EntryPointFlags::CONSTRUCTOR);
+        manifest.extend(encode_manifest_record("deposit", EntryPointFlags::PAYABLE));
+
+        assert_eq!(
+            find_entry_point_flags(&manifest, "new"),
+            Some(EntryPointFlags::CONSTRUCTOR)
+        );
+        assert_eq!(
+            find_entry_point_flags(&manifest, "deposit"),
+            Some(EntryPointFlags::PAYABLE)
+        );
+        assert_eq!(find_entry_point_flags(&manifest, "withdraw"), None);
+        assert_eq!(find_entry_point_flags(&manifest[..10], "deposit"), None);
+    }
+}
@@ -1,3 +1,4 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use safe_transmute::TriviallyTransmutable;
 
 #[derive(Clone, Copy)]
@@ -9,6 +10,50 @@ pub struct EnvInfo {
     pub caller_kind: u32,
     pub callee_addr: [u8; 32],
     pub callee_kind: u32,
+    /// Execution phase tag, see [`Phase::tag`]. Stored as `u64` so the struct has no padding
+    /// bytes.
+    pub phase: u64,
 }
 
 unsafe impl TriviallyTransmutable for EnvInfo {}
+
+/// The phase in which a given contract is executing.
+///
+/// Shared between the host and the contracts so both sides agree on the encoding of
+/// [`EnvInfo::phase`]. Tags match the discriminants of the host's `casper_types::Phase`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Set while committing the genesis or upgrade configurations.
+    System,
+    /// Set while executing the payment code of a transaction.
+    Payment,
+    /// Set while executing the session code of a transaction.
+    Session,
+    /// Set while finalizing payment at the end of a transaction.
+    FinalizePayment,
+}
+
+impl Phase {
+    /// Get the tag of the phase, as stored in [`EnvInfo::phase`].
+    #[must_use]
+    pub fn tag(&self) -> u64 {
+        match self {
+            Phase::System => 0,
+            Phase::Payment => 1,
+            Phase::Session => 2,
+            Phase::FinalizePayment => 3,
+        }
+    }
+
+    /// Get the phase out of a tag returned by [`Phase::tag`], or `None` for an unknown tag.
+    #[must_use]
+    pub fn from_tag(tag: u64) -> Option<Self> {
+        match tag {
+            0 => Some(Phase::System),
+            1 => Some(Phase::Payment),
+            2 => Some(Phase::Session),
+            3 => Some(Phase::FinalizePayment),
+            _ => None,
+        }
+    }
+}
@@ -2,9 +2,15 @@
 //!
 //! API inspired by `std::io::Error` and `std::io::ErrorKind` but somewhat more memory efficient.
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use thiserror::Error;
 
-#[derive(Debug, Default, PartialEq)]
+/// Result of a host function call.
+///
+/// Serializes with the host error code as the tag, so a contract can revert with it and its
+/// caller can decode it.
+#[derive(Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+#[borsh(use_discriminant = true)]
 #[non_exhaustive]
 #[repr(u32)]
 pub enum CommonResult {
@@ -103,12 +109,28 @@ pub enum TrapCode {
     UnreachableCodeReached,
 }
 
+/// Error a contract raises on purpose rather than by trapping.
+///
+/// The host reports it through [`CallError::Host`] and passes it to a calling contract serialized
+/// in place of the output of the call, which the SDK turns back into this type.
+#[derive(Debug, Clone, PartialEq, Eq, Error, BorshSerialize, BorshDeserialize)]
+pub enum HostError {
+    /// Tokens were transferred to an entry point which is not payable.
+    #[error("entry point is not payable")]
+    NotPayable,
+    /// The contract aborted with a message.
+    #[error("aborted: {0}")]
+    Aborted(String),
+}
+
 pub const CALLEE_SUCCEEDED: u32 = 0;
 pub const CALLEE_REVERTED: u32 = 1;
 pub const CALLEE_TRAPPED: u32 = 2;
 pub const CALLEE_GAS_DEPLETED: u32 = 3;
 pub const CALLEE_NOT_CALLABLE: u32 = 4;
 pub const CALLEE_HOST_ERROR: u32 = 5;
+pub const CALLEE_ABORTED: u32 = 6;
+pub const CALLEE_NOT_PAYABLE: u32 = 7;
 
 /// Represents the result of a host function call.
 ///
@@ -130,6 +152,12 @@ pub enum CallError {
     /// Encountered a host function error.
     #[error("internal host")]
     InternalHost,
+    /// Called contract raised a host error, for example by aborting with a message.
+    #[error("callee {0}")]
+    Host(HostError),
+    /// Tokens were transferred to an entry point which is not payable.
+    #[error("not payable")]
+    NotPayable,
 }
 
 impl CallError {
@@ -142,6 +170,9 @@ impl CallError {
             Self::CalleeGasDepleted => CALLEE_GAS_DEPLETED,
             Self::NotCallable => CALLEE_NOT_CALLABLE,
             Self::InternalHost => CALLEE_HOST_ERROR,
+            Self::Host(HostError::NotPayable) => CALLEE_NOT_PAYABLE,
+            Self::Host(HostError::Aborted(_)) => CALLEE_ABORTED,
+            Self::NotPayable => CALLEE_NOT_PAYABLE,
         }
     }
 }
@@ -173,4 +204,12 @@ mod tests {
         let error = CommonResult::from(10);
         assert_eq!(error, CommonResult::Other(10));
     }
+
+    #[test]
+    fn test_host_error_into_u32() {
+        let error = CallError::Host(HostError::Aborted("out of stock".into()));
+        assert_eq!(error.into_u32(), CALLEE_ABORTED);
+        let error = CallError::Host(HostError::NotPayable);
+        assert_eq!(error.into_u32(), CALLEE_NOT_PAYABLE);
+    }
 }
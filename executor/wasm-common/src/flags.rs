@@ -18,6 +18,8 @@ bitflags! {
     pub struct EntryPointFlags: u32 {
         const CONSTRUCTOR = 0x0000_0001;
         const FALLBACK = 0x0000_0002;
+        /// The entry point accepts tokens transferred along with the call.
+        const PAYABLE = 0x0000_0004;
     }
 
     /// Flags that can be passed as part of calling contracts.
@@ -1,3 +1,5 @@
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use borsh::BorshSerialize;
 use num_derive::{FromPrimitive, ToPrimitive};
 
 #[repr(u64)]
@@ -50,6 +52,61 @@ impl Keyspace<'_> {
     }
 }
 
+/// Builds the payload of a [`Keyspace::Context`] out of typed components.
+///
+/// Each component is length-prefixed (as a little-endian `u32`) before being appended, so that
+/// composite keys such as `("ab", "c")` and `("a", "bc")` never collide.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyspaceBuilder {
+    bytes: Vec<u8>,
+}
+
+impl KeyspaceBuilder {
+    /// Creates an empty builder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a raw byte component.
+    #[must_use]
+    pub fn bytes(mut self, component: &[u8]) -> Self {
+        let length = u32::try_from(component.len()).expect("component too large");
+        self.bytes.extend_from_slice(&length.to_le_bytes());
+        self.bytes.extend_from_slice(component);
+        self
+    }
+
+    /// Appends a component serialized with borsh.
+    #[must_use]
+    pub fn push<T: BorshSerialize>(self, component: &T) -> Self {
+        let bytes = borsh::to_vec(component).expect("Serialization to vec is infallible");
+        self.bytes(&bytes)
+    }
+
+    /// Appends the blake2b hash of a component serialized with borsh.
+    ///
+    /// Useful for large components where only a fixed size key is desired.
+    #[must_use]
+    pub fn hashed<T: BorshSerialize>(self, component: &T) -> Self {
+        let bytes = borsh::to_vec(component).expect("Serialization to vec is infallible");
+        let hash: [u8; 32] = Blake2b::<U32>::digest(bytes).into();
+        self.bytes(&hash)
+    }
+
+    /// Returns the bytes accumulated so far.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns a [`Keyspace::Context`] borrowing the accumulated bytes.
+    #[must_use]
+    pub fn build(&self) -> Keyspace<'_> {
+        Keyspace::Context(&self.bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +157,43 @@ mod tests {
         let keyspace: Keyspace = Keyspace::PaymentInfo(name);
         assert_eq!(keyspace.as_u64(), 3);
     }
+
+    #[test]
+    fn test_keyspace_builder_matches_raw_bytes() {
+        let builder = KeyspaceBuilder::new().bytes(b"balances").push(&42u64);
+
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&8u32.to_le_bytes());
+        raw.extend_from_slice(b"balances");
+        raw.extend_from_slice(&8u32.to_le_bytes());
+        raw.extend_from_slice(&42u64.to_le_bytes());
+
+        assert_eq!(builder.build(), Keyspace::Context(&raw));
+    }
+
+    #[test]
+    fn test_keyspace_builder_same_components_are_equal() {
+        let first = KeyspaceBuilder::new().push(&"allowances").push(&[1u8; 32]);
+        let second = KeyspaceBuilder::new()
+            .push(&String::from("allowances"))
+            .push(&[1u8; 32]);
+        assert_eq!(first.build(), second.build());
+    }
+
+    #[test]
+    fn test_keyspace_builder_components_do_not_collide() {
+        let first = KeyspaceBuilder::new().bytes(b"ab").bytes(b"c");
+        let second = KeyspaceBuilder::new().bytes(b"a").bytes(b"bc");
+        assert_ne!(first.build(), second.build());
+    }
+
+    #[test]
+    fn test_keyspace_builder_hashed() {
+        let hashed = KeyspaceBuilder::new().hashed(&"a long component");
+        assert_eq!(hashed.as_bytes().len(), 4 + 32);
+        assert_eq!(
+            hashed,
+            KeyspaceBuilder::new().hashed(&String::from("a long component"))
+        );
+    }
 }
@@ -1,11 +1,17 @@
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 use bytes::Bytes;
-use casper_executor_wasm_interface::executor::Executor;
-use casper_storage::{global_state::GlobalStateReader, AddressGenerator, TrackingCopy};
+use casper_executor_wasm_interface::{
+    executor::{CallFrame, Executor, SystemInteraction},
+    InstructionBudget, RandomCounter,
+};
+use casper_storage::{
+    global_state::GlobalStateReader, tracking_copy::TrackingCopyCache, AddressGenerator,
+    TrackingCopy,
+};
 use casper_types::{
-    account::AccountHash, BlockTime, Key, MessageLimits, StorageCosts, TransactionHash,
-    WasmV2Config,
+    account::AccountHash, contract_messages::Messages, execution::Effects, BlockTime, EntityAddr,
+    HashAddr, Key, MessageLimits, Phase, StorageCosts, TransactionHash, WasmV2Config,
 };
 use parking_lot::RwLock;
 
@@ -16,8 +22,13 @@ pub struct Context<S: GlobalStateReader, E: Executor> {
     /// The address of the addressable entity that is currently executing the contract or session
     /// code.
     pub caller: Key,
+    /// The typed entity address of [`Context::caller`], computed once with
+    /// [`Key::as_entity_addr`] when the context is built.
+    pub caller_entity: EntityAddr,
     /// The address of the addressable entity that is being called.
     pub callee: Key,
+    /// The address of the bytecode being executed, or `None` when executing session code.
+    pub byte_code_addr: Option<HashAddr>,
     /// The state of the global state at the time of the call based on the currently executing
     /// contract or session address.
     // pub state_address: Address,
@@ -33,4 +44,40 @@ pub struct Context<S: GlobalStateReader, E: Executor> {
     pub chain_name: Arc<str>,
     pub input: Bytes,
     pub block_time: BlockTime,
+    /// The phase in which the contract or session code is executed.
+    pub phase: Phase,
+    /// The instruction budget shared by all call frames of the execution, if any.
+    pub instruction_budget: Option<InstructionBudget>,
+    /// The pseudo-random byte request counter shared by all call frames of the execution.
+    pub random_counter: RandomCounter,
+    /// Call frames completed by the successful sub-calls of this frame, in order of completion.
+    pub frames: Vec<CallFrame>,
+    /// Positions in the tracking copy effects of the effects merged from successful sub-calls.
+    pub sub_call_effects: Vec<Range<usize>>,
+    /// Calls into system contracts made by this frame and its successful sub-calls, in order.
+    pub system_interactions: Vec<SystemInteraction>,
+}
+
+impl<S: GlobalStateReader, E: Executor> Context<S, E> {
+    /// Applies the changes of a successful sub-call to this frame, which takes over the call
+    /// frames of the sub-call one level deeper along with its system interactions.
+    pub fn apply_sub_call(
+        &mut self,
+        effects: Effects,
+        cache: TrackingCopyCache,
+        messages: Messages,
+        frames: Vec<CallFrame>,
+        system_interactions: Vec<SystemInteraction>,
+    ) {
+        let start = self.tracking_copy.effects_len();
+        self.tracking_copy.apply_changes(effects, cache, messages);
+        self.sub_call_effects
+            .push(start..self.tracking_copy.effects_len());
+        self.frames
+            .extend(frames.into_iter().map(|frame| CallFrame {
+                depth: frame.depth + 1,
+                ..frame
+            }));
+        self.system_interactions.extend(system_interactions);
+    }
 }
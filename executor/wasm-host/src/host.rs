@@ -7,19 +7,23 @@ use casper_executor_wasm_common::{
         ENTRY_POINT_PAYMENT_CALLER, ENTRY_POINT_PAYMENT_DIRECT_INVOCATION_ONLY,
         ENTRY_POINT_PAYMENT_SELF_ONWARD,
     },
-    env_info::EnvInfo,
+    env_info::{self, EnvInfo},
     error::{
-        CallError, CALLEE_NOT_CALLABLE, CALLEE_SUCCEEDED, CALLEE_TRAPPED, HOST_ERROR_INVALID_DATA,
-        HOST_ERROR_INVALID_INPUT, HOST_ERROR_MAX_MESSAGES_PER_BLOCK_EXCEEDED,
-        HOST_ERROR_MESSAGE_TOPIC_FULL, HOST_ERROR_NOT_FOUND, HOST_ERROR_PAYLOAD_TOO_LONG,
-        HOST_ERROR_SUCCESS, HOST_ERROR_TOO_MANY_TOPICS, HOST_ERROR_TOPIC_TOO_LONG,
+        CallError, CALLEE_HOST_ERROR, CALLEE_NOT_CALLABLE, CALLEE_SUCCEEDED, CALLEE_TRAPPED,
+        HOST_ERROR_INVALID_DATA, HOST_ERROR_INVALID_INPUT,
+        HOST_ERROR_MAX_MESSAGES_PER_BLOCK_EXCEEDED, HOST_ERROR_MESSAGE_TOPIC_FULL,
+        HOST_ERROR_NOT_FOUND, HOST_ERROR_PAYLOAD_TOO_LONG, HOST_ERROR_SUCCESS,
+        HOST_ERROR_TOO_MANY_TOPICS, HOST_ERROR_TOPIC_TOO_LONG,
     },
     flags::ReturnFlags,
     keyspace::{Keyspace, KeyspaceTag},
 };
 use casper_executor_wasm_interface::{
-    executor::{ExecuteError, ExecuteRequestBuilder, ExecuteResult, ExecutionKind, Executor},
-    u32_from_host_result, Caller, InternalHostError, VMError, VMResult,
+    executor::{
+        ExecuteError, ExecuteRequestBuilder, ExecuteResult, ExecutionKind, Executor,
+        SystemInteraction,
+    },
+    u32_from_host_result, Caller, GasUsage, InternalHostError, VMError, VMResult,
 };
 use casper_storage::{
     global_state::GlobalStateReader,
@@ -33,8 +37,8 @@ use casper_types::{
     AddressableEntity, BlockGlobalAddr, BlockHash, BlockTime, ByteCode, ByteCodeAddr, ByteCodeHash,
     ByteCodeKind, CLType, CLValue, ContractRuntimeTag, Digest, EntityAddr, EntityEntryPoint,
     EntityKind, EntryPointAccess, EntryPointAddr, EntryPointPayment, EntryPointType,
-    EntryPointValue, HashAddr, HostFunctionV2, Key, Package, PackageHash, ProtocolVersion,
-    StoredValue, URef, U512,
+    EntryPointValue, Gas, HashAddr, HostFunctionV2, Key, Package, PackageHash, Phase,
+    ProtocolVersion, StoredValue, URef, U512,
 };
 use either::Either;
 use num_derive::FromPrimitive;
@@ -78,12 +82,13 @@ fn charge_gas_storage<S: GlobalStateReader, E: Executor>(
     Ok(())
 }
 
-/// Consumes a set amount of gas for the specified host function and weights
+/// Consumes a set amount of gas for the specified host function and weights, returning the amount
+/// consumed.
 fn charge_host_function_call<S, E, const N: usize>(
     caller: &mut impl Caller<Context = Context<S, E>>,
     host_function: &HostFunctionV2<[u64; N]>,
     weights: [u64; N],
-) -> VMResult<()>
+) -> VMResult<Gas>
 where
     S: GlobalStateReader,
     E: Executor,
@@ -94,7 +99,7 @@ where
     };
 
     caller.consume_gas(cost.value().as_u64())?;
-    Ok(())
+    Ok(cost)
 }
 
 /// Writes a message to the global state and charges for storage used.
@@ -423,7 +428,7 @@ pub fn casper_read<S: GlobalStateReader, E: Executor>(
             // I think if we were to pursue this we'd add a new `Keyspace` enum variant for each old
             // VM supported Key types (i.e. URef, Dictionary perhaps) for some period of time, then
             // deprecate this.
-            todo!("Unsupported {stored_value:?}")
+            todo!("Unsupported {}", stored_value.tag().name())
         }
         Ok(None) => return Ok(HOST_ERROR_NOT_FOUND), // Entry does not exists
         Err(error) => {
@@ -561,6 +566,26 @@ pub fn casper_return<S: GlobalStateReader, E: Executor>(
     Err(VMError::Return { flags, data })
 }
 
+/// Aborts the execution of a smart contract with a message.
+///
+/// Unlike a trap, the message is preserved and reported to the caller.
+pub fn casper_abort<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    message_ptr: u32,
+    message_size: u32,
+) -> VMResult<()> {
+    let abort_cost = caller.context().config.host_function_costs().abort;
+    charge_host_function_call(
+        &mut caller,
+        &abort_cost,
+        [u64::from(message_ptr), u64::from(message_size)],
+    )?;
+
+    let vec = caller.memory_read(message_ptr, message_size.try_into_wrapped()?)?;
+    let message = String::from_utf8_lossy(&vec).into_owned();
+    Err(VMError::Abort(message))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
     mut caller: impl Caller<Context = Context<S, E>>,
@@ -593,6 +618,98 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
         ],
     )?;
 
+    create_contract(
+        &mut caller,
+        code_ptr,
+        code_len,
+        transferred_value,
+        entry_point_ptr,
+        entry_point_len,
+        input_ptr,
+        input_len,
+        seed_ptr,
+        seed_len,
+        result_ptr,
+        0,
+        0,
+    )
+}
+
+/// Creates a contract like [`casper_create`], and passes the data a reverting constructor returned
+/// to the `cb_alloc` callback, the same way [`casper_call`] passes the callee's output.
+#[allow(clippy::too_many_arguments)]
+pub fn casper_try_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    code_ptr: u32,
+    code_len: u32,
+    transferred_value: u64,
+    entry_point_ptr: u32,
+    entry_point_len: u32,
+    input_ptr: u32,
+    input_len: u32,
+    seed_ptr: u32,
+    seed_len: u32,
+    result_ptr: u32,
+    cb_alloc: u32,
+    cb_ctx: u32,
+) -> VMResult<u32> {
+    let try_create_cost = caller.context().config.host_function_costs().try_create;
+    charge_host_function_call(
+        &mut caller,
+        &try_create_cost,
+        [
+            u64::from(code_ptr),
+            u64::from(code_len),
+            transferred_value,
+            u64::from(entry_point_ptr),
+            u64::from(entry_point_len),
+            u64::from(input_ptr),
+            u64::from(input_len),
+            u64::from(seed_ptr),
+            u64::from(seed_len),
+            u64::from(result_ptr),
+            u64::from(cb_alloc),
+            u64::from(cb_ctx),
+        ],
+    )?;
+
+    create_contract(
+        &mut caller,
+        code_ptr,
+        code_len,
+        transferred_value,
+        entry_point_ptr,
+        entry_point_len,
+        input_ptr,
+        input_len,
+        seed_ptr,
+        seed_len,
+        result_ptr,
+        cb_alloc,
+        cb_ctx,
+    )
+}
+
+/// Creates a contract and calls its constructor, if any.
+///
+/// Data returned by a reverting constructor is written through `cb_alloc` unless both `cb_alloc`
+/// and `cb_ctx` are zero.
+#[allow(clippy::too_many_arguments)]
+fn create_contract<S: GlobalStateReader + 'static, E: Executor + 'static>(
+    caller: &mut impl Caller<Context = Context<S, E>>,
+    code_ptr: u32,
+    code_len: u32,
+    transferred_value: u64,
+    entry_point_ptr: u32,
+    entry_point_len: u32,
+    input_ptr: u32,
+    input_len: u32,
+    seed_ptr: u32,
+    seed_len: u32,
+    result_ptr: u32,
+    cb_alloc: u32,
+    cb_ctx: u32,
+) -> VMResult<u32> {
     let code = if code_ptr != 0 {
         caller
             .memory_read(code_ptr, code_len as usize)
@@ -647,6 +764,16 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
     let bytecode = ByteCode::new(ByteCodeKind::V2CasperWasm, code.clone().into());
     let bytecode_addr = ByteCodeAddr::V2CasperWasm(bytecode_hash);
 
+    // Refuse denied bytecode before anything is written, so that no contract is left behind.
+    if let Err(error) = caller
+        .context()
+        .executor
+        .check_bytecode_allowed(|| bytecode_addr)
+    {
+        error!(%error, "denied bytecode while performing create");
+        return Ok(CALLEE_NOT_CALLABLE);
+    }
+
     // 1. Store package hash
     let mut smart_contract_package = Package::default();
 
@@ -683,14 +810,14 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
     }
 
     metered_write(
-        &mut caller,
+        caller,
         Key::SmartContract(smart_contract_addr),
         StoredValue::SmartContract(smart_contract_package),
     )?;
 
     // 2. Store wasm
     metered_write(
-        &mut caller,
+        caller,
         Key::ByteCode(bytecode_addr),
         StoredValue::ByteCode(bytecode),
     )?;
@@ -700,7 +827,6 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
     let entity_addr = EntityAddr::SmartContract(contract_hash);
     let addressable_entity_key = Key::AddressableEntity(entity_addr);
 
-    // TODO: abort(str) as an alternative to trap
     let address_generator = Arc::clone(&caller.context().address_generator);
     let transaction_hash = caller.context().transaction_hash;
     let main_purse: URef = match system::mint_mint(
@@ -729,7 +855,7 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
     );
 
     metered_write(
-        &mut caller,
+        caller,
         addressable_entity_key,
         StoredValue::AddressableEntity(addressable_entity),
     )?;
@@ -756,10 +882,13 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
                 // We're using shared address generator there as we need to preserve and advance the
                 // state of deterministic address generator across chain of calls.
                 .with_shared_address_generator(Arc::clone(&caller.context().address_generator))
+                .with_shared_instruction_budget(caller.context().instruction_budget.clone())
+                .with_shared_random_counter(caller.context().random_counter.clone())
                 .with_chain_name(caller.context().chain_name.clone())
                 .with_block_time(caller.context().block_time)
                 .with_state_hash(Digest::from_raw([0; 32])) // TODO: Carry on state root hash
                 .with_block_height(1) // TODO: Carry on block height
+                .with_phase(caller.context().phase)
                 .with_parent_block_hash(BlockHash::new(Digest::from_raw([0; 32]))) // TODO: Carry on parent block hash
                 .build()
                 .map_err(|_| InternalHostError::ExecuteRequestBuildFailure)?;
@@ -778,18 +907,39 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
                     effects,
                     cache,
                     messages,
+                    reads: _,
+                    frames,
+                    system_interactions,
                 }) => {
                     // output
                     caller.consume_gas(gas_usage.gas_spent())?;
 
                     if let Some(host_error) = host_error {
+                        // Surface the data the constructor reverted with so the caller can decode
+                        // the reason of the failure.
+                        if let Some(output) = output {
+                            let out_ptr: u32 = if cb_alloc != 0 {
+                                caller.alloc(cb_alloc, output.len(), cb_ctx)?
+                            } else {
+                                // treats alloc_ctx as data
+                                cb_ctx
+                            };
+
+                            if out_ptr != 0 {
+                                caller.memory_write(out_ptr, &output)?;
+                            }
+                        }
+
                         return Ok(host_error.into_u32());
                     }
 
-                    caller
-                        .context_mut()
-                        .tracking_copy
-                        .apply_changes(effects, cache, messages);
+                    caller.context_mut().apply_sub_call(
+                        effects,
+                        cache,
+                        messages,
+                        frames,
+                        system_interactions,
+                    );
 
                     output
                 }
@@ -798,6 +948,30 @@ pub fn casper_create<S: GlobalStateReader + 'static, E: Executor + 'static>(
                     // phase when the contract was stored in the global state.
                     todo!()
                 }
+                Err(ExecuteError::InvalidContractGraph { key, reason }) => {
+                    // As in `casper_call`, the constructor is simply not callable.
+                    error!(%key, reason, "invalid contract graph while performing create");
+                    return Ok(CALLEE_NOT_CALLABLE);
+                }
+                Err(error @ ExecuteError::UnexpectedEntityKind { .. }) => {
+                    error!(%error, "unexpected entity kind while performing create");
+                    return Ok(CALLEE_NOT_CALLABLE);
+                }
+                Err(error @ ExecuteError::VersionDisabled { .. }) => {
+                    error!(%error, "disabled version while performing create");
+                    return Ok(CALLEE_HOST_ERROR);
+                }
+                Err(error @ ExecuteError::BytecodeDenied { .. }) => {
+                    error!(%error, "denied bytecode while performing create");
+                    return Ok(CALLEE_NOT_CALLABLE);
+                }
+                Err(error @ ExecuteError::UnexpectedCaller { .. }) => {
+                    error!(%error, "unexpected caller while performing create");
+                    return Ok(CALLEE_NOT_CALLABLE);
+                }
+                Err(ExecuteError::InstructionBudgetExceeded) => {
+                    return Err(VMError::InstructionBudgetExceeded);
+                }
             }
         }
         None => None,
@@ -898,10 +1072,13 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
         // We're using shared address generator there as we need to preserve and advance the state
         // of deterministic address generator across chain of calls.
         .with_shared_address_generator(Arc::clone(&caller.context().address_generator))
+        .with_shared_instruction_budget(caller.context().instruction_budget.clone())
+        .with_shared_random_counter(caller.context().random_counter.clone())
         .with_chain_name(caller.context().chain_name.clone())
         .with_block_time(caller.context().block_time)
         .with_state_hash(Digest::from_raw([0; 32])) // TODO: Carry on state root hash
         .with_block_height(1) // TODO: Carry on block height
+        .with_phase(caller.context().phase)
         .with_parent_block_hash(BlockHash::new(Digest::from_raw([0; 32]))) // TODO: Carry on parent block hash
         .build()
         .map_err(|_| InternalHostError::ExecuteRequestBuildFailure)?;
@@ -918,7 +1095,19 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
             effects,
             cache,
             messages,
+            reads: _,
+            frames,
+            system_interactions,
         }) => {
+            // A callee which raised a host error has no output, so the error is passed along in
+            // its place. The result code tells the caller which of the two it received.
+            let output = match &host_error {
+                Some(CallError::Host(host_error)) => Some(Bytes::from(
+                    borsh::to_vec(host_error).map_err(|_| InternalHostError::TypeConversion)?,
+                )),
+                _ => output,
+            };
+
             if let Some(output) = output {
                 let out_ptr: u32 = if cb_alloc != 0 {
                     caller.alloc(cb_alloc, output.len(), cb_ctx)?
@@ -935,10 +1124,13 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
             let host_result = match host_error {
                 Some(host_error) => Err(host_error),
                 None => {
-                    caller
-                        .context_mut()
-                        .tracking_copy
-                        .apply_changes(effects, cache, messages);
+                    caller.context_mut().apply_sub_call(
+                        effects,
+                        cache,
+                        messages,
+                        frames,
+                        system_interactions,
+                    );
                     Ok(())
                 }
             };
@@ -950,6 +1142,47 @@ pub fn casper_call<S: GlobalStateReader + 'static, E: Executor + 'static>(
             // when the contract was stored in the global state.
             unreachable!("Preparation error: {:?}", preparation_error)
         }
+        Err(ExecuteError::InvalidContractGraph { key, reason }) => {
+            // Corrupted contract data must not bring down the caller; the callee is simply not
+            // callable.
+            error!(%key, reason, "invalid contract graph while performing call");
+            (
+                GasUsage::new(gas_limit, gas_limit),
+                Err(CallError::NotCallable),
+            )
+        }
+        Err(error @ ExecuteError::UnexpectedEntityKind { .. }) => {
+            error!(%error, "unexpected entity kind while performing call");
+            (
+                GasUsage::new(gas_limit, gas_limit),
+                Err(CallError::NotCallable),
+            )
+        }
+        Err(error @ ExecuteError::VersionDisabled { .. }) => {
+            error!(%error, "disabled version while performing call");
+            (
+                GasUsage::new(gas_limit, gas_limit),
+                Err(CallError::NotCallable),
+            )
+        }
+        Err(error @ ExecuteError::BytecodeDenied { .. }) => {
+            error!(%error, "denied bytecode while performing call");
+            (
+                GasUsage::new(gas_limit, gas_limit),
+                Err(CallError::NotCallable),
+            )
+        }
+        Err(error @ ExecuteError::UnexpectedCaller { .. }) => {
+            error!(%error, "unexpected caller while performing call");
+            (
+                GasUsage::new(gas_limit, gas_limit),
+                Err(CallError::NotCallable),
+            )
+        }
+        Err(ExecuteError::InstructionBudgetExceeded) => {
+            // Exceeding the budget aborts the whole execution, so the caller is stopped as well.
+            return Err(VMError::InstructionBudgetExceeded);
+        }
     };
 
     let gas_spent = gas_usage
@@ -1093,13 +1326,39 @@ pub fn casper_env_balance<S: GlobalStateReader, E: Executor>(
 }
 
 pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
+    caller: impl Caller<Context = Context<S, E>>,
+    entity_addr_ptr: u32,
+    entity_addr_len: u32,
+    amount_ptr: u32,
+) -> VMResult<u32> {
+    transfer(caller, entity_addr_ptr, entity_addr_len, amount_ptr, None)
+}
+
+pub fn casper_transfer_with_id<S: GlobalStateReader + 'static, E: Executor>(
+    caller: impl Caller<Context = Context<S, E>>,
+    entity_addr_ptr: u32,
+    entity_addr_len: u32,
+    amount_ptr: u32,
+    id: u64,
+) -> VMResult<u32> {
+    transfer(
+        caller,
+        entity_addr_ptr,
+        entity_addr_len,
+        amount_ptr,
+        Some(id),
+    )
+}
+
+fn transfer<S: GlobalStateReader + 'static, E: Executor>(
     mut caller: impl Caller<Context = Context<S, E>>,
     entity_addr_ptr: u32,
     entity_addr_len: u32,
     amount_ptr: u32,
+    id: Option<u64>,
 ) -> VMResult<u32> {
     let transfer_cost = caller.context().config.host_function_costs().transfer;
-    charge_host_function_call(
+    let gas = charge_host_function_call(
         &mut caller,
         &transfer_cost,
         [
@@ -1120,7 +1379,7 @@ pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
         u64::from_le_bytes(amount_bytes)
     };
 
-    let (target_entity_addr, _runtime_footprint) = {
+    let (account_hash, target_entity_addr, _runtime_footprint) = {
         let entity_addr = caller.memory_read(entity_addr_ptr, entity_addr_len as usize)?;
         debug_assert_eq!(entity_addr.len(), 32);
 
@@ -1143,7 +1402,7 @@ pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
                 panic!("Error while reading from storage")
             }
         };
-        (entity_addr, runtime_footprint)
+        (account_hash, entity_addr, runtime_footprint)
     };
 
     let callee_addressable_entity_key = match caller.context().callee {
@@ -1155,7 +1414,7 @@ pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
                         .into_t::<Key>()
                         .map_err(|_| InternalHostError::TypeConversion)?
                 }
-                Ok(Some(other)) => panic!("should be cl value but got {other:?}"),
+                Ok(Some(other)) => panic!("should be cl value but got {}", other.tag().name()),
                 Ok(None) => return Ok(u32_from_host_result(Err(CallError::NotCallable))),
                 Err(error) => {
                     error!(
@@ -1183,7 +1442,9 @@ pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
                         }
                     }
                 }
-                Ok(Some(other)) => panic!("should be smart contract but got {other:?}"),
+                Ok(Some(other)) => {
+                    panic!("should be smart contract but got {}", other.tag().name())
+                }
                 Ok(None) => return Ok(u32_from_host_result(Err(CallError::NotCallable))),
                 Err(error) => {
                     error!(
@@ -1236,7 +1497,16 @@ pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
         target: target_purse,
         amount: U512::from(amount),
         maybe_to: None,
-        id: None,
+        id,
+    };
+
+    let mint_transfer = SystemInteraction::MintTransfer {
+        source: args.source,
+        target: args.target,
+        amount: args.amount,
+        to: Some(account_hash),
+        gas,
+        id,
     };
 
     let result = system::mint_transfer(
@@ -1245,6 +1515,9 @@ pub fn casper_transfer<S: GlobalStateReader + 'static, E: Executor>(
         address_generator,
         args,
     );
+    if result.is_ok() {
+        caller.context_mut().system_interactions.push(mint_transfer);
+    }
 
     Ok(u32_from_host_result(result))
 }
@@ -1328,7 +1601,9 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
                         }
                     }
                 }
-                Ok(Some(other)) => panic!("should be smart contract but got {other:?}"),
+                Ok(Some(other)) => {
+                    panic!("should be smart contract but got {}", other.tag().name())
+                }
                 Ok(None) => return Ok(CALLEE_NOT_CALLABLE),
                 Err(error) => {
                     error!(
@@ -1402,10 +1677,13 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
             // We're using shared address generator there as we need to preserve and advance the
             // state of deterministic address generator across chain of calls.
             .with_shared_address_generator(Arc::clone(&caller.context().address_generator))
+            .with_shared_instruction_budget(caller.context().instruction_budget.clone())
+            .with_shared_random_counter(caller.context().random_counter.clone())
             .with_chain_name(caller.context().chain_name.clone())
             .with_block_time(caller.context().block_time)
             .with_state_hash(Digest::from_raw([0; 32])) // TODO: Carry on state root hash
             .with_block_height(1) // TODO: Carry on block height
+            .with_phase(caller.context().phase)
             .with_parent_block_hash(BlockHash::new(Digest::from_raw([0; 32]))) // TODO: Carry on parent block hash
             .build()
             .map_err(|_| InternalHostError::ExecuteRequestBuildFailure)?;
@@ -1424,6 +1702,9 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
                 effects,
                 cache,
                 messages,
+                reads: _,
+                frames,
+                system_interactions,
             }) => {
                 // output
                 caller.consume_gas(gas_usage.gas_spent())?;
@@ -1432,10 +1713,13 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
                     return Ok(host_error.into_u32());
                 }
 
-                caller
-                    .context_mut()
-                    .tracking_copy
-                    .apply_changes(effects, cache, messages);
+                caller.context_mut().apply_sub_call(
+                    effects,
+                    cache,
+                    messages,
+                    frames,
+                    system_interactions,
+                );
 
                 if let Some(output) = output {
                     info!(
@@ -1453,12 +1737,100 @@ pub fn casper_upgrade<S: GlobalStateReader + 'static, E: Executor>(
                 );
                 return Ok(CALLEE_NOT_CALLABLE);
             }
+            Err(ExecuteError::InvalidContractGraph { key, reason }) => {
+                error!(%key, reason, "invalid contract graph while performing upgrade");
+                return Ok(CALLEE_NOT_CALLABLE);
+            }
+            Err(error @ ExecuteError::UnexpectedEntityKind { .. }) => {
+                error!(%error, "unexpected entity kind while performing upgrade");
+                return Ok(CALLEE_NOT_CALLABLE);
+            }
+            Err(error @ ExecuteError::VersionDisabled { .. }) => {
+                error!(%error, "disabled version while performing upgrade");
+                return Ok(CALLEE_NOT_CALLABLE);
+            }
+            Err(error @ ExecuteError::BytecodeDenied { .. }) => {
+                error!(%error, "denied bytecode while performing upgrade");
+                return Ok(CALLEE_NOT_CALLABLE);
+            }
+            Err(error @ ExecuteError::UnexpectedCaller { .. }) => {
+                error!(%error, "unexpected caller while performing upgrade");
+                return Ok(CALLEE_NOT_CALLABLE);
+            }
+            Err(ExecuteError::InstructionBudgetExceeded) => {
+                return Err(VMError::InstructionBudgetExceeded);
+            }
         }
     }
 
     Ok(CALLEE_SUCCEEDED)
 }
 
+/// Writes pseudo-random bytes derived from the transaction hash, the address of the callee and the
+/// random counter of the execution, which is advanced on every call.
+///
+/// The output is deterministic, so it must not be relied upon as a source of secure randomness.
+pub fn casper_random_bytes<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    out_ptr: u32,
+    out_size: u32,
+) -> VMResult<u32> {
+    let random_bytes_cost = caller.context().config.host_function_costs().random_bytes;
+    charge_host_function_call(
+        &mut caller,
+        &random_bytes_cost,
+        [u64::from(out_ptr), u64::from(out_size)],
+    )?;
+
+    let out_size: usize = out_size.try_into_wrapped()?;
+    if out_size > chain_utils::MAX_RANDOM_BYTES {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+
+    let Some(callee_entity) = caller.context().callee.as_entity_addr() else {
+        error!(callee = ?caller.context().callee, "unexpected callee while generating random bytes");
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    };
+    let address = callee_entity.value();
+    let seed = caller.context().transaction_hash.digest().value();
+    let counter = caller.context().random_counter.next();
+    let mut output = vec![0; out_size];
+    chain_utils::fill_pseudo_random_bytes(seed, address, counter, &mut output);
+    caller.memory_write(out_ptr, &output)?;
+
+    Ok(HOST_ERROR_SUCCESS)
+}
+
+/// Writes the hash of the Wasm bytecode of the currently executing contract.
+///
+/// The bytecode of a contract is addressed by its hash, so this is the bytecode address of the
+/// executing entity. It changes when the contract is upgraded. Returns [`HOST_ERROR_NOT_FOUND`]
+/// when called from session code.
+pub fn casper_self_code_hash<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    out_ptr: u32,
+    out_size: u32,
+) -> VMResult<u32> {
+    let self_code_hash_cost = caller.context().config.host_function_costs().self_code_hash;
+    charge_host_function_call(
+        &mut caller,
+        &self_code_hash_cost,
+        [u64::from(out_ptr), u64::from(out_size)],
+    )?;
+
+    let out_size: usize = out_size.try_into_wrapped()?;
+    if out_size != size_of::<[u8; 32]>() {
+        return Ok(HOST_ERROR_INVALID_INPUT);
+    }
+
+    let Some(byte_code_addr) = caller.context().byte_code_addr else {
+        return Ok(HOST_ERROR_NOT_FOUND);
+    };
+    caller.memory_write(out_ptr, &byte_code_addr)?;
+
+    Ok(HOST_ERROR_SUCCESS)
+}
+
 pub fn casper_env_info<S: GlobalStateReader, E: Executor>(
     mut caller: impl Caller<Context = Context<S, E>>,
     info_ptr: u32,
@@ -1471,10 +1843,10 @@ pub fn casper_env_info<S: GlobalStateReader, E: Executor>(
         [u64::from(info_ptr), u64::from(info_size)],
     )?;
 
-    let (caller_kind, caller_addr) = match &caller.context().caller {
-        Key::Account(account_hash) => (EntityKindTag::Account as u32, account_hash.value()),
-        Key::SmartContract(smart_contract_addr) => {
-            (EntityKindTag::Contract as u32, *smart_contract_addr)
+    let (caller_kind, caller_addr) = match caller.context().caller_entity {
+        EntityAddr::Account(account_hash) => (EntityKindTag::Account as u32, account_hash),
+        EntityAddr::SmartContract(smart_contract_addr) => {
+            (EntityKindTag::Contract as u32, smart_contract_addr)
         }
         other => panic!("Unexpected caller: {other:?}"),
     };
@@ -1491,6 +1863,14 @@ pub fn casper_env_info<S: GlobalStateReader, E: Executor>(
 
     let block_time = caller.context().block_time.value();
 
+    let phase = match caller.context().phase {
+        Phase::System => env_info::Phase::System,
+        Phase::Payment => env_info::Phase::Payment,
+        Phase::Session => env_info::Phase::Session,
+        Phase::FinalizePayment => env_info::Phase::FinalizePayment,
+    }
+    .tag();
+
     // `EnvInfo` in little-endian representation.
     let env_info_le = EnvInfo {
         caller_addr,
@@ -1499,6 +1879,7 @@ pub fn casper_env_info<S: GlobalStateReader, E: Executor>(
         callee_kind: callee_kind.to_le(),
         transferred_value: transferred_value.to_le(),
         block_time: block_time.to_le(),
+        phase: phase.to_le(),
     };
 
     let env_info_bytes = safe_transmute::transmute_one_to_bytes(&env_info_le);
@@ -1529,19 +1910,83 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
         ],
     )?;
 
+    match emit_message(
+        &mut caller,
+        topic_name_ptr,
+        topic_name_size,
+        payload_ptr,
+        payload_size,
+    )? {
+        Ok(_topic_message_index) => Ok(HOST_ERROR_SUCCESS),
+        Err(host_error) => Ok(host_error),
+    }
+}
+
+/// Emits a message like [`casper_emit`], and writes the index the message was assigned among the
+/// messages emitted under the same topic in the current block.
+pub fn casper_emit_with_index<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    topic_name_ptr: u32,
+    topic_name_size: u32,
+    payload_ptr: u32,
+    payload_size: u32,
+    out_index_ptr: u32,
+) -> VMResult<u32> {
+    let emit_with_index_host_function = caller
+        .context()
+        .config
+        .host_function_costs()
+        .emit_with_index;
+
+    charge_host_function_call(
+        &mut caller,
+        &emit_with_index_host_function,
+        [
+            u64::from(topic_name_ptr),
+            u64::from(topic_name_size),
+            u64::from(payload_ptr),
+            u64::from(payload_size),
+            u64::from(out_index_ptr),
+        ],
+    )?;
+
+    match emit_message(
+        &mut caller,
+        topic_name_ptr,
+        topic_name_size,
+        payload_ptr,
+        payload_size,
+    )? {
+        Ok(topic_message_index) => {
+            caller.memory_write(out_index_ptr, &topic_message_index.to_le_bytes())?;
+            Ok(HOST_ERROR_SUCCESS)
+        }
+        Err(host_error) => Ok(host_error),
+    }
+}
+
+/// Emits a message, returning the index it was assigned among the messages emitted under its topic
+/// in the current block, or the host error code to report to the contract.
+fn emit_message<S: GlobalStateReader, E: Executor>(
+    caller: &mut impl Caller<Context = Context<S, E>>,
+    topic_name_ptr: u32,
+    topic_name_size: u32,
+    payload_ptr: u32,
+    payload_size: u32,
+) -> VMResult<Result<u64, u32>> {
     if topic_name_size > caller.context().message_limits.max_topic_name_size {
-        return Ok(HOST_ERROR_TOPIC_TOO_LONG);
+        return Ok(Err(HOST_ERROR_TOPIC_TOO_LONG));
     }
 
     if payload_size > caller.context().message_limits.max_message_size {
-        return Ok(HOST_ERROR_PAYLOAD_TOO_LONG);
+        return Ok(Err(HOST_ERROR_PAYLOAD_TOO_LONG));
     }
 
     let topic_name = {
         let topic: Vec<u8> = caller.memory_read(topic_name_ptr, topic_name_size as usize)?;
         let Ok(topic) = String::from_utf8(topic) else {
             // Not a valid UTF-8 string
-            return Ok(HOST_ERROR_INVALID_DATA);
+            return Ok(Err(HOST_ERROR_INVALID_DATA));
         };
         topic
     };
@@ -1558,8 +2003,11 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
             panic!("Error while reading from storage; aborting error={error:?}")
         });
 
-    if message_topics.len() >= caller.context().message_limits.max_topics_per_contract as usize {
-        return Ok(HOST_ERROR_TOO_MANY_TOPICS);
+    // Topics registered upfront can still be emitted once the limit is reached.
+    if !message_topics.has_topic(&topic_name)
+        && message_topics.len() >= caller.context().message_limits.max_topics_per_contract as usize
+    {
+        return Ok(Err(HOST_ERROR_TOO_MANY_TOPICS));
     }
 
     let topic_name_hash = Digest::hash(&topic_name).value().into();
@@ -1574,11 +2022,11 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
         }
         Err(MessageTopicError::MaxTopicsExceeded) => {
             // We're validating the size of topics before adding them
-            return Ok(HOST_ERROR_TOO_MANY_TOPICS);
+            return Ok(Err(HOST_ERROR_TOO_MANY_TOPICS));
         }
         Err(MessageTopicError::TopicNameSizeExceeded) => {
             // We're validating the length of topic before adding it
-            return Ok(HOST_ERROR_TOPIC_TOO_LONG);
+            return Ok(Err(HOST_ERROR_TOPIC_TOO_LONG));
         }
         Err(error) => {
             // These error variants are non_exhaustive, and we should handle them explicitly.
@@ -1593,7 +2041,7 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
     let prev_topic_summary = match caller.context_mut().tracking_copy.read(&topic_key) {
         Ok(Some(StoredValue::MessageTopic(message_topic_summary))) => message_topic_summary,
         Ok(Some(stored_value)) => {
-            panic!("Unexpected stored value: {stored_value:?}");
+            panic!("Unexpected stored value: {}", stored_value.tag().name());
         }
         Ok(None) => {
             let message_topic_summary =
@@ -1647,7 +2095,7 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
                 0
             }
         }
-        Ok(Some(other)) => panic!("Unexpected stored value: {other:?}"),
+        Ok(Some(other)) => panic!("Unexpected stored value: {}", other.tag().name()),
         Ok(None) => {
             // No messages in current block yet
             0
@@ -1658,11 +2106,11 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
     };
 
     let Some(topic_message_count) = topic_message_index.checked_add(1) else {
-        return Ok(HOST_ERROR_MESSAGE_TOPIC_FULL);
+        return Ok(Err(HOST_ERROR_MESSAGE_TOPIC_FULL));
     };
 
     let Some(block_message_count) = block_message_index.checked_add(1) else {
-        return Ok(HOST_ERROR_MAX_MESSAGES_PER_BLOCK_EXCEEDED);
+        return Ok(Err(HOST_ERROR_MAX_MESSAGES_PER_BLOCK_EXCEEDED));
     };
 
     // Under v2 runtime messages are only limited to bytes.
@@ -1697,7 +2145,7 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
     let bytes_count = topic_value.serialized_length()
         + message_value.serialized_length()
         + block_message_count_value.serialized_length();
-    charge_gas_storage(&mut caller, bytes_count)?;
+    charge_gas_storage(caller, bytes_count)?;
 
     caller.context_mut().tracking_copy.emit_message(
         topic_key,
@@ -1708,5 +2156,115 @@ pub fn casper_emit<S: GlobalStateReader, E: Executor>(
         message,
     );
 
+    Ok(Ok(u64::from(topic_message_index)))
+}
+
+/// Registers a message topic for the current contract without emitting a message.
+///
+/// The topic is stored the same way as a topic created lazily by [`casper_emit`], and is validated
+/// against the configured message limits. Registering an existing topic is a no-op.
+pub fn casper_register_topic<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    topic_name_ptr: u32,
+    topic_name_size: u32,
+) -> VMResult<u32> {
+    let register_topic_cost = caller.context().config.host_function_costs().register_topic;
+    charge_host_function_call(
+        &mut caller,
+        &register_topic_cost,
+        [u64::from(topic_name_ptr), u64::from(topic_name_size)],
+    )?;
+
+    if topic_name_size > caller.context().message_limits.max_topic_name_size {
+        return Ok(HOST_ERROR_TOPIC_TOO_LONG);
+    }
+
+    let topic: Vec<u8> = caller.memory_read(topic_name_ptr, topic_name_size as usize)?;
+    let Ok(topic_name) = String::from_utf8(topic) else {
+        return Ok(HOST_ERROR_INVALID_DATA);
+    };
+
+    let entity_addr = context_to_entity_addr(caller.context());
+
+    let message_topics = caller
+        .context_mut()
+        .tracking_copy
+        .get_message_topics(entity_addr)
+        .map_err(|_| VMError::Internal(InternalHostError::TrackingCopy))?;
+
+    if message_topics.has_topic(&topic_name) {
+        return Ok(HOST_ERROR_SUCCESS);
+    }
+
+    if message_topics.len() >= caller.context().message_limits.max_topics_per_contract as usize {
+        return Ok(HOST_ERROR_TOO_MANY_TOPICS);
+    }
+
+    let topic_name_hash = Digest::hash(&topic_name).value().into();
+    let topic_key = Key::Message(MessageAddr::new_topic_addr(entity_addr, topic_name_hash));
+    let topic_value = StoredValue::MessageTopic(MessageTopicSummary::new(
+        0,
+        caller.context().block_time,
+        topic_name,
+    ));
+
+    charge_gas_storage(&mut caller, topic_value.serialized_length())?;
+
+    caller
+        .context_mut()
+        .tracking_copy
+        .write(topic_key, topic_value);
+
+    Ok(HOST_ERROR_SUCCESS)
+}
+
+/// Writes the number of messages the current contract emitted under a topic in the current block.
+///
+/// The count starts over whenever the block time changes. Returns `HOST_ERROR_NOT_FOUND` if the
+/// contract has no such topic.
+pub fn casper_message_count<S: GlobalStateReader, E: Executor>(
+    mut caller: impl Caller<Context = Context<S, E>>,
+    topic_name_ptr: u32,
+    topic_name_size: u32,
+    out_count_ptr: u32,
+) -> VMResult<u32> {
+    let message_count_cost = caller.context().config.host_function_costs().message_count;
+    charge_host_function_call(
+        &mut caller,
+        &message_count_cost,
+        [
+            u64::from(topic_name_ptr),
+            u64::from(topic_name_size),
+            u64::from(out_count_ptr),
+        ],
+    )?;
+
+    if topic_name_size > caller.context().message_limits.max_topic_name_size {
+        return Ok(HOST_ERROR_NOT_FOUND);
+    }
+
+    let topic_name: Vec<u8> = caller.memory_read(topic_name_ptr, topic_name_size as usize)?;
+    let topic_name_hash = Digest::hash(&topic_name).value().into();
+
+    let entity_addr = context_to_entity_addr(caller.context());
+    let topic_key = Key::Message(MessageAddr::new_topic_addr(entity_addr, topic_name_hash));
+
+    let topic_summary = match caller.context_mut().tracking_copy.read(&topic_key) {
+        Ok(Some(StoredValue::MessageTopic(message_topic_summary))) => message_topic_summary,
+        Ok(Some(stored_value)) => {
+            panic!("Unexpected stored value: {}", stored_value.tag().name());
+        }
+        Ok(None) => return Ok(HOST_ERROR_NOT_FOUND),
+        Err(error) => panic!("Error while reading from storage; aborting error={error:?}"),
+    };
+
+    let message_count = if topic_summary.blocktime() == caller.context().block_time {
+        u64::from(topic_summary.message_count())
+    } else {
+        0
+    };
+
+    caller.memory_write(out_count_ptr, &message_count.to_le_bytes())?;
+
     Ok(HOST_ERROR_SUCCESS)
 }
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use borsh::BorshSerialize;
 use bytes::Bytes;
@@ -8,13 +11,20 @@ use casper_storage::{
     AddressGenerator, TrackingCopy,
 };
 use casper_types::{
-    account::AccountHash, contract_messages::Messages, execution::Effects, BlockHash, BlockTime,
-    Digest, HashAddr, Key, TransactionHash,
+    account::AccountHash,
+    contract_messages::Messages,
+    execution::{Effects, TransformKindV2},
+    AccessRights, BlockHash, BlockTime, ByteCodeAddr, Digest, EntityAddr, EntityKind, Gas,
+    HashAddr, InitiatorAddr, Key, Phase, StoredValue, TransactionHash, Transfer, TransferV2, URef,
+    U512,
 };
 use parking_lot::RwLock;
 use thiserror::Error;
 
-use crate::{CallError, GasUsage, WasmPreparationError};
+use crate::{CallError, GasUsage, InstructionBudget, RandomCounter, WasmPreparationError};
+
+/// Export invoked by default when executing [`ExecutionKind::SessionBytes`].
+pub const DEFAULT_SESSION_ENTRY_POINT: &str = "call";
 
 /// Request to execute a Wasm contract.
 pub struct ExecuteRequest {
@@ -22,12 +32,18 @@ pub struct ExecuteRequest {
     pub initiator: AccountHash,
     /// Caller's address key.
     ///
-    /// Either a `[`Key::Account`]` or a `[`Key::AddressableEntity`].
+    /// Either a `[`Key::Account`]` or a `[`Key::AddressableEntity`]. It may differ from the
+    /// initiator, e.g. when a relayer initiates the transaction on behalf of another account: the
+    /// contract observes the caller, while the initiator pays for the execution.
     pub caller_key: Key,
     /// Gas limit.
     pub gas_limit: u64,
     /// Target for execution.
     pub execution_kind: ExecutionKind,
+    /// Export invoked when executing [`ExecutionKind::SessionBytes`].
+    ///
+    /// Ignored for stored contracts, which are called through the entry point of their target.
+    pub session_entry_point: String,
     /// Input data.
     pub input: Bytes,
     /// Value transferred to the contract.
@@ -51,6 +67,17 @@ pub struct ExecuteRequest {
     pub parent_block_hash: BlockHash,
     /// Block height.
     pub block_height: u64,
+    /// Execution phase.
+    pub phase: Phase,
+    /// Whether to record the keys read during the execution, reported in
+    /// [`ExecuteResult::reads`].
+    pub record_reads: bool,
+    /// Instruction budget shared across a chain of execution requests.
+    ///
+    /// If not set, the executor starts a new budget from its configuration.
+    pub instruction_budget: Option<InstructionBudget>,
+    /// Counter of pseudo-random byte requests shared across a chain of execution requests.
+    pub random_counter: RandomCounter,
 }
 
 /// Builder for `ExecuteRequest`.
@@ -60,6 +87,7 @@ pub struct ExecuteRequestBuilder {
     caller_key: Option<Key>,
     gas_limit: Option<u64>,
     target: Option<ExecutionKind>,
+    session_entry_point: Option<String>,
     input: Option<Bytes>,
     value: Option<u64>,
     transaction_hash: Option<TransactionHash>,
@@ -69,6 +97,10 @@ pub struct ExecuteRequestBuilder {
     state_hash: Option<Digest>,
     parent_block_hash: Option<BlockHash>,
     block_height: Option<u64>,
+    phase: Option<Phase>,
+    record_reads: bool,
+    instruction_budget: Option<InstructionBudget>,
+    random_counter: Option<RandomCounter>,
 }
 
 impl ExecuteRequestBuilder {
@@ -80,6 +112,8 @@ impl ExecuteRequestBuilder {
     }
 
     /// Set the caller's key.
+    ///
+    /// Defaults to the initiator's account if not set.
     #[must_use]
     pub fn with_caller_key(mut self, caller_key: Key) -> Self {
         self.caller_key = Some(caller_key);
@@ -100,6 +134,15 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    /// Set the export invoked when executing session bytes.
+    ///
+    /// Defaults to [`DEFAULT_SESSION_ENTRY_POINT`] if not set.
+    #[must_use]
+    pub fn with_session_entry_point(mut self, entry_point: impl Into<String>) -> Self {
+        self.session_entry_point = Some(entry_point.into());
+        self
+    }
+
     /// Pass input data.
     #[must_use]
     pub fn with_input(mut self, input: Bytes) -> Self {
@@ -188,12 +231,50 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    /// Set the execution phase.
+    ///
+    /// Defaults to [`Phase::Session`] if not set.
+    #[must_use]
+    pub fn with_phase(mut self, phase: Phase) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    /// Record the keys read during the execution.
+    #[must_use]
+    pub fn with_record_reads(mut self, record_reads: bool) -> Self {
+        self.record_reads = record_reads;
+        self
+    }
+
+    /// Set the instruction budget shared across a chain of execution requests.
+    #[must_use]
+    pub fn with_shared_instruction_budget(
+        mut self,
+        instruction_budget: Option<InstructionBudget>,
+    ) -> Self {
+        self.instruction_budget = instruction_budget;
+        self
+    }
+
+    /// Set the pseudo-random byte request counter shared across a chain of execution requests.
+    ///
+    /// If not set, a new counter starting at zero is used.
+    #[must_use]
+    pub fn with_shared_random_counter(mut self, random_counter: RandomCounter) -> Self {
+        self.random_counter = Some(random_counter);
+        self
+    }
+
     /// Build the `ExecuteRequest`.
     pub fn build(self) -> Result<ExecuteRequest, &'static str> {
         let initiator = self.initiator.ok_or("Initiator is not set")?;
-        let caller_key = self.caller_key.ok_or("Caller is not set")?;
+        let caller_key = self.caller_key.unwrap_or(Key::Account(initiator));
         let gas_limit = self.gas_limit.ok_or("Gas limit is not set")?;
         let execution_kind = self.target.ok_or("Target is not set")?;
+        let session_entry_point = self
+            .session_entry_point
+            .unwrap_or_else(|| DEFAULT_SESSION_ENTRY_POINT.to_string());
         let input = self.input.ok_or("Input is not set")?;
         let transferred_value = self.value.ok_or("Value is not set")?;
         let transaction_hash = self.transaction_hash.ok_or("Transaction hash is not set")?;
@@ -207,11 +288,13 @@ impl ExecuteRequestBuilder {
             .parent_block_hash
             .ok_or("Parent block hash is not set")?;
         let block_height = self.block_height.ok_or("Block height is not set")?;
+        let phase = self.phase.unwrap_or(Phase::Session);
         Ok(ExecuteRequest {
             initiator,
             caller_key,
             gas_limit,
             execution_kind,
+            session_entry_point,
             input,
             transferred_value,
             transaction_hash,
@@ -221,6 +304,10 @@ impl ExecuteRequestBuilder {
             state_hash,
             parent_block_hash,
             block_height,
+            phase,
+            record_reads: self.record_reads,
+            instruction_budget: self.instruction_budget,
+            random_counter: self.random_counter.unwrap_or_default(),
         })
     }
 }
@@ -240,6 +327,19 @@ pub struct ExecuteResult {
     pub cache: TrackingCopyCache,
     /// Messages produced by the execution.
     pub messages: Messages,
+    /// Keys read during the execution, including reads of reverted nested calls and keys listed
+    /// by prefix reads. Empty unless requested with [`ExecuteRequest::record_reads`].
+    pub reads: BTreeSet<Key>,
+    /// Effects of each Wasm call frame, in order of completion.
+    ///
+    /// The frame which produced this result comes last with depth 0, preceded by the frames of its
+    /// successful sub-calls.
+    pub frames: Vec<CallFrame>,
+    /// Calls into system contracts made during the execution, in order.
+    ///
+    /// Includes the interactions of successful sub-calls; those of failed ones are discarded
+    /// along with their effects.
+    pub system_interactions: Vec<SystemInteraction>,
 }
 
 impl ExecuteResult {
@@ -263,6 +363,173 @@ impl ExecuteResult {
     pub fn gas_usage(&self) -> &GasUsage {
         &self.gas_usage
     }
+
+    pub fn reads(&self) -> &BTreeSet<Key> {
+        &self.reads
+    }
+
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    pub fn system_interactions(&self) -> &[SystemInteraction] {
+        &self.system_interactions
+    }
+
+    /// Summarizes the effects of the execution, reading prior balances from `pre_state`.
+    pub fn summary<R: GlobalStateReader>(
+        &self,
+        transaction_hash: TransactionHash,
+        initiator: AccountHash,
+        pre_state: &R,
+    ) -> Result<ExecutionSummary, GlobalStateError> {
+        ExecutionSummary::new(
+            &self.effects,
+            &self.system_interactions,
+            transaction_hash,
+            initiator,
+            pre_state,
+        )
+    }
+}
+
+/// Compact summary of an execution, suitable for indexing by block explorers.
+///
+/// The summary is derived from the effects and system interactions of an execution and is kept
+/// separate from them; committing an execution still requires its raw effects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionSummary {
+    /// Token transfers made by the mint, in order.
+    pub transfers: Vec<Transfer>,
+    /// Purses whose balance was modified, with their balance before and after the execution.
+    ///
+    /// Ordered by purse address.
+    pub balance_changes: Vec<(URef, U512, U512)>,
+    /// Number of distinct keys written or modified by the execution.
+    pub keys_written: usize,
+}
+
+impl ExecutionSummary {
+    /// Builds a summary out of the `effects` and `system_interactions` of an execution.
+    ///
+    /// Balances before the execution are read from `pre_state`, the state the execution was run
+    /// against. Purses are reported with [`AccessRights::NONE`], as effects only record their
+    /// address.
+    pub fn new<R: GlobalStateReader>(
+        effects: &Effects,
+        system_interactions: &[SystemInteraction],
+        transaction_hash: TransactionHash,
+        initiator: AccountHash,
+        pre_state: &R,
+    ) -> Result<Self, GlobalStateError> {
+        let transfers = system_interactions
+            .iter()
+            .map(|interaction| match interaction {
+                SystemInteraction::MintTransfer {
+                    source,
+                    target,
+                    amount,
+                    to,
+                    gas,
+                    id,
+                } => Transfer::V2(TransferV2::new(
+                    transaction_hash,
+                    InitiatorAddr::AccountHash(initiator),
+                    *to,
+                    *source,
+                    *target,
+                    *amount,
+                    *gas,
+                    *id,
+                )),
+            })
+            .collect();
+
+        let mut keys_written = BTreeSet::new();
+        let mut balances: BTreeMap<_, (U512, U512)> = BTreeMap::new();
+        for transform in effects.transforms() {
+            let key = *transform.key();
+            if matches!(transform.kind(), TransformKindV2::Identity) {
+                continue;
+            }
+            keys_written.insert(key);
+
+            let Key::Balance(purse_addr) = key else {
+                continue;
+            };
+            let (_, after) = match balances.entry(purse_addr) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let before = read_balance(pre_state, &key)?;
+                    entry.insert((before, before))
+                }
+            };
+            match transform.kind() {
+                TransformKindV2::Write(StoredValue::CLValue(cl_value)) => {
+                    if let Ok(balance) = cl_value.to_t() {
+                        *after = balance;
+                    }
+                }
+                TransformKindV2::AddUInt512(amount) => *after = after.saturating_add(*amount),
+                _ => {}
+            }
+        }
+
+        let balance_changes = balances
+            .into_iter()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(purse_addr, (before, after))| {
+                (URef::new(purse_addr, AccessRights::NONE), before, after)
+            })
+            .collect();
+
+        Ok(Self {
+            transfers,
+            balance_changes,
+            keys_written: keys_written.len(),
+        })
+    }
+}
+
+fn read_balance<R: GlobalStateReader>(pre_state: &R, key: &Key) -> Result<U512, GlobalStateError> {
+    let balance = match pre_state.read(key)? {
+        Some(StoredValue::CLValue(cl_value)) => cl_value.to_t().unwrap_or_default(),
+        _ => U512::zero(),
+    };
+    Ok(balance)
+}
+
+/// Effects produced by a single Wasm call frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallFrame {
+    /// Nesting depth of the frame, where the top level execution has depth 0.
+    pub depth: usize,
+    /// Target executed in this frame.
+    pub execution_kind: ExecutionKind,
+    /// Effects produced by the frame itself; the effects of its sub-calls are reported by their
+    /// own frames.
+    pub effects: Effects,
+}
+
+/// A call into a system contract made during an execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SystemInteraction {
+    /// Tokens were transferred by the mint, either attached to a contract call or sent by the
+    /// executing code.
+    MintTransfer {
+        /// Purse the tokens were transferred from.
+        source: URef,
+        /// Purse the tokens were transferred to.
+        target: URef,
+        /// Amount of tokens transferred.
+        amount: U512,
+        /// Account the tokens were sent to, if the target is an account.
+        to: Option<AccountHash>,
+        /// Gas charged for the transfer.
+        gas: Gas,
+        /// User-defined ID of the transfer.
+        id: Option<u64>,
+    },
 }
 
 /// Result of executing a Wasm contract on a state provider.
@@ -280,10 +547,17 @@ pub struct ExecuteWithProviderResult {
     post_state_hash: Digest,
     /// Messages produced by the execution.
     messages: Messages,
+    /// Keys read during the execution.
+    reads: BTreeSet<Key>,
+    /// Effects of each Wasm call frame, in order of completion.
+    frames: Vec<CallFrame>,
+    /// Calls into system contracts made during the execution, in order.
+    system_interactions: Vec<SystemInteraction>,
 }
 
 impl ExecuteWithProviderResult {
     #[must_use]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         host_error: Option<CallError>,
         output: Option<Bytes>,
@@ -291,6 +565,9 @@ impl ExecuteWithProviderResult {
         effects: Effects,
         post_state_hash: Digest,
         messages: Messages,
+        reads: BTreeSet<Key>,
+        frames: Vec<CallFrame>,
+        system_interactions: Vec<SystemInteraction>,
     ) -> Self {
         Self {
             host_error,
@@ -299,6 +576,9 @@ impl ExecuteWithProviderResult {
             effects,
             post_state_hash,
             messages,
+            reads,
+            frames,
+            system_interactions,
         }
     }
 
@@ -322,6 +602,34 @@ impl ExecuteWithProviderResult {
     pub fn messages(&self) -> &Messages {
         &self.messages
     }
+
+    pub fn reads(&self) -> &BTreeSet<Key> {
+        &self.reads
+    }
+
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    pub fn system_interactions(&self) -> &[SystemInteraction] {
+        &self.system_interactions
+    }
+
+    /// Summarizes the effects of the execution, reading prior balances from `pre_state`.
+    pub fn summary<R: GlobalStateReader>(
+        &self,
+        transaction_hash: TransactionHash,
+        initiator: AccountHash,
+        pre_state: &R,
+    ) -> Result<ExecutionSummary, GlobalStateError> {
+        ExecutionSummary::new(
+            &self.effects,
+            &self.system_interactions,
+            transaction_hash,
+            initiator,
+            pre_state,
+        )
+    }
 }
 
 /// Target for Wasm execution.
@@ -349,6 +657,43 @@ pub enum ExecuteError {
     /// No wasm was executed at this point.
     #[error("Wasm error error: {0}")]
     WasmPreparation(#[from] WasmPreparationError),
+    /// The stored contract could not be resolved to an executable entity, e.g. because its
+    /// package refers back to itself or to a value of an unexpected kind.
+    ///
+    /// No wasm was executed at this point.
+    #[error("Invalid contract graph at {key}: {reason}")]
+    InvalidContractGraph { key: Key, reason: &'static str },
+    /// The stored contract resolved to an entity which is not a smart contract, e.g. an account or
+    /// a system contract.
+    ///
+    /// No wasm was executed at this point.
+    #[error("Unexpected entity kind at {}: {kind}", Key::SmartContract(*address))]
+    UnexpectedEntityKind { address: HashAddr, kind: EntityKind },
+    /// The latest version of the stored contract's package is disabled, and the executor is
+    /// configured to reject calls to it rather than fall back to an enabled version.
+    ///
+    /// No wasm was executed at this point.
+    #[error("Version {entity_addr} of {} is disabled", Key::SmartContract(*address))]
+    VersionDisabled {
+        address: HashAddr,
+        entity_addr: EntityAddr,
+    },
+    /// Wasm executed more instructions than the executor's instruction budget allows.
+    ///
+    /// Unlike running out of gas, this aborts the whole execution including all of its callers,
+    /// and no effects are produced.
+    #[error("Instruction budget exceeded")]
+    InstructionBudgetExceeded,
+    /// The caller of the execution is not addressed by a key of an entity.
+    ///
+    /// No wasm was executed at this point.
+    #[error("Unexpected caller {caller}")]
+    UnexpectedCaller { caller: Key },
+    /// The hash of the bytecode to execute is on the executor's denylist.
+    ///
+    /// No wasm was executed at this point.
+    #[error("Bytecode {byte_code_addr} is denied")]
+    BytecodeDenied { byte_code_addr: ByteCodeAddr },
 }
 
 #[derive(Debug, Error)]
@@ -373,4 +718,16 @@ pub trait Executor: Clone + Send {
         tracking_copy: TrackingCopy<R>,
         execute_request: ExecuteRequest,
     ) -> Result<ExecuteResult, ExecuteError>;
+
+    /// Checks that the bytecode stored under the address returned by `byte_code_addr` may be
+    /// executed, failing with [`ExecuteError::BytecodeDenied`] otherwise.
+    ///
+    /// Lets the host refuse bytecode before storing it, rather than after its constructor fails.
+    /// All bytecode is allowed by default.
+    fn check_bytecode_allowed(
+        &self,
+        _byte_code_addr: impl FnOnce() -> ByteCodeAddr,
+    ) -> Result<(), ExecuteError> {
+        Ok(())
+    }
 }
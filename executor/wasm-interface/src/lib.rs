@@ -1,5 +1,10 @@
 pub mod executor;
 
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
 use bytes::Bytes;
 use thiserror::Error;
 
@@ -18,6 +23,20 @@ use casper_executor_wasm_common::{
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct InterfaceVersion(u32);
 
+impl InterfaceVersion {
+    /// Creates a new interface version.
+    #[must_use]
+    pub const fn new(value: u32) -> Self {
+        InterfaceVersion(value)
+    }
+
+    /// Returns the numeric value of the interface version.
+    #[must_use]
+    pub const fn value(&self) -> u32 {
+        self.0
+    }
+}
+
 impl From<u32> for InterfaceVersion {
     fn from(value: u32) -> Self {
         InterfaceVersion(value)
@@ -109,6 +128,9 @@ pub enum VMError {
     Export(ExportError),
     #[error("Out of gas")]
     OutOfGas,
+    /// The instance executed more instructions than its instruction budget allows.
+    #[error("Instruction budget exceeded")]
+    InstructionBudgetExceeded,
     #[error("Internal host error")]
     Internal(InternalHostError),
     /// Error while executing Wasm: traps, memory access errors, etc.
@@ -117,6 +139,9 @@ pub enum VMError {
     /// extract memory access errors, trap codes, and unify error reporting.
     #[error("Trap: {0}")]
     Trap(TrapCode),
+    /// Contract explicitly aborted its execution with a message.
+    #[error("Abort: {0}")]
+    Abort(String),
 }
 
 impl VMError {
@@ -138,11 +163,52 @@ impl From<InternalHostError> for VMError {
     }
 }
 
+/// Number of Wasm instructions an execution may still run.
+///
+/// A single budget is shared by all the call frames of an execution, so that nested calls draw
+/// from the budget of their caller instead of starting with a new one.
+#[derive(Clone, Debug)]
+pub struct InstructionBudget(Arc<AtomicU64>);
+
+impl InstructionBudget {
+    /// Creates a new budget of `limit` instructions.
+    #[must_use]
+    pub fn new(limit: u64) -> Self {
+        Self(Arc::new(AtomicU64::new(limit)))
+    }
+
+    /// Returns the number of instructions left.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Sets the number of instructions left.
+    pub fn set_remaining(&self, remaining: u64) {
+        self.0.store(remaining, Ordering::SeqCst);
+    }
+}
+
+/// Counter of the pseudo-random byte requests served during an execution.
+///
+/// Like the [`InstructionBudget`], a single counter is shared by all the call frames of an
+/// execution, so that every request draws different bytes.
+#[derive(Clone, Debug, Default)]
+pub struct RandomCounter(Arc<AtomicU64>);
+
+impl RandomCounter {
+    /// Returns the current value of the counter and advances it.
+    pub fn next(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
 /// Configuration for the Wasm engine.
 #[derive(Clone, Debug)]
 pub struct Config {
     gas_limit: u64,
     memory_limit: u32,
+    instruction_budget: Option<InstructionBudget>,
 }
 
 impl Config {
@@ -155,6 +221,12 @@ impl Config {
     pub fn memory_limit(&self) -> u32 {
         self.memory_limit
     }
+
+    /// Instruction budget the instance draws from, or `None` if unlimited.
+    #[must_use]
+    pub fn instruction_budget(&self) -> Option<&InstructionBudget> {
+        self.instruction_budget.as_ref()
+    }
 }
 
 /// Configuration for the Wasm engine.
@@ -163,6 +235,7 @@ pub struct ConfigBuilder {
     gas_limit: Option<u64>,
     /// Memory limit in pages.
     memory_limit: Option<u32>,
+    instruction_budget: Option<InstructionBudget>,
 }
 
 impl ConfigBuilder {
@@ -186,6 +259,18 @@ impl ConfigBuilder {
         self
     }
 
+    /// Instruction budget, independent of the gas limit.
+    ///
+    /// Unlimited if not set.
+    #[must_use]
+    pub fn with_instruction_budget(
+        mut self,
+        instruction_budget: Option<InstructionBudget>,
+    ) -> Self {
+        self.instruction_budget = instruction_budget;
+        self
+    }
+
     /// Build the configuration.
     #[must_use]
     pub fn build(self) -> Config {
@@ -196,6 +281,7 @@ impl ConfigBuilder {
         Config {
             gas_limit,
             memory_limit,
+            instruction_budget: self.instruction_budget,
         }
     }
 }
@@ -258,6 +344,8 @@ pub enum WasmPreparationError {
     Memory(String),
     #[error("Instantiation error: {0}")]
     Instantiation(String),
+    #[error("Unsupported interface version {0}")]
+    UnsupportedInterfaceVersion(u32),
 }
 
 #[derive(Debug)]
@@ -300,4 +388,6 @@ pub trait WasmInstance {
 
     fn call_export(&mut self, name: &str) -> (Result<(), VMError>, GasUsage);
     fn teardown(self) -> Self::Context;
+    /// Returns the interface version negotiated with the Wasm module at instantiation time.
+    fn interface_version(&self) -> InterfaceVersion;
 }
@@ -218,4 +218,7 @@ pub enum InstallContractError {
 
     #[error("constructor error: {host_error}")]
     Constructor { host_error: CallError },
+
+    #[error("unexpected constructor output: {output:?}")]
+    UnexpectedConstructorOutput { output: Bytes },
 }
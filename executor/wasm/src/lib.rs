@@ -1,8 +1,10 @@
 pub mod install;
-pub(crate) mod system;
+pub mod system;
+pub mod warm_up;
 
 use std::{
-    collections::{BTreeSet, VecDeque},
+    collections::{BTreeSet, HashSet, VecDeque},
+    num::NonZeroU64,
     sync::Arc,
 };
 
@@ -13,18 +15,19 @@ use casper_execution_engine::{
 };
 use casper_executor_wasm_common::{
     chain_utils,
-    error::{CallError, TrapCode},
-    flags::ReturnFlags,
+    error::{CallError, HostError, TrapCode},
+    flags::{EntryPointFlags, ReturnFlags},
 };
 use casper_executor_wasm_host::context::Context;
 use casper_executor_wasm_interface::{
     executor::{
-        ExecuteError, ExecuteRequest, ExecuteRequestBuilder, ExecuteResult,
+        CallFrame, ExecuteError, ExecuteRequest, ExecuteRequestBuilder, ExecuteResult,
         ExecuteWithProviderError, ExecuteWithProviderResult, ExecutionKind, Executor,
+        SystemInteraction,
     },
-    ConfigBuilder, GasUsage, VMError, WasmInstance,
+    ConfigBuilder, GasUsage, InstructionBudget, VMError, WasmInstance, WasmPreparationError,
 };
-use casper_executor_wasmer_backend::WasmerEngine;
+use casper_executor_wasmer_backend::{ModuleCacheStats, WasmerEngine};
 use casper_storage::{
     global_state::{
         error::Error as GlobalStateError,
@@ -36,18 +39,20 @@ use casper_storage::{
 use casper_types::{
     account::AccountHash,
     addressable_entity::{ActionThresholds, AssociatedKeys},
-    bytesrepr, AddressableEntity, ByteCode, ByteCodeAddr, ByteCodeHash, ByteCodeKind,
-    ContractRuntimeTag, Digest, EntityAddr, EntityKind, Gas, Groups, InitiatorAddr, Key,
-    MessageLimits, Package, PackageHash, PackageStatus, Phase, ProtocolVersion, StorageCosts,
-    StoredValue, TransactionInvocationTarget, URef, WasmV2Config, U512,
+    bytesrepr,
+    contracts::ContractHash,
+    execution::Effects,
+    AddressableEntity, ByteCode, ByteCodeAddr, ByteCodeHash, ByteCodeKind, ContractRuntimeTag,
+    Digest, EntityAddr, EntityKind, Gas, Groups, HashAddr, InitiatorAddr, Key, MessageLimits,
+    Package, PackageHash, PackageStatus, Phase, ProtocolVersion, StorageCosts, StoredValue,
+    TransactionInvocationTarget, Transfer, URef, WasmV2Config, U512,
 };
 use either::Either;
 use install::{InstallContractError, InstallContractRequest, InstallContractResult};
 use parking_lot::RwLock;
-use system::{MintArgs, MintTransferArgs};
+use system::{MintArgs, MintProvider, MintTransferArgs, SystemMint};
 use tracing::{error, warn};
-
-const DEFAULT_WASM_ENTRY_POINT: &str = "call";
+use warm_up::WarmUpError;
 
 const DEFAULT_MINT_TRANSFER_GAS_COST: u64 = 1; // NOTE: Require gas while executing and set this to at least 100_000_000 (or use chainspec)
 
@@ -59,6 +64,44 @@ pub enum ExecutorKind {
     Compiled,
 }
 
+/// Determines how much of the caller's remaining gas is forwarded to a sub-call.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GasForwardingPolicy {
+    /// Forward all of the caller's remaining gas to the callee.
+    ///
+    /// This is the default policy.
+    #[default]
+    ForwardAll,
+    /// Retain one `n`-th of the caller's remaining gas and forward the rest to the callee.
+    ///
+    /// This guarantees the caller can still run its post-call logic after a callee has consumed
+    /// all of the gas it was given. `AllButOneNth(64)` mirrors the EVM's 63/64 rule.
+    AllButOneNth(NonZeroU64),
+}
+
+impl GasForwardingPolicy {
+    /// Returns the amount of gas forwarded to a sub-call given the caller's `remaining_gas`.
+    #[must_use]
+    pub fn forwarded_gas(&self, remaining_gas: u64) -> u64 {
+        match self {
+            GasForwardingPolicy::ForwardAll => remaining_gas,
+            GasForwardingPolicy::AllButOneNth(n) => remaining_gas - remaining_gas / n.get(),
+        }
+    }
+}
+
+/// Determines how a call to a package whose latest version is disabled is resolved.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DisabledVersionPolicy {
+    /// Call the latest enabled version of the package instead.
+    ///
+    /// This is the default policy.
+    #[default]
+    FallBackToLatestEnabled,
+    /// Reject the call with [`ExecuteError::VersionDisabled`].
+    Reject,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct ExecutorConfig {
     memory_limit: u32,
@@ -66,6 +109,10 @@ pub struct ExecutorConfig {
     wasm_config: WasmV2Config,
     storage_costs: StorageCosts,
     message_limits: MessageLimits,
+    gas_forwarding_policy: GasForwardingPolicy,
+    disabled_version_policy: DisabledVersionPolicy,
+    strict_constructors: bool,
+    instruction_budget: Option<u64>,
 }
 
 impl ExecutorConfigBuilder {
@@ -81,6 +128,10 @@ pub struct ExecutorConfigBuilder {
     wasm_config: Option<WasmV2Config>,
     storage_costs: Option<StorageCosts>,
     message_limits: Option<MessageLimits>,
+    gas_forwarding_policy: Option<GasForwardingPolicy>,
+    disabled_version_policy: Option<DisabledVersionPolicy>,
+    strict_constructors: bool,
+    instruction_budget: Option<u64>,
 }
 
 impl ExecutorConfigBuilder {
@@ -114,6 +165,46 @@ impl ExecutorConfigBuilder {
         self
     }
 
+    /// Set the gas forwarding policy applied to sub-calls.
+    ///
+    /// Defaults to [`GasForwardingPolicy::ForwardAll`] if not set.
+    pub fn with_gas_forwarding_policy(
+        mut self,
+        gas_forwarding_policy: GasForwardingPolicy,
+    ) -> Self {
+        self.gas_forwarding_policy = Some(gas_forwarding_policy);
+        self
+    }
+
+    /// Set how calls to a package whose latest version is disabled are resolved.
+    ///
+    /// Defaults to [`DisabledVersionPolicy::FallBackToLatestEnabled`] if not set.
+    pub fn with_disabled_version_policy(
+        mut self,
+        disabled_version_policy: DisabledVersionPolicy,
+    ) -> Self {
+        self.disabled_version_policy = Some(disabled_version_policy);
+        self
+    }
+
+    /// Set whether output returned by a constructor fails the installation.
+    ///
+    /// When disabled (the default), unexpected constructor output is logged and discarded.
+    pub fn with_strict_constructors(mut self, strict_constructors: bool) -> Self {
+        self.strict_constructors = strict_constructors;
+        self
+    }
+
+    /// Set the maximum number of Wasm instructions an execution may run, including nested calls.
+    ///
+    /// The budget is enforced independently of gas, and exceeding it fails the whole execution
+    /// with [`ExecuteError::InstructionBudgetExceeded`]. Unlimited if not set, in which case the
+    /// Wasm is not instrumented to count instructions.
+    pub fn with_instruction_budget(mut self, instruction_budget: u64) -> Self {
+        self.instruction_budget = Some(instruction_budget);
+        self
+    }
+
     /// Build the `ExecutorConfig`.
     pub fn build(self) -> Result<ExecutorConfig, &'static str> {
         let memory_limit = self.memory_limit.ok_or("Memory limit is not set")?;
@@ -121,6 +212,10 @@ impl ExecutorConfigBuilder {
         let wasm_config = self.wasm_config.ok_or("Wasm config is not set")?;
         let storage_costs = self.storage_costs.ok_or("Storage costs are not set")?;
         let message_limits = self.message_limits.ok_or("Message limits are not set")?;
+        let gas_forwarding_policy = self.gas_forwarding_policy.unwrap_or_default();
+        let disabled_version_policy = self.disabled_version_policy.unwrap_or_default();
+        let strict_constructors = self.strict_constructors;
+        let instruction_budget = self.instruction_budget;
 
         Ok(ExecutorConfig {
             memory_limit,
@@ -128,19 +223,28 @@ impl ExecutorConfigBuilder {
             wasm_config,
             storage_costs,
             message_limits,
+            gas_forwarding_policy,
+            disabled_version_policy,
+            strict_constructors,
+            instruction_budget,
         })
     }
 }
 
 #[derive(Clone)]
-pub struct ExecutorV2 {
+pub struct ExecutorV2<M: MintProvider = SystemMint> {
     config: ExecutorConfig,
     compiled_wasm_engine: Arc<WasmerEngine>,
+    /// Targets of the frames being executed, shared only by the executors of a single top level
+    /// execution.
     execution_stack: Arc<RwLock<VecDeque<ExecutionKind>>>,
+    /// Hashes of the bytecode the executor refuses to run.
+    bytecode_denylist: Arc<HashSet<[u8; 32]>>,
     execution_engine_v1: Arc<ExecutionEngineV1>,
+    mint: M,
 }
 
-impl ExecutorV2 {
+impl<M: MintProvider> ExecutorV2<M> {
     pub fn install_contract<R>(
         &self,
         state_root_hash: Digest,
@@ -212,13 +316,6 @@ impl ExecutorV2 {
         );
         debug_assert_eq!(entity_version_key.entity_version(), next_version);
 
-        let smart_contract_addr = chain_utils::compute_predictable_address(
-            chain_name.as_bytes(),
-            initiator.value(),
-            bytecode_hash,
-            seed,
-        );
-
         tracking_copy.write(
             Key::SmartContract(smart_contract_addr),
             StoredValue::SmartContract(smart_contract),
@@ -238,7 +335,7 @@ impl ExecutorV2 {
         let addressable_entity_key = Key::AddressableEntity(EntityAddr::SmartContract(entity_hash));
 
         // TODO: abort(str) as an alternative to trap
-        let main_purse: URef = match system::mint_mint(
+        let main_purse: URef = match self.mint.mint(
             &mut tracking_copy,
             transaction_hash,
             Arc::clone(&address_generator),
@@ -303,17 +400,25 @@ impl ExecutorV2 {
                         effects,
                         cache,
                         messages,
+                        reads: _,
+                        frames: _,
+                        system_interactions: _,
                     }) => {
                         if let Some(host_error) = host_error {
                             return Err(InstallContractError::Constructor { host_error });
                         }
 
-                        tracking_copy.apply_changes(effects, cache, messages);
-
                         if let Some(output) = output {
+                            if self.config.strict_constructors {
+                                return Err(InstallContractError::UnexpectedConstructorOutput {
+                                    output,
+                                });
+                            }
                             warn!(?output, "unexpected output from constructor");
                         }
 
+                        tracking_copy.apply_changes(effects, cache, messages);
+
                         gas_usage
                     }
                     Err(error) => {
@@ -343,6 +448,29 @@ impl ExecutorV2 {
     }
 
     fn execute_with_tracking_copy<R: GlobalStateReader + 'static>(
+        &self,
+        tracking_copy: TrackingCopy<R>,
+        execute_request: ExecuteRequest,
+    ) -> Result<ExecuteResult, ExecuteError> {
+        let result = self.execute_call_frame(tracking_copy, execute_request);
+
+        if !self.execution_stack.read().is_empty() {
+            return result;
+        }
+
+        let mut execute_result = result?;
+
+        // Only the top level result is ordered by key; the effects of nested results keep the
+        // order in which they were produced so their callers can tell the sub-calls apart.
+        execute_result.effects.order_by_key();
+        for frame in &mut execute_result.frames {
+            frame.effects.order_by_key();
+        }
+
+        Ok(execute_result)
+    }
+
+    fn execute_call_frame<R: GlobalStateReader + 'static>(
         &self,
         mut tracking_copy: TrackingCopy<R>,
         execute_request: ExecuteRequest,
@@ -352,6 +480,7 @@ impl ExecutorV2 {
             caller_key,
             gas_limit,
             execution_kind,
+            session_entry_point,
             input,
             transferred_value,
             transaction_hash,
@@ -361,55 +490,85 @@ impl ExecutorV2 {
             state_hash,
             parent_block_hash,
             block_height,
+            phase,
+            record_reads,
+            instruction_budget,
+            random_counter,
         } = execute_request;
 
+        if record_reads {
+            tracking_copy.record_reads();
+        }
+
+        // Nested calls draw from the budget of the top level execution.
+        let instruction_budget = instruction_budget
+            .or_else(|| self.config.instruction_budget.map(InstructionBudget::new));
+
+        // Sub-calls only receive the portion of the caller's remaining gas allowed by the policy.
+        let gas_limit = if self.execution_stack.read().is_empty() {
+            gas_limit
+        } else {
+            self.config.gas_forwarding_policy.forwarded_gas(gas_limit)
+        };
+
+        // Effects inherited from the calling frame precede the ones produced by this frame.
+        let inherited_effects_len = tracking_copy.effects().len();
+
+        // Calls into system contracts made before the frame starts executing.
+        let mut system_interactions = Vec::new();
+
         // TODO: Purse uref does not need to be optional once value transfers to WasmBytes are
         // supported. let caller_entity_addr = EntityAddr::new_account(caller);
+        //
+        // Transferred value is drawn from the caller, which is not necessarily the initiator paying
+        // for the execution.
         let source_purse = get_purse_for_entity(&mut tracking_copy, caller_key);
 
-        let (wasm_bytes, export_or_selector): (_, Either<&str, u32>) = match &execution_kind {
+        let (wasm_bytes, export_or_selector, byte_code_addr) = match &execution_kind {
             ExecutionKind::SessionBytes(wasm_bytes) => {
-                // self.execute_wasm(tracking_copy, address, gas_limit, wasm_bytes, input)
-                (wasm_bytes.clone(), Either::Left(DEFAULT_WASM_ENTRY_POINT))
+                self.check_bytecode_allowed(|| {
+                    ByteCodeAddr::V2CasperWasm(chain_utils::compute_wasm_bytecode_hash(wasm_bytes))
+                })?;
+                if !self
+                    .compiled_wasm_engine
+                    .has_export(wasm_bytes, &session_entry_point)?
+                {
+                    return Err(ExecuteError::WasmPreparation(
+                        WasmPreparationError::MissingExport(session_entry_point),
+                    ));
+                }
+                (
+                    wasm_bytes.clone(),
+                    Either::<_, u32>::Left(session_entry_point.as_str()),
+                    None,
+                )
             }
             ExecutionKind::Stored {
                 address: smart_contract_addr,
                 entry_point,
             } => {
                 let smart_contract_key = Key::SmartContract(*smart_contract_addr);
-                let legacy_key = Key::Hash(*smart_contract_addr);
-
-                let mut contract = tracking_copy
-                    .read_first(&[&legacy_key, &smart_contract_key])
-                    .expect("should read contract");
-
-                // let entity_addr: EntityAddr;
-
-                // Resolve indirection - get the latest version from the smart contract package
-                // versions. let old_contract = contract.clone();
-                // let latest_version_key;
-                if let Some(StoredValue::SmartContract(smart_contract_package)) = &contract {
-                    let contract_hash = smart_contract_package
-                        .versions()
-                        .latest()
-                        .expect("should have last entry");
-                    let entity_addr = EntityAddr::SmartContract(contract_hash.value());
-                    let latest_version_key = Key::AddressableEntity(entity_addr);
-                    assert_ne!(&entity_addr.value(), smart_contract_addr);
-                    let new_contract = tracking_copy
-                        .read(&latest_version_key)
-                        .expect("should read latest version");
-                    contract = new_contract;
-                };
+                let contract = resolve_stored_contract(
+                    &mut tracking_copy,
+                    *smart_contract_addr,
+                    self.config.disabled_version_policy,
+                )?;
 
                 match contract {
-                    Some(StoredValue::AddressableEntity(addressable_entity)) => {
+                    Some((entity_addr, StoredValue::AddressableEntity(addressable_entity))) => {
                         let wasm_key = match addressable_entity.kind() {
-                            EntityKind::System(_) => todo!(),
-                            EntityKind::Account(_) => todo!(),
+                            kind @ (EntityKind::System(_) | EntityKind::Account(_)) => {
+                                return Err(ExecuteError::UnexpectedEntityKind {
+                                    address: *smart_contract_addr,
+                                    kind,
+                                });
+                            }
                             EntityKind::SmartContract(ContractRuntimeTag::VmCasperV1) => {
                                 // We need to short circuit here to execute v1 contracts with legacy
                                 // execut
+                                self.check_bytecode_allowed(|| {
+                                    ByteCodeAddr::V1CasperWasm(addressable_entity.byte_code_addr())
+                                })?;
 
                                 let block_info = BlockInfo::new(
                                     state_hash,
@@ -419,8 +578,6 @@ impl ExecutorV2 {
                                     self.execution_engine_v1.config().protocol_version(),
                                 );
 
-                                let entity_addr = EntityAddr::SmartContract(*smart_contract_addr);
-
                                 return self.execute_legacy_wasm_byte_code(
                                     initiator,
                                     &entity_addr,
@@ -430,9 +587,13 @@ impl ExecutorV2 {
                                     block_info,
                                     transaction_hash,
                                     gas_limit,
+                                    phase,
                                 );
                             }
                             EntityKind::SmartContract(ContractRuntimeTag::VmCasperV2) => {
+                                self.check_bytecode_allowed(|| {
+                                    ByteCodeAddr::V2CasperWasm(addressable_entity.byte_code_addr())
+                                })?;
                                 Key::ByteCode(ByteCodeAddr::V2CasperWasm(
                                     addressable_entity.byte_code_addr(),
                                 ))
@@ -451,6 +612,27 @@ impl ExecutorV2 {
                             .take_bytes();
 
                         if transferred_value != 0 {
+                            let entry_point_flags = self
+                                .compiled_wasm_engine
+                                .entry_point_flags(&wasm_bytes, entry_point)?;
+                            if entry_point_flags
+                                .is_some_and(|flags| !flags.contains(EntryPointFlags::PAYABLE))
+                            {
+                                // The entry point does not accept tokens; reject the call before
+                                // any value is moved.
+                                return Ok(ExecuteResult {
+                                    host_error: Some(CallError::NotPayable),
+                                    output: None,
+                                    gas_usage: GasUsage::new(gas_limit, gas_limit),
+                                    effects: tracking_copy.effects(),
+                                    cache: tracking_copy.cache(),
+                                    messages: tracking_copy.messages(),
+                                    reads: tracking_copy.reads(),
+                                    frames: Vec::new(),
+                                    system_interactions: Vec::new(),
+                                });
+                            }
+
                             let args = {
                                 let maybe_to = None;
                                 let source = source_purse;
@@ -465,8 +647,17 @@ impl ExecutorV2 {
                                     id,
                                 }
                             };
+                            // No gas is charged for transferring the attached value.
+                            let mint_transfer = SystemInteraction::MintTransfer {
+                                source: args.source,
+                                target: args.target,
+                                amount: args.amount,
+                                to: None,
+                                gas: Gas::zero(),
+                                id: None,
+                            };
 
-                            match system::mint_transfer(
+                            match self.mint.transfer(
                                 &mut tracking_copy,
                                 transaction_hash,
                                 Arc::clone(&address_generator),
@@ -474,6 +665,7 @@ impl ExecutorV2 {
                             ) {
                                 Ok(()) => {
                                     // Transfer succeed, go on
+                                    system_interactions.push(mint_transfer);
                                 }
                                 Err(error) => {
                                     return Ok(ExecuteResult {
@@ -486,14 +678,24 @@ impl ExecutorV2 {
                                         effects: tracking_copy.effects(),
                                         cache: tracking_copy.cache(),
                                         messages: tracking_copy.messages(),
+                                        reads: tracking_copy.reads(),
+                                        frames: Vec::new(),
+                                        system_interactions: Vec::new(),
                                     });
                                 }
                             }
                         }
 
-                        (Bytes::from(wasm_bytes), Either::Left(entry_point.as_str()))
+                        (
+                            Bytes::from(wasm_bytes),
+                            Either::Left(entry_point.as_str()),
+                            Some(addressable_entity.byte_code_addr()),
+                        )
                     }
-                    Some(StoredValue::Contract(_legacy_contract)) => {
+                    Some((entity_addr, StoredValue::Contract(legacy_contract))) => {
+                        self.check_bytecode_allowed(|| {
+                            ByteCodeAddr::V1CasperWasm(legacy_contract.contract_wasm_hash().value())
+                        })?;
                         let block_info = BlockInfo::new(
                             state_hash,
                             block_time,
@@ -502,8 +704,6 @@ impl ExecutorV2 {
                             self.execution_engine_v1.config().protocol_version(),
                         );
 
-                        let entity_addr = EntityAddr::SmartContract(*smart_contract_addr);
-
                         return self.execute_legacy_wasm_byte_code(
                             initiator,
                             &entity_addr,
@@ -513,13 +713,14 @@ impl ExecutorV2 {
                             block_info,
                             transaction_hash,
                             gas_limit,
+                            phase,
                         );
                     }
-                    Some(stored_value) => {
-                        todo!(
-                            "Unexpected {stored_value:?} under key {:?}",
-                            &execution_kind
-                        );
+                    Some((_entity_addr, _stored_value)) => {
+                        return Err(ExecuteError::InvalidContractGraph {
+                            key: smart_contract_key,
+                            reason: "resolved value is not a contract",
+                        });
                     }
                     None => {
                         panic!("No code found in {smart_contract_key:?}");
@@ -541,58 +742,85 @@ impl ExecutorV2 {
             ExecutionKind::SessionBytes(_wasm_bytes) => Key::Account(initiator),
         };
 
+        // Each top level execution gets its own stack, shared by the executors of its sub-calls.
+        let executor = if self.execution_stack.read().is_empty() {
+            ExecutorV2 {
+                execution_stack: Default::default(),
+                ..self.clone()
+            }
+        } else {
+            self.clone()
+        };
+        let _execution_stack_guard = ExecutionStackGuard::push(
+            Arc::clone(&executor.execution_stack),
+            execution_kind.clone(),
+        );
+
+        let caller_entity = caller_key
+            .as_entity_addr()
+            .ok_or(ExecuteError::UnexpectedCaller { caller: caller_key })?;
+
         let context = Context {
             initiator,
             config: self.config.wasm_config,
             storage_costs: self.config.storage_costs,
             caller: caller_key,
+            caller_entity,
             callee: callee_key,
+            byte_code_addr,
             transferred_value,
             tracking_copy,
-            executor: self.clone(),
+            executor,
             address_generator: Arc::clone(&address_generator),
             transaction_hash,
             chain_name,
             input,
             block_time,
+            phase,
             message_limits: self.config.message_limits,
+            instruction_budget: instruction_budget.clone(),
+            random_counter,
+            frames: Vec::new(),
+            sub_call_effects: Vec::new(),
+            system_interactions: Vec::new(),
         };
 
         let wasm_instance_config = ConfigBuilder::new()
             .with_gas_limit(gas_limit)
             .with_memory_limit(self.config.memory_limit)
+            .with_instruction_budget(instruction_budget)
             .build();
 
         let mut instance = vm.instantiate(wasm_bytes, context, wasm_instance_config)?;
 
-        self.push_execution_stack(execution_kind.clone());
         let (vm_result, gas_usage) = match export_or_selector {
             Either::Left(export_name) => instance.call_export(export_name),
             Either::Right(_entry_point) => todo!("Restore selectors"), /* instance.call_export(&
                                                                         * entry_point), */
         };
 
-        let top_execution_kind = self
-            .pop_execution_stack()
-            .expect("should have execution kind"); // SAFETY: We just pushed
-        debug_assert_eq!(&top_execution_kind, &execution_kind);
-
         let context = instance.teardown();
 
         let Context {
             tracking_copy: final_tracking_copy,
+            mut frames,
+            sub_call_effects,
+            system_interactions: frame_system_interactions,
             ..
         } = context;
 
-        match vm_result {
-            Ok(()) => Ok(ExecuteResult {
+        let mut execute_result = match vm_result {
+            Ok(()) => ExecuteResult {
                 host_error: None,
                 output: None,
                 gas_usage,
                 effects: final_tracking_copy.effects(),
                 cache: final_tracking_copy.cache(),
                 messages: final_tracking_copy.messages(),
-            }),
+                reads: final_tracking_copy.reads(),
+                frames: Vec::new(),
+                system_interactions: Vec::new(),
+            },
             Err(VMError::Return { flags, data }) => {
                 let host_error = if flags.contains(ReturnFlags::REVERT) {
                     // The contract has reverted.
@@ -608,54 +836,112 @@ impl ExecutorV2 {
                     None
                 };
 
-                Ok(ExecuteResult {
+                ExecuteResult {
                     host_error,
                     output: data,
                     gas_usage,
                     effects: initial_tracking_copy.effects(),
                     cache: initial_tracking_copy.cache(),
                     messages: initial_tracking_copy.messages(),
-                })
+                    reads: final_tracking_copy.reads(),
+                    frames: Vec::new(),
+                    system_interactions: Vec::new(),
+                }
             }
-            Err(VMError::OutOfGas) => Ok(ExecuteResult {
+            Err(VMError::OutOfGas) => ExecuteResult {
                 host_error: Some(CallError::CalleeGasDepleted),
                 output: None,
                 gas_usage,
                 effects: final_tracking_copy.effects(),
                 cache: final_tracking_copy.cache(),
                 messages: final_tracking_copy.messages(),
-            }),
-            Err(VMError::Trap(trap_code)) => Ok(ExecuteResult {
+                reads: final_tracking_copy.reads(),
+                frames: Vec::new(),
+                system_interactions: Vec::new(),
+            },
+            Err(VMError::InstructionBudgetExceeded) => {
+                return Err(ExecuteError::InstructionBudgetExceeded);
+            }
+            Err(VMError::Trap(trap_code)) => ExecuteResult {
                 host_error: Some(CallError::CalleeTrapped(trap_code)),
                 output: None,
                 gas_usage,
                 effects: initial_tracking_copy.effects(),
                 cache: initial_tracking_copy.cache(),
                 messages: initial_tracking_copy.messages(),
-            }),
+                reads: final_tracking_copy.reads(),
+                frames: Vec::new(),
+                system_interactions: Vec::new(),
+            },
+            Err(VMError::Abort(message)) => ExecuteResult {
+                host_error: Some(CallError::Host(HostError::Aborted(message))),
+                output: None,
+                gas_usage,
+                effects: initial_tracking_copy.effects(),
+                cache: initial_tracking_copy.cache(),
+                messages: initial_tracking_copy.messages(),
+                reads: final_tracking_copy.reads(),
+                frames: Vec::new(),
+                system_interactions: Vec::new(),
+            },
             Err(VMError::Export(export_error)) => {
                 error!(?export_error, "export error");
-                Ok(ExecuteResult {
+                ExecuteResult {
                     host_error: Some(CallError::NotCallable),
                     output: None,
                     gas_usage,
                     effects: initial_tracking_copy.effects(),
                     cache: initial_tracking_copy.cache(),
                     messages: initial_tracking_copy.messages(),
-                })
+                    reads: final_tracking_copy.reads(),
+                    frames: Vec::new(),
+                    system_interactions: Vec::new(),
+                }
             }
             Err(VMError::Internal(host_error)) => {
                 error!(?host_error, "host error");
-                Ok(ExecuteResult {
+                ExecuteResult {
                     host_error: Some(CallError::InternalHost),
                     output: None,
                     gas_usage,
                     effects: initial_tracking_copy.effects(),
                     cache: initial_tracking_copy.cache(),
                     messages: initial_tracking_copy.messages(),
-                })
+                    reads: final_tracking_copy.reads(),
+                    frames: Vec::new(),
+                    system_interactions: Vec::new(),
+                }
+            }
+        };
+
+        // The frame only reports the effects it produced itself, as the effects merged from its
+        // sub-calls are reported by their own frames.
+        let mut frame_effects = Effects::new();
+        for (index, transform) in execute_result
+            .effects
+            .transforms()
+            .iter()
+            .enumerate()
+            .skip(inherited_effects_len)
+        {
+            if !sub_call_effects.iter().any(|span| span.contains(&index)) {
+                frame_effects.push(transform.clone());
             }
         }
+        frames.push(CallFrame {
+            depth: 0,
+            execution_kind,
+            effects: frame_effects,
+        });
+        execute_result.frames = frames;
+
+        // Interactions made by a frame which failed are discarded along with its effects.
+        if execute_result.host_error.is_none() {
+            system_interactions.extend(frame_system_interactions);
+        }
+        execute_result.system_interactions = system_interactions;
+
+        Ok(execute_result)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -669,6 +955,7 @@ impl ExecutorV2 {
         block_info: BlockInfo,
         transaction_hash: casper_types::TransactionHash,
         gas_limit: u64,
+        phase: Phase,
     ) -> Result<ExecuteResult, ExecuteError>
     where
         R: GlobalStateReader + 'static,
@@ -679,7 +966,6 @@ impl ExecutorV2 {
             ExecutableItem::Invocation(TransactionInvocationTarget::ByHash(entity_addr.value()));
         let entry_point = entry_point.clone();
         let args = bytesrepr::deserialize_from_slice(input).expect("should deserialize");
-        let phase = Phase::Session;
 
         let wasm_v1_result = {
             let forked_tc = tracking_copy.fork2();
@@ -699,6 +985,28 @@ impl ExecutorV2 {
 
         let effects = wasm_v1_result.effects();
         let messages = wasm_v1_result.messages();
+        let system_interactions = wasm_v1_result
+            .transfers()
+            .iter()
+            .map(|transfer| match transfer {
+                Transfer::V1(transfer) => SystemInteraction::MintTransfer {
+                    source: transfer.source,
+                    target: transfer.target,
+                    amount: transfer.amount,
+                    to: transfer.to,
+                    gas: Gas::new(transfer.gas),
+                    id: transfer.id,
+                },
+                Transfer::V2(transfer) => SystemInteraction::MintTransfer {
+                    source: transfer.source,
+                    target: transfer.target,
+                    amount: transfer.amount,
+                    to: transfer.to,
+                    gas: transfer.gas,
+                    id: transfer.id,
+                },
+            })
+            .collect();
 
         match wasm_v1_result.cache() {
             Some(cache) => {
@@ -753,6 +1061,9 @@ impl ExecutorV2 {
             effects: fork2.effects(),
             cache: fork2.cache(),
             messages: fork2.messages(),
+            reads: fork2.reads(),
+            frames: Vec::new(),
+            system_interactions,
         })
     }
 
@@ -786,6 +1097,9 @@ impl ExecutorV2 {
                 effects,
                 cache: _,
                 messages,
+                reads,
+                frames,
+                system_interactions,
             }) => match state_provider.commit_effects(state_root_hash, effects.clone()) {
                 Ok(post_state_hash) => Ok(ExecuteWithProviderResult::new(
                     host_error,
@@ -794,42 +1108,144 @@ impl ExecutorV2 {
                     effects,
                     post_state_hash,
                     messages,
+                    reads,
+                    frames,
+                    system_interactions,
                 )),
                 Err(error) => Err(error.into()),
             },
             Err(error) => Err(ExecuteWithProviderError::Execute(error)),
         }
     }
+
+    /// Compiles the byte code of the given contracts ahead of time, so that executing them later
+    /// on is served from the module cache.
+    ///
+    /// Returns the outcome for each of `contract_addrs`, in the same order. Fails as a whole only
+    /// if `state_root_hash` can't be checked out.
+    pub fn warm_up<R>(
+        &self,
+        state_root_hash: Digest,
+        state_provider: &R,
+        contract_addrs: &[HashAddr],
+    ) -> Result<Vec<Result<(), WarmUpError>>, GlobalStateError>
+    where
+        R: StateProvider,
+        <R as StateProvider>::Reader: 'static,
+    {
+        let reader = state_provider
+            .checkout(state_root_hash)?
+            .ok_or(GlobalStateError::RootNotFound)?;
+        let mut tracking_copy = TrackingCopy::new(reader, 1, state_provider.enable_entity());
+
+        Ok(contract_addrs
+            .iter()
+            .map(|contract_addr| self.warm_up_contract(&mut tracking_copy, *contract_addr))
+            .collect())
+    }
+
+    fn warm_up_contract<R: GlobalStateReader>(
+        &self,
+        tracking_copy: &mut TrackingCopy<R>,
+        contract_addr: HashAddr,
+    ) -> Result<(), WarmUpError> {
+        let wasm_key = match resolve_stored_contract(
+            tracking_copy,
+            contract_addr,
+            self.config.disabled_version_policy,
+        )? {
+            Some((_entity_addr, StoredValue::AddressableEntity(addressable_entity)))
+                if addressable_entity.kind()
+                    == EntityKind::SmartContract(ContractRuntimeTag::VmCasperV2) =>
+            {
+                Key::ByteCode(ByteCodeAddr::V2CasperWasm(
+                    addressable_entity.byte_code_addr(),
+                ))
+            }
+            Some(_) => return Err(WarmUpError::UnsupportedContract(contract_addr)),
+            None => return Err(WarmUpError::ContractNotFound(contract_addr)),
+        };
+
+        let Some(StoredValue::ByteCode(byte_code)) = tracking_copy.read(&wasm_key)? else {
+            return Err(WarmUpError::ByteCodeNotFound(contract_addr));
+        };
+
+        self.compiled_wasm_engine
+            .precompile(byte_code.bytes())
+            .map_err(WarmUpError::Preparation)
+    }
+
+    /// Returns the number of module cache hits and misses so far.
+    pub fn module_cache_stats(&self) -> ModuleCacheStats {
+        self.compiled_wasm_engine.module_cache_stats()
+    }
 }
 
 impl ExecutorV2 {
     /// Create a new `ExecutorV2` instance.
     pub fn new(config: ExecutorConfig, execution_engine_v1: Arc<ExecutionEngineV1>) -> Self {
+        ExecutorV2::with_mint(config, execution_engine_v1, SystemMint)
+    }
+}
+
+impl<M: MintProvider> ExecutorV2<M> {
+    /// Set the hashes of the bytecode the executor refuses to run.
+    ///
+    /// Executing session bytes or calling a V1 or V2 contract whose bytecode hash is listed fails
+    /// with [`ExecuteError::BytecodeDenied`]. Stored bytecode is identified by the address it is
+    /// stored under, which for V2 bytecode is its hash. Empty if not set.
+    #[must_use]
+    pub fn with_bytecode_denylist(mut self, bytecode_denylist: HashSet<[u8; 32]>) -> Self {
+        self.bytecode_denylist = Arc::new(bytecode_denylist);
+        self
+    }
+
+    /// Create a new `ExecutorV2` instance performing mint operations through `mint`.
+    pub fn with_mint(
+        config: ExecutorConfig,
+        execution_engine_v1: Arc<ExecutionEngineV1>,
+        mint: M,
+    ) -> Self {
         let wasm_engine = match config.executor_kind {
+            // Counting instructions has a cost, so Wasm is only instrumented if there is a budget.
+            ExecutorKind::Compiled if config.instruction_budget.is_some() => {
+                WasmerEngine::with_instruction_budget()
+            }
             ExecutorKind::Compiled => WasmerEngine::new(),
         };
         ExecutorV2 {
             config,
             compiled_wasm_engine: Arc::new(wasm_engine),
             execution_stack: Default::default(),
+            bytecode_denylist: Default::default(),
             execution_engine_v1,
+            mint,
         }
     }
+}
+
+/// Pushes a frame onto an execution stack, and pops it when dropped.
+struct ExecutionStackGuard {
+    execution_stack: Arc<RwLock<VecDeque<ExecutionKind>>>,
+}
 
-    /// Push the execution stack.
-    pub(crate) fn push_execution_stack(&self, execution_kind: ExecutionKind) {
-        let mut execution_stack = self.execution_stack.write();
-        execution_stack.push_back(execution_kind);
+impl ExecutionStackGuard {
+    fn push(
+        execution_stack: Arc<RwLock<VecDeque<ExecutionKind>>>,
+        execution_kind: ExecutionKind,
+    ) -> Self {
+        execution_stack.write().push_back(execution_kind);
+        Self { execution_stack }
     }
+}
 
-    /// Pop the execution stack.
-    pub(crate) fn pop_execution_stack(&self) -> Option<ExecutionKind> {
-        let mut execution_stack = self.execution_stack.write();
-        execution_stack.pop_back()
+impl Drop for ExecutionStackGuard {
+    fn drop(&mut self) {
+        self.execution_stack.write().pop_back();
     }
 }
 
-impl Executor for ExecutorV2 {
+impl<M: MintProvider> Executor for ExecutorV2<M> {
     /// Execute a Wasm contract.
     ///
     /// # Errors
@@ -844,6 +1260,95 @@ impl Executor for ExecutorV2 {
     ) -> Result<ExecuteResult, ExecuteError> {
         self.execute_with_tracking_copy(tracking_copy, execute_request)
     }
+
+    /// Rejects bytecode whose hash is on the denylist.
+    ///
+    /// The address is only computed if the denylist is not empty.
+    fn check_bytecode_allowed(
+        &self,
+        byte_code_addr: impl FnOnce() -> ByteCodeAddr,
+    ) -> Result<(), ExecuteError> {
+        if self.bytecode_denylist.is_empty() {
+            return Ok(());
+        }
+        let byte_code_addr = byte_code_addr();
+        let denied = match byte_code_addr {
+            ByteCodeAddr::V1CasperWasm(hash) | ByteCodeAddr::V2CasperWasm(hash) => {
+                self.bytecode_denylist.contains(&hash)
+            }
+            ByteCodeAddr::Empty => false,
+        };
+        if denied {
+            return Err(ExecuteError::BytecodeDenied { byte_code_addr });
+        }
+        Ok(())
+    }
+}
+
+/// Reads the contract stored under `smart_contract_addr`, following at most one package
+/// indirection to the latest version of the package.
+///
+/// If the latest version is disabled, `disabled_version_policy` decides whether the latest enabled
+/// version is used instead or [`ExecuteError::VersionDisabled`] is returned. Packages whose
+/// resolved version refers back to the package itself, is missing, or is another package are
+/// rejected with [`ExecuteError::InvalidContractGraph`].
+fn resolve_stored_contract<R: GlobalStateReader>(
+    tracking_copy: &mut TrackingCopy<R>,
+    smart_contract_addr: HashAddr,
+    disabled_version_policy: DisabledVersionPolicy,
+) -> Result<Option<(EntityAddr, StoredValue)>, ExecuteError> {
+    let smart_contract_key = Key::SmartContract(smart_contract_addr);
+    let legacy_key = Key::Hash(smart_contract_addr);
+
+    let contract = tracking_copy
+        .read_first(&[&legacy_key, &smart_contract_key])
+        .expect("should read contract");
+
+    let Some(StoredValue::SmartContract(smart_contract_package)) = &contract else {
+        // Anything stored directly under the address is a legacy contract addressed by its hash.
+        let entity_addr =
+            EntityAddr::from_legacy_contract_hash(ContractHash::new(smart_contract_addr));
+        return Ok(contract.map(|contract| (entity_addr, contract)));
+    };
+
+    let invalid_contract_graph = |reason| ExecuteError::InvalidContractGraph {
+        key: smart_contract_key,
+        reason,
+    };
+
+    let latest_contract_hash = *smart_contract_package
+        .versions()
+        .latest()
+        .ok_or_else(|| invalid_contract_graph("package has no versions"))?;
+    let version_disabled = || ExecuteError::VersionDisabled {
+        address: smart_contract_addr,
+        entity_addr: latest_contract_hash,
+    };
+    let contract_hash = if smart_contract_package.is_entity_enabled(&latest_contract_hash) {
+        latest_contract_hash
+    } else {
+        match disabled_version_policy {
+            DisabledVersionPolicy::FallBackToLatestEnabled => smart_contract_package
+                .current_entity_hash()
+                .ok_or_else(version_disabled)?,
+            DisabledVersionPolicy::Reject => return Err(version_disabled()),
+        }
+    };
+    if contract_hash.value() == smart_contract_addr {
+        return Err(invalid_contract_graph("package refers to itself"));
+    }
+
+    let latest_version_key = Key::AddressableEntity(contract_hash);
+    match tracking_copy
+        .read(&latest_version_key)
+        .expect("should read latest version")
+    {
+        Some(StoredValue::SmartContract(_)) => {
+            Err(invalid_contract_graph("latest version refers to a package"))
+        }
+        Some(latest_version) => Ok(Some((contract_hash, latest_version))),
+        None => Err(invalid_contract_graph("latest version does not exist")),
+    }
 }
 
 fn get_purse_for_entity<R: GlobalStateReader>(
@@ -871,13 +1376,14 @@ fn get_purse_for_entity<R: GlobalStateReader>(
             addressable_entity.main_purse()
         }
         StoredValue::Account(account) => account.main_purse(),
+        // A caller named by its entity key, rather than through its account.
+        StoredValue::AddressableEntity(addressable_entity) => addressable_entity.main_purse(),
         StoredValue::SmartContract(smart_contract_package) => {
             let contract_hash = smart_contract_package
                 .versions()
                 .latest()
                 .expect("should have last entry");
-            let entity_addr = EntityAddr::SmartContract(contract_hash.value());
-            let latest_version_key = Key::AddressableEntity(entity_addr);
+            let latest_version_key = Key::AddressableEntity(*contract_hash);
             let new_contract = tracking_copy
                 .read(&latest_version_key)
                 .expect("should read latest version");
@@ -112,9 +112,61 @@ fn dispatch_system_contract<R: GlobalStateReader, Ret: PartialEq>(
     Ok(ret)
 }
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) struct MintArgs {
-    pub(crate) initial_balance: U512,
+/// Mint operations performed by the executor.
+///
+/// [`SystemMint`] dispatches to the real mint system contract; tests may substitute their own
+/// implementation through [`crate::ExecutorV2::with_mint`].
+pub trait MintProvider: Clone + Send + Sync + 'static {
+    /// Creates a new purse holding `args.initial_balance`.
+    fn mint<R: GlobalStateReader>(
+        &self,
+        tracking_copy: &mut TrackingCopy<R>,
+        transaction_hash: TransactionHash,
+        address_generator: Arc<RwLock<AddressGenerator>>,
+        args: MintArgs,
+    ) -> Result<URef, CallError>;
+
+    /// Transfers tokens between purses.
+    fn transfer<R: GlobalStateReader>(
+        &self,
+        tracking_copy: &mut TrackingCopy<R>,
+        transaction_hash: TransactionHash,
+        address_generator: Arc<RwLock<AddressGenerator>>,
+        args: MintTransferArgs,
+    ) -> HostResult;
+}
+
+/// The mint system contract.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemMint;
+
+impl MintProvider for SystemMint {
+    fn mint<R: GlobalStateReader>(
+        &self,
+        tracking_copy: &mut TrackingCopy<R>,
+        transaction_hash: TransactionHash,
+        address_generator: Arc<RwLock<AddressGenerator>>,
+        args: MintArgs,
+    ) -> Result<URef, CallError> {
+        mint_mint(tracking_copy, transaction_hash, address_generator, args)
+    }
+
+    fn transfer<R: GlobalStateReader>(
+        &self,
+        tracking_copy: &mut TrackingCopy<R>,
+        transaction_hash: TransactionHash,
+        address_generator: Arc<RwLock<AddressGenerator>>,
+        args: MintTransferArgs,
+    ) -> HostResult {
+        mint_transfer(tracking_copy, transaction_hash, address_generator, args)
+    }
+}
+
+/// Arguments of a purse creation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintArgs {
+    /// Balance of the new purse.
+    pub initial_balance: U512,
 }
 
 pub(crate) fn mint_mint<R: GlobalStateReader>(
@@ -148,13 +200,19 @@ pub(crate) fn mint_mint<R: GlobalStateReader>(
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub(crate) struct MintTransferArgs {
-    pub(crate) maybe_to: Option<AccountHash>,
-    pub(crate) source: URef,
-    pub(crate) target: URef,
-    pub(crate) amount: U512,
-    pub(crate) id: Option<u64>,
+/// Arguments of a transfer between purses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MintTransferArgs {
+    /// Account the transfer is recorded against, if any.
+    pub maybe_to: Option<AccountHash>,
+    /// Purse the tokens are taken from.
+    pub source: URef,
+    /// Purse the tokens are added to.
+    pub target: URef,
+    /// Amount of tokens to transfer.
+    pub amount: U512,
+    /// Optional transfer id.
+    pub id: Option<u64>,
 }
 
 pub(crate) fn mint_transfer<R: GlobalStateReader>(
@@ -0,0 +1,26 @@
+use casper_executor_wasm_interface::{executor::ExecuteError, WasmPreparationError};
+use casper_storage::tracking_copy::TrackingCopyError;
+use casper_types::HashAddr;
+use thiserror::Error;
+
+/// Reasons a contract could not be warmed up.
+#[derive(Debug, Error)]
+pub enum WarmUpError {
+    #[error("contract {0:?} not found")]
+    ContractNotFound(HashAddr),
+
+    #[error("contract {0:?} is not a Wasm V2 contract")]
+    UnsupportedContract(HashAddr),
+
+    #[error("byte code of contract {0:?} not found")]
+    ByteCodeNotFound(HashAddr),
+
+    #[error("execute: {0}")]
+    Execute(#[from] ExecuteError),
+
+    #[error("tracking copy error: {0}")]
+    TrackingCopy(#[from] TrackingCopyError),
+
+    #[error("preparation error: {0}")]
+    Preparation(#[from] WasmPreparationError),
+}
@@ -1,8 +1,11 @@
 use std::{
+    collections::HashSet,
     env,
+    fmt::Write,
     fs::{self, File},
+    num::NonZeroU64,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use bytes::Bytes;
@@ -12,32 +15,52 @@ use casper_executor_wasm::{
         InstallContractError, InstallContractRequest, InstallContractRequestBuilder,
         InstallContractResult,
     },
-    ExecutorConfigBuilder, ExecutorKind, ExecutorV2,
+    system::{MintArgs, MintProvider, MintTransferArgs},
+    warm_up::WarmUpError,
+    DisabledVersionPolicy, ExecutorConfig, ExecutorConfigBuilder, ExecutorKind, ExecutorV2,
+    GasForwardingPolicy,
 };
-use casper_executor_wasm_common::error::CallError;
-use casper_executor_wasm_interface::executor::{
-    ExecuteRequest, ExecuteRequestBuilder, ExecuteWithProviderResult, ExecutionKind,
+use casper_executor_wasm_common::{
+    chain_utils,
+    error::{CallError, HostError, CALLEE_ABORTED, CALLEE_NOT_CALLABLE},
+};
+use casper_executor_wasm_interface::{
+    executor::{
+        CallFrame, ExecuteError, ExecuteRequest, ExecuteRequestBuilder, ExecuteWithProviderError,
+        ExecuteWithProviderResult, ExecutionKind, SystemInteraction,
+    },
+    HostResult, WasmPreparationError,
 };
 use casper_storage::{
     data_access_layer::{
         prefixed_values::{PrefixedValuesRequest, PrefixedValuesResult},
-        GenesisRequest, GenesisResult, MessageTopicsRequest, MessageTopicsResult, QueryRequest,
-        QueryResult,
+        BalanceIdentifier, GenesisRequest, GenesisResult, HandleFeeMode, HandleFeeRequest,
+        HandleFeeResult, MessageTopicsRequest, MessageTopicsResult, QueryRequest, QueryResult,
     },
     global_state::{
         self,
         state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
         transaction_source::lmdb::LmdbEnvironment,
         trie_store::lmdb::LmdbTrieStore,
+        GlobalStateReader,
     },
-    system::runtime_native::Id,
-    AddressGenerator, KeyPrefix,
+    system::runtime_native::{Config as NativeRuntimeConfig, Id},
+    AddressGenerator, KeyPrefix, TrackingCopy,
 };
 use casper_types::{
-    account::AccountHash, BlockHash, ChainspecRegistry, Digest, EntityAddr, GenesisAccount,
-    GenesisConfig, HostFunctionCostsV2, HostFunctionV2, Key, MessageLimits, Motes, Phase,
-    ProtocolVersion, PublicKey, SecretKey, StorageCosts, StoredValue, SystemConfig, Timestamp,
-    TransactionHash, TransactionV1Hash, WasmConfig, WasmV2Config, U512,
+    account::AccountHash,
+    addressable_entity::{ActionThresholds, AssociatedKeys},
+    bytesrepr::ToBytes,
+    contracts::{ContractPackageHash, EntryPoints},
+    execution::{Effects, TransformKindV2, TransformV2},
+    system::SystemEntityType,
+    AccessRights, AddressableEntity, BlockHash, ByteCodeAddr, ByteCodeHash, Chainspec,
+    ChainspecRegistry, Contract, ContractWasmHash, Digest, EntityAddr, EntityKind, Gas,
+    GenesisAccount, GenesisConfig, Groups, HashAddr, HostFunctionCostsV2, HostFunctionV2,
+    InitiatorAddr, Key, MessageLimits, Motes, NamedKeys, Package, PackageHash, PackageStatus,
+    Phase, ProtocolVersion, PublicKey, SecretKey, StorageCosts, StoredValue, SystemConfig,
+    Timestamp, TransactionHash, TransactionV1Hash, Transfer, TransferV2, URef, WasmConfig,
+    WasmV2Config, U512,
 };
 use fs_extra::dir;
 use itertools::Itertools;
@@ -51,6 +74,11 @@ static DEFAULT_ACCOUNT_PUBLIC_KEY: Lazy<casper_types::PublicKey> =
     Lazy::new(|| PublicKey::from(&*DEFAULT_ACCOUNT_SECRET_KEY));
 static DEFAULT_ACCOUNT_HASH: Lazy<AccountHash> =
     Lazy::new(|| DEFAULT_ACCOUNT_PUBLIC_KEY.to_account_hash());
+static OTHER_ACCOUNT_PUBLIC_KEY: Lazy<casper_types::PublicKey> = Lazy::new(|| {
+    PublicKey::from(&SecretKey::ed25519_from_bytes([200; SecretKey::ED25519_LENGTH]).unwrap())
+});
+static OTHER_ACCOUNT_HASH: Lazy<AccountHash> =
+    Lazy::new(|| OTHER_ACCOUNT_PUBLIC_KEY.to_account_hash());
 
 const CSPR: u64 = 10u64.pow(9);
 
@@ -94,152 +122,2281 @@ fn read_wasm<P: AsRef<Path>>(filename: P) -> Bytes {
         RUST_TOOL_WASM_PATH.clone(),
     ];
 
-    for path in &paths {
-        let wasm_path = path.join(&filename);
-        match fs::read(wasm_path) {
-            Ok(bytes) => return Bytes::from(bytes),
-            Err(err) => {
-                if err.kind() == std::io::ErrorKind::NotFound {
-                    continue;
-                } else {
-                    panic!(
-                        "Failed to read Wasm file at {}: {}",
-                        filename.as_ref().display(),
-                        err
-                    );
-                }
-            }
-        }
-    }
+    for path in &paths {
+        let wasm_path = path.join(&filename);
+        match fs::read(wasm_path) {
+            Ok(bytes) => return Bytes::from(bytes),
+            Err(err) => {
+                if err.kind() == std::io::ErrorKind::NotFound {
+                    continue;
+                } else {
+                    panic!(
+                        "Failed to read Wasm file at {}: {}",
+                        filename.as_ref().display(),
+                        err
+                    );
+                }
+            }
+        }
+    }
+
+    panic!(
+        "Failed to find Wasm file at {} in any of the paths: {:?}",
+        filename.as_ref().display(),
+        paths
+    );
+}
+
+const TRANSACTION_HASH_BYTES: [u8; 32] = [55; 32];
+const TRANSACTION_HASH: TransactionHash =
+    TransactionHash::V1(TransactionV1Hash::from_raw(TRANSACTION_HASH_BYTES));
+const DEFAULT_GAS_LIMIT: u64 = 1_000_000 * CSPR;
+const DEFAULT_CHAIN_NAME: &str = "casper-test";
+
+// TODO: This is a temporary value, it should be set in the config. Default value from V1 engine
+// does not apply to V2 engine due to different cost structure. Rather than hardcoding it here, we
+// should probably reflect gas costs in a dynamic costs in host function charge. Proper value is
+// pending calculation.
+const DEFAULT_GAS_PER_BYTE_COST: u32 = 1_117_587;
+
+fn make_address_generator() -> Arc<RwLock<AddressGenerator>> {
+    let id = Id::Transaction(TRANSACTION_HASH);
+    Arc::new(RwLock::new(AddressGenerator::new(
+        &id.seed(),
+        Phase::Session,
+    )))
+}
+
+fn base_execute_builder() -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::default()
+        .with_initiator(*DEFAULT_ACCOUNT_HASH)
+        .with_caller_key(Key::Account(*DEFAULT_ACCOUNT_HASH))
+        .with_gas_limit(DEFAULT_GAS_LIMIT)
+        .with_transferred_value(1000)
+        .with_transaction_hash(TRANSACTION_HASH)
+        .with_chain_name(DEFAULT_CHAIN_NAME)
+        .with_block_time(Timestamp::now().into())
+        .with_state_hash(Digest::hash(b"state"))
+        .with_block_height(1)
+        .with_parent_block_hash(BlockHash::new(Digest::hash(b"block1")))
+}
+
+fn base_install_request_builder() -> InstallContractRequestBuilder {
+    InstallContractRequestBuilder::default()
+        .with_initiator(*DEFAULT_ACCOUNT_HASH)
+        .with_gas_limit(DEFAULT_GAS_LIMIT)
+        .with_transaction_hash(TRANSACTION_HASH)
+        .with_chain_name(DEFAULT_CHAIN_NAME)
+        .with_block_time(Timestamp::now().into())
+        .with_state_hash(Digest::hash(b"state"))
+        .with_block_height(1)
+        .with_parent_block_hash(BlockHash::new(Digest::hash(b"block1")))
+}
+
+#[test]
+fn harness() {
+    let mut executor = make_executor();
+
+    let (mut global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let address_generator = make_address_generator();
+
+    let flipper_address;
+
+    state_root_hash = {
+        let input_data = borsh::to_vec(&("Foo Token".to_string(),))
+            .map(Bytes::from)
+            .unwrap();
+
+        let install_request = base_install_request_builder()
+            .with_wasm_bytes(read_wasm("vm2_cep18.wasm"))
+            .with_shared_address_generator(Arc::clone(&address_generator))
+            .with_transferred_value(0)
+            .with_entry_point("new".to_string())
+            .with_input(input_data)
+            .build()
+            .expect("should build");
+
+        let create_result = run_create_contract(
+            &mut executor,
+            &mut global_state,
+            state_root_hash,
+            install_request,
+        );
+
+        flipper_address = *create_result.smart_contract_addr();
+
+        global_state
+            .commit_effects(state_root_hash, create_result.effects().clone())
+            .expect("Should commit")
+    };
+
+    let execute_request = ExecuteRequestBuilder::default()
+        .with_initiator(*DEFAULT_ACCOUNT_HASH)
+        .with_caller_key(Key::Account(*DEFAULT_ACCOUNT_HASH))
+        .with_gas_limit(DEFAULT_GAS_LIMIT)
+        .with_transferred_value(1000)
+        .with_transaction_hash(TRANSACTION_HASH)
+        .with_target(ExecutionKind::SessionBytes(read_wasm("vm2-harness.wasm")))
+        .with_serialized_input((flipper_address,))
+        .with_shared_address_generator(address_generator)
+        .with_chain_name(DEFAULT_CHAIN_NAME)
+        .with_block_time(Timestamp::now().into())
+        .with_state_hash(state_root_hash)
+        .with_block_height(1)
+        .with_parent_block_hash(BlockHash::new(Digest::hash(b"bl0ck")))
+        .build()
+        .expect("should build");
+
+    run_wasm_session(
+        &mut executor,
+        &mut global_state,
+        state_root_hash,
+        execute_request,
+    );
+}
+
+#[test]
+fn should_record_keys_read_during_execution() {
+    let mut executor = make_executor();
+    let (mut global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let install_request = base_install_request_builder()
+        .with_wasm_bytes(read_wasm("vm2_host.wasm"))
+        .with_shared_address_generator(make_address_generator())
+        .with_transferred_value(0)
+        .with_entry_point("default".to_string())
+        .with_input(Bytes::new())
+        .build()
+        .expect("should build");
+
+    let create_result = run_create_contract(
+        &mut executor,
+        &mut global_state,
+        state_root_hash,
+        install_request,
+    );
+    let contract_address = *create_result.smart_contract_addr();
+
+    state_root_hash = global_state
+        .commit_effects(state_root_hash, create_result.effects().clone())
+        .expect("Should commit");
+
+    let execute_request = |record_reads| {
+        base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address: contract_address,
+                entry_point: "get_balance_of".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .with_record_reads(record_reads)
+            .build()
+            .expect("should build")
+    };
+
+    // Reads are only recorded on request.
+    let result = run_wasm_session(
+        &mut executor,
+        &mut global_state,
+        state_root_hash,
+        execute_request(false),
+    );
+    assert!(result.reads().is_empty());
+
+    let result = run_wasm_session(
+        &mut executor,
+        &global_state,
+        state_root_hash,
+        execute_request(true),
+    );
+
+    let reads = result.reads();
+    assert!(reads.contains(&Key::State(EntityAddr::SmartContract(contract_address))));
+    // `get_balance_of` queries the balance of the account with a zeroed account hash.
+    assert!(reads.contains(&Key::Account(AccountHash::new([0; 32]))));
+}
+
+#[test]
+fn should_reject_malformed_package_entries() {
+    const PACKAGE_ADDR: [u8; 32] = [11; 32];
+    const OTHER_PACKAGE_ADDR: [u8; 32] = [12; 32];
+
+    let executor = make_executor();
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let package_with_latest_version = |entity_addr| {
+        let mut package = Package::new(
+            Default::default(),
+            Default::default(),
+            Groups::default(),
+            PackageStatus::Unlocked,
+        );
+        package.insert_entity_version(2, EntityAddr::SmartContract(entity_addr));
+        StoredValue::SmartContract(package)
+    };
+
+    let mut effects = Effects::new();
+    // A package whose latest version refers back to the package itself.
+    effects.push(TransformV2::new(
+        Key::SmartContract(PACKAGE_ADDR),
+        TransformKindV2::Write(package_with_latest_version(PACKAGE_ADDR)),
+    ));
+    // A package whose latest version resolves to another package rather than an entity.
+    effects.push(TransformV2::new(
+        Key::SmartContract(OTHER_PACKAGE_ADDR),
+        TransformKindV2::Write(package_with_latest_version([13; 32])),
+    ));
+    effects.push(TransformV2::new(
+        Key::AddressableEntity(EntityAddr::SmartContract([13; 32])),
+        TransformKindV2::Write(package_with_latest_version(PACKAGE_ADDR)),
+    ));
+    let state_root_hash = global_state
+        .commit_effects(state_root_hash, effects)
+        .expect("Should commit");
+
+    for package_addr in [PACKAGE_ADDR, OTHER_PACKAGE_ADDR] {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address: package_addr,
+                entry_point: "call".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+
+        let result =
+            executor.execute_with_provider(state_root_hash, &global_state, execute_request);
+        match result {
+            Err(ExecuteWithProviderError::Execute(ExecuteError::InvalidContractGraph {
+                key,
+                ..
+            })) => assert_eq!(key, Key::SmartContract(package_addr)),
+            other => panic!("expected invalid contract graph error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn should_reject_execution_against_non_contract_entities() {
+    const SYSTEM_ENTITY_ADDR: [u8; 32] = [21; 32];
+    const ACCOUNT_ENTITY_ADDR: [u8; 32] = [22; 32];
+
+    let executor = make_executor();
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let entity_of_kind = |entity_kind| {
+        StoredValue::AddressableEntity(AddressableEntity::new(
+            PackageHash::new([23; 32]),
+            ByteCodeHash::new([24; 32]),
+            ProtocolVersion::V2_0_0,
+            URef::default(),
+            AssociatedKeys::default(),
+            ActionThresholds::default(),
+            entity_kind,
+        ))
+    };
+    let system_kind = EntityKind::System(SystemEntityType::Mint);
+    let account_kind = EntityKind::Account(AccountHash::new([25; 32]));
+
+    let mut effects = Effects::new();
+    effects.push(TransformV2::new(
+        Key::SmartContract(SYSTEM_ENTITY_ADDR),
+        TransformKindV2::Write(entity_of_kind(system_kind)),
+    ));
+    effects.push(TransformV2::new(
+        Key::SmartContract(ACCOUNT_ENTITY_ADDR),
+        TransformKindV2::Write(entity_of_kind(account_kind)),
+    ));
+    let state_root_hash = global_state
+        .commit_effects(state_root_hash, effects)
+        .expect("Should commit");
+
+    for (entity_addr, expected_kind) in [
+        (SYSTEM_ENTITY_ADDR, system_kind),
+        (ACCOUNT_ENTITY_ADDR, account_kind),
+    ] {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address: entity_addr,
+                entry_point: "call".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+
+        let result =
+            executor.execute_with_provider(state_root_hash, &global_state, execute_request);
+        match result {
+            Err(ExecuteWithProviderError::Execute(ExecuteError::UnexpectedEntityKind {
+                address,
+                kind,
+            })) => {
+                assert_eq!(address, entity_addr);
+                assert_eq!(kind, expected_kind);
+            }
+            other => panic!("expected unexpected entity kind error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn should_apply_disabled_version_policy_to_latest_version() {
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "noop")))
+    "#;
+    const DISABLED_ENTITY_ADDR: EntityAddr = EntityAddr::SmartContract([31; 32]);
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let contract_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CONTRACT_WAT).unwrap(),
+    );
+
+    // Publish a newer version of the package and disable it straight away. The disabled version
+    // has no entity, so resolving it would fail.
+    let package_key = Key::SmartContract(contract_address);
+    let mut package =
+        match global_state.query(QueryRequest::new(state_root_hash, package_key, Vec::new())) {
+            QueryResult::Success { value, .. } => match *value {
+                StoredValue::SmartContract(package) => package,
+                other => panic!("expected package, got {other:?}"),
+            },
+            other => panic!("expected package, got {other:?}"),
+        };
+    package.insert_entity_version(2, DISABLED_ENTITY_ADDR);
+    package
+        .disable_entity_version(DISABLED_ENTITY_ADDR)
+        .expect("should disable version");
+    let mut effects = Effects::new();
+    effects.push(TransformV2::new(
+        package_key,
+        TransformKindV2::Write(StoredValue::SmartContract(package)),
+    ));
+    state_root_hash = global_state
+        .commit_effects(state_root_hash, effects)
+        .expect("Should commit");
+
+    let execute = |disabled_version_policy| {
+        let config = ExecutorConfigBuilder::default()
+            .with_memory_limit(17)
+            .with_executor_kind(ExecutorKind::Compiled)
+            .with_wasm_config(WasmV2Config::default())
+            .with_storage_costs(StorageCosts::new(DEFAULT_GAS_PER_BYTE_COST))
+            .with_message_limits(MessageLimits::default())
+            .with_disabled_version_policy(disabled_version_policy)
+            .build()
+            .expect("Should build");
+        let executor = ExecutorV2::new(config, Arc::new(ExecutionEngineV1::default()));
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address: contract_address,
+                entry_point: "noop".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor.execute_with_provider(state_root_hash, &global_state, execute_request)
+    };
+
+    let result = execute(DisabledVersionPolicy::FallBackToLatestEnabled)
+        .expect("should call the latest enabled version");
+    assert!(result.host_error.is_none());
+
+    match execute(DisabledVersionPolicy::Reject) {
+        Err(ExecuteWithProviderError::Execute(ExecuteError::VersionDisabled {
+            address,
+            entity_addr,
+        })) => {
+            assert_eq!(address, contract_address);
+            assert_eq!(entity_addr, DISABLED_ENTITY_ADDR);
+        }
+        other => panic!("expected disabled version error, got {other:?}"),
+    }
+}
+
+#[test]
+fn should_report_effects_of_each_call_frame() {
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 0) "callee")
+            (func (export "callee")
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 6)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    let mut executor = make_executor();
+    let (mut global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let callee_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CALLEE_WAT).unwrap(),
+    );
+
+    // The caller writes to its own state, then calls into the callee which writes to its state.
+    let escaped_address = wat_bytes_literal(&callee_address);
+    let caller_wat = format!(
+        r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_call" (func $call (param i32 i32 i64 i32 i32 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 0) "{escaped_address}")
+            (data (i32.const 32) "callee")
+            (data (i32.const 64) "caller")
+            (func (export "call")
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 64) (i32.const 6)) (i32.const 0))
+                    (then unreachable))
+                (if (i32.ne (call $call (i32.const 0) (i32.const 32) (i64.const 0) (i32.const 32) (i32.const 6) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0)) (i32.const 0))
+                    (then unreachable))))
+        "#
+    );
+
+    let execute_request = base_execute_builder()
+        .with_transferred_value(0)
+        .with_target(ExecutionKind::SessionBytes(Bytes::from(
+            wat::parse_str(caller_wat).unwrap(),
+        )))
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    let result = run_wasm_session(
+        &mut executor,
+        &mut global_state,
+        state_root_hash,
+        execute_request,
+    );
+
+    let caller_key = Key::State(EntityAddr::new_account(DEFAULT_ACCOUNT_HASH.value()));
+    let callee_key = Key::State(EntityAddr::new_smart_contract(callee_address));
+    let writes = |frame: &CallFrame| -> Vec<Key> {
+        frame
+            .effects
+            .transforms()
+            .iter()
+            .filter(|transform| matches!(transform.kind(), TransformKindV2::Write(_)))
+            .map(|transform| *transform.key())
+            .collect()
+    };
+
+    // Frames are reported in order of completion, so the callee comes first.
+    let [callee_frame, caller_frame] = result.frames() else {
+        panic!("expected two call frames, got {:?}", result.frames());
+    };
+
+    assert_eq!(callee_frame.depth, 1);
+    assert_eq!(
+        callee_frame.execution_kind,
+        ExecutionKind::Stored {
+            address: callee_address,
+            entry_point: "callee".to_string(),
+        }
+    );
+    assert!(writes(callee_frame).contains(&callee_key));
+    assert!(!writes(callee_frame).contains(&caller_key));
+
+    assert_eq!(caller_frame.depth, 0);
+    assert!(matches!(
+        caller_frame.execution_kind,
+        ExecutionKind::SessionBytes(_)
+    ));
+    // The effects merged from the sub-call are only reported by the callee's frame.
+    assert!(writes(caller_frame).contains(&caller_key));
+    assert!(!writes(caller_frame).contains(&callee_key));
+    assert_eq!(
+        caller_frame.effects.len() + callee_frame.effects.len(),
+        result.effects().len()
+    );
+}
+
+#[test]
+fn should_report_caller_entity_of_each_call_frame() {
+    // Stores the caller address and kind reported by `casper_env_info` in the contract's state.
+    const PROBE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_env_info" (func $env_info (param i32 i32) (result i32)))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (func (export "probe")
+                (drop (call $env_info (i32.const 0) (i32.const 96)))
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 16) (i32.const 36)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let probe_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(PROBE_WAT).unwrap(),
+    );
+
+    let escaped_address = wat_bytes_literal(&probe_address);
+    let relay_wat = format!(
+        r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_call" (func $call (param i32 i32 i64 i32 i32 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 0) "{escaped_address}")
+            (data (i32.const 32) "probe")
+            (func (export "relay")
+                (if (i32.ne (call $call (i32.const 0) (i32.const 32) (i64.const 0) (i32.const 32) (i32.const 5) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0)) (i32.const 0))
+                    (then unreachable))))
+        "#
+    );
+    let relay_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(relay_wat).unwrap(),
+    );
+
+    let reported_caller = |address: HashAddr, entry_point: &str| -> Vec<u8> {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address,
+                entry_point: entry_point.to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        let result = executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed");
+        assert!(result.host_error.is_none());
+        let post_state_hash = global_state
+            .commit_effects(state_root_hash, result.effects().clone())
+            .expect("Should commit");
+        match global_state.query(QueryRequest::new(
+            post_state_hash,
+            Key::State(EntityAddr::new_smart_contract(probe_address)),
+            Vec::new(),
+        )) {
+            QueryResult::Success { value, .. } => match *value {
+                StoredValue::RawBytes(bytes) => bytes,
+                other => panic!("expected raw bytes, got {other:?}"),
+            },
+            other => panic!("expected probe state, got {other:?}"),
+        }
+    };
+
+    let mut expected_account_caller = DEFAULT_ACCOUNT_HASH.value().to_vec();
+    expected_account_caller.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(
+        reported_caller(probe_address, "probe"),
+        expected_account_caller
+    );
+
+    let mut expected_contract_caller = relay_address.to_vec();
+    expected_contract_caller.extend_from_slice(&1u32.to_le_bytes());
+    assert_eq!(
+        reported_caller(relay_address, "relay"),
+        expected_contract_caller
+    );
+}
+
+#[test]
+fn should_report_phase_of_execute_request() {
+    // Stores the phase tag reported by `casper_env_info` in the contract's state.
+    const PROBE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_env_info" (func $env_info (param i32 i32) (result i32)))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (func (export "probe")
+                (drop (call $env_info (i32.const 0) (i32.const 96)))
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 88) (i32.const 8)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let probe_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(PROBE_WAT).unwrap(),
+    );
+
+    for phase in [Phase::Payment, Phase::Session] {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address: probe_address,
+                entry_point: "probe".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .with_phase(phase)
+            .build()
+            .expect("should build");
+        let result = executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed");
+        assert!(result.host_error.is_none());
+        let post_state_hash = global_state
+            .commit_effects(state_root_hash, result.effects().clone())
+            .expect("Should commit");
+        let reported_phase = match global_state.query(QueryRequest::new(
+            post_state_hash,
+            Key::State(EntityAddr::new_smart_contract(probe_address)),
+            Vec::new(),
+        )) {
+            QueryResult::Success { value, .. } => match *value {
+                StoredValue::RawBytes(bytes) => {
+                    u64::from_le_bytes(bytes.try_into().expect("should be a u64"))
+                }
+                other => panic!("expected raw bytes, got {other:?}"),
+            },
+            other => panic!("expected probe state, got {other:?}"),
+        };
+        assert_eq!(reported_phase, phase as u64);
+    }
+}
+
+#[test]
+fn should_surface_abort_message_to_caller() {
+    // Writes to its state and then aborts, which discards the write.
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_abort" (func $abort (param i32 i32)))
+            (data (i32.const 0) "out of stock")
+            (func (export "callee")
+                (drop (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 12)))
+                (call $abort (i32.const 0) (i32.const 12))))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let callee_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CALLEE_WAT).unwrap(),
+    );
+
+    // Returns the result code of calling the callee, followed by the host error passed in place of
+    // the output of the call.
+    let escaped_address = wat_bytes_literal(&callee_address);
+    let caller_wat = format!(
+        r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_call" (func $call (param i32 i32 i64 i32 i32 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_return" (func $return (param i32 i32 i32)))
+            (data (i32.const 0) "{escaped_address}")
+            (data (i32.const 32) "callee")
+            (func (export "caller")
+                (i32.store (i32.const 64) (call $call (i32.const 0) (i32.const 32) (i64.const 0) (i32.const 32) (i32.const 6) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 68)))
+                (call $return (i32.const 0) (i32.const 64) (i32.const 21))))
+        "#
+    );
+    let caller_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(caller_wat).unwrap(),
+    );
+
+    let execute = |address: HashAddr, entry_point: &str| {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address,
+                entry_point: entry_point.to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed")
+    };
+
+    let callee_state_key = Key::State(EntityAddr::new_smart_contract(callee_address));
+
+    let result = execute(callee_address, "callee");
+    assert!(matches!(
+        &result.host_error,
+        Some(CallError::Host(HostError::Aborted(message))) if message == "out of stock"
+    ));
+    assert!(!result
+        .effects()
+        .transforms()
+        .iter()
+        .any(|transform| *transform.key() == callee_state_key));
+
+    let result = execute(caller_address, "caller");
+    assert!(result.host_error.is_none());
+    assert_eq!(
+        result.output().map(|output| output.as_ref()),
+        Some(
+            &[
+                &CALLEE_ABORTED.to_le_bytes()[..],
+                &borsh::to_vec(&HostError::Aborted("out of stock".into())).unwrap(),
+            ]
+            .concat()[..]
+        )
+    );
+    assert!(!result
+        .effects()
+        .transforms()
+        .iter()
+        .any(|transform| *transform.key() == callee_state_key));
+}
+
+#[test]
+fn should_order_effects_of_transfer_and_write_deterministically() {
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 0) "callee")
+            (func (export "callee")
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 6)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    let mut executor = make_executor();
+    let (mut global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let callee_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CALLEE_WAT).unwrap(),
+    );
+
+    // Transfers value to the callee's purse and then writes to the callee's state.
+    let mut run = || {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(1000)
+            .with_target(ExecutionKind::Stored {
+                address: callee_address,
+                entry_point: "callee".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        let result = run_wasm_session(
+            &mut executor,
+            &mut global_state,
+            state_root_hash,
+            execute_request,
+        );
+        result.effects().clone()
+    };
+
+    let first = run();
+    let second = run();
+
+    let keys: Vec<Key> = first
+        .transforms()
+        .iter()
+        .map(|transform| *transform.key())
+        .collect();
+    assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(keys.iter().any(|key| matches!(key, Key::Balance(_))));
+    assert!(keys.contains(&Key::State(EntityAddr::new_smart_contract(callee_address))));
+    assert_eq!(
+        first.to_bytes().expect("should serialize"),
+        second.to_bytes().expect("should serialize")
+    );
+}
+
+#[test]
+fn should_record_mint_transfer_of_value_bearing_call() {
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "callee")))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let callee_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CALLEE_WAT).unwrap(),
+    );
+
+    let call_callee = |transferred_value| {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(transferred_value)
+            .with_target(ExecutionKind::Stored {
+                address: callee_address,
+                entry_point: "callee".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed")
+    };
+
+    let result = call_callee(1000);
+    assert!(result.host_error.is_none());
+    assert!(matches!(
+        result.system_interactions(),
+        [SystemInteraction::MintTransfer { source, target, amount, .. }]
+            if source != target && *amount == U512::from(1000)
+    ));
+
+    let result = call_callee(0);
+    assert!(result.system_interactions().is_empty());
+}
+
+#[test]
+fn should_record_mint_transfer_made_by_contract() {
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    // Both entry points send 400 motes to the other account under id 7; `pay_and_revert` then
+    // reverts.
+    let escaped_account = wat_bytes_literal(&OTHER_ACCOUNT_HASH.value());
+    let payer_wat = format!(
+        r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_transfer_with_id" (func $transfer (param i32 i32 i32 i64) (result i32)))
+            (import "env" "casper_return" (func $return (param i32 i32 i32)))
+            (data (i32.const 16) "{escaped_account}")
+            (data (i32.const 64) "\90\01\00\00\00\00\00\00")
+            (func $pay
+                (if (i32.ne (call $transfer (i32.const 16) (i32.const 32) (i32.const 64) (i64.const 7)) (i32.const 0))
+                    (then unreachable)))
+            (func (export "pay")
+                (call $pay))
+            (func (export "pay_and_revert")
+                (call $pay)
+                (call $return (i32.const 1) (i32.const 0) (i32.const 0))))
+        "#
+    );
+
+    let payer_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(payer_wat).unwrap(),
+    );
+
+    let call_payer = |entry_point: &str| {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(1000)
+            .with_target(ExecutionKind::Stored {
+                address: payer_address,
+                entry_point: entry_point.to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed")
+    };
+
+    let result = call_payer("pay");
+    assert!(result.host_error.is_none());
+    let [SystemInteraction::MintTransfer {
+        target: payer_purse,
+        amount: attached,
+        ..
+    }, SystemInteraction::MintTransfer {
+        source,
+        target,
+        amount,
+        ..
+    }] = result.system_interactions()
+    else {
+        panic!(
+            "expected two mint transfers, got {:?}",
+            result.system_interactions()
+        );
+    };
+    assert_eq!(*attached, U512::from(1000));
+    assert_eq!(source, payer_purse);
+    let other_purse = main_purse_of(&global_state, state_root_hash, *OTHER_ACCOUNT_HASH);
+    assert_eq!(target.addr(), other_purse.addr());
+    assert_eq!(*amount, U512::from(400));
+
+    // The summary reports the recipient, id and gas charge of the transfer made by the contract.
+    let pre_state = global_state
+        .checkout(state_root_hash)
+        .expect("should checkout")
+        .expect("should have state");
+    let summary = result
+        .summary(TRANSACTION_HASH, *DEFAULT_ACCOUNT_HASH, &pre_state)
+        .expect("should summarize");
+    let [_, Transfer::V2(transfer)] = summary.transfers.as_slice() else {
+        panic!("expected two transfers, got {:?}", summary.transfers);
+    };
+    let transfer_gas = WasmV2Config::default()
+        .host_function_costs()
+        .transfer
+        .calculate_gas_cost([16, 32, 64])
+        .expect("should calculate gas cost");
+    assert_eq!(transfer.to, Some(*OTHER_ACCOUNT_HASH));
+    assert_eq!(transfer.id, Some(7));
+    assert_eq!(transfer.gas, transfer_gas);
+    assert!(transfer.gas > Gas::zero());
+
+    // The transfer made by a reverted frame is discarded along with its effects.
+    let result = call_payer("pay_and_revert");
+    assert!(matches!(result.host_error, Some(CallError::CalleeReverted)));
+    assert!(matches!(
+        result.system_interactions(),
+        [SystemInteraction::MintTransfer { amount, .. }] if *amount == U512::from(1000)
+    ));
+}
+
+#[test]
+fn should_summarize_value_bearing_call() {
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "callee")))
+    "#;
+
+    let mut executor = make_executor();
+    let (mut global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let callee_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CALLEE_WAT).unwrap(),
+    );
+
+    let execute_request = base_execute_builder()
+        .with_transferred_value(1000)
+        .with_target(ExecutionKind::Stored {
+            address: callee_address,
+            entry_point: "callee".to_string(),
+        })
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    let result = run_wasm_session(
+        &mut executor,
+        &mut global_state,
+        state_root_hash,
+        execute_request,
+    );
+
+    let pre_state = global_state
+        .checkout(state_root_hash)
+        .expect("should checkout")
+        .expect("should have state");
+    let summary = result
+        .summary(TRANSACTION_HASH, *DEFAULT_ACCOUNT_HASH, &pre_state)
+        .expect("should summarize");
+
+    let [SystemInteraction::MintTransfer {
+        source,
+        target,
+        amount,
+        ..
+    }] = result.system_interactions()
+    else {
+        panic!(
+            "expected a single mint transfer, got {:?}",
+            result.system_interactions()
+        );
+    };
+    assert_eq!(*amount, U512::from(1000));
+    assert_eq!(
+        summary.transfers,
+        vec![Transfer::V2(TransferV2::new(
+            TRANSACTION_HASH,
+            InitiatorAddr::AccountHash(*DEFAULT_ACCOUNT_HASH),
+            None,
+            *source,
+            *target,
+            *amount,
+            Gas::zero(),
+            None,
+        ))]
+    );
+
+    // Only the balances of both purses were modified.
+    assert_eq!(summary.keys_written, 2);
+    let balance_change = |purse: &URef| {
+        summary
+            .balance_changes
+            .iter()
+            .find(|(changed, _, _)| changed.addr() == purse.addr())
+            .map(|(_, before, after)| (*before, *after))
+            .expect("should record balance change")
+    };
+    let (source_before, source_after) = balance_change(source);
+    assert_eq!(source_before - source_after, U512::from(1000));
+    assert_eq!(balance_change(target), (U512::zero(), U512::from(1000)));
+    assert_eq!(summary.balance_changes.len(), 2);
+}
+
+fn main_purse_of(global_state: &LmdbGlobalState, state_hash: Digest, account: AccountHash) -> URef {
+    let query = |key: Key| match global_state.query(QueryRequest::new(state_hash, key, Vec::new()))
+    {
+        QueryResult::Success { value, .. } => *value,
+        other => panic!("expected {key:?} to exist, got {other:?}"),
+    };
+    match query(Key::Account(account)) {
+        StoredValue::Account(account) => account.main_purse(),
+        StoredValue::CLValue(entity_key) => {
+            let entity_key: Key = entity_key.into_t().expect("should be key");
+            query(entity_key)
+                .into_addressable_entity()
+                .expect("should be addressable entity")
+                .main_purse()
+        }
+        other => panic!("expected account, got {other:?}"),
+    }
+}
+
+#[test]
+fn should_observe_caller_distinct_from_initiator() {
+    // Stores the caller address and kind reported by `casper_env_info` in the contract's state.
+    const PROBE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_env_info" (func $env_info (param i32 i32) (result i32)))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (func (export "probe")
+                (drop (call $env_info (i32.const 0) (i32.const 96)))
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 16) (i32.const 36)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    let mut executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let probe_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(PROBE_WAT).unwrap(),
+    );
+
+    // A relayer initiates the transaction on behalf of another account, which is named by its
+    // entity key.
+    let execute_request = base_execute_builder()
+        .with_caller_key(Key::AddressableEntity(EntityAddr::Account(
+            OTHER_ACCOUNT_HASH.value(),
+        )))
+        .with_transferred_value(1000)
+        .with_target(ExecutionKind::Stored {
+            address: probe_address,
+            entry_point: "probe".to_string(),
+        })
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    let initiator = execute_request.initiator;
+    assert_eq!(initiator, *DEFAULT_ACCOUNT_HASH);
+    let result = run_wasm_session(
+        &mut executor,
+        &global_state,
+        state_root_hash,
+        execute_request,
+    );
+    let post_state_hash = global_state
+        .commit_effects(state_root_hash, result.effects().clone())
+        .expect("Should commit");
+
+    // The contract observes the caller rather than the initiator.
+    let reported_caller = match global_state.query(QueryRequest::new(
+        post_state_hash,
+        Key::State(EntityAddr::new_smart_contract(probe_address)),
+        Vec::new(),
+    )) {
+        QueryResult::Success { value, .. } => match *value {
+            StoredValue::RawBytes(bytes) => bytes,
+            other => panic!("expected raw bytes, got {other:?}"),
+        },
+        other => panic!("expected probe state, got {other:?}"),
+    };
+    let mut expected_caller = OTHER_ACCOUNT_HASH.value().to_vec();
+    expected_caller.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(reported_caller, expected_caller);
+
+    // Transferred value is drawn from the caller's purse, leaving the initiator's untouched...
+    let caller_purse = main_purse_of(&global_state, state_root_hash, *OTHER_ACCOUNT_HASH);
+    let initiator_purse = main_purse_of(&global_state, state_root_hash, initiator);
+    let caller_balance = balance_of(&global_state, state_root_hash, caller_purse);
+    let initiator_balance = balance_of(&global_state, state_root_hash, initiator_purse);
+    assert_eq!(
+        balance_of(&global_state, post_state_hash, caller_purse),
+        caller_balance - 1000
+    );
+    assert_eq!(
+        balance_of(&global_state, post_state_hash, initiator_purse),
+        initiator_balance
+    );
+
+    // ...while the fee for the gas used is paid by the initiator of the transaction.
+    let fee = U512::from(result.gas_usage().gas_spent());
+    assert!(!fee.is_zero());
+    let handle_fee_request = HandleFeeRequest::new(
+        NativeRuntimeConfig::from_chainspec(&Chainspec::default()),
+        post_state_hash,
+        ProtocolVersion::V2_0_0,
+        TRANSACTION_HASH,
+        HandleFeeMode::pay(
+            Box::new(InitiatorAddr::AccountHash(initiator)),
+            BalanceIdentifier::Account(initiator),
+            BalanceIdentifier::Payment,
+            fee,
+        ),
+    );
+    let fee_effects = match global_state.handle_fee(handle_fee_request) {
+        HandleFeeResult::Success { effects, .. } => effects,
+        other => panic!("expected the fee to be paid, got {other:?}"),
+    };
+    let paid_state_hash = global_state
+        .commit_effects(post_state_hash, fee_effects)
+        .expect("Should commit");
+    assert_eq!(
+        balance_of(&global_state, paid_state_hash, initiator_purse),
+        initiator_balance - fee
+    );
+    assert_eq!(
+        balance_of(&global_state, paid_state_hash, caller_purse),
+        caller_balance - 1000
+    );
+}
+
+fn balance_of(global_state: &LmdbGlobalState, state_hash: Digest, purse: URef) -> U512 {
+    match global_state.query(QueryRequest::new(
+        state_hash,
+        Key::Balance(purse.addr()),
+        Vec::new(),
+    )) {
+        QueryResult::Success { value, .. } => match *value {
+            StoredValue::CLValue(balance) => balance.into_t().expect("should be balance"),
+            other => panic!("expected balance, got {other:?}"),
+        },
+        other => panic!("expected balance of {purse}, got {other:?}"),
+    }
+}
+
+#[test]
+fn should_reject_value_transfer_to_non_payable_entry_point() {
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "callee"))
+            (@custom "casper_manifest" "\00\00\00\00\06\00\00\00callee"))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let callee_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CALLEE_WAT).unwrap(),
+    );
+
+    let execute_request = base_execute_builder()
+        .with_transferred_value(1000)
+        .with_target(ExecutionKind::Stored {
+            address: callee_address,
+            entry_point: "callee".to_string(),
+        })
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    let result = executor
+        .execute_with_provider(state_root_hash, &global_state, execute_request)
+        .expect("Succeed");
+
+    assert!(matches!(result.host_error, Some(CallError::NotPayable)));
+    assert!(!result
+        .effects()
+        .transforms()
+        .iter()
+        .any(|transform| matches!(transform.key(), Key::Balance(_))));
+}
+
+#[test]
+fn should_run_session_bytes_with_custom_entry_point() {
+    const SESSION_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_return" (func $return (param i32 i32 i32)))
+            (data (i32.const 64) "started")
+            (func (export "_start")
+                (call $return (i32.const 0) (i32.const 64) (i32.const 7))))
+    "#;
+
+    let mut executor = make_executor();
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let session_request = |entry_point: Option<&str>| {
+        let builder = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::SessionBytes(Bytes::from(
+                wat::parse_str(SESSION_WAT).unwrap(),
+            )))
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator());
+        match entry_point {
+            Some(entry_point) => builder.with_session_entry_point(entry_point),
+            None => builder,
+        }
+        .build()
+        .expect("should build")
+    };
+
+    let result = run_wasm_session(
+        &mut executor,
+        &global_state,
+        state_root_hash,
+        session_request(Some("_start")),
+    );
+    assert_eq!(
+        result.output().map(|output| &output[..]),
+        Some(&b"started"[..])
+    );
+
+    // The module does not export the default `call` entry point.
+    let result =
+        executor.execute_with_provider(state_root_hash, &global_state, session_request(None));
+    match result {
+        Err(ExecuteWithProviderError::Execute(ExecuteError::WasmPreparation(
+            WasmPreparationError::MissingExport(name),
+        ))) => assert_eq!(name, "call"),
+        other => panic!("expected missing export error, got {other:?}"),
+    }
+}
+
+#[test]
+fn should_reserve_gas_for_caller_after_gas_hungry_callee() {
+    const CALLEE_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "callee")
+                (loop $spin (br $spin))))
+    "#;
+    const SESSION_GAS_LIMIT: u64 = 10_000_000;
+
+    let run = |gas_forwarding_policy: GasForwardingPolicy| {
+        let config = ExecutorConfigBuilder::default()
+            .with_memory_limit(17)
+            .with_executor_kind(ExecutorKind::Compiled)
+            .with_wasm_config(WasmV2Config::default())
+            .with_storage_costs(StorageCosts::new(DEFAULT_GAS_PER_BYTE_COST))
+            .with_message_limits(MessageLimits::default())
+            .with_gas_forwarding_policy(gas_forwarding_policy)
+            .build()
+            .expect("Should build");
+        let executor = ExecutorV2::new(config, Arc::new(ExecutionEngineV1::default()));
+        let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+        let callee_address = install_contract(
+            &executor,
+            &global_state,
+            &mut state_root_hash,
+            wat::parse_str(CALLEE_WAT).unwrap(),
+        );
+
+        // The caller ignores the outcome of the sub-call and returns data afterwards.
+        let escaped_address = wat_bytes_literal(&callee_address);
+        let caller_wat = format!(
+            r#"
+            (module
+                (import "env" "memory" (memory 17))
+                (import "env" "casper_return" (func $return (param i32 i32 i32)))
+                (import "env" "casper_call" (func $call (param i32 i32 i64 i32 i32 i32 i32 i32 i32) (result i32)))
+                (data (i32.const 0) "{escaped_address}")
+                (data (i32.const 32) "callee")
+                (data (i32.const 64) "caller")
+                (func (export "call")
+                    (drop (call $call (i32.const 0) (i32.const 32) (i64.const 0) (i32.const 32) (i32.const 6) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0)))
+                    (call $return (i32.const 0) (i32.const 64) (i32.const 6))))
+            "#
+        );
+
+        let execute_request = base_execute_builder()
+            .with_gas_limit(SESSION_GAS_LIMIT)
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::SessionBytes(Bytes::from(
+                wat::parse_str(caller_wat).unwrap(),
+            )))
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed")
+    };
+
+    let starved = run(GasForwardingPolicy::ForwardAll);
+    assert!(starved.host_error.is_some());
+
+    let reserved = run(GasForwardingPolicy::AllButOneNth(
+        NonZeroU64::new(64).unwrap(),
+    ));
+    assert!(reserved.host_error.is_none());
+    assert_eq!(
+        reserved.output().map(|output| &output[..]),
+        Some(&b"caller"[..])
+    );
+}
+
+#[derive(Clone, Default)]
+struct RecordingMint {
+    mint_calls: Arc<Mutex<Vec<MintArgs>>>,
+}
+
+impl MintProvider for RecordingMint {
+    fn mint<R: GlobalStateReader>(
+        &self,
+        _tracking_copy: &mut TrackingCopy<R>,
+        _transaction_hash: TransactionHash,
+        _address_generator: Arc<RwLock<AddressGenerator>>,
+        args: MintArgs,
+    ) -> Result<URef, CallError> {
+        self.mint_calls.lock().unwrap().push(args);
+        Ok(URef::new([42; 32], AccessRights::READ_ADD_WRITE))
+    }
+
+    fn transfer<R: GlobalStateReader>(
+        &self,
+        _tracking_copy: &mut TrackingCopy<R>,
+        _transaction_hash: TransactionHash,
+        _address_generator: Arc<RwLock<AddressGenerator>>,
+        _args: MintTransferArgs,
+    ) -> HostResult {
+        Ok(())
+    }
+}
+
+#[test]
+fn should_create_main_purse_through_injected_mint() {
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "noop")))
+    "#;
+
+    let mint = RecordingMint::default();
+    let executor = ExecutorV2::with_mint(
+        make_executor_config(),
+        Arc::new(ExecutionEngineV1::default()),
+        mint.clone(),
+    );
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let install_request = base_install_request_builder()
+        .with_wasm_bytes(Bytes::from(wat::parse_str(CONTRACT_WAT).unwrap()))
+        .with_shared_address_generator(make_address_generator())
+        .with_transferred_value(0)
+        .with_input(Bytes::new())
+        .build()
+        .expect("should build");
+    executor
+        .install_contract(state_root_hash, &global_state, install_request)
+        .expect("should install");
+
+    assert_eq!(
+        *mint.mint_calls.lock().unwrap(),
+        vec![MintArgs {
+            initial_balance: U512::zero(),
+        }]
+    );
+}
+
+#[test]
+fn should_reference_same_package_from_installed_entity() {
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "noop")))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let install_request = base_install_request_builder()
+        .with_wasm_bytes(Bytes::from(wat::parse_str(CONTRACT_WAT).unwrap()))
+        .with_shared_address_generator(make_address_generator())
+        .with_transferred_value(0)
+        .with_input(Bytes::new())
+        .build()
+        .expect("should build");
+    let install_result = executor
+        .install_contract(state_root_hash, &global_state, install_request)
+        .expect("should install");
+
+    let written_package_addrs: Vec<HashAddr> = install_result
+        .effects()
+        .transforms()
+        .iter()
+        .filter_map(|transform| match (transform.key(), transform.kind()) {
+            (Key::SmartContract(addr), TransformKindV2::Write(StoredValue::SmartContract(_))) => {
+                Some(*addr)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_package_addrs,
+        vec![*install_result.smart_contract_addr()]
+    );
+
+    let entity_package_hashes: Vec<PackageHash> = install_result
+        .effects()
+        .transforms()
+        .iter()
+        .filter_map(|transform| match transform.kind() {
+            TransformKindV2::Write(StoredValue::AddressableEntity(entity)) => {
+                Some(entity.package_hash())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        entity_package_hashes,
+        vec![PackageHash::new(*install_result.smart_contract_addr())]
+    );
+}
+
+// Constructor which returns data instead of only initializing the contract.
+const RETURNING_CONSTRUCTOR_WAT: &str = r#"
+    (module
+        (import "env" "memory" (memory 17))
+        (import "env" "casper_return" (func $return (param i32 i32 i32)))
+        (data (i32.const 8) "oops")
+        (func (export "new")
+            (call $return (i32.const 0) (i32.const 8) (i32.const 4))))
+"#;
+
+fn install_returning_constructor(
+    strict_constructors: bool,
+) -> Result<InstallContractResult, InstallContractError> {
+    let config = ExecutorConfigBuilder::default()
+        .with_memory_limit(17)
+        .with_executor_kind(ExecutorKind::Compiled)
+        .with_wasm_config(WasmV2Config::default())
+        .with_storage_costs(StorageCosts::new(DEFAULT_GAS_PER_BYTE_COST))
+        .with_message_limits(MessageLimits::default())
+        .with_strict_constructors(strict_constructors)
+        .build()
+        .expect("Should build");
+    let executor = ExecutorV2::new(config, Arc::new(ExecutionEngineV1::default()));
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let install_request = base_install_request_builder()
+        .with_wasm_bytes(Bytes::from(
+            wat::parse_str(RETURNING_CONSTRUCTOR_WAT).unwrap(),
+        ))
+        .with_shared_address_generator(make_address_generator())
+        .with_transferred_value(0)
+        .with_entry_point("new".to_string())
+        .with_input(Bytes::new())
+        .build()
+        .expect("should build");
+    executor.install_contract(state_root_hash, &global_state, install_request)
+}
+
+#[test]
+fn should_discard_constructor_output_when_lenient() {
+    install_returning_constructor(false).expect("should install");
+}
+
+#[test]
+fn should_reject_constructor_output_when_strict() {
+    match install_returning_constructor(true) {
+        Err(InstallContractError::UnexpectedConstructorOutput { output }) => {
+            assert_eq!(output, Bytes::from_static(b"oops"));
+        }
+        other => panic!(
+            "expected unexpected constructor output, got {:?}",
+            other.err()
+        ),
+    }
+}
+
+#[test]
+fn predicted_address_should_match_installed_contract_address() {
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "noop")))
+    "#;
+    let seed = [7; 32];
+    let wasm_bytes = wat::parse_str(CONTRACT_WAT).unwrap();
+
+    let mut executor = make_executor();
+    let (global_state, state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let install_request = base_install_request_builder()
+        .with_wasm_bytes(Bytes::from(wasm_bytes.clone()))
+        .with_shared_address_generator(make_address_generator())
+        .with_transferred_value(0)
+        .with_input(Bytes::new())
+        .with_seed(seed)
+        .build()
+        .expect("should build");
+    let create_result = run_create_contract(
+        &mut executor,
+        &global_state,
+        state_root_hash,
+        install_request,
+    );
+
+    let predicted = casper_sdk::predict_address(
+        DEFAULT_CHAIN_NAME,
+        &DEFAULT_ACCOUNT_HASH.value(),
+        &wasm_bytes,
+        Some(&seed),
+    );
+    assert_eq!(*create_result.smart_contract_addr(), predicted);
+}
+
+#[test]
+fn self_code_hash_should_match_installed_bytecode() {
+    // Returns the code hash of the contract; the version tag only makes the bytecode differ.
+    let contract_wat = |version: &str| {
+        format!(
+            r#"
+            (module
+                (import "env" "memory" (memory 17))
+                (import "env" "casper_self_code_hash" (func $self_code_hash (param i32 i32) (result i32)))
+                (import "env" "casper_return" (func $return (param i32 i32 i32)))
+                (data (i32.const 64) "{version}")
+                (func (export "code_hash")
+                    (drop (call $self_code_hash (i32.const 32) (i32.const 32)))
+                    (call $return (i32.const 0) (i32.const 32) (i32.const 32))))
+            "#
+        )
+    };
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let mut code_hash_of_installed = |wasm_bytes: Vec<u8>| {
+        let address = install_contract(&executor, &global_state, &mut state_root_hash, wasm_bytes);
+
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address,
+                entry_point: "code_hash".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        let result = executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed");
+        assert!(result.host_error.is_none());
+        result.output().cloned().expect("should return code hash")
+    };
+
+    let v1_wasm = wat::parse_str(contract_wat("v1")).unwrap();
+    let v2_wasm = wat::parse_str(contract_wat("v2")).unwrap();
+    let v1_code_hash = code_hash_of_installed(v1_wasm.clone());
+    let v2_code_hash = code_hash_of_installed(v2_wasm.clone());
+
+    assert_eq!(
+        v1_code_hash.as_ref(),
+        chain_utils::compute_wasm_bytecode_hash(&v1_wasm)
+    );
+    assert_eq!(
+        v2_code_hash.as_ref(),
+        chain_utils::compute_wasm_bytecode_hash(&v2_wasm)
+    );
+    assert_ne!(v1_code_hash, v2_code_hash);
+}
+
+#[test]
+fn random_bytes_should_depend_on_contract_and_counter() {
+    // Returns two consecutive draws of 32 pseudo-random bytes.
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_random_bytes" (func $random_bytes (param i32 i32) (result i32)))
+            (import "env" "casper_return" (func $return (param i32 i32 i32)))
+            (func (export "draw")
+                (drop (call $random_bytes (i32.const 32) (i32.const 32)))
+                (drop (call $random_bytes (i32.const 64) (i32.const 32)))
+                (call $return (i32.const 0) (i32.const 32) (i32.const 64))))
+    "#;
+
+    let mut executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let mut install = |seed: [u8; 32]| {
+        let install_request = base_install_request_builder()
+            .with_wasm_bytes(Bytes::from(wat::parse_str(CONTRACT_WAT).unwrap()))
+            .with_shared_address_generator(make_address_generator())
+            .with_transferred_value(0)
+            .with_input(Bytes::new())
+            .with_seed(seed)
+            .build()
+            .expect("should build");
+        let create_result = run_create_contract(
+            &mut executor,
+            &global_state,
+            state_root_hash,
+            install_request,
+        );
+        state_root_hash = global_state
+            .commit_effects(state_root_hash, create_result.effects().clone())
+            .expect("Should commit");
+        *create_result.smart_contract_addr()
+    };
+    let first_contract = install([1; 32]);
+    let second_contract = install([2; 32]);
+    assert_ne!(first_contract, second_contract);
+
+    let draw = |address: HashAddr| {
+        let execute_request = base_execute_builder()
+            .with_transferred_value(0)
+            .with_target(ExecutionKind::Stored {
+                address,
+                entry_point: "draw".to_string(),
+            })
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        let result = executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("Succeed");
+        assert!(result.host_error.is_none());
+        result
+            .output()
+            .cloned()
+            .expect("should return random bytes")
+    };
+
+    let first_draw = draw(first_contract);
+    assert_ne!(first_draw[..32], first_draw[32..]);
+    // Re-executing the same transaction reproduces the bytes.
+    assert_eq!(draw(first_contract), first_draw);
+    assert_ne!(draw(second_contract), first_draw);
+}
 
-    panic!(
-        "Failed to find Wasm file at {} in any of the paths: {:?}",
-        filename.as_ref().display(),
-        paths
+#[test]
+fn should_serve_warmed_up_contract_from_module_cache() {
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "noop")))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let contract_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CONTRACT_WAT).unwrap(),
     );
-}
 
-const TRANSACTION_HASH_BYTES: [u8; 32] = [55; 32];
-const TRANSACTION_HASH: TransactionHash =
-    TransactionHash::V1(TransactionV1Hash::from_raw(TRANSACTION_HASH_BYTES));
-const DEFAULT_GAS_LIMIT: u64 = 1_000_000 * CSPR;
-const DEFAULT_CHAIN_NAME: &str = "casper-test";
+    let missing_address = [255; 32];
+    let outcomes = executor
+        .warm_up(
+            state_root_hash,
+            &global_state,
+            &[contract_address, missing_address],
+        )
+        .expect("should check out state root");
+    assert!(matches!(outcomes[0], Ok(())));
+    assert!(matches!(
+        outcomes[1],
+        Err(WarmUpError::ContractNotFound(address)) if address == missing_address
+    ));
+    let warmed_up_stats = executor.module_cache_stats();
+    assert_eq!(warmed_up_stats.misses, 1);
 
-// TODO: This is a temporary value, it should be set in the config. Default value from V1 engine
-// does not apply to V2 engine due to different cost structure. Rather than hardcoding it here, we
-// should probably reflect gas costs in a dynamic costs in host function charge. Proper value is
-// pending calculation.
-const DEFAULT_GAS_PER_BYTE_COST: u32 = 1_117_587;
+    let execute_request = base_execute_builder()
+        .with_target(ExecutionKind::Stored {
+            address: contract_address,
+            entry_point: "noop".to_string(),
+        })
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    let result = executor
+        .execute_with_provider(state_root_hash, &global_state, execute_request)
+        .expect("Succeed");
+    assert!(result.host_error.is_none());
 
-fn make_address_generator() -> Arc<RwLock<AddressGenerator>> {
-    let id = Id::Transaction(TRANSACTION_HASH);
-    Arc::new(RwLock::new(AddressGenerator::new(
-        &id.seed(),
-        Phase::Session,
-    )))
+    // Both the manifest lookup for the transferred value and the instantiation are served from
+    // the cache.
+    let stats = executor.module_cache_stats();
+    assert_eq!(stats.hits, warmed_up_stats.hits + 2);
+    assert_eq!(stats.misses, warmed_up_stats.misses);
 }
 
-fn base_execute_builder() -> ExecuteRequestBuilder {
-    ExecuteRequestBuilder::default()
-        .with_initiator(*DEFAULT_ACCOUNT_HASH)
-        .with_caller_key(Key::Account(*DEFAULT_ACCOUNT_HASH))
-        .with_gas_limit(DEFAULT_GAS_LIMIT)
-        .with_transferred_value(1000)
-        .with_transaction_hash(TRANSACTION_HASH)
-        .with_chain_name(DEFAULT_CHAIN_NAME)
-        .with_block_time(Timestamp::now().into())
-        .with_state_hash(Digest::hash(b"state"))
-        .with_block_height(1)
-        .with_parent_block_hash(BlockHash::new(Digest::hash(b"block1")))
-}
+#[test]
+fn should_stop_tight_loop_exceeding_instruction_budget() {
+    // Spins for 100k iterations, which is far below the gas limit.
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "spin")
+                (local $i i32)
+                (local.set $i (i32.const 100000))
+                (loop $continue
+                    (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+                    (br_if $continue (local.get $i)))))
+    "#;
+
+    let run_spin = |instruction_budget: Option<u64>| {
+        let mut config_builder = ExecutorConfigBuilder::default()
+            .with_memory_limit(17)
+            .with_executor_kind(ExecutorKind::Compiled)
+            .with_wasm_config(WasmV2Config::default())
+            .with_storage_costs(StorageCosts::new(DEFAULT_GAS_PER_BYTE_COST))
+            .with_message_limits(MessageLimits::default());
+        if let Some(instruction_budget) = instruction_budget {
+            config_builder = config_builder.with_instruction_budget(instruction_budget);
+        }
+        let config = config_builder.build().expect("Should build");
+        let executor = ExecutorV2::new(config, Arc::new(ExecutionEngineV1::default()));
+        let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+        let contract_address = install_contract(
+            &executor,
+            &global_state,
+            &mut state_root_hash,
+            wat::parse_str(CONTRACT_WAT).unwrap(),
+        );
 
-fn base_install_request_builder() -> InstallContractRequestBuilder {
-    InstallContractRequestBuilder::default()
-        .with_initiator(*DEFAULT_ACCOUNT_HASH)
-        .with_gas_limit(DEFAULT_GAS_LIMIT)
-        .with_transaction_hash(TRANSACTION_HASH)
-        .with_chain_name(DEFAULT_CHAIN_NAME)
-        .with_block_time(Timestamp::now().into())
-        .with_state_hash(Digest::hash(b"state"))
-        .with_block_height(1)
-        .with_parent_block_hash(BlockHash::new(Digest::hash(b"block1")))
+        let execute_request = base_execute_builder()
+            .with_target(ExecutionKind::Stored {
+                address: contract_address,
+                entry_point: "spin".to_string(),
+            })
+            .with_transferred_value(0)
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor.execute_with_provider(state_root_hash, &global_state, execute_request)
+    };
+
+    let unlimited = run_spin(None).expect("should run without a budget");
+    assert!(unlimited.host_error.is_none());
+
+    assert!(matches!(
+        run_spin(Some(10_000)),
+        Err(ExecuteWithProviderError::Execute(
+            ExecuteError::InstructionBudgetExceeded
+        ))
+    ));
 }
 
 #[test]
-fn harness() {
-    let mut executor = make_executor();
+fn should_share_instruction_budget_with_nested_calls() {
+    // Each nested constructor call spins for 20k iterations, so one of them fits in the budget but
+    // two do not, unless every call frame gets a budget of its own.
+    const CONTRACT_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_create" (func $create (param i32 i32 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 16) "spin")
+            (data (i32.const 64) "\01")
+            (func (export "spin")
+                (local $i i32)
+                (local.set $i (i32.const 20000))
+                (loop $continue
+                    (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+                    (br_if $continue (local.get $i))))
+            (func $spin_nested (param $seed_ptr i32)
+                (if (i32.ne
+                        (call $create
+                            (i32.const 0) (i32.const 0) (i64.const 0)
+                            (i32.const 16) (i32.const 4)
+                            (i32.const 0) (i32.const 0)
+                            (local.get $seed_ptr) (i32.const 32)
+                            (i32.const 128))
+                        (i32.const 0))
+                    (then unreachable)))
+            (func (export "spin_once")
+                (call $spin_nested (i32.const 32)))
+            (func (export "spin_twice")
+                (call $spin_nested (i32.const 32))
+                (call $spin_nested (i32.const 64))))
+    "#;
+
+    let config = ExecutorConfigBuilder::default()
+        .with_memory_limit(17)
+        .with_executor_kind(ExecutorKind::Compiled)
+        .with_wasm_config(WasmV2Config::default())
+        .with_storage_costs(StorageCosts::new(DEFAULT_GAS_PER_BYTE_COST))
+        .with_message_limits(MessageLimits::default())
+        .with_instruction_budget(300_000)
+        .build()
+        .expect("Should build");
+    let executor = ExecutorV2::new(config, Arc::new(ExecutionEngineV1::default()));
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let contract_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(CONTRACT_WAT).unwrap(),
+    );
 
-    let (mut global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+    let call = |entry_point: &str| {
+        let execute_request = base_execute_builder()
+            .with_target(ExecutionKind::Stored {
+                address: contract_address,
+                entry_point: entry_point.to_string(),
+            })
+            .with_transferred_value(0)
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        executor.execute_with_provider(state_root_hash, &global_state, execute_request)
+    };
 
-    let address_generator = make_address_generator();
+    let spin_once = call("spin_once").expect("should fit in the budget");
+    assert!(spin_once.host_error.is_none());
 
-    let flipper_address;
+    assert!(matches!(
+        call("spin_twice"),
+        Err(ExecuteWithProviderError::Execute(
+            ExecuteError::InstructionBudgetExceeded
+        ))
+    ));
+}
 
-    state_root_hash = {
-        let input_data = borsh::to_vec(&("Foo Token".to_string(),))
-            .map(Bytes::from)
-            .unwrap();
+#[test]
+fn should_refuse_to_execute_denied_bytecode() {
+    const DENIED_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "denied")))
+    "#;
+    const ALLOWED_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "allowed")))
+    "#;
+
+    let denied_wasm = Bytes::from(wat::parse_str(DENIED_WAT).unwrap());
+    let allowed_wasm = Bytes::from(wat::parse_str(ALLOWED_WAT).unwrap());
+    let denied_hash = chain_utils::compute_wasm_bytecode_hash(&denied_wasm);
+    let denied_legacy_wasm_hash = [7; 32];
+
+    let executor = make_executor()
+        .with_bytecode_denylist(HashSet::from([denied_hash, denied_legacy_wasm_hash]));
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let denied_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        denied_wasm.clone(),
+    );
+    let allowed_address =
+        install_contract(&executor, &global_state, &mut state_root_hash, allowed_wasm);
+
+    // A legacy V1 contract whose Wasm is never stored, as it is refused before being loaded.
+    let legacy_address = [8; 32];
+    let legacy_contract = Contract::new(
+        ContractPackageHash::new([9; 32]),
+        ContractWasmHash::new(denied_legacy_wasm_hash),
+        NamedKeys::new(),
+        EntryPoints::new(),
+        ProtocolVersion::V1_0_0,
+    );
+    let mut effects = Effects::new();
+    effects.push(TransformV2::new(
+        Key::Hash(legacy_address),
+        TransformKindV2::Write(StoredValue::Contract(legacy_contract)),
+    ));
+    state_root_hash = global_state
+        .commit_effects(state_root_hash, effects)
+        .expect("Should commit");
 
-        let install_request = base_install_request_builder()
-            .with_wasm_bytes(read_wasm("vm2_cep18.wasm"))
-            .with_shared_address_generator(Arc::clone(&address_generator))
+    let call = |address: HashAddr, entry_point: &str| {
+        let execute_request = base_execute_builder()
+            .with_target(ExecutionKind::Stored {
+                address,
+                entry_point: entry_point.to_string(),
+            })
             .with_transferred_value(0)
-            .with_entry_point("new".to_string())
-            .with_input(input_data)
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
             .build()
             .expect("should build");
+        executor.execute_with_provider(state_root_hash, &global_state, execute_request)
+    };
 
-        let create_result = run_create_contract(
-            &mut executor,
-            &mut global_state,
-            state_root_hash,
-            install_request,
+    match call(denied_address, "denied") {
+        Err(ExecuteWithProviderError::Execute(ExecuteError::BytecodeDenied { byte_code_addr })) => {
+            assert_eq!(byte_code_addr, ByteCodeAddr::V2CasperWasm(denied_hash));
+        }
+        other => panic!("expected denied bytecode error, got {other:?}"),
+    }
+
+    let result = call(allowed_address, "allowed").expect("should execute allowed contract");
+    assert!(result.host_error.is_none());
+
+    // The same bytecode is refused when executed as session bytes.
+    let execute_request = base_execute_builder()
+        .with_target(ExecutionKind::SessionBytes(denied_wasm))
+        .with_session_entry_point("denied")
+        .with_transferred_value(0)
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    assert!(matches!(
+        executor.execute_with_provider(state_root_hash, &global_state, execute_request),
+        Err(ExecuteWithProviderError::Execute(
+            ExecuteError::BytecodeDenied { .. }
+        ))
+    ));
+
+    // Legacy V1 contracts are refused by the address of their Wasm.
+    match call(legacy_address, "call") {
+        Err(ExecuteWithProviderError::Execute(ExecuteError::BytecodeDenied { byte_code_addr })) => {
+            assert_eq!(
+                byte_code_addr,
+                ByteCodeAddr::V1CasperWasm(denied_legacy_wasm_hash)
+            );
+        }
+        other => panic!("expected denied bytecode error, got {other:?}"),
+    }
+}
+
+#[test]
+fn should_refuse_to_create_contract_from_denied_bytecode() {
+    const DENIED_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (func (export "denied")))
+    "#;
+
+    let denied_wasm = wat::parse_str(DENIED_WAT).unwrap();
+    let denied_hash = chain_utils::compute_wasm_bytecode_hash(&denied_wasm);
+
+    // Creates a contract out of the denied bytecode, without a constructor through `casper_create`
+    // and with one through `casper_try_create`, and returns the result code of the creation.
+    let escaped_wasm = wat_bytes_literal(&denied_wasm);
+    let creator_wat = format!(
+        r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_create" (func $create (param i32 i32 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_try_create" (func $try_create (param i32 i32 i64 i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_return" (func $return (param i32 i32 i32)))
+            (data (i32.const 128) "denied")
+            (data (i32.const 1024) "{escaped_wasm}")
+            (func (export "create")
+                (i32.store (i32.const 16)
+                    (call $create
+                        (i32.const 1024) (i32.const {len}) (i64.const 0)
+                        (i32.const 0) (i32.const 0)
+                        (i32.const 0) (i32.const 0)
+                        (i32.const 0) (i32.const 0)
+                        (i32.const 64)))
+                (call $return (i32.const 0) (i32.const 16) (i32.const 4)))
+            (func (export "try_create")
+                (i32.store (i32.const 16)
+                    (call $try_create
+                        (i32.const 1024) (i32.const {len}) (i64.const 0)
+                        (i32.const 128) (i32.const 6)
+                        (i32.const 0) (i32.const 0)
+                        (i32.const 0) (i32.const 0)
+                        (i32.const 64) (i32.const 0) (i32.const 0)))
+                (call $return (i32.const 0) (i32.const 16) (i32.const 4))))
+        "#,
+        len = denied_wasm.len(),
+    );
+
+    let executor = make_executor().with_bytecode_denylist(HashSet::from([denied_hash]));
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let creator_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(creator_wat).unwrap(),
+    );
+
+    for entry_point in ["create", "try_create"] {
+        let execute_request = base_execute_builder()
+            .with_target(ExecutionKind::Stored {
+                address: creator_address,
+                entry_point: entry_point.to_string(),
+            })
+            .with_transferred_value(0)
+            .with_input(Bytes::new())
+            .with_shared_address_generator(make_address_generator())
+            .build()
+            .expect("should build");
+        let result = executor
+            .execute_with_provider(state_root_hash, &global_state, execute_request)
+            .expect("should execute");
+
+        assert!(result.host_error.is_none());
+        assert_eq!(
+            result.output().map(|output| output.as_ref()),
+            Some(&CALLEE_NOT_CALLABLE.to_le_bytes()[..])
         );
+        // Nothing of the contract is stored.
+        assert!(!result
+            .effects()
+            .transforms()
+            .iter()
+            .any(|transform| matches!(transform.kind(), TransformKindV2::Write(_))));
+    }
+}
 
-        flipper_address = *create_result.smart_contract_addr();
+#[test]
+fn should_register_message_topic_without_emitting() {
+    // Registers the "transfer" topic, trapping unless the host reports success.
+    const REGISTER_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_register_topic" (func $register_topic (param i32 i32) (result i32)))
+            (data (i32.const 0) "transfer")
+            (func (export "register")
+                (if (i32.ne (call $register_topic (i32.const 0) (i32.const 8)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let contract_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(REGISTER_WAT).unwrap(),
+    );
 
-        global_state
-            .commit_effects(state_root_hash, create_result.effects().clone())
-            .expect("Should commit")
+    let execute_request = base_execute_builder()
+        .with_transferred_value(0)
+        .with_target(ExecutionKind::Stored {
+            address: contract_address,
+            entry_point: "register".to_string(),
+        })
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
+        .build()
+        .expect("should build");
+    let result = executor
+        .execute_with_provider(state_root_hash, &global_state, execute_request)
+        .expect("Succeed");
+    assert!(result.host_error.is_none());
+    state_root_hash = global_state
+        .commit_effects(state_root_hash, result.effects().clone())
+        .expect("Should commit");
+
+    let entity_addr = EntityAddr::new_smart_contract(contract_address);
+    let MessageTopicsResult::Success { message_topics } =
+        global_state.message_topics(MessageTopicsRequest::new(state_root_hash, entity_addr))
+    else {
+        panic!("Expected success")
     };
+    let topic_name_hash = *message_topics
+        .get("transfer")
+        .expect("should register the topic");
+
+    let topic_key = Key::message_topic(entity_addr, topic_name_hash);
+    match global_state.query(QueryRequest::new(state_root_hash, topic_key, Vec::new())) {
+        QueryResult::Success { value, .. } => match *value {
+            StoredValue::MessageTopic(summary) => assert_eq!(summary.message_count(), 0),
+            other => panic!("expected a message topic, got {other:?}"),
+        },
+        other => panic!("expected a message topic, got {other:?}"),
+    }
+}
 
-    let execute_request = ExecuteRequestBuilder::default()
-        .with_initiator(*DEFAULT_ACCOUNT_HASH)
-        .with_caller_key(Key::Account(*DEFAULT_ACCOUNT_HASH))
-        .with_gas_limit(DEFAULT_GAS_LIMIT)
-        .with_transferred_value(1000)
-        .with_transaction_hash(TRANSACTION_HASH)
-        .with_target(ExecutionKind::SessionBytes(read_wasm("vm2-harness.wasm")))
-        .with_serialized_input((flipper_address,))
-        .with_shared_address_generator(address_generator)
-        .with_chain_name(DEFAULT_CHAIN_NAME)
-        .with_block_time(Timestamp::now().into())
-        .with_state_hash(state_root_hash)
-        .with_block_height(1)
-        .with_parent_block_hash(BlockHash::new(Digest::hash(b"bl0ck")))
+/// Installs `wat`, calls its `probe` export and returns the `u64` it stored in the contract's
+/// state.
+fn call_probe_and_read_state(wat: &str) -> u64 {
+    let executor = make_executor();
+    let (global_state, mut state_root_hash, _tempdir) = make_global_state_with_genesis();
+
+    let contract_address = install_contract(
+        &executor,
+        &global_state,
+        &mut state_root_hash,
+        wat::parse_str(wat).unwrap(),
+    );
+
+    let execute_request = base_execute_builder()
+        .with_transferred_value(0)
+        .with_target(ExecutionKind::Stored {
+            address: contract_address,
+            entry_point: "probe".to_string(),
+        })
+        .with_input(Bytes::new())
+        .with_shared_address_generator(make_address_generator())
         .build()
         .expect("should build");
+    let result = executor
+        .execute_with_provider(state_root_hash, &global_state, execute_request)
+        .expect("Succeed");
+    assert!(result.host_error.is_none());
+    state_root_hash = global_state
+        .commit_effects(state_root_hash, result.effects().clone())
+        .expect("Should commit");
 
-    run_wasm_session(
-        &mut executor,
-        &mut global_state,
+    match global_state.query(QueryRequest::new(
         state_root_hash,
-        execute_request,
-    );
+        Key::State(EntityAddr::new_smart_contract(contract_address)),
+        Vec::new(),
+    )) {
+        QueryResult::Success { value, .. } => match *value {
+            StoredValue::RawBytes(bytes) => {
+                u64::from_le_bytes(bytes.try_into().expect("should be a u64"))
+            }
+            other => panic!("expected raw bytes, got {other:?}"),
+        },
+        other => panic!("expected contract state, got {other:?}"),
+    }
 }
 
-pub(crate) fn make_executor() -> ExecutorV2 {
+#[test]
+fn should_report_message_count_of_topic() {
+    // Emits two messages under "transfer" and stores the count reported by the host in the
+    // contract's state. Traps unless an unknown topic is reported as not found.
+    const EMITTER_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_emit" (func $emit (param i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_message_count" (func $message_count (param i32 i32 i32) (result i32)))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 0) "transfer")
+            (data (i32.const 8) "missing")
+            (func (export "probe")
+                (drop (call $emit (i32.const 0) (i32.const 8) (i32.const 0) (i32.const 0)))
+                (drop (call $emit (i32.const 0) (i32.const 8) (i32.const 0) (i32.const 0)))
+                (if (i32.ne (call $message_count (i32.const 8) (i32.const 7) (i32.const 16)) (i32.const 1))
+                    (then unreachable))
+                (if (i32.ne (call $message_count (i32.const 0) (i32.const 8) (i32.const 16)) (i32.const 0))
+                    (then unreachable))
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 16) (i32.const 8)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    assert_eq!(call_probe_and_read_state(EMITTER_WAT), 2);
+}
+
+#[test]
+fn should_write_back_index_of_emitted_message() {
+    // Emits two messages under "transfer", then a third one with its index written back, and
+    // stores that index in the contract's state.
+    const EMITTER_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_emit" (func $emit (param i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_emit_with_index" (func $emit_with_index (param i32 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 0) "transfer")
+            (func (export "probe")
+                (drop (call $emit (i32.const 0) (i32.const 8) (i32.const 0) (i32.const 0)))
+                (drop (call $emit (i32.const 0) (i32.const 8) (i32.const 0) (i32.const 0)))
+                (if (i32.ne (call $emit_with_index (i32.const 0) (i32.const 8) (i32.const 0) (i32.const 0) (i32.const 16)) (i32.const 0))
+                    (then unreachable))
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 16) (i32.const 8)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    assert_eq!(call_probe_and_read_state(EMITTER_WAT), 2);
+}
+
+#[test]
+fn should_write_back_data_of_reverted_constructor() {
+    // Creates a copy of itself through `casper_try_create` with a constructor that reverts with a
+    // u64, and stores the data written back through the data pointer in the contract's state.
+    const CREATOR_WAT: &str = r#"
+        (module
+            (import "env" "memory" (memory 17))
+            (import "env" "casper_return" (func $return (param i32 i32 i32)))
+            (import "env" "casper_try_create" (func $try_create (param i32 i32 i64 i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)))
+            (import "env" "casper_write" (func $write (param i64 i32 i32 i32 i32) (result i32)))
+            (data (i32.const 16) "init")
+            (data (i32.const 24) "\2a\00\00\00\00\00\00\00")
+            (func (export "init")
+                (call $return (i32.const 1) (i32.const 24) (i32.const 8)))
+            (func (export "probe")
+                (if (i32.ne
+                        (call $try_create
+                            (i32.const 0) (i32.const 0) (i64.const 0)
+                            (i32.const 16) (i32.const 4)
+                            (i32.const 0) (i32.const 0)
+                            (i32.const 0) (i32.const 0)
+                            (i32.const 64) (i32.const 0) (i32.const 128))
+                        (i32.const 1))
+                    (then unreachable))
+                (if (i32.ne (call $write (i64.const 0) (i32.const 0) (i32.const 0) (i32.const 128) (i32.const 8)) (i32.const 0))
+                    (then unreachable))))
+    "#;
+
+    assert_eq!(call_probe_and_read_state(CREATOR_WAT), 42);
+}
+
+fn make_executor_config() -> ExecutorConfig {
     let storage_costs = StorageCosts::new(DEFAULT_GAS_PER_BYTE_COST);
-    let execution_engine_v1 = ExecutionEngineV1::default();
-    let executor_config = ExecutorConfigBuilder::default()
+    ExecutorConfigBuilder::default()
         .with_memory_limit(17)
         .with_executor_kind(ExecutorKind::Compiled)
         .with_wasm_config(WasmV2Config::default())
         .with_storage_costs(storage_costs)
         .with_message_limits(MessageLimits::default())
         .build()
-        .expect("Should build");
-    ExecutorV2::new(executor_config, Arc::new(execution_engine_v1))
+        .expect("Should build")
+}
+
+pub(crate) fn make_executor() -> ExecutorV2 {
+    let execution_engine_v1 = ExecutionEngineV1::default();
+    ExecutorV2::new(make_executor_config(), Arc::new(execution_engine_v1))
 }
 
 #[test]
@@ -406,11 +2563,18 @@ fn cep18() {
 }
 
 fn make_global_state_with_genesis() -> (LmdbGlobalState, Digest, TempDir) {
-    let default_accounts = vec![GenesisAccount::Account {
-        public_key: DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
-        balance: Motes::new(U512::from(100 * CSPR)),
-        validator: None,
-    }];
+    let default_accounts = vec![
+        GenesisAccount::Account {
+            public_key: DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
+            balance: Motes::new(U512::from(100 * CSPR)),
+            validator: None,
+        },
+        GenesisAccount::Account {
+            public_key: OTHER_ACCOUNT_PUBLIC_KEY.clone(),
+            balance: Motes::new(U512::from(100 * CSPR)),
+            validator: None,
+        },
+    ];
 
     let (global_state, _state_root_hash, _tempdir) =
         global_state::state::lmdb::make_temporary_global_state([]);
@@ -637,6 +2801,41 @@ fn run_create_contract(
         .expect("Succeed")
 }
 
+/// Installs `wasm_bytes` without a constructor, commits the effects onto `state_root_hash` and
+/// returns the address of the new contract.
+fn install_contract(
+    executor: &ExecutorV2,
+    global_state: &LmdbGlobalState,
+    state_root_hash: &mut Digest,
+    wasm_bytes: impl Into<Bytes>,
+) -> HashAddr {
+    let install_request = base_install_request_builder()
+        .with_wasm_bytes(wasm_bytes.into())
+        .with_shared_address_generator(make_address_generator())
+        .with_transferred_value(0)
+        .with_input(Bytes::new())
+        .build()
+        .expect("should build");
+    let create_result = executor
+        .install_contract(*state_root_hash, global_state, install_request)
+        .expect("should install");
+    *state_root_hash = global_state
+        .commit_effects(*state_root_hash, create_result.effects().clone())
+        .expect("Should commit");
+    *create_result.smart_contract_addr()
+}
+
+/// Escapes `bytes` so they can be embedded in a string literal of a WAT data segment.
+fn wat_bytes_literal(bytes: &[u8]) -> String {
+    bytes.iter().fold(
+        String::with_capacity(bytes.len() * 3),
+        |mut escaped, byte| {
+            let _ = write!(escaped, "\\{byte:02x}");
+            escaped
+        },
+    )
+}
+
 fn run_wasm_session(
     executor: &mut ExecutorV2,
     global_state: &LmdbGlobalState,
@@ -842,6 +3041,13 @@ fn call_dummy_host_fn_by_name(
                 print: HostFunctionV2::fixed(1),
                 emit: HostFunctionV2::fixed(1),
                 env_info: HostFunctionV2::fixed(1),
+                register_topic: HostFunctionV2::fixed(1),
+                message_count: HostFunctionV2::fixed(1),
+                emit_with_index: HostFunctionV2::fixed(1),
+                try_create: HostFunctionV2::fixed(1),
+                self_code_hash: HostFunctionV2::fixed(1),
+                abort: HostFunctionV2::fixed(1),
+                random_bytes: HostFunctionV2::fixed(1),
             },
         );
         let executor_config = ExecutorConfigBuilder::default()
@@ -935,6 +3141,13 @@ fn write_n_bytes_at_limit(
                 print: HostFunctionV2::fixed(0),
                 emit: HostFunctionV2::fixed(0),
                 env_info: HostFunctionV2::fixed(0),
+                register_topic: HostFunctionV2::fixed(0),
+                message_count: HostFunctionV2::fixed(0),
+                emit_with_index: HostFunctionV2::fixed(0),
+                try_create: HostFunctionV2::fixed(0),
+                self_code_hash: HostFunctionV2::fixed(0),
+                abort: HostFunctionV2::fixed(0),
+                random_bytes: HostFunctionV2::fixed(0),
             },
         );
         let executor_config = ExecutorConfigBuilder::default()
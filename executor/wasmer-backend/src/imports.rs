@@ -67,11 +67,19 @@ pub(crate) fn generate_casper_imports<S: GlobalStateReader + 'static, E: Executo
                         // List all types and statically mapped C types into wasm types
                         $($($arg: <$argty as $crate::imports::WasmerConvert>::Output,)*)?
                     | -> VMResult<visit_host_function!(@convert_ret $($ret)?)> {
-                        let wasmer_caller = $crate::WasmerCaller { env };
+                        let mut env = env;
+
+                        // The host function may call other contracts, which draw from the same
+                        // instruction budget.
+                        $crate::WasmerCaller { env: env.as_mut() }.save_instruction_budget();
+
+                        let wasmer_caller = $crate::WasmerCaller { env: env.as_mut() };
 
                         // Dispatch to the actual host function. This also ensures that the return type of host function impl has expected type.
                         let result: VMResult< visit_host_function!(@convert_ret $($ret)?) > = casper_executor_wasm_host::host::$name(wasmer_caller, $($($arg,)*)?);
 
+                        $crate::WasmerCaller { env: env.as_mut() }.restore_instruction_budget();
+
                         match result {
                             Ok(ret) => Ok(ret),
                             Err(error) => {
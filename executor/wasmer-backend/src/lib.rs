@@ -1,29 +1,33 @@
 pub(crate) mod imports;
 pub(crate) mod middleware;
+mod module_cache;
 
 use std::{
     collections::BinaryHeap,
+    mem,
     sync::{Arc, LazyLock, Weak},
 };
 
 use bytes::Bytes;
-use casper_executor_wasm_common::error::TrapCode;
+use casper_executor_wasm_common::{
+    entry_point::{find_entry_point_flags, MANIFEST_SECTION},
+    error::TrapCode,
+    flags::EntryPointFlags,
+};
 use casper_executor_wasm_host::context::Context;
 use casper_executor_wasm_interface::{
-    executor::Executor, Caller, Config, ExportError, GasUsage, InterfaceVersion, MeteringPoints,
-    VMError, VMResult, WasmInstance, WasmPreparationError,
+    executor::Executor, Caller, Config, ExportError, GasUsage, InstructionBudget, InterfaceVersion,
+    MeteringPoints, VMError, VMResult, WasmInstance, WasmPreparationError,
 };
 use casper_storage::global_state::GlobalStateReader;
-use middleware::{
-    gas_metering,
-    gatekeeper::{Gatekeeper, GatekeeperConfig},
-};
+use middleware::instruction_budget;
+pub use module_cache::ModuleCacheStats;
+use module_cache::{CompiledModule, ModuleCache};
 use regex::Regex;
 use wasmer::{
-    AsStoreMut, AsStoreRef, CompilerConfig, Engine, Function, FunctionEnv, FunctionEnvMut,
-    Instance, Memory, MemoryView, Module, RuntimeError, Store, StoreMut, Table, TypedFunction,
+    AsStoreMut, AsStoreRef, Function, FunctionEnv, FunctionEnvMut, Instance, Memory, MemoryView,
+    RuntimeError, Store, StoreMut, Table, TypedFunction,
 };
-use wasmer_compiler_singlepass::Singlepass;
 use wasmer_middlewares::metering;
 
 fn from_wasmer_memory_access_error(error: wasmer::MemoryAccessError) -> VMError {
@@ -75,21 +79,85 @@ fn from_wasmer_export_error(error: wasmer::ExportError) -> VMError {
     VMError::Export(export_error)
 }
 
+/// Interface version assumed for modules that do not import any `interface_version_X` marker.
+const DEFAULT_INTERFACE_VERSION: InterfaceVersion = InterfaceVersion::new(1);
+
+/// Highest interface version implemented by the host.
+///
+/// Modules importing the marker of a higher version are rejected.
+const MAX_INTERFACE_VERSION: InterfaceVersion = InterfaceVersion::new(2);
+
+fn interface_version_import_name(interface_version: InterfaceVersion) -> String {
+    format!("interface_version_{}", interface_version.value())
+}
+
 #[derive(Default)]
-pub struct WasmerEngine(());
+pub struct WasmerEngine {
+    module_cache: ModuleCache,
+}
 
 impl WasmerEngine {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Creates an engine which instruments Wasm to count the instructions it executes, so that
+    /// instances can be stopped once their [`InstructionBudget`] is exhausted.
+    pub fn with_instruction_budget() -> Self {
+        Self {
+            module_cache: ModuleCache::with_instruction_budget(),
+        }
+    }
+
     pub fn instantiate<T: Into<Bytes>, S: GlobalStateReader + 'static, E: Executor + 'static>(
         &self,
         wasm_bytes: T,
         context: Context<S, E>,
         config: Config,
     ) -> Result<impl WasmInstance<Context = Context<S, E>>, WasmPreparationError> {
-        WasmerInstance::from_wasm_bytes(wasm_bytes, context, config)
+        let wasm_bytes: Bytes = wasm_bytes.into();
+        let compiled_module = self.module_cache.get_or_compile(&wasm_bytes)?;
+        WasmerInstance::from_compiled_module(wasm_bytes, compiled_module, context, config)
+    }
+
+    /// Compiles the Wasm module ahead of time, so that instantiating it later on is served from
+    /// the module cache.
+    pub fn precompile(&self, wasm_bytes: &[u8]) -> Result<(), WasmPreparationError> {
+        self.module_cache.get_or_compile(wasm_bytes).map(|_| ())
+    }
+
+    /// Returns the number of module cache hits and misses so far.
+    pub fn module_cache_stats(&self) -> ModuleCacheStats {
+        self.module_cache.stats()
+    }
+
+    /// Checks whether the Wasm module exports an item called `name`.
+    ///
+    /// The module is compiled through the module cache, so instantiating it afterwards is cheap.
+    pub fn has_export(&self, wasm_bytes: &[u8], name: &str) -> Result<bool, WasmPreparationError> {
+        let compiled_module = self.module_cache.get_or_compile(wasm_bytes)?;
+        let has_export = compiled_module
+            .module
+            .exports()
+            .any(|export| export.name() == name);
+        Ok(has_export)
+    }
+
+    /// Returns the flags the manifest of the Wasm module declares for the entry point `name`.
+    ///
+    /// Returns `None` if the module has no manifest, or the manifest does not list the entry
+    /// point.
+    pub fn entry_point_flags(
+        &self,
+        wasm_bytes: &[u8],
+        name: &str,
+    ) -> Result<Option<EntryPointFlags>, WasmPreparationError> {
+        let compiled_module = self.module_cache.get_or_compile(wasm_bytes)?;
+        let entry_point_flags = compiled_module
+            .module
+            .custom_sections(MANIFEST_SECTION)
+            .find_map(|manifest| find_entry_point_flags(&manifest, name));
+        Ok(entry_point_flags)
     }
 }
 
@@ -99,6 +167,8 @@ struct WasmerEnv<S: GlobalStateReader, E: Executor> {
     bytecode: Bytes,
     exported_runtime: Option<ExportedRuntime>,
     interface_version: InterfaceVersion,
+    /// Budget the instance counts its instructions against, if it is instrumented to do so.
+    instruction_budget: Option<InstructionBudget>,
 }
 
 pub(crate) struct WasmerCaller<'a, S: GlobalStateReader, E: Executor> {
@@ -140,6 +210,34 @@ impl<S: GlobalStateReader + 'static, E: Executor + 'static> WasmerCaller<'_, S,
             metering::set_remaining_points(&mut store, instance, new_value);
         })
     }
+
+    /// Stores the instructions left to the instance in the shared budget, before a host function
+    /// possibly calls into other contracts.
+    pub(crate) fn save_instruction_budget(&mut self) {
+        let Some(budget) = self.env.data().instruction_budget.clone() else {
+            return;
+        };
+        self.with_store_and_instance(|mut store, instance| {
+            budget.set_remaining(instruction_budget::get_remaining_instructions(
+                &mut store, instance,
+            ));
+        })
+    }
+
+    /// Loads the instructions left in the shared budget back into the instance, after a host
+    /// function possibly called into other contracts.
+    pub(crate) fn restore_instruction_budget(&mut self) {
+        let Some(budget) = self.env.data().instruction_budget.clone() else {
+            return;
+        };
+        self.with_store_and_instance(|mut store, instance| {
+            instruction_budget::set_remaining_instructions(
+                &mut store,
+                instance,
+                budget.remaining(),
+            );
+        })
+    }
 }
 
 impl<S: GlobalStateReader + 'static, E: Executor + 'static> Caller for WasmerCaller<'_, S, E> {
@@ -220,13 +318,19 @@ impl<S: GlobalStateReader + 'static, E: Executor + 'static> Caller for WasmerCal
 }
 
 impl<S: GlobalStateReader, E: Executor> WasmerEnv<S, E> {
-    fn new(context: Context<S, E>, code: Bytes, interface_version: InterfaceVersion) -> Self {
+    fn new(
+        context: Context<S, E>,
+        code: Bytes,
+        interface_version: InterfaceVersion,
+        instruction_budget: Option<InstructionBudget>,
+    ) -> Self {
         Self {
             context,
             instance: Weak::new(),
             exported_runtime: None,
             bytecode: code,
             interface_version,
+            instruction_budget,
         }
     }
     pub(crate) fn exported_runtime(&self) -> &ExportedRuntime {
@@ -282,30 +386,68 @@ where
         Ok(())
     }
 
-    pub(crate) fn from_wasm_bytes<C: Into<Bytes>>(
-        wasm_bytes: C,
+    fn from_compiled_module(
+        wasm_bytes: Bytes,
+        compiled_module: CompiledModule,
         context: Context<S, E>,
         config: Config,
     ) -> Result<Self, WasmPreparationError> {
-        let engine = {
-            let mut singlepass_compiler = Singlepass::new();
-            let gatekeeper_config = GatekeeperConfig::default();
-            singlepass_compiler.push_middleware(Arc::new(Gatekeeper::new(gatekeeper_config)));
-            singlepass_compiler
-                .push_middleware(gas_metering::gas_metering_middleware(config.gas_limit()));
-            singlepass_compiler
-        };
+        let CompiledModule {
+            engine,
+            module,
+            instruction_budget: counts_instructions,
+        } = compiled_module;
 
-        let engine = Engine::from(engine);
+        let interface_versions = {
+            static RE: LazyLock<Regex> =
+                LazyLock::new(|| Regex::new(r"^interface_version_(?P<version>\d+)$").unwrap());
 
-        let wasm_bytes: Bytes = wasm_bytes.into();
+            let mut interface_versions = BinaryHeap::new();
+            for import in module.imports() {
+                if import.module() == "env" {
+                    if let Some(caps) = RE.captures(import.name()) {
+                        let version = &caps["version"];
+                        let version: u32 = version.parse().expect("valid number"); // SAFETY: regex guarantees this is a number, and imports table guarantees
+                                                                                   // limited set of values.
+                        interface_versions.push(InterfaceVersion::from(version));
+                    }
+                }
+            }
+            interface_versions
+        };
 
-        let module = Module::new(&engine, &wasm_bytes)
-            .map_err(|error| WasmPreparationError::Compile(error.to_string()))?;
+        // Get the highest one assuming given Wasm can support all previous interface versions.
+        let interface_version = interface_versions
+            .peek()
+            .copied()
+            .unwrap_or(DEFAULT_INTERFACE_VERSION);
+
+        if interface_version > MAX_INTERFACE_VERSION {
+            return Err(WasmPreparationError::UnsupportedInterfaceVersion(
+                interface_version.value(),
+            ));
+        }
 
         let mut store = Store::new(engine);
 
-        let wasmer_env = WasmerEnv::new(context, wasm_bytes, InterfaceVersion::from(1u32));
+        let instruction_budget = if counts_instructions {
+            // Instances of an instrumented module without a budget are not limited.
+            Some(
+                config
+                    .instruction_budget()
+                    .cloned()
+                    .unwrap_or_else(|| InstructionBudget::new(u64::MAX)),
+            )
+        } else {
+            None
+        };
+
+        let wasmer_env = WasmerEnv::new(
+            context,
+            wasm_bytes,
+            interface_version,
+            instruction_budget.clone(),
+        );
         let function_env = FunctionEnv::new(&mut store, wasmer_env);
 
         let memory = Memory::new(
@@ -323,11 +465,15 @@ where
 
             imports.define("env", "memory", memory.clone());
 
-            imports.define(
-                "env",
-                "interface_version_1",
-                Function::new_typed(&mut store, || {}),
-            );
+            // Interface version imports are markers only, and the negotiated version is exposed to
+            // the host instead.
+            for version in DEFAULT_INTERFACE_VERSION.value()..=MAX_INTERFACE_VERSION.value() {
+                imports.define(
+                    "env",
+                    &interface_version_import_name(InterfaceVersion::new(version)),
+                    Function::new_typed(&mut store, || {}),
+                );
+            }
 
             imports
         };
@@ -339,6 +485,16 @@ where
             let instance = Instance::new(&mut store, &module, &imports)
                 .map_err(|error| WasmPreparationError::Instantiation(error.to_string()))?;
 
+            // Cached modules are compiled without any gas, so the gas limit is set per instance.
+            metering::set_remaining_points(&mut store, &instance, config.gas_limit());
+            if let Some(instruction_budget) = &instruction_budget {
+                instruction_budget::set_remaining_instructions(
+                    &mut store,
+                    &instance,
+                    instruction_budget.remaining(),
+                );
+            }
+
             // We don't necessarily need atomic counter. Arc's purpose is to be able to retrieve a
             // Weak reference to the instance to be able to invoke recursive calls to the wasm
             // itself from within a host function implementation.
@@ -347,26 +503,6 @@ where
             Arc::new(instance)
         };
 
-        let interface_version = {
-            static RE: LazyLock<Regex> =
-                LazyLock::new(|| Regex::new(r"^interface_version_(?P<version>\d+)$").unwrap());
-
-            let mut interface_versions = BinaryHeap::new();
-            for import in module.imports() {
-                if import.module() == "env" {
-                    if let Some(caps) = RE.captures(import.name()) {
-                        let version = &caps["version"];
-                        let version: u32 = version.parse().expect("valid number"); // SAFETY: regex guarantees this is a number, and imports table guarantees
-                                                                                   // limited set of values.
-                        interface_versions.push(InterfaceVersion::from(version));
-                    }
-                }
-            }
-
-            // Get the highest one assuming given Wasm can support all previous interface versions.
-            interface_versions.pop()
-        };
-
         // TODO: get first export of type table as some compilers generate different names (i.e.
         // rust __indirect_function_table, assemblyscript `table` etc). There's only one table
         // allowed in a valid module.
@@ -385,9 +521,6 @@ where
                 memory,
                 exported_table: table,
             });
-            if let Some(interface_version) = interface_version {
-                function_env_mut.interface_version = interface_version;
-            }
         }
 
         Ok(Self {
@@ -406,7 +539,17 @@ where
 {
     type Context = Context<S, E>;
     fn call_export(&mut self, name: &str) -> (Result<(), VMError>, GasUsage) {
-        let vm_result = self.call_export(name);
+        let mut vm_result = self.call_export(name);
+        if let Some(budget) = self.env.as_ref(&self.store).instruction_budget.clone() {
+            // Hand the instructions left over to the caller, if any.
+            budget.set_remaining(instruction_budget::get_remaining_instructions(
+                &mut self.store,
+                &self.instance,
+            ));
+            if instruction_budget::is_budget_exhausted(&mut self.store, &self.instance) {
+                vm_result = Err(VMError::InstructionBudgetExceeded);
+            }
+        }
         let remaining_points = metering::get_remaining_points(&mut self.store, &self.instance);
         match remaining_points {
             metering::MeteringPoints::Remaining(remaining_points) => {
@@ -420,6 +563,10 @@ where
         }
     }
 
+    fn interface_version(&self) -> InterfaceVersion {
+        self.env.as_ref(&self.store).interface_version
+    }
+
     /// Consume instance object and retrieve the [`Context`] object.
     fn teardown(self) -> Context<S, E> {
         let WasmerInstance { env, mut store, .. } = self;
@@ -433,7 +580,9 @@ where
         Context {
             initiator: data.context.initiator,
             caller: data.context.caller,
+            caller_entity: data.context.caller_entity,
             callee: data.context.callee,
+            byte_code_addr: data.context.byte_code_addr,
             config: data.context.config,
             storage_costs: data.context.storage_costs,
             transferred_value: data.context.transferred_value,
@@ -444,7 +593,154 @@ where
             chain_name: data.context.chain_name.clone(),
             input: data.context.input.clone(),
             block_time: data.context.block_time,
+            phase: data.context.phase,
             message_limits: data.context.message_limits,
+            instruction_budget: data.context.instruction_budget.clone(),
+            random_counter: data.context.random_counter.clone(),
+            frames: mem::take(&mut data.context.frames),
+            sub_call_effects: mem::take(&mut data.context.sub_call_effects),
+            system_interactions: mem::take(&mut data.context.system_interactions),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use casper_executor_wasm_host::context::Context;
+    use casper_executor_wasm_interface::{
+        executor::{ExecuteError, ExecuteRequest, ExecuteResult, Executor},
+        ConfigBuilder, InterfaceVersion, RandomCounter, WasmInstance, WasmPreparationError,
+    };
+    use casper_storage::{
+        global_state::{
+            state::{lmdb::make_temporary_global_state, StateProvider},
+            GlobalStateReader,
+        },
+        AddressGenerator, TrackingCopy,
+    };
+    use casper_types::{
+        account::AccountHash, BlockTime, EntityAddr, Key, MessageLimits, Phase, StorageCosts,
+        TransactionHash, TransactionV1Hash, WasmV2Config,
+    };
+    use parking_lot::RwLock;
+
+    use super::WasmerEngine;
+
+    #[derive(Clone)]
+    struct UnreachableExecutor;
+
+    impl Executor for UnreachableExecutor {
+        fn execute<R: GlobalStateReader + 'static>(
+            &self,
+            _tracking_copy: TrackingCopy<R>,
+            _execute_request: ExecuteRequest,
+        ) -> Result<ExecuteResult, ExecuteError> {
+            unreachable!("test modules do not call other contracts")
         }
     }
+
+    fn negotiate_interface_version(wat: &str) -> Result<InterfaceVersion, WasmPreparationError> {
+        instantiate_with(WasmerEngine::new(), wat)
+    }
+
+    fn instantiate_with(
+        engine: WasmerEngine,
+        wat: &str,
+    ) -> Result<InterfaceVersion, WasmPreparationError> {
+        let (global_state, root_hash, _tempdir) = make_temporary_global_state([]);
+        let tracking_copy = global_state
+            .tracking_copy(root_hash)
+            .expect("should obtain tracking copy")
+            .expect("root hash should exist");
+        let transaction_hash = TransactionHash::V1(TransactionV1Hash::from_raw([1; 32]));
+        let context = Context {
+            initiator: AccountHash::new([2; 32]),
+            caller: Key::Account(AccountHash::new([2; 32])),
+            caller_entity: EntityAddr::Account([2; 32]),
+            callee: Key::Account(AccountHash::new([2; 32])),
+            byte_code_addr: None,
+            transferred_value: 0,
+            config: WasmV2Config::default(),
+            storage_costs: StorageCosts::default(),
+            message_limits: MessageLimits::default(),
+            tracking_copy,
+            executor: UnreachableExecutor,
+            transaction_hash,
+            address_generator: Arc::new(RwLock::new(AddressGenerator::new(
+                transaction_hash.as_ref(),
+                Phase::Session,
+            ))),
+            chain_name: Arc::from("casper-test"),
+            input: Bytes::new(),
+            block_time: BlockTime::new(0),
+            phase: Phase::Session,
+            instruction_budget: None,
+            random_counter: RandomCounter::default(),
+            frames: Vec::new(),
+            sub_call_effects: Vec::new(),
+            system_interactions: Vec::new(),
+        };
+        let config = ConfigBuilder::new()
+            .with_gas_limit(1_000_000)
+            .with_memory_limit(17)
+            .build();
+
+        let wasm_bytes = wat::parse_str(wat).expect("should parse wat");
+        engine
+            .instantiate(wasm_bytes, context, config)
+            .map(|instance| instance.interface_version())
+    }
+
+    fn negotiated_interface_version(wat: &str) -> InterfaceVersion {
+        negotiate_interface_version(wat)
+            .unwrap_or_else(|error| panic!("should instantiate: {error:?}"))
+    }
+
+    #[test]
+    fn should_default_to_interface_version_1() {
+        let wat = r#"(module (func (export "call")))"#;
+        assert_eq!(negotiated_interface_version(wat), InterfaceVersion::from(1));
+    }
+
+    #[test]
+    fn should_select_highest_interface_version() {
+        let wat = r#"
+            (module
+                (import "env" "interface_version_1" (func))
+                (import "env" "interface_version_2" (func))
+                (func (export "call")))
+        "#;
+        assert_eq!(negotiated_interface_version(wat), InterfaceVersion::from(2));
+    }
+
+    #[test]
+    fn should_reject_unsupported_interface_version() {
+        let wat = r#"
+            (module
+                (import "env" "interface_version_1" (func))
+                (import "env" "interface_version_3" (func))
+                (func (export "call")))
+        "#;
+        assert!(matches!(
+            negotiate_interface_version(wat),
+            Err(WasmPreparationError::UnsupportedInterfaceVersion(3))
+        ));
+    }
+
+    #[test]
+    fn should_reject_module_exporting_instruction_budget_names() {
+        let wat = r#"
+            (module
+                (global (export "casper_instruction_budget_remaining") i64 (i64.const 0))
+                (func (export "call")))
+        "#;
+        assert!(negotiate_interface_version(wat).is_ok());
+        assert!(matches!(
+            instantiate_with(WasmerEngine::with_instruction_budget(), wat),
+            Err(WasmPreparationError::Compile(_))
+        ));
+    }
 }
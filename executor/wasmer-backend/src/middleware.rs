@@ -1,2 +1,3 @@
 pub(crate) mod gas_metering;
 pub(crate) mod gatekeeper;
+pub(crate) mod instruction_budget;
@@ -0,0 +1,202 @@
+//! Instruction budget middleware.
+//!
+//! Counts executed Wasm instructions independently of gas metering, and stops the instance once
+//! its budget is exhausted. Running after gas metering, it also counts the instructions metering
+//! injects, while metering does not charge for the ones injected here. The counter lives in two
+//! globals exported by the instance, so the budget can be set per instance on modules compiled
+//! ahead of time. Modules that already export either name are rejected, rather than having their
+//! export replaced.
+use std::sync::Mutex;
+
+use wasmer::{
+    wasmparser::{BlockType, Operator},
+    AsStoreMut, ExportIndex, FunctionMiddleware, GlobalInit, GlobalType, Instance,
+    LocalFunctionIndex, MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Mutability, Type,
+    Value,
+};
+use wasmer_middlewares::metering::is_accounting;
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+const REMAINING_INSTRUCTIONS_EXPORT: &str = "casper_instruction_budget_remaining";
+const BUDGET_EXHAUSTED_EXPORT: &str = "casper_instruction_budget_exhausted";
+
+#[derive(Copy, Clone, Debug)]
+struct BudgetGlobals {
+    remaining: GlobalIndex,
+    exhausted: GlobalIndex,
+}
+
+/// Module-level instruction budget middleware.
+///
+/// As with gas metering, an instance of this middleware must not be shared between modules.
+#[derive(Debug, Default)]
+pub(crate) struct InstructionBudget {
+    globals: Mutex<Option<BudgetGlobals>>,
+}
+
+impl InstructionBudget {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ModuleMiddleware for InstructionBudget {
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        let globals = self
+            .globals
+            .lock()
+            .expect("instruction budget lock poisoned")
+            .expect("module info should be transformed first");
+        Box::new(FunctionInstructionBudget {
+            globals,
+            accumulated: 0,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let mut globals = self
+            .globals
+            .lock()
+            .expect("instruction budget lock poisoned");
+        assert!(
+            globals.is_none(),
+            "InstructionBudget middleware must not be shared between modules"
+        );
+
+        for name in [REMAINING_INSTRUCTIONS_EXPORT, BUDGET_EXHAUSTED_EXPORT] {
+            if module_info.exports.contains_key(name) {
+                return Err(MiddlewareError::new(
+                    "InstructionBudget",
+                    format!("export {name} is reserved for the instruction budget"),
+                ));
+            }
+        }
+
+        // Modules are compiled without any budget, so it has to be set on every instance.
+        let remaining = module_info
+            .globals
+            .push(GlobalType::new(Type::I64, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I64Const(0));
+        module_info.exports.insert(
+            REMAINING_INSTRUCTIONS_EXPORT.to_string(),
+            ExportIndex::Global(remaining),
+        );
+
+        let exhausted = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+        module_info.exports.insert(
+            BUDGET_EXHAUSTED_EXPORT.to_string(),
+            ExportIndex::Global(exhausted),
+        );
+
+        *globals = Some(BudgetGlobals {
+            remaining,
+            exhausted,
+        });
+        Ok(())
+    }
+}
+
+/// Function-level instruction budget middleware.
+///
+/// Instructions are counted per basic block, and the counter is checked at every branch source
+/// and target.
+#[derive(Debug)]
+struct FunctionInstructionBudget {
+    globals: BudgetGlobals,
+    accumulated: u64,
+}
+
+impl FunctionMiddleware for FunctionInstructionBudget {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        self.accumulated += 1;
+
+        if is_accounting(&operator) {
+            let remaining = self.globals.remaining.as_u32();
+            let exhausted = self.globals.exhausted.as_u32();
+            let accumulated = self.accumulated as i64;
+            state.extend(&[
+                Operator::GlobalGet {
+                    global_index: remaining,
+                },
+                Operator::I64Const { value: accumulated },
+                Operator::I64LtU,
+                Operator::If {
+                    blockty: BlockType::Empty,
+                },
+                Operator::I32Const { value: 1 },
+                Operator::GlobalSet {
+                    global_index: exhausted,
+                },
+                Operator::Unreachable,
+                Operator::End,
+                Operator::GlobalGet {
+                    global_index: remaining,
+                },
+                Operator::I64Const { value: accumulated },
+                Operator::I64Sub,
+                Operator::GlobalSet {
+                    global_index: remaining,
+                },
+            ]);
+            self.accumulated = 0;
+        }
+
+        state.push_operator(operator);
+        Ok(())
+    }
+}
+
+/// Sets the number of instructions the instance may still execute.
+pub(crate) fn set_remaining_instructions(
+    store: &mut impl AsStoreMut,
+    instance: &Instance,
+    remaining: u64,
+) {
+    instance
+        .exports
+        .get_global(REMAINING_INSTRUCTIONS_EXPORT)
+        .expect("instance should export the instruction budget")
+        .set(store, Value::I64(remaining as i64))
+        .expect("instruction budget global should be mutable");
+    instance
+        .exports
+        .get_global(BUDGET_EXHAUSTED_EXPORT)
+        .expect("instance should export the instruction budget")
+        .set(store, Value::I32(0))
+        .expect("instruction budget global should be mutable");
+}
+
+/// Returns the number of instructions the instance may still execute.
+pub(crate) fn get_remaining_instructions(store: &mut impl AsStoreMut, instance: &Instance) -> u64 {
+    let remaining: i64 = instance
+        .exports
+        .get_global(REMAINING_INSTRUCTIONS_EXPORT)
+        .expect("instance should export the instruction budget")
+        .get(store)
+        .try_into()
+        .expect("instruction budget should be an i64");
+    remaining as u64
+}
+
+/// Checks whether the instance was stopped because its instruction budget was exhausted.
+pub(crate) fn is_budget_exhausted(store: &mut impl AsStoreMut, instance: &Instance) -> bool {
+    let exhausted: i32 = instance
+        .exports
+        .get_global(BUDGET_EXHAUSTED_EXPORT)
+        .expect("instance should export the instruction budget")
+        .get(store)
+        .try_into()
+        .expect("instruction budget flag should be an i32");
+    exhausted != 0
+}
@@ -0,0 +1,203 @@
+//! Cache of compiled Wasm modules, keyed by the hash of their bytecode.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use casper_executor_wasm_common::chain_utils;
+use casper_executor_wasm_interface::WasmPreparationError;
+use wasmer::{CompilerConfig, Engine, Module};
+use wasmer_compiler_singlepass::Singlepass;
+
+use crate::middleware::{
+    gas_metering,
+    gatekeeper::{Gatekeeper, GatekeeperConfig},
+    instruction_budget::InstructionBudget,
+};
+
+/// Maximum number of compiled modules kept in the cache.
+const MODULE_CACHE_CAPACITY: usize = 256;
+
+/// A compiled module together with the engine it was compiled with.
+///
+/// Each module needs its own engine, as the gas metering middleware can't be shared between
+/// modules.
+#[derive(Clone)]
+pub(crate) struct CompiledModule {
+    pub(crate) engine: Engine,
+    pub(crate) module: Module,
+    /// Whether the module counts the instructions it executes.
+    pub(crate) instruction_budget: bool,
+}
+
+impl CompiledModule {
+    /// Compiles a Wasm module.
+    ///
+    /// Modules are compiled without any gas or instruction budget, so both have to be set on every
+    /// instance before it is used.
+    fn compile(wasm_bytes: &[u8], instruction_budget: bool) -> Result<Self, WasmPreparationError> {
+        let mut singlepass_compiler = Singlepass::new();
+        let gatekeeper_config = GatekeeperConfig::default();
+        singlepass_compiler.push_middleware(Arc::new(Gatekeeper::new(gatekeeper_config)));
+        singlepass_compiler.push_middleware(gas_metering::gas_metering_middleware(0));
+        if instruction_budget {
+            // Pushed after gas metering, so that enabling the budget does not change the gas cost
+            // of a module. The budget therefore also counts the instructions injected by gas
+            // metering.
+            singlepass_compiler.push_middleware(Arc::new(InstructionBudget::new()));
+        }
+
+        let engine = Engine::from(singlepass_compiler);
+        let module = Module::new(&engine, wasm_bytes)
+            .map_err(|error| WasmPreparationError::Compile(error.to_string()))?;
+        Ok(Self {
+            engine,
+            module,
+            instruction_budget,
+        })
+    }
+}
+
+/// Number of module cache lookups that were served from the cache, and that required compiling
+/// the module.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// Number of lookups served from the cache.
+    pub hits: u64,
+    /// Number of lookups which compiled the module.
+    pub misses: u64,
+}
+
+/// A cached module, along with the time it was last looked up.
+struct CacheEntry {
+    compiled_module: CompiledModule,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct ModuleCacheInner {
+    modules: HashMap<[u8; 32], CacheEntry>,
+    /// Incremented on every lookup, and used to find the least recently used module.
+    clock: u64,
+    stats: ModuleCacheStats,
+}
+
+impl ModuleCacheInner {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Removes the least recently used module.
+    fn evict(&mut self) {
+        let least_recently_used = self
+            .modules
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(bytecode_hash, _)| *bytecode_hash);
+        if let Some(bytecode_hash) = least_recently_used {
+            self.modules.remove(&bytecode_hash);
+        }
+    }
+}
+
+pub(crate) struct ModuleCache {
+    inner: Mutex<ModuleCacheInner>,
+    /// Whether modules are compiled to count the instructions they execute.
+    instruction_budget: bool,
+    /// Maximum number of modules kept; the least recently used module is evicted beyond it.
+    capacity: usize,
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::default(),
+            instruction_budget: false,
+            capacity: MODULE_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl ModuleCache {
+    /// Creates a cache of modules compiled to count the instructions they execute.
+    pub(crate) fn with_instruction_budget() -> Self {
+        Self {
+            instruction_budget: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the compiled module for the given bytecode, compiling and caching it if needed.
+    pub(crate) fn get_or_compile(
+        &self,
+        wasm_bytes: &[u8],
+    ) -> Result<CompiledModule, WasmPreparationError> {
+        let bytecode_hash = chain_utils::compute_wasm_bytecode_hash(wasm_bytes);
+        {
+            let mut inner = self.inner.lock().expect("module cache lock poisoned");
+            let now = inner.tick();
+            if let Some(entry) = inner.modules.get_mut(&bytecode_hash) {
+                entry.last_used = now;
+                let compiled_module = entry.compiled_module.clone();
+                inner.stats.hits += 1;
+                return Ok(compiled_module);
+            }
+            inner.stats.misses += 1;
+        }
+
+        // Compile without holding the lock, so other modules can be looked up meanwhile.
+        let compiled_module = CompiledModule::compile(wasm_bytes, self.instruction_budget)?;
+
+        let mut inner = self.inner.lock().expect("module cache lock poisoned");
+        if !inner.modules.contains_key(&bytecode_hash) && inner.modules.len() >= self.capacity {
+            inner.evict();
+        }
+        let last_used = inner.tick();
+        inner.modules.insert(
+            bytecode_hash,
+            CacheEntry {
+                compiled_module: compiled_module.clone(),
+                last_used,
+            },
+        );
+        Ok(compiled_module)
+    }
+
+    pub(crate) fn stats(&self) -> ModuleCacheStats {
+        self.inner.lock().expect("module cache lock poisoned").stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(export_name: &str) -> Vec<u8> {
+        wat::parse_str(format!(r#"(module (func (export "{export_name}")))"#))
+            .expect("should parse wat")
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = ModuleCache {
+            capacity: 2,
+            ..ModuleCache::default()
+        };
+        let (first, second, third) = (module("first"), module("second"), module("third"));
+
+        cache.get_or_compile(&first).unwrap();
+        cache.get_or_compile(&second).unwrap();
+        // Using the first module again makes the second one the least recently used.
+        cache.get_or_compile(&first).unwrap();
+        cache.get_or_compile(&third).unwrap();
+        assert_eq!(cache.stats(), ModuleCacheStats { hits: 1, misses: 3 });
+
+        cache.get_or_compile(&first).unwrap();
+        cache.get_or_compile(&third).unwrap();
+        assert_eq!(cache.stats().hits, 3);
+
+        cache.get_or_compile(&second).unwrap();
+        assert_eq!(cache.stats().misses, 4);
+    }
+}
@@ -1160,6 +1160,7 @@ impl reactor::Reactor for Reactor {
                                 total_balance: Default::default(),
                                 available_balance: motes,
                                 proofs_result,
+                                holds_breakdown: None,
                             }
                         };
                     responder.respond(balance_result).ignore()
@@ -706,7 +706,7 @@ impl TestFixture {
 
         let delegator_kind = delegator_public_key.map(|pk| DelegatorKind::PublicKey(pk.clone()));
 
-        if let BidsResult::Success { bids } = bids_result {
+        if let BidsResult::Success { bids, .. } = bids_result {
             match bids.iter().find(|bid_kind| {
                 &bid_kind.validator_public_key() == validator_public_key
                     && bid_kind.delegator_kind() == delegator_kind
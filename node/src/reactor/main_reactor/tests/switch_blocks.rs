@@ -61,7 +61,7 @@ impl SwitchBlocks {
         for runner in nodes.values() {
             let request = BidsRequest::new(state_root_hash);
             let data_provider = runner.main_reactor().contract_runtime().data_access_layer();
-            if let BidsResult::Success { bids } = data_provider.bids(request) {
+            if let BidsResult::Success { bids, .. } = data_provider.bids(request) {
                 return bids;
             }
         }
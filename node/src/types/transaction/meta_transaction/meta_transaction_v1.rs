@@ -744,20 +744,8 @@ impl MetaTransactionV1 {
 
     /// Returns the gas price tolerance for the given transaction.
     pub(crate) fn gas_price_tolerance(&self) -> u8 {
-        match self.pricing_mode {
-            PricingMode::PaymentLimited {
-                gas_price_tolerance,
-                ..
-            } => gas_price_tolerance,
-            PricingMode::Fixed {
-                gas_price_tolerance,
-                ..
-            } => gas_price_tolerance,
-            PricingMode::Prepaid { .. } => {
-                // TODO: Change this when prepaid gets implemented.
-                0u8
-            }
-        }
+        // TODO: Change this when prepaid gets implemented.
+        self.pricing_mode.gas_price_tolerance().unwrap_or(0)
     }
 
     /// Returns the serialized length of the transaction.
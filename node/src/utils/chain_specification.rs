@@ -321,6 +321,13 @@ mod tests {
             print: HostFunctionV2::new(112, [0, 1]),
             emit: HostFunctionV2::new(113, [0, 1, 2, 3]),
             env_info: HostFunctionV2::new(114, [0, 1]),
+            register_topic: HostFunctionV2::new(115, [0, 1]),
+            message_count: HostFunctionV2::new(116, [0, 1, 2]),
+            emit_with_index: HostFunctionV2::new(117, [0, 1, 2, 3, 4]),
+            try_create: HostFunctionV2::new(118, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+            self_code_hash: HostFunctionV2::new(119, [0, 1]),
+            abort: HostFunctionV2::new(120, [0, 1]),
+            random_bytes: HostFunctionV2::new(121, [0, 1]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         let wasm_v1_config = WasmV1Config::new(
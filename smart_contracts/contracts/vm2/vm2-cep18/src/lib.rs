@@ -5,7 +5,8 @@ use casper_sdk::{
 };
 
 use casper_sdk::contrib::cep18::{
-    Burnable, BurnableExt, CEP18Ext, CEP18State, Mintable, MintableExt, ADMIN_ROLE, CEP18,
+    Burnable, BurnableExt, CEP18Ext, CEP18State, EnumerableAllowances, EnumerableAllowancesExt,
+    Mintable, MintableExt, ADMIN_ROLE, CEP18,
 };
 
 #[casper(contract_state)]
@@ -80,6 +81,9 @@ impl Mintable for TokenContract {}
 #[casper(path = casper_sdk::contrib::cep18)]
 impl Burnable for TokenContract {}
 
+#[casper(path = casper_sdk::contrib::cep18)]
+impl EnumerableAllowances for TokenContract {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,8 +92,8 @@ mod tests {
         casper::{
             self,
             native::{
-                current_environment, dispatch_with, with_current_environment, Environment,
-                DEFAULT_ADDRESS,
+                current_environment, dispatch_with, read_contract_state, with_current_environment,
+                Environment, DEFAULT_ADDRESS,
             },
             Entity,
         },
@@ -272,4 +276,128 @@ mod tests {
 
         assert!(matches!(result, Ok(())));
     }
+
+    #[test]
+    fn should_enumerate_allowances_of_owner() {
+        const CAROL: Entity = Entity::Account([3; 32]);
+
+        let stub = Environment::new(Default::default(), DEFAULT_ADDRESS);
+
+        let result = casper::native::dispatch_with(stub, || {
+            let mut contract = TokenContract::new("Foo Token".to_string());
+            let owner = casper::get_caller();
+
+            assert!(contract.allowances_of(owner, 0, 10).is_empty());
+
+            contract.approve(ALICE, U256::from(100u64)).unwrap();
+            contract.approve(BOB, U256::from(200u64)).unwrap();
+            contract
+                .increase_allowance(CAROL, U256::from(300u64))
+                .unwrap();
+            contract.approve(ALICE, U256::from(150u64)).unwrap();
+
+            assert_eq!(
+                contract.allowances_of(owner, 0, 10),
+                vec![
+                    (ALICE, U256::from(150u64)),
+                    (BOB, U256::from(200u64)),
+                    (CAROL, U256::from(300u64)),
+                ]
+            );
+            assert!(contract.allowances_of(ALICE, 0, 10).is_empty());
+
+            // Allowances are listed in pages.
+            assert_eq!(
+                contract.allowances_of(owner, 0, 2),
+                vec![(ALICE, U256::from(150u64)), (BOB, U256::from(200u64))]
+            );
+            assert_eq!(
+                contract.allowances_of(owner, 2, 2),
+                vec![(CAROL, U256::from(300u64))]
+            );
+            assert!(contract.allowances_of(owner, 3, 2).is_empty());
+
+            // Spenders whose allowance drops to zero are no longer listed.
+            contract
+                .decrease_allowance(BOB, U256::from(200u64))
+                .unwrap();
+            assert_eq!(
+                contract.allowances_of(owner, 0, 10),
+                vec![(ALICE, U256::from(150u64)), (CAROL, U256::from(300u64))]
+            );
+            contract.approve(ALICE, U256::from(0u64)).unwrap();
+            assert_eq!(
+                contract.allowances_of(owner, 0, 10),
+                vec![(CAROL, U256::from(300u64))]
+            );
+        });
+        assert!(matches!(result, Ok(())));
+    }
+
+    #[test]
+    fn should_list_allowances_approved_before_upgrade() {
+        const CAROL: Entity = Entity::Account([3; 32]);
+        const DAVE: Entity = Entity::Account([4; 32]);
+
+        let stub = Environment::new(Default::default(), DEFAULT_ADDRESS);
+
+        let result = casper::native::dispatch_with(stub, || {
+            let mut contract = TokenContract::new("Foo Token".to_string());
+            let owner = casper::get_caller();
+
+            // Approvals made before the token implemented `EnumerableAllowances` only wrote the
+            // allowances map.
+            CEP18::state_mut(&mut contract)
+                .allowances
+                .insert(&(owner, ALICE), &U256::from(100u64));
+            CEP18::state_mut(&mut contract)
+                .allowances
+                .insert(&(owner, BOB), &U256::from(200u64));
+            contract.approve(CAROL, U256::from(300u64)).unwrap();
+
+            assert_eq!(
+                contract.allowances_of(owner, 0, 10),
+                vec![(CAROL, U256::from(300u64))]
+            );
+
+            // Dave holds no allowance and stays unlisted, and Alice is not listed twice.
+            contract.index_allowances(owner, vec![ALICE, BOB, CAROL, DAVE]);
+            contract.index_allowances(owner, vec![ALICE]);
+
+            assert_eq!(
+                contract.allowances_of(owner, 0, 10),
+                vec![
+                    (CAROL, U256::from(300u64)),
+                    (ALICE, U256::from(100u64)),
+                    (BOB, U256::from(200u64)),
+                ]
+            );
+        });
+        assert!(matches!(result, Ok(())));
+    }
+
+    #[test]
+    fn should_read_contract_state_directly() {
+        let result = casper::native::dispatch(|| {
+            let constructor = TokenContractRef::new("Foo Token".to_string());
+            let ctor_input_data = constructor.input_data();
+            let create_result = casper::create(
+                None,
+                0,
+                Some(constructor.entry_point()),
+                ctor_input_data.as_deref(),
+                None,
+            )
+            .expect("Should create");
+
+            let contract: TokenContract =
+                read_contract_state(&create_result.contract_address).expect("should have state");
+            assert_eq!(contract.state.name, "Foo Token");
+            assert_eq!(contract.state.symbol, "Default symbol");
+
+            assert!(read_contract_state::<TokenContract>(&[0; 32]).is_none());
+        });
+
+        assert!(matches!(result, Ok(())));
+    }
 }
@@ -163,13 +163,6 @@ impl HasTraits {
     }
 }
 
-#[casper]
-impl HasTraits {
-    pub fn multiple_impl_blocks_should_work() {
-        log!("Multiple impl blocks work!");
-    }
-}
-
 fn perform_test() {
     let contract_handle = ContractBuilder::<HasTraitsRef>::new()
         .default_create()
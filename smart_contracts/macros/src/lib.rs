@@ -11,7 +11,11 @@ use syn::{
     LitStr, Type,
 };
 
-use casper_executor_wasm_common::flags::EntryPointFlags;
+use casper_executor_wasm_common::{
+    chain_utils,
+    entry_point::{encode_manifest_record, MANIFEST_SECTION},
+    flags::EntryPointFlags,
+};
 const CASPER_RESERVED_FALLBACK_EXPORT: &str = "__casper_fallback";
 
 #[derive(Debug, FromAttributes)]
@@ -32,6 +36,51 @@ struct MethodAttribute {
     fallback: bool,
 }
 
+impl MethodAttribute {
+    /// Rejects attributes that are only meaningful on methods exported as entry points.
+    fn check_not_entry_point(&self, func_name: &syn::Ident) -> Result<(), syn::Error> {
+        let attribute = if self.payable {
+            "payable"
+        } else if self.fallback {
+            "fallback"
+        } else if self.constructor {
+            "constructor"
+        } else {
+            return Ok(());
+        };
+        Err(syn::Error::new(
+            func_name.span(),
+            format!("`{attribute}` can only be used on methods exported as entry points"),
+        ))
+    }
+}
+
+/// Strips `#[casper(...)]` attributes off an associated const, returning whether it was marked
+/// with `#[casper(const)]` for inclusion in the schema.
+fn take_const_attribute(attrs: &mut Vec<syn::Attribute>) -> Result<bool, syn::Error> {
+    let mut schema_constant = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("casper")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("const") {
+                schema_constant = true;
+                Ok(())
+            } else {
+                Err(meta.error("only `const` can be used on associated consts"))
+            }
+        })?;
+    }
+    attrs.retain(|attr| !attr.path().is_ident("casper"));
+    Ok(schema_constant)
+}
+
+#[derive(Debug, FromAttributes)]
+#[darling(attributes(casper))]
+struct ArgAttribute {
+    /// Value used when the argument is omitted by the caller.
+    #[darling(default)]
+    default: Option<syn::Expr>,
+}
+
 #[derive(Debug, FromMeta)]
 struct StructMeta {
     #[darling(default)]
@@ -42,6 +91,18 @@ struct StructMeta {
     /// Message is a special struct that is used to send messages to other contracts.
     #[darling(default)]
     message: bool,
+    /// Traits implemented by the contract state through their default methods, each exporting
+    /// its entry points as if implemented with `#[casper(path = ...)]`.
+    #[darling(default)]
+    impls: darling::util::PathList,
+    /// Version reported by the contract's metadata, defaulting to the version of its crate.
+    #[darling(default)]
+    version: Option<String>,
+    /// Exports the contract's metadata as `__casper_metadata`.
+    ///
+    /// The export has a fixed name, so at most one contract state per crate may set this.
+    #[darling(default)]
+    export_metadata: bool,
 }
 
 #[derive(Debug, FromMeta)]
@@ -252,10 +313,16 @@ fn generate_export_function(func: &ItemFn) -> TokenStream {
     let _ctor_name = format_ident!("{func_name}_ctor");
 
     let exported_func_name = format_ident!("__casper_export_{func_name}");
+    let entry_point_func_name = format_ident!("__casper_entry_point_{func_name}");
     quote! {
         #[export_name = stringify!(#func_name)]
         #[no_mangle]
         pub extern "C" fn #exported_func_name() {
+            #entry_point_func_name();
+        }
+
+        // Kept out of the `extern "C"` export so a native call can unwind through it.
+        fn #entry_point_func_name() {
             #[cfg(target_arch = "wasm32")]
             {
                 casper_sdk::set_panic_hook();
@@ -282,7 +349,8 @@ fn generate_export_function(func: &ItemFn) -> TokenStream {
             #[linkme(crate = casper_sdk::linkme)]
             pub static EXPORTS: casper_sdk::casper::native::EntryPoint = casper_sdk::casper::native::EntryPoint {
                 kind: casper_sdk::casper::native::EntryPointKind::Function { name: stringify!(#func_name) },
-                fptr: || { #exported_func_name(); },
+                flags: None,
+                fptr: || { #entry_point_func_name(); },
                 module_path: module_path!(),
                 file: file!(),
                 line: line!(),
@@ -314,6 +382,8 @@ fn generate_impl_for_contract(
     let mut defs = defs;
     #[cfg(feature = "__abi_generator")]
     let mut defs_linkme = Vec::new();
+    #[cfg(feature = "__abi_generator")]
+    let mut constants_linkme = Vec::new();
     let mut names = Vec::new();
     let mut extern_entry_points = Vec::new();
     let _abi_generator_entry_points = [quote! {}]; // TODO: Dummy element which may not be necessary but is used for expansion later
@@ -326,18 +396,66 @@ fn generate_impl_for_contract(
         let mut populate_definitions = Vec::new();
 
         let method_attribute;
+        #[cfg(feature = "__abi_generator")]
+        let docs;
         let mut flag_value = EntryPointFlags::empty();
 
         // let selector_value;
 
         let func = match entry_point {
-            syn::ImplItem::Const(_) => todo!("Const"),
+            syn::ImplItem::Const(ref mut item_const) => {
+                let schema_constant = match take_const_attribute(&mut item_const.attrs) {
+                    Ok(schema_constant) => schema_constant,
+                    Err(error) => return TokenStream::from(error.to_compile_error()),
+                };
+
+                #[cfg(feature = "__abi_generator")]
+                if schema_constant {
+                    let const_name = &item_const.ident;
+                    let ty = &item_const.ty;
+
+                    let linkme_schema_constant_ident =
+                        format_ident!("__casper_schema_constant_{const_name}");
+                    defs.push(quote! {
+                        #[allow(non_snake_case)]
+                        fn #linkme_schema_constant_ident() -> casper_sdk::schema::SchemaConstant {
+                            casper_sdk::schema::SchemaConstant {
+                                name: stringify!(#const_name).into(),
+                                decl: <#ty as casper_sdk::abi::CasperABI>::declaration(),
+                            }
+                        }
+                    });
+                    constants_linkme.push(linkme_schema_constant_ident);
+
+                    let linkme_abi_populate_defs_ident =
+                        format_ident!("__casper_populate_definitions_{const_name}");
+                    defs.push(quote! {
+                        #[allow(non_snake_case)]
+                        fn #linkme_abi_populate_defs_ident(definitions: &mut casper_sdk::abi::Definitions) {
+                            definitions.populate_one::<#ty>();
+                        }
+                    });
+                    populate_definitions_linkme.push(linkme_abi_populate_defs_ident);
+                }
+                #[cfg(not(feature = "__abi_generator"))]
+                let _ = schema_constant;
+
+                continue;
+            }
             syn::ImplItem::Fn(ref mut func) => {
                 let vis = &func.vis;
                 match vis {
                     syn::Visibility::Public(_) => {}
                     syn::Visibility::Inherited => {
                         // As the doc says this "usually means private"
+                        if let Err(error) = MethodAttribute::from_attributes(&func.attrs)
+                            .map_err(syn::Error::from)
+                            .and_then(|method_attribute| {
+                                method_attribute.check_not_entry_point(&func.sig.ident)
+                            })
+                        {
+                            return TokenStream::from(error.to_compile_error());
+                        }
                         continue;
                     }
                     syn::Visibility::Restricted(_restricted) => {}
@@ -351,6 +469,11 @@ fn generate_impl_for_contract(
 
                 method_attribute = MethodAttribute::from_attributes(&func.attrs).unwrap();
 
+                #[cfg(feature = "__abi_generator")]
+                {
+                    docs = utils::collect_doc_comments(&func.attrs);
+                }
+
                 func.attrs.clear();
 
                 let func_name = func.sig.ident.clone();
@@ -370,7 +493,18 @@ fn generate_impl_for_contract(
                     format_ident!("{}", &func_name)
                 };
 
-                names.push(func_name.clone());
+                names.push(export_name.to_string());
+
+                let mut arg_defaults = Vec::new();
+                for input in func.sig.inputs.iter_mut() {
+                    if let syn::FnArg::Typed(typed) = input {
+                        match ArgAttribute::from_attributes(&typed.attrs) {
+                            Ok(arg_attribute) => arg_defaults.push(arg_attribute.default),
+                            Err(error) => return TokenStream::from(error.write_errors()),
+                        }
+                        typed.attrs.retain(|attr| !attr.path().is_ident("casper"));
+                    }
+                }
 
                 let arg_names_and_types = func
                     .sig
@@ -393,6 +527,25 @@ fn generate_impl_for_contract(
                     .map(|(name, ty)| quote! { #name: #ty })
                     .collect();
 
+                // Arguments with a default value can be omitted from the end of the input, so none
+                // of the arguments following them can be required.
+                let required_arg_count = arg_defaults
+                    .iter()
+                    .position(Option::is_some)
+                    .unwrap_or(arg_defaults.len());
+                if let Some(index) = arg_defaults[required_arg_count..]
+                    .iter()
+                    .position(Option::is_none)
+                {
+                    return TokenStream::from(
+                        syn::Error::new(
+                            arg_names[required_arg_count + index].span(),
+                            "arguments without a default value must precede arguments with one",
+                        )
+                        .to_compile_error(),
+                    );
+                }
+
                 // Entry point has &self or &mut self
                 let mut entry_point_requires_state: bool = false;
 
@@ -479,17 +632,44 @@ fn generate_impl_for_contract(
 
                 let mut prelude = Vec::new();
 
-                prelude.push(quote! {
-                    #[derive(casper_sdk::serializers::borsh::BorshDeserialize)]
-                    #[borsh(crate = "casper_sdk::serializers::borsh")]
-                    struct Arguments {
-                        #(#arg_attrs,)*
-                    }
+                if required_arg_count == arg_defaults.len() {
+                    prelude.push(quote! {
+                        #[derive(casper_sdk::serializers::borsh::BorshDeserialize)]
+                        #[borsh(crate = "casper_sdk::serializers::borsh")]
+                        struct Arguments {
+                            #(#arg_attrs,)*
+                        }
 
 
-                    let input = casper_sdk::prelude::casper::copy_input();
-                    let args: Arguments = casper_sdk::serializers::borsh::from_slice(&input).unwrap();
-                });
+                        let input = casper_sdk::prelude::casper::copy_input();
+                        let args: Arguments = casper_sdk::serializers::borsh::from_slice(&input).unwrap();
+                    });
+                } else {
+                    // Omitted trailing arguments take their default values.
+                    let read_args = arg_names.iter().zip(&arg_defaults).map(|(name, default)| {
+                        let read_arg = quote! {
+                            casper_sdk::serializers::borsh::BorshDeserialize::deserialize(&mut reader).unwrap()
+                        };
+                        match default {
+                            Some(default) => quote! {
+                                #name: if reader.is_empty() { #default } else { #read_arg }
+                            },
+                            None => quote! { #name: #read_arg },
+                        }
+                    });
+                    prelude.push(quote! {
+                        struct Arguments {
+                            #(#arg_attrs,)*
+                        }
+
+                        let input = casper_sdk::prelude::casper::copy_input();
+                        let mut reader: &[u8] = &input;
+                        let args = Arguments {
+                            #(#read_args,)*
+                        };
+                        assert!(reader.is_empty(), "Unexpected trailing input");
+                    });
+                }
 
                 if method_attribute.constructor {
                     prelude.push(quote! {
@@ -500,14 +680,12 @@ fn generate_impl_for_contract(
                 }
 
                 if !method_attribute.payable {
-                    let panic_msg = format!(
-                        r#"Entry point "{func_name}" is not payable and does not accept tokens"#
-                    );
+                    // The host rejects value sent to entry points the manifest lists as not
+                    // payable; this covers callers which bypass that check.
                     prelude.push(quote! {
-                        if casper_sdk::casper::transferred_value() != 0 {
-                            // TODO: Be precise and unambigious about the error
-                            panic!(#panic_msg);
-                        }
+                        casper_sdk::UnwrapOrRevert::unwrap_or_revert(
+                            casper_sdk::casper::assert_not_payable(),
+                        );
                     });
                 }
 
@@ -549,14 +727,37 @@ fn generate_impl_for_contract(
                     flag_value |= EntryPointFlags::FALLBACK;
                 }
 
-                let _bits = flag_value.bits();
+                if method_attribute.payable {
+                    flag_value |= EntryPointFlags::PAYABLE;
+                }
+
+                let bits = flag_value.bits();
+
+                // Lets the host read the flags of the entry point, e.g. to reject token transfers
+                // before it is invoked.
+                let manifest_record = encode_manifest_record(&export_name.to_string(), flag_value);
+                let manifest_record_len = manifest_record.len();
+                extern_entry_points.push(quote! {
+                    #[cfg(target_arch = "wasm32")]
+                    const _: () = {
+                        #[link_section = #MANIFEST_SECTION]
+                        #[used]
+                        static MANIFEST_RECORD: [u8; #manifest_record_len] = [#(#manifest_record,)*];
+                    };
+                });
 
                 let extern_func_name = format_ident!("__casper_export_{func_name}");
+                let entry_point_func_name = format_ident!("__casper_entry_point_{func_name}");
 
                 extern_entry_points.push(quote! {
 
                     #[export_name = stringify!(#export_name)]
                     #vis extern "C" fn #extern_func_name() {
+                        #entry_point_func_name();
+                    }
+
+                    // Kept out of the `extern "C"` export so a native call can unwind through it.
+                    fn #entry_point_func_name() {
                         // Set panic hook (assumes std is enabled etc.)
                         #[cfg(target_arch = "wasm32")]
                         {
@@ -582,7 +783,8 @@ fn generate_impl_for_contract(
                         #[linkme(crate = casper_sdk::linkme)]
                         pub static EXPORTS: casper_sdk::casper::native::EntryPoint = casper_sdk::casper::native::EntryPoint {
                             kind: casper_sdk::casper::native::EntryPointKind::SmartContract { name: stringify!(#export_name), struct_name: stringify!(#struct_name) },
-                            fptr: || -> () { #extern_func_name(); },
+                            flags: Some(casper_sdk::casper_executor_wasm_common::flags::EntryPointFlags::from_bits_retain(#bits)),
+                            fptr: || -> () { #entry_point_func_name(); },
                             module_path: module_path!(),
                             file: file!(),
                             line: line!(),
@@ -636,7 +838,7 @@ fn generate_impl_for_contract(
 
                         if !method_attribute.fallback {
                             extra_code.push(quote! {
-                                        pub fn #func_name<'a>(#self_ty #(#arg_names: #arg_types,)*) -> impl casper_sdk::ToCallData<Return<'a> = #call_data_return_lifetime> {
+                                        pub fn #func_name<'a>(#self_ty #(#arg_names: #arg_types,)*) -> impl casper_sdk::ToCallData<Return<'a> = #call_data_return_lifetime> + core::fmt::Debug {
                                             #[derive(casper_sdk::serializers::borsh::BorshSerialize, PartialEq, Debug)]
                                             #[borsh(crate = "casper_sdk::serializers::borsh")]
                                             struct #ident {
@@ -660,6 +862,48 @@ fn generate_impl_for_contract(
                                             }
                                         }
                                     });
+
+                            if required_arg_count < arg_defaults.len() {
+                                let with_defaults_func_name =
+                                    format_ident!("{func_name}_with_defaults");
+                                let with_defaults_ident = format_ident!("{ident}_with_defaults");
+                                let required_arg_names = &arg_names[..required_arg_count];
+                                let required_arg_types = &arg_types[..required_arg_count];
+                                let input_data_content = if required_arg_names.is_empty() {
+                                    quote! {
+                                        None
+                                    }
+                                } else {
+                                    quote! {
+                                        Some(casper_sdk::serializers::borsh::to_vec(&self).expect("Serialization to succeed"))
+                                    }
+                                };
+                                extra_code.push(quote! {
+                                    /// Calls the entry point leaving out the arguments that have a
+                                    /// default value.
+                                    pub fn #with_defaults_func_name<'a>(#self_ty #(#required_arg_names: #required_arg_types,)*) -> impl casper_sdk::ToCallData<Return<'a> = #call_data_return_lifetime> + core::fmt::Debug {
+                                        #[derive(casper_sdk::serializers::borsh::BorshSerialize, PartialEq, Debug)]
+                                        #[borsh(crate = "casper_sdk::serializers::borsh")]
+                                        struct #with_defaults_ident {
+                                            #(#required_arg_names: #required_arg_types,)*
+                                        }
+
+                                        impl casper_sdk::ToCallData for #with_defaults_ident {
+                                            type Return<'a> = #call_data_return_lifetime;
+
+                                            fn entry_point(&self) -> &str { stringify!(#func_name) }
+
+                                            fn input_data(&self) -> Option<casper_sdk::serializers::borsh::__private::maybestd::vec::Vec<u8>> {
+                                                #input_data_content
+                                            }
+                                        }
+
+                                        #with_defaults_ident {
+                                            #(#required_arg_names,)*
+                                        }
+                                    }
+                                });
+                            }
                         }
                     }
 
@@ -758,6 +1002,7 @@ fn generate_impl_for_contract(
                         arguments: vec![ #(#args,)* ],
                         result: #result,
                         flags: casper_sdk::casper_executor_wasm_common::flags::EntryPointFlags::from_bits(#bits).unwrap(),
+                        docs: #docs.into(),
                     }
                 }
             });
@@ -800,6 +1045,14 @@ fn generate_impl_for_contract(
                     static ENTRY_POINTS: fn() -> casper_sdk::schema::SchemaEntryPoint = <#struct_name>::#defs_linkme;
                 };
             )*
+
+            #(
+                const _: () = {
+                    #[casper_sdk::linkme::distributed_slice(casper_sdk::abi_generator::CONSTANTS)]
+                    #[linkme(crate = casper_sdk::linkme)]
+                    static CONSTANT: fn() -> casper_sdk::schema::SchemaConstant = <#struct_name>::#constants_linkme;
+                };
+            )*
         }
     }
     #[cfg(not(feature = "__abi_generator"))]
@@ -821,6 +1074,13 @@ fn generate_impl_for_contract(
                 #(#defs)*
             }
 
+            // Defined once per impl block, so a contract declaring its entry points across
+            // several `#[casper]` impl blocks fails to compile instead of listing only some.
+            impl #struct_name {
+                /// Names of the entry points exported by this contract.
+                pub const ENTRY_POINT_NAMES: &'static [&'static str] = &[#(#names,)*];
+            }
+
             #maybe_abi_collectors
 
             #maybe_entrypoint_defs
@@ -895,7 +1155,7 @@ fn generate_impl_trait_for_contract(
         quote! {
             const _: () = {
                 macro_rules! visitor {
-                    ($($vis:vis $name:ident as $export_name:ident => $dispatch:ident,)*) => {
+                    ($($vis:vis $name:ident as $export_name:ident => $dispatch:ident [$flags:literal; $record_len:literal; $($record:literal),*],)*) => {
                         $(
                             $vis fn $name() {
                                 #path_to_macro::$dispatch::<#self_ty>();
@@ -911,19 +1171,27 @@ fn generate_impl_trait_for_contract(
         quote! {
             const _: () = {
                 macro_rules! visitor {
-                    ($($vis:vis $name:ident as $export_name:ident => $dispatch:ident,)*) => {
+                    ($($vis:vis $name:ident as $export_name:ident => $dispatch:ident [$flags:literal; $record_len:literal; $($record:literal),*],)*) => {
                         $(
                             #[export_name = stringify!($export_name)]
                             $vis extern "C" fn $name() {
                                 #path_to_macro::$dispatch::<#self_ty>();
                             }
 
+                            #[cfg(target_arch = "wasm32")]
+                            const _: () = {
+                                #[link_section = #MANIFEST_SECTION]
+                                #[used]
+                                static MANIFEST_RECORD: [u8; $record_len] = [$($record,)*];
+                            };
+
                             #[cfg(not(target_arch = "wasm32"))]
                             const _: () = {
                                 #[casper_sdk::linkme::distributed_slice(casper_sdk::casper::native::ENTRY_POINTS)]
                                 #[linkme(crate = casper_sdk::linkme)]
                                 pub static EXPORTS: casper_sdk::casper::native::EntryPoint = casper_sdk::casper::native::EntryPoint {
                                     kind: casper_sdk::casper::native::EntryPointKind::TraitImpl { trait_name: stringify!(#trait_name), impl_name: stringify!(#self_ty), name: stringify!($export_name) },
+                                    flags: Some(casper_sdk::casper_executor_wasm_common::flags::EntryPointFlags::from_bits_retain($flags)),
                                     fptr: || -> () { $name(); },
                                     module_path: module_path!(),
                                     file: file!(),
@@ -941,7 +1209,12 @@ fn generate_impl_trait_for_contract(
 
     code.push(visitor);
 
-    let ref_trait = format_ident!("{}Ext", trait_path.require_ident().unwrap());
+    let mut ref_trait = trait_path.clone();
+    let ref_trait_segment = ref_trait
+        .segments
+        .last_mut()
+        .expect("Expected non-empty path");
+    ref_trait_segment.ident = format_ident!("{}Ext", ref_trait_segment.ident);
 
     let ref_name = format_ident!("{self_ty}Ref");
 
@@ -991,6 +1264,9 @@ fn casper_trait_definition(mut item_trait: ItemTrait, trait_meta: TraitMeta) ->
                 func.attrs.clear();
 
                 if method_attribute.private {
+                    if let Err(error) = method_attribute.check_not_entry_point(&func.sig.ident) {
+                        return TokenStream::from(error.to_compile_error());
+                    }
                     continue;
                 }
 
@@ -1080,9 +1356,32 @@ fn casper_trait_definition(mut item_trait: ItemTrait, trait_meta: TraitMeta) ->
                     });
                 }
 
-                let flags = EntryPointFlags::empty();
+                let mut flags = EntryPointFlags::empty();
 
-                let _flags = flags.bits();
+                if method_attribute.fallback {
+                    flags |= EntryPointFlags::FALLBACK;
+                }
+
+                if method_attribute.payable {
+                    flags |= EntryPointFlags::PAYABLE;
+                }
+
+                let flag_bits = flags.bits();
+
+                // Emitted by each implementing contract, which lets the host read the flags of the
+                // entry point just like for inherent entry points.
+                let manifest_record = encode_manifest_record(&export_name.to_string(), flags);
+                let manifest_record_len = manifest_record.len();
+
+                let check_not_payable = if method_attribute.payable {
+                    quote! {}
+                } else {
+                    quote! {
+                        #crate_path::UnwrapOrRevert::unwrap_or_revert(
+                            #crate_path::casper::assert_not_payable(),
+                        );
+                    }
+                };
 
                 let handle_dispatch = match func.sig.inputs.first() {
                     Some(syn::FnArg::Receiver(_receiver)) => {
@@ -1104,6 +1403,8 @@ fn casper_trait_definition(mut item_trait: ItemTrait, trait_meta: TraitMeta) ->
                                     #(#args_attrs,)*
                                 }
 
+                                #check_not_payable
+
                                 let mut flags = #crate_path::casper_executor_wasm_common::flags::ReturnFlags::empty();
                                 let mut instance: T = #crate_path::casper::read_state().unwrap();
                                 let input = #crate_path::prelude::casper::copy_input();
@@ -1126,6 +1427,8 @@ fn casper_trait_definition(mut item_trait: ItemTrait, trait_meta: TraitMeta) ->
                         );
                         quote! {
                             #vis extern "C"  fn #dispatch_func_name<T: #trait_name>() {
+                                #check_not_payable
+
                                 #[derive(#crate_path::serializers::borsh::BorshDeserialize)]
                                 #[borsh(crate = #borsh_path)]
                                 struct Arguments {
@@ -1144,7 +1447,7 @@ fn casper_trait_definition(mut item_trait: ItemTrait, trait_meta: TraitMeta) ->
                 };
 
                 macro_symbols.push(quote! {
-                    #vis #func_name as #export_name => #dispatch_func_name
+                    #vis #func_name as #export_name => #dispatch_func_name [#flag_bits; #manifest_record_len; #(#manifest_record),*]
                 });
 
                 dispatch_functions.push(quote! { #handle_dispatch });
@@ -1170,8 +1473,8 @@ fn casper_trait_definition(mut item_trait: ItemTrait, trait_meta: TraitMeta) ->
 
                 if !is_fallback {
                     extra_code.push(quote! {
-                    fn #func_name<'a>(#self_ty #(#arg_names: #arg_types,)*) -> impl #crate_path::ToCallData<Return<'a> = #call_data_return_lifetime> {
-                        #[derive(#crate_path::serializers::borsh::BorshSerialize)]
+                    fn #func_name<'a>(#self_ty #(#arg_names: #arg_types,)*) -> impl #crate_path::ToCallData<Return<'a> = #call_data_return_lifetime> + core::fmt::Debug {
+                        #[derive(#crate_path::serializers::borsh::BorshSerialize, Debug)]
                         #[borsh(crate = #borsh_path)]
                         struct CallData {
                             #(pub #arg_names: #arg_types,)*
@@ -1366,7 +1669,12 @@ fn process_casper_contract_state_for_struct(
 
     let maybe_derive_abi = get_maybe_derive_abi(crate_path.clone());
 
-    // Optionally, generate a schema export if the appropriate flag
+    let version = match &struct_meta.version {
+        Some(version) => quote! { #version },
+        None => quote! { env!("CARGO_PKG_VERSION") },
+    };
+
+    // Optionally, generate schema and metadata exports if the appropriate flag
     // is set.
     let maybe_casper_schema = {
         #[cfg(feature = "__embed_schema")]
@@ -1385,6 +1693,45 @@ fn process_casper_contract_state_for_struct(
         quote! {}
     };
 
+    // Unlike the schema, the metadata export is requested explicitly, so it doesn't depend on the
+    // schema being embedded.
+    let maybe_casper_metadata = if struct_meta.export_metadata {
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn __casper_metadata() {
+                use #crate_path::casper::ret;
+                use #crate_path::casper_executor_wasm_common::flags::ReturnFlags;
+                let metadata = <#struct_name as #crate_path::Contract>::metadata();
+                let bytes = #crate_path::serializers::borsh::to_vec(&metadata)
+                    .expect("should serialize contract metadata");
+                ret(ReturnFlags::empty(), Some(&bytes));
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // #[casper(contract_state, impls(Trait, ...))]
+    let trait_impls = struct_meta.impls.iter().map(|trait_path| {
+        let item_impl: ItemImpl = syn::parse_quote! {
+            impl #trait_path for #struct_name {}
+        };
+        // The entry point dispatchers live next to the trait.
+        let module_path = (trait_path.segments.len() > 1).then(|| {
+            let mut module_path = trait_path.clone();
+            module_path.segments.pop();
+            module_path.segments.pop_punct();
+            module_path
+        });
+        let impl_meta = ImplTraitForContractMeta {
+            path: module_path,
+            compile_as_dependency: false,
+        };
+        proc_macro2::TokenStream::from(generate_impl_trait_for_contract(
+            &item_impl, trait_path, impl_meta,
+        ))
+    });
+
     quote! {
         #[derive(#crate_path::serializers::borsh::BorshSerialize, #crate_path::serializers::borsh::BorshDeserialize)]
         #[borsh(crate = #borsh_path)]
@@ -1399,11 +1746,45 @@ fn process_casper_contract_state_for_struct(
             }
         }
 
+        impl #crate_path::Contract for #struct_name {
+            type Ref = #ref_name;
+
+            fn name() -> &'static str {
+                stringify!(#struct_name)
+            }
+
+            fn version() -> &'static str {
+                #version
+            }
+        }
+
+        #(#trait_impls)*
+
         #maybe_casper_schema
+
+        #maybe_casper_metadata
     }
     .into()
 }
 
+/// Returns `T` if `ty` is spelled as `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 #[proc_macro_attribute]
 pub fn entry_point(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let func = parse_macro_input!(item as ItemFn);
@@ -1430,8 +1811,32 @@ pub fn entry_point(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         let ty = &typed.ty;
 
-        let tok = quote! {
-            let #typed = casper_sdk::get_named_arg(stringify!(#name)).expect("should get named arg");
+        // An argument which can't be decoded reverts with the argument name and expected type.
+        let read_arg = |value_ty| {
+            quote! {
+                match casper_sdk::casper::read_named_arg::<#value_ty>(stringify!(#name)) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        casper_sdk::revert!(error);
+                        return;
+                    }
+                }
+            }
+        };
+        let tok = match option_inner_type(ty) {
+            // An absent optional argument is treated as `None`.
+            Some(inner) => {
+                let read_arg = read_arg(inner);
+                quote! {
+                    let #typed = #read_arg;
+                }
+            }
+            None => {
+                let read_arg = read_arg(ty.as_ref());
+                quote! {
+                    let #typed = #read_arg.expect("should get named arg");
+                }
+            }
         };
         handle_args.push(tok);
 
@@ -1471,8 +1876,6 @@ pub fn entry_point(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
-    println!("{gen}");
-
     // quote!(fn foo() {})
     // item
     gen.into()
@@ -1768,6 +2171,22 @@ pub fn blake2b256(input: TokenStream) -> TokenStream {
     })
 }
 
+/// Computes the selector of an entry point at compile time.
+///
+/// The selector is the first 4 bytes of the blake2b256 hash of the entry point name, interpreted
+/// as a little-endian `u32`.
+#[proc_macro]
+pub fn selector(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LitStr);
+    let entry_point = input.value();
+
+    let selector = chain_utils::compute_selector(&entry_point);
+
+    TokenStream::from(quote! {
+        #selector
+    })
+}
+
 #[proc_macro]
 pub fn test(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemFn);
@@ -1777,6 +2196,63 @@ pub fn test(item: TokenStream) -> TokenStream {
     })
 }
 
+/// Declares a test which runs a contract flow end-to-end in a native environment.
+///
+/// The body runs within `dispatch_with`, and may take a single `&TestEnv` argument to create and
+/// fund accounts, or to act as one of them.
+///
+/// ```ignore
+/// #[contract_test]
+/// fn should_create_contract(env: &TestEnv) {
+///     let alice = env.create_account(1000);
+///     env.as_account(alice, || { /* create and call contracts */ }).unwrap();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn contract_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(item as ItemFn);
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = &func;
+    let name = &sig.ident;
+    let output = &sig.output;
+
+    let param = match sig.inputs.len() {
+        0 => quote! { _ },
+        1 => match sig.inputs.first() {
+            Some(syn::FnArg::Typed(typed)) => {
+                let pat = &typed.pat;
+                let ty = &typed.ty;
+                quote! { #pat: #ty }
+            }
+            _ => {
+                return syn::Error::new_spanned(&sig.inputs, "expected a `&TestEnv` argument")
+                    .to_compile_error()
+                    .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &sig.inputs,
+                "contract tests take at most one `&TestEnv` argument",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    TokenStream::from(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #output {
+            casper_sdk::casper::native::TestEnv::new().run(|#param| #block)
+        }
+    })
+}
+
 /// `PanicOnDefault` generates implementation for `Default` trait that panics with the following
 /// message `The contract is not initialized` when `default()` is called.
 ///
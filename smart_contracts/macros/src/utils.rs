@@ -3,3 +3,28 @@ pub(crate) fn compute_blake2b256(bytes: &[u8]) -> [u8; 32] {
     context.update(bytes);
     context.finalize().as_bytes().try_into().unwrap()
 }
+
+/// Joins the text of all `#[doc = "..."]` attributes, one line per attribute.
+#[cfg(feature = "__abi_generator")]
+pub(crate) fn collect_doc_comments(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| match line.strip_prefix(' ') {
+            Some(stripped) => stripped.to_string(),
+            None => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
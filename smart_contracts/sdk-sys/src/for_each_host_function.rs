@@ -25,6 +25,8 @@ macro_rules! for_each_host_function {
             ) -> u32;
             pub fn casper_print(msg_ptr: *const u8, msg_size: usize,);
             pub fn casper_return(flags: u32, data_ptr: *const u8, data_len: usize,);
+            #[doc = r"Abort the execution of the current contract with a message."]
+            pub fn casper_abort(msg_ptr: *const u8, msg_size: usize,);
             pub fn casper_copy_input(
                 alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
                 alloc_ctx: *const core::ffi::c_void,
@@ -41,6 +43,21 @@ macro_rules! for_each_host_function {
                 seed_size: usize,
                 result_ptr: *mut $crate::CreateResult,
             ) -> u32;
+            #[doc = r"Create a contract like `casper_create`, passing the data a reverting constructor returned to the `alloc` callback."]
+            pub fn casper_try_create(
+                code_ptr: *const u8,
+                code_size: usize,
+                transferred_value: u64,
+                constructor_ptr: *const u8,
+                constructor_size: usize,
+                input_ptr: *const u8,
+                input_size: usize,
+                seed_ptr: *const u8,
+                seed_size: usize,
+                result_ptr: *mut $crate::CreateResult,
+                alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8, // For capturing constructor revert data
+                alloc_ctx: *const core::ffi::c_void,
+            ) -> u32;
 
             // We don't offer any special protection against smart contracts on the host side
             pub fn casper_call(
@@ -65,8 +82,20 @@ macro_rules! for_each_host_function {
             #[doc = r"Get balance of an entity by its address."]
             pub fn casper_env_balance(entity_kind: u32, entity_addr_ptr: *const u8, entity_addr_len: usize, output_ptr: *mut core::ffi::c_void,) -> u32;
             pub fn casper_env_info(info_ptr: *const u8, info_size: u32,) -> u32;
+            #[doc = r"Write the 32 byte hash of the currently executing contract's Wasm bytecode."]
+            pub fn casper_self_code_hash(out_ptr: *mut u8, out_size: usize,) -> u32;
+            #[doc = r"Write pseudo-random bytes derived from the transaction hash, the address of the current contract and a counter advanced by the host."]
+            pub fn casper_random_bytes(out_ptr: *mut u8, out_size: usize,) -> u32;
             pub fn casper_transfer(entity_addr_ptr: *const u8, entity_addr_len: usize, amount: *const core::ffi::c_void,) -> u32;
+            #[doc = r"Transfer tokens to an account, recording the transfer under the given id."]
+            pub fn casper_transfer_with_id(entity_addr_ptr: *const u8, entity_addr_len: usize, amount: *const core::ffi::c_void, id: u64,) -> u32;
             pub fn casper_emit(topic_ptr: *const u8, topic_size: usize, payload_ptr: *const u8, payload_size: usize,) -> u32;
+            #[doc = r"Emit a message, writing the index it was assigned among the messages emitted under its topic in the current block."]
+            pub fn casper_emit_with_index(topic_ptr: *const u8, topic_size: usize, payload_ptr: *const u8, payload_size: usize, out_index_ptr: *mut u64,) -> u32;
+            #[doc = r"Register a message topic for the current contract without emitting a message."]
+            pub fn casper_register_topic(topic_ptr: *const u8, topic_size: usize,) -> u32;
+            #[doc = r"Write the number of messages emitted under a topic in the current block."]
+            pub fn casper_message_count(topic_ptr: *const u8, topic_size: usize, out_count_ptr: *mut u64,) -> u32;
         }
     };
 }
@@ -3,7 +3,7 @@ use core::{mem, ptr::NonNull};
 use crate::{
     abi::{Declaration, Definitions},
     linkme::distributed_slice,
-    schema::{Schema, SchemaMessage, SchemaType},
+    schema::{Schema, SchemaConstant, SchemaMessage, SchemaType},
 };
 
 #[derive(Debug)]
@@ -43,6 +43,11 @@ pub static ABI_COLLECTORS: [fn(&mut crate::abi::Definitions)] = [..];
 #[linkme(crate = crate::linkme)]
 pub static MESSAGES: [Message] = [..];
 
+/// Constants declared with `#[casper(const)]` will be registered here.
+#[distributed_slice]
+#[linkme(crate = crate::linkme)]
+pub static CONSTANTS: [fn() -> SchemaConstant] = [..];
+
 /// This function is called by the host to collect the schema from the contract.
 ///
 /// This is considered internal implementation detail and should not be used directly.
@@ -78,6 +83,9 @@ pub unsafe extern "C" fn cargo_casper_collect_schema(size_ptr: *mut u64) -> *mut
         messages
     };
 
+    // Collect constants
+    let constants = CONSTANTS.iter().map(|constant| constant()).collect();
+
     // Collect entrypoints
     let entry_points = {
         let mut entry_points = Vec::new();
@@ -97,6 +105,7 @@ pub unsafe extern "C" fn cargo_casper_collect_schema(size_ptr: *mut u64) -> *mut
         definitions,
         entry_points,
         messages,
+        constants,
     };
 
     // Write the schema using the provided writer
@@ -4,17 +4,20 @@ pub mod native;
 use crate::{
     abi::{CasperABI, EnumVariant},
     prelude::{
+        collections::BTreeMap,
         ffi::c_void,
         marker::PhantomData,
         mem::MaybeUninit,
         ptr::{self, NonNull},
+        string::{String, ToString},
     },
     reserve_vec_space,
-    serializers::borsh::{BorshDeserialize, BorshSerialize},
-    types::{Address, CallError},
-    Message, ToCallData,
+    serializers::borsh::{self, BorshDeserialize, BorshSerialize},
+    types::{AccountHash, Address, CallError, HostError, NamedArgError, Timestamp},
+    ContractHandle, ContractRef, Message, ToCallData, UnwrapOrRevert,
 };
 
+pub use casper_executor_wasm_common::env_info::Phase;
 use casper_executor_wasm_common::{
     env_info::EnvInfo,
     error::{result_from_code, CommonResult, HOST_ERROR_SUCCESS},
@@ -89,6 +92,46 @@ pub fn copy_input_to(dest: &mut [u8]) -> Option<&[u8]> {
     Some(&dest[..length])
 }
 
+/// Returns the value of the named argument `name` if it was passed to the entry point.
+///
+/// Named arguments are passed as the input data, encoded as a borsh map of argument names to the
+/// borsh encoded values. Returns `None` if the argument is absent, which lets entry points treat
+/// optional arguments as not set. An argument which is present but can't be decoded as `T` reverts
+/// with a [`NamedArgError`]; use [`read_named_arg`] to handle that case instead.
+///
+/// # Panics
+///
+/// Panics if the input data is not a named argument map.
+pub fn try_named_arg<T: BorshDeserialize>(name: &str) -> Option<T> {
+    read_named_arg(name).unwrap_or_revert()
+}
+
+/// Reads the named argument `name` from the input data.
+///
+/// Returns `Ok(None)` if the argument is absent, and a [`NamedArgError`] naming the argument and
+/// the expected type if it can't be decoded as `T`.
+///
+/// # Panics
+///
+/// Panics if the input data is not a named argument map.
+pub fn read_named_arg<T: BorshDeserialize>(name: &str) -> Result<Option<T>, NamedArgError> {
+    let input = copy_input();
+    let mut named_args: BTreeMap<String, Vec<u8>> = if input.is_empty() {
+        BTreeMap::new()
+    } else {
+        borsh::from_slice(&input).expect("should decode named args")
+    };
+    let Some(bytes) = named_args.remove(name) else {
+        return Ok(None);
+    };
+    borsh::from_slice(&bytes)
+        .map(Some)
+        .map_err(|_| NamedArgError {
+            name: name.to_string(),
+            expected_type: core::any::type_name::<T>().to_string(),
+        })
+}
+
 /// Return from the contract.
 pub fn ret(flags: ReturnFlags, data: Option<&[u8]>) {
     let (data_ptr, data_len) = match data {
@@ -100,6 +143,18 @@ pub fn ret(flags: ReturnFlags, data: Option<&[u8]>) {
     unreachable!()
 }
 
+/// Abort the execution of the contract with a message.
+///
+/// Unlike a `panic!`, which traps, the message is reported to the caller of the contract. All
+/// changes made by the contract are discarded.
+pub fn abort(message: &str) -> ! {
+    unsafe { casper_sdk_sys::casper_abort(message.as_ptr(), message.len()) };
+    #[cfg(all(not(target_arch = "wasm32"), feature = "std"))]
+    native::unwind_abort();
+    #[cfg(not(all(not(target_arch = "wasm32"), feature = "std")))]
+    unreachable!()
+}
+
 /// Read from the global state.
 pub fn read<F: FnOnce(usize) -> Option<ptr::NonNull<u8>>>(
     key: Keyspace,
@@ -220,6 +275,75 @@ pub fn create(
     }
 }
 
+/// Create a new contract instance, capturing the data the constructor reverted with.
+pub fn try_create(
+    code: Option<&[u8]>,
+    transferred_value: u64,
+    constructor: Option<&str>,
+    input_data: Option<&[u8]>,
+    seed: Option<&[u8; 32]>,
+) -> (
+    Option<Vec<u8>>,
+    Result<casper_sdk_sys::CreateResult, CallError>,
+) {
+    let mut output = None;
+    let result = try_create_into(
+        code,
+        transferred_value,
+        constructor,
+        input_data,
+        seed,
+        Some(|size| {
+            let mut vec = Vec::new();
+            reserve_vec_space(&mut vec, size);
+            let result = Some(unsafe { ptr::NonNull::new_unchecked(vec.as_mut_ptr()) });
+            output = Some(vec);
+            result
+        }),
+    );
+    (output, result)
+}
+
+fn try_create_into<F: FnOnce(usize) -> Option<ptr::NonNull<u8>>>(
+    code: Option<&[u8]>,
+    transferred_value: u64,
+    constructor: Option<&str>,
+    input_data: Option<&[u8]>,
+    seed: Option<&[u8; 32]>,
+    alloc: Option<F>,
+) -> Result<casper_sdk_sys::CreateResult, CallError> {
+    let (code_ptr, code_size): (*const u8, usize) = match code {
+        Some(code) => (code.as_ptr(), code.len()),
+        None => (ptr::null(), 0),
+    };
+
+    let mut result = MaybeUninit::uninit();
+
+    let call_error = unsafe {
+        casper_sdk_sys::casper_try_create(
+            code_ptr,
+            code_size,
+            transferred_value,
+            constructor.map(|s| s.as_ptr()).unwrap_or(ptr::null()),
+            constructor.map(|s| s.len()).unwrap_or(0),
+            input_data.map(|s| s.as_ptr()).unwrap_or(ptr::null()),
+            input_data.map(|s| s.len()).unwrap_or(0),
+            seed.map(|s| s.as_ptr()).unwrap_or(ptr::null()),
+            seed.map(|s| s.len()).unwrap_or(0),
+            result.as_mut_ptr(),
+            alloc_callback::<F>,
+            &alloc as *const _ as *mut _,
+        )
+    };
+
+    if call_error == 0 {
+        let result = unsafe { result.assume_init() };
+        Ok(result)
+    } else {
+        Err(CallError::try_from(call_error).expect("Unexpected error code"))
+    }
+}
+
 pub(crate) fn call_into<F: FnOnce(usize) -> Option<ptr::NonNull<u8>>>(
     address: &Address,
     transferred_value: u64,
@@ -343,6 +467,8 @@ pub fn write_state<T: BorshSerialize>(state: &T) -> Result<(), CommonResult> {
 pub struct CallResult<T: ToCallData> {
     pub data: Option<Vec<u8>>,
     pub result: Result<(), CallError>,
+    /// Error raised by the callee, such as the message it aborted with.
+    pub host_error: Option<HostError>,
     pub marker: PhantomData<T>,
 }
 
@@ -363,6 +489,15 @@ impl<T: ToCallData> CallResult<T> {
     pub fn did_revert(&self) -> bool {
         self.result == Err(CallError::CalleeReverted)
     }
+
+    /// Returns the message the callee aborted with, or `None` if it didn't abort.
+    #[must_use]
+    pub fn abort_message(&self) -> Option<&str> {
+        match &self.host_error {
+            Some(HostError::Aborted(message)) => Some(message),
+            _ => None,
+        }
+    }
 }
 
 /// Call a contract.
@@ -383,6 +518,14 @@ pub fn call<T: ToCallData>(
         Ok(()) | Err(CallError::CalleeReverted) => Ok(CallResult::<T> {
             data: maybe_data,
             result: result_code,
+            host_error: None,
+            marker: PhantomData,
+        }),
+        // The host passes the error of an aborted callee in place of its output.
+        Err(CallError::CalleeAborted) => Ok(CallResult::<T> {
+            data: None,
+            result: result_code,
+            host_error: maybe_data.and_then(|data| borsh::from_slice(&data).ok()),
             marker: PhantomData,
         }),
         Err(error) => Err(error),
@@ -407,6 +550,23 @@ pub fn get_env_info() -> EnvInfo {
     }
 }
 
+/// Returns the hash of the Wasm bytecode of the currently executing contract.
+///
+/// The hash covers the bytecode currently stored for the contract, so an upgraded contract
+/// reports the hash of its new code. This lets a contract tell which version of itself is running.
+///
+/// # Panics
+///
+/// Panics if called from session code, which has no stored bytecode.
+#[must_use]
+pub fn self_code_hash() -> [u8; 32] {
+    let mut code_hash = [0; 32];
+    let ret =
+        unsafe { casper_sdk_sys::casper_self_code_hash(code_hash.as_mut_ptr(), code_hash.len()) };
+    result_from_code(ret).expect("should get code hash of a contract");
+    code_hash
+}
+
 /// Get the caller.
 #[must_use]
 pub fn get_caller() -> Entity {
@@ -420,6 +580,13 @@ pub fn get_callee() -> Entity {
     Entity::from_parts(info.callee_kind, info.callee_addr).expect("Invalid callee kind")
 }
 
+/// Get the phase in which the current code is executing.
+#[must_use]
+pub fn get_phase() -> Phase {
+    let info = get_env_info();
+    Phase::from_tag(info.phase).expect("Invalid phase")
+}
+
 /// Enum representing either an account or a contract.
 #[derive(
     BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
@@ -456,6 +623,30 @@ impl Entity {
     }
 }
 
+impl From<AccountHash> for Entity {
+    fn from(account_hash: AccountHash) -> Self {
+        Entity::Account(account_hash.value())
+    }
+}
+
+impl TryFrom<Entity> for AccountHash {
+    /// The original entity is returned if it is not an account.
+    type Error = Entity;
+
+    fn try_from(entity: Entity) -> Result<Self, Self::Error> {
+        match entity {
+            Entity::Account(address) => Ok(AccountHash::new(address)),
+            Entity::Contract(_) => Err(entity),
+        }
+    }
+}
+
+impl<T: ContractRef> From<ContractHandle<T>> for Entity {
+    fn from(contract_handle: ContractHandle<T>) -> Self {
+        Entity::Contract(contract_handle.contract_address())
+    }
+}
+
 impl CasperABI for Entity {
     fn populate_definitions(definitions: &mut crate::abi::Definitions) {
         definitions.populate_one::<[u8; 32]>();
@@ -513,6 +704,18 @@ pub fn transferred_value() -> u64 {
     info.transferred_value
 }
 
+/// Checks that no tokens were transferred to the current entry point.
+///
+/// Entry points not marked as `payable` call this before running, and revert with the returned
+/// error instead of trapping. Returns [`HostError::NotPayable`] if a non-zero value was
+/// transferred.
+pub fn assert_not_payable() -> Result<(), HostError> {
+    if transferred_value() != 0 {
+        return Err(HostError::NotPayable);
+    }
+    Ok(())
+}
+
 /// Transfer tokens from the current contract to another account or contract.
 pub fn transfer(target_account: &Address, amount: u64) -> Result<(), CallError> {
     let amount: *const c_void = &amount as *const _ as *const c_void;
@@ -522,6 +725,23 @@ pub fn transfer(target_account: &Address, amount: u64) -> Result<(), CallError>
     call_result_from_code(result_code)
 }
 
+/// Transfer tokens from the current contract to another account, recording the transfer under
+/// `id`.
+///
+/// The id is an arbitrary memo, commonly used by exchanges to match incoming transfers.
+pub fn transfer_with_id(target_account: &Address, amount: u64, id: u64) -> Result<(), CallError> {
+    let amount: *const c_void = &amount as *const _ as *const c_void;
+    let result_code = unsafe {
+        casper_sdk_sys::casper_transfer_with_id(
+            target_account.as_ptr(),
+            target_account.len(),
+            amount,
+            id,
+        )
+    };
+    call_result_from_code(result_code)
+}
+
 /// Get the current block time.
 #[inline]
 pub fn get_block_time() -> u64 {
@@ -529,6 +749,16 @@ pub fn get_block_time() -> u64 {
     info.block_time
 }
 
+/// Get the current block time as a [`Timestamp`].
+///
+/// This is the same value as returned by [`get_block_time`], i.e. the number of milliseconds since
+/// the Unix epoch.
+#[inline]
+#[must_use]
+pub fn block_timestamp() -> Timestamp {
+    Timestamp::from_millis(get_block_time())
+}
+
 #[doc(hidden)]
 pub fn emit_raw(topic: &str, payload: &[u8]) -> Result<(), CommonResult> {
     let ret = unsafe {
@@ -537,12 +767,136 @@ pub fn emit_raw(topic: &str, payload: &[u8]) -> Result<(), CommonResult> {
     result_from_code(ret)
 }
 
+/// Registers a message topic for the current contract.
+///
+/// Topics are otherwise created lazily by the first message emitted under them, so registering
+/// them upfront, e.g. in a constructor, surfaces a violation of the configured message limits
+/// before any message is emitted. Registering an existing topic is a no-op.
+///
+/// Returns [`CommonResult::TopicTooLong`] if the name exceeds the maximum topic name size, and
+/// [`CommonResult::TooManyTopics`] if the contract already has the maximum number of topics.
+pub fn register_topic(name: &str) -> Result<(), CommonResult> {
+    let ret = unsafe { casper_sdk_sys::casper_register_topic(name.as_ptr(), name.len()) };
+    result_from_code(ret)
+}
+
+/// Returns the number of messages the current contract emitted under `topic` in the current
+/// block.
+///
+/// The count is maintained by the host for every message, including those emitted with
+/// [`emit_raw`], and starts over whenever the block time changes.
+pub fn message_count(topic: &str) -> Result<u64, CommonResult> {
+    let mut count = MaybeUninit::<u64>::uninit();
+    let ret = unsafe {
+        casper_sdk_sys::casper_message_count(topic.as_ptr(), topic.len(), count.as_mut_ptr())
+    };
+    match result_from_code(ret) {
+        Ok(()) => Ok(unsafe { count.assume_init() }),
+        Err(CommonResult::NotFound) => Ok(0),
+        Err(error) => Err(error),
+    }
+}
+
 /// Emit a message.
+///
+/// Messages are pub/sub only: they are published under the contract's topic for off-chain
+/// consumers and cannot be addressed to, or read back by, another contract. Use
+/// [`message_count`] to find out how many messages were emitted under a topic in the current
+/// block, or [`emit_with_index`] to learn the index assigned to the message.
+///
+/// A topic that was not registered with [`register_topic`] is created by the first message
+/// emitted under it.
 pub fn emit<M>(message: M) -> Result<(), CommonResult>
+where
+    M: Message,
+{
+    emit_raw(M::TOPIC, &message.payload())
+}
+
+/// Emit a message, returning its index among the messages the current contract emitted under the
+/// same topic in the current block.
+///
+/// The index is the one the host assigned to the published message, and starts at zero in every
+/// block, so indexers can correlate an event with the message published for it. See [`emit`] for
+/// the topic rules.
+pub fn emit_with_index<M>(message: M) -> Result<u64, CommonResult>
 where
     M: Message,
 {
     let topic = M::TOPIC;
     let payload = message.payload();
-    emit_raw(topic, &payload)
+    let mut index = MaybeUninit::<u64>::uninit();
+    let ret = unsafe {
+        casper_sdk_sys::casper_emit_with_index(
+            topic.as_ptr(),
+            topic.len(),
+            payload.as_ptr(),
+            payload.len(),
+            index.as_mut_ptr(),
+        )
+    };
+    result_from_code(ret)?;
+    Ok(unsafe { index.assume_init() })
+}
+
+/// Returns `len` pseudo-random bytes.
+///
+/// The bytes are derived from the hash of the current transaction, the address of the current
+/// contract and a counter the host advances on every call. Each call therefore returns different
+/// bytes, while re-executing the same transaction reproduces them exactly, as required for
+/// consensus.
+///
+/// **This is NOT secure randomness.** The transaction hash is known before the transaction is
+/// executed, so anyone can predict the returned bytes. Do not use them for lotteries, key
+/// generation or anything else where an adversary benefits from knowing the outcome.
+///
+/// # Panics
+///
+/// Panics if `len` exceeds
+/// [`MAX_RANDOM_BYTES`](casper_executor_wasm_common::chain_utils::MAX_RANDOM_BYTES).
+#[must_use]
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut output = vec![0; len];
+    let ret = unsafe { casper_sdk_sys::casper_random_bytes(output.as_mut_ptr(), output.len()) };
+    result_from_code(ret).expect("should get random bytes");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DummyRef;
+
+    impl ContractRef for DummyRef {
+        fn new() -> Self {
+            DummyRef
+        }
+    }
+
+    #[test]
+    fn account_hash_should_convert_to_account_entity() {
+        let account_hash = AccountHash::new([1; 32]);
+        assert_eq!(Entity::from(account_hash), Entity::Account([1; 32]));
+    }
+
+    #[test]
+    fn account_entity_should_convert_to_account_hash() {
+        assert_eq!(
+            AccountHash::try_from(Entity::Account([2; 32])),
+            Ok(AccountHash::new([2; 32]))
+        );
+    }
+
+    #[test]
+    fn contract_entity_should_not_convert_to_account_hash() {
+        let entity = Entity::Contract([3; 32]);
+        assert_eq!(AccountHash::try_from(entity), Err(entity));
+    }
+
+    #[test]
+    fn contract_handle_should_convert_to_contract_entity() {
+        let handle = ContractHandle::<DummyRef>::from_address([4; 32]);
+        assert_eq!(Entity::from(handle), Entity::Contract([4; 32]));
+    }
 }
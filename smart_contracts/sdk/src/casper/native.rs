@@ -12,18 +12,25 @@ use std::{
 use crate::linkme::distributed_slice;
 use bytes::Bytes;
 use casper_executor_wasm_common::{
+    chain_utils,
     env_info::EnvInfo,
     error::{
-        CALLEE_REVERTED, CALLEE_SUCCEEDED, CALLEE_TRAPPED, HOST_ERROR_INTERNAL,
-        HOST_ERROR_NOT_FOUND, HOST_ERROR_SUCCESS,
+        HostError, CALLEE_ABORTED, CALLEE_NOT_CALLABLE, CALLEE_NOT_PAYABLE, CALLEE_REVERTED,
+        CALLEE_SUCCEEDED, CALLEE_TRAPPED, HOST_ERROR_INTERNAL, HOST_ERROR_INVALID_DATA,
+        HOST_ERROR_INVALID_INPUT, HOST_ERROR_NOT_FOUND, HOST_ERROR_PAYLOAD_TOO_LONG,
+        HOST_ERROR_SUCCESS, HOST_ERROR_TOO_MANY_TOPICS, HOST_ERROR_TOPIC_TOO_LONG,
     },
-    flags::ReturnFlags,
+    flags::{EntryPointFlags, ReturnFlags},
+    keyspace::{Keyspace, KeyspaceTag},
 };
 #[cfg(not(target_arch = "wasm32"))]
 use rand::Rng;
 
-use super::Entity;
-use crate::types::Address;
+use super::{Entity, Phase};
+use crate::{
+    serializers::borsh::{self, BorshDeserialize},
+    types::Address,
+};
 
 /// The kind of export that is being registered.
 ///
@@ -86,6 +93,9 @@ impl EntryPointKind {
 pub struct EntryPoint {
     /// The kind of entry point that is being registered.
     pub kind: EntryPointKind,
+    /// Flags the manifest declares for the entry point, or `None` if it isn't listed in the
+    /// manifest.
+    pub flags: Option<EntryPointFlags>,
     pub fptr: fn() -> (),
     pub module_path: &'static str,
     pub file: &'static str,
@@ -100,6 +110,7 @@ impl fmt::Debug for EntryPoint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
             kind,
+            flags,
             fptr: _,
             module_path,
             file,
@@ -108,6 +119,7 @@ impl fmt::Debug for EntryPoint {
 
         f.debug_struct("Export")
             .field("kind", kind)
+            .field("flags", flags)
             .field("fptr", &"<fptr>")
             .field("module_path", module_path)
             .field("file", file)
@@ -139,6 +151,7 @@ pub fn invoke_export_by_name(name: &str) {
 pub enum NativeTrap {
     Return(ReturnFlags, Bytes),
     Panic(Box<dyn std::any::Any + Send + 'static>),
+    Abort(String),
 }
 
 pub type Container = BTreeMap<u64, BTreeMap<Bytes, Bytes>>;
@@ -156,14 +169,71 @@ impl From<&casper_sdk_sys::Param> for NativeParam {
     }
 }
 
+/// Message limits enforced by the native environment.
+///
+/// These mirror the `[wasm.messages_limits]` section of the chainspec, and default to the values
+/// used by the production chainspec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageLimits {
+    /// Maximum size of a topic name in bytes.
+    pub max_topic_name_size: usize,
+    /// Maximum number of topics a contract can register.
+    pub max_topics_per_contract: usize,
+    /// Maximum size of a message payload in bytes.
+    pub max_message_size: usize,
+}
+
+impl Default for MessageLimits {
+    fn default() -> Self {
+        Self {
+            max_topic_name_size: 256,
+            max_topics_per_contract: 128,
+            max_message_size: 1_024,
+        }
+    }
+}
+
+/// A message topic of a contract in the native environment.
+#[derive(Clone, Copy, Debug, Default)]
+struct NativeMessageTopic {
+    /// Block time of the last message emitted under the topic.
+    block_time: u64,
+    /// Number of messages emitted under the topic at `block_time`.
+    message_count: u64,
+}
+
+/// A token transfer recorded by the native environment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NativeTransfer {
+    /// Entity that initiated the transfer.
+    pub from: Entity,
+    /// Account the tokens were transferred to.
+    pub to: Address,
+    /// Amount of tokens transferred.
+    pub amount: u64,
+    /// Optional transfer id.
+    pub id: Option<u64>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Environment {
     pub db: Arc<RwLock<Container>>,
     contracts: Arc<RwLock<BTreeSet<Address>>>,
+    transfers: Arc<RwLock<Vec<NativeTransfer>>>,
+    balances: Arc<RwLock<BTreeMap<Entity, u64>>>,
+    message_topics: Arc<RwLock<BTreeMap<Entity, BTreeMap<String, NativeMessageTopic>>>>,
+    message_limits: MessageLimits,
     // input_data: Arc<RwLock<Option<Bytes>>>,
     input_data: Option<Bytes>,
+    transferred_value: u64,
     caller: Entity,
     callee: Entity,
+    phase: Phase,
+    block_time: u64,
+    transaction_hash: [u8; 32],
+    /// Counter of the pseudo-random byte requests, shared with nested calls.
+    random_counter: Arc<RwLock<u64>>,
+    code_hashes: BTreeMap<Address, [u8; 32]>,
 }
 
 impl Default for Environment {
@@ -171,9 +241,19 @@ impl Default for Environment {
         Self {
             db: Default::default(),
             contracts: Default::default(),
+            transfers: Default::default(),
+            balances: Default::default(),
+            message_topics: Default::default(),
+            message_limits: MessageLimits::default(),
             input_data: Default::default(),
+            transferred_value: 0,
             caller: DEFAULT_ADDRESS,
             callee: DEFAULT_ADDRESS,
+            phase: Phase::Session,
+            block_time: 0,
+            transaction_hash: [0; 32],
+            random_counter: Default::default(),
+            code_hashes: BTreeMap::new(),
         }
     }
 }
@@ -186,9 +266,19 @@ impl Environment {
         Self {
             db: Arc::new(RwLock::new(db)),
             contracts: Default::default(),
+            transfers: Default::default(),
+            balances: Default::default(),
+            message_topics: Default::default(),
+            message_limits: MessageLimits::default(),
             input_data: Default::default(),
+            transferred_value: 0,
             caller,
             callee: caller,
+            phase: Phase::Session,
+            block_time: 0,
+            transaction_hash: [0; 32],
+            random_counter: Default::default(),
+            code_hashes: BTreeMap::new(),
         }
     }
 
@@ -222,12 +312,93 @@ impl Environment {
         env
     }
 
+    #[must_use]
+    pub fn with_phase(&self, phase: Phase) -> Self {
+        let mut env = self.clone();
+        env.phase = phase;
+        env
+    }
+
+    /// Sets the block time, in milliseconds since the Unix epoch, reported to the contract.
+    #[must_use]
+    pub fn with_block_time(&self, block_time: u64) -> Self {
+        let mut env = self.clone();
+        env.block_time = block_time;
+        env
+    }
+
+    /// Sets the hash of the transaction being executed, which seeds the pseudo-random bytes
+    /// returned to the contract.
+    #[must_use]
+    pub fn with_transaction_hash(&self, transaction_hash: [u8; 32]) -> Self {
+        let mut env = self.clone();
+        env.transaction_hash = transaction_hash;
+        env
+    }
+
+    /// Sets the bytecode hash reported to the contract at `address`.
+    ///
+    /// There is no Wasm code in native mode, so a contract has a code hash only if it was set
+    /// here.
+    #[must_use]
+    pub fn with_code_hash(&self, address: Address, code_hash: [u8; 32]) -> Self {
+        let mut env = self.clone();
+        env.code_hashes.insert(address, code_hash);
+        env
+    }
+
+    /// Sets the message limits enforced when registering topics and emitting messages.
+    #[must_use]
+    pub fn with_message_limits(&self, message_limits: MessageLimits) -> Self {
+        let mut env = self.clone();
+        env.message_limits = message_limits;
+        env
+    }
+
     #[must_use]
     pub fn with_input_data(&self, input_data: Vec<u8>) -> Self {
         let mut env = self.clone();
         env.input_data = Some(Bytes::from(input_data));
         env
     }
+
+    /// Returns the transfers made so far, in order.
+    #[must_use]
+    pub fn transfers(&self) -> Vec<NativeTransfer> {
+        self.transfers.read().unwrap().clone()
+    }
+
+    /// Sets the balance reported for `entity`.
+    ///
+    /// Balances are shared by all environments derived from this one. Value transferred when
+    /// creating or calling a contract, or with `casper::transfer`, moves between them.
+    pub fn set_balance(&self, entity: Entity, balance: u64) {
+        self.balances.write().unwrap().insert(entity, balance);
+    }
+
+    /// Returns the balance of `entity`, or `None` if it was never set.
+    #[must_use]
+    pub fn balance_of(&self, entity: &Entity) -> Option<u64> {
+        self.balances.read().unwrap().get(entity).copied()
+    }
+
+    /// Moves `amount` from the callee's balance to `target`'s balance.
+    ///
+    /// Returns `false`, leaving both balances unchanged, if the callee cannot afford it.
+    fn transfer_value(&self, target: Entity, amount: u64) -> bool {
+        if amount == 0 {
+            return true;
+        }
+        let mut balances = self.balances.write().unwrap();
+        let source_balance = balances.get(&self.callee).copied().unwrap_or_default();
+        let Some(source_balance) = source_balance.checked_sub(amount) else {
+            return false;
+        };
+        balances.insert(self.callee, source_balance);
+        let target_balance = balances.entry(target).or_default();
+        *target_balance = target_balance.saturating_add(amount);
+        true
+    }
 }
 
 impl Environment {
@@ -322,8 +493,10 @@ impl Environment {
         let key_bytes = self.key_prefix(key_bytes);
 
         let mut db = self.db.write().unwrap();
-        if let Some(values) = db.get_mut(&key_space) {
-            values.remove(key_bytes.as_slice());
+        let removed = db
+            .get_mut(&key_space)
+            .and_then(|values| values.remove(key_bytes.as_slice()));
+        if removed.is_some() {
             Ok(HOST_ERROR_SUCCESS)
         } else {
             Ok(HOST_ERROR_NOT_FOUND)
@@ -352,6 +525,12 @@ impl Environment {
         Err(NativeTrap::Return(return_flags, data))
     }
 
+    fn casper_abort(&self, msg_ptr: *const u8, msg_size: usize) -> Result<Infallible, NativeTrap> {
+        let msg_bytes = unsafe { slice::from_raw_parts(msg_ptr, msg_size) };
+        let msg = String::from_utf8_lossy(msg_bytes).into_owned();
+        Err(NativeTrap::Abort(msg))
+    }
+
     fn casper_copy_input(
         &self,
         alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
@@ -391,6 +570,72 @@ impl Environment {
         seed_ptr: *const u8,
         seed_size: usize,
         result_ptr: *mut casper_sdk_sys::CreateResult,
+    ) -> Result<u32, NativeTrap> {
+        self.create_contract(
+            code_ptr,
+            code_size,
+            transferred_value,
+            constructor_ptr,
+            constructor_size,
+            input_ptr,
+            input_size,
+            seed_ptr,
+            seed_size,
+            result_ptr,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn casper_try_create(
+        &self,
+        code_ptr: *const u8,
+        code_size: usize,
+        transferred_value: u64,
+        constructor_ptr: *const u8,
+        constructor_size: usize,
+        input_ptr: *const u8,
+        input_size: usize,
+        seed_ptr: *const u8,
+        seed_size: usize,
+        result_ptr: *mut casper_sdk_sys::CreateResult,
+        alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
+        alloc_ctx: *const core::ffi::c_void,
+    ) -> Result<u32, NativeTrap> {
+        self.create_contract(
+            code_ptr,
+            code_size,
+            transferred_value,
+            constructor_ptr,
+            constructor_size,
+            input_ptr,
+            input_size,
+            seed_ptr,
+            seed_size,
+            result_ptr,
+            Some((alloc, alloc_ctx)),
+        )
+    }
+
+    /// Creates a contract and calls its constructor, if any. Data returned by a reverting
+    /// constructor is passed to `alloc`, if given.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn create_contract(
+        &self,
+        code_ptr: *const u8,
+        code_size: usize,
+        transferred_value: u64,
+        constructor_ptr: *const u8,
+        constructor_size: usize,
+        input_ptr: *const u8,
+        input_size: usize,
+        seed_ptr: *const u8,
+        seed_size: usize,
+        result_ptr: *mut casper_sdk_sys::CreateResult,
+        alloc: Option<(
+            extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
+            *const core::ffi::c_void,
+        )>,
     ) -> Result<u32, NativeTrap> {
         // let manifest =
         //     NonNull::new(manifest_ptr as *mut casper_sdk_sys::Manifest).expect("Manifest
@@ -417,28 +662,41 @@ impl Environment {
             Some(unsafe { slice::from_raw_parts(input_ptr, input_size) })
         };
 
-        let _seed = if seed_ptr.is_null() {
+        let seed = if seed_ptr.is_null() {
             None
         } else {
             Some(unsafe { slice::from_raw_parts(seed_ptr, seed_size) })
         };
 
-        assert_eq!(
-            transferred_value, 0,
-            "Creating new contracts with transferred value is not supported in native mode"
-        );
-
         let mut rng = rand::thread_rng();
-        let contract_address = rng.gen();
-        let package_address = rng.gen();
+        let package_address = match seed {
+            // There is no chain nor Wasm code in native mode, so a seeded address is predicted
+            // with an empty chain name and empty bytecode.
+            Some(seed) => crate::predict_address(
+                "",
+                self.callee.address(),
+                &[],
+                Some(seed.try_into().expect("Seed should be 32 bytes")),
+            ),
+            None => rng.gen(),
+        };
 
         let mut result = NonNull::new(result_ptr).expect("Valid pointer");
         unsafe {
             result.as_mut().contract_address = package_address;
         }
 
-        let mut contracts = self.contracts.write().unwrap();
-        contracts.insert(contract_address);
+        // Value transferred by the constructor itself is rolled back along with the attached
+        // value.
+        let balances = self.balances.read().unwrap().clone();
+
+        // Insufficient funds are reported the same way the mint failure is on chain, before the
+        // contract is registered.
+        if !self.transfer_value(Entity::Contract(package_address), transferred_value) {
+            return Ok(CALLEE_REVERTED);
+        }
+
+        self.contracts.write().unwrap().insert(package_address);
 
         if let Some(entry_point) = constructor {
             let entry_point = ENTRY_POINTS
@@ -448,6 +706,7 @@ impl Environment {
 
             let mut stub = with_current_environment(|stub| stub);
             stub.input_data = input_data.map(Bytes::copy_from_slice);
+            stub.transferred_value = transferred_value;
 
             stub.caller = stub.callee;
             stub.callee = Entity::Contract(package_address);
@@ -456,20 +715,48 @@ impl Environment {
             // Call constructor, expect a trap
             let result = dispatch_with(stub, || {
                 // TODO: Handle panic inside constructor
-                (entry_point.fptr)();
+                dispatch_export_call(|| {
+                    (entry_point.fptr)();
+                })
             });
 
-            match result {
-                Ok(()) => {}
+            let unfolded = match result {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(error)) | Err(error) => Err(error),
+            };
+
+            let result = match unfolded {
+                Ok(()) => CALLEE_SUCCEEDED,
                 Err(NativeTrap::Return(flags, bytes)) => {
                     if flags.contains(ReturnFlags::REVERT) {
-                        todo!("Constructor returned with a revert flag");
+                        let ptr = alloc.and_then(|(alloc, alloc_ctx)| {
+                            NonNull::new(alloc(bytes.len(), alloc_ctx.cast_mut()))
+                        });
+                        if let Some(output_ptr) = ptr {
+                            unsafe {
+                                ptr::copy_nonoverlapping(
+                                    bytes.as_ptr(),
+                                    output_ptr.as_ptr(),
+                                    bytes.len(),
+                                );
+                            }
+                        }
+                        CALLEE_REVERTED
+                    } else {
+                        assert!(bytes.is_empty(), "When returning from the constructor it is expected that no bytes are passed in a return function");
+                        CALLEE_SUCCEEDED
                     }
-                    assert!(bytes.is_empty(), "When returning from the constructor it is expected that no bytes are passed in a return function");
-                }
-                Err(NativeTrap::Panic(_panic)) => {
-                    todo!();
                 }
+                Err(NativeTrap::Abort(_message)) => CALLEE_ABORTED,
+                Err(NativeTrap::Panic(_panic)) => CALLEE_TRAPPED,
+            };
+
+            if result != CALLEE_SUCCEEDED {
+                // The constructor failed, so the contract is not created and the attached value
+                // goes back to the creator.
+                self.contracts.write().unwrap().remove(&package_address);
+                *self.balances.write().unwrap() = balances;
+                return Ok(result);
             }
         }
 
@@ -500,11 +787,6 @@ impl Environment {
             entry_point.to_string()
         };
 
-        assert_eq!(
-            transferred_value, 0,
-            "Transferred value is not supported in native mode"
-        );
-
         let export = ENTRY_POINTS
             .iter()
             .find(|export|
@@ -513,10 +795,27 @@ impl Environment {
             )
             .expect("Existing entry point");
 
+        if transferred_value != 0
+            && export
+                .flags
+                .is_some_and(|flags| !flags.contains(EntryPointFlags::PAYABLE))
+        {
+            return Ok(CALLEE_NOT_PAYABLE);
+        }
+
+        // Value transferred by the callee itself is rolled back along with the attached value.
+        let balances = self.balances.read().unwrap().clone();
+
+        let callee = Entity::Contract(address.try_into().expect("Size to match"));
+        if !self.transfer_value(callee, transferred_value) {
+            return Ok(CALLEE_REVERTED);
+        }
+
         let mut new_stub = with_current_environment(|stub| stub.clone());
         new_stub.input_data = Some(Bytes::copy_from_slice(input_data));
+        new_stub.transferred_value = transferred_value;
         new_stub.caller = new_stub.callee;
-        new_stub.callee = Entity::Contract(address.try_into().expect("Size to match"));
+        new_stub.callee = callee;
 
         let ret = dispatch_with(new_stub, || {
             // We need to convert any panic inside the entry point into a native trap. This probably
@@ -531,27 +830,39 @@ impl Environment {
             Ok(Err(error)) | Err(error) => Err(error),
         };
 
-        match unfolded {
-            Ok(()) => Ok(CALLEE_SUCCEEDED),
+        let (result, output) = match unfolded {
+            Ok(()) => (CALLEE_SUCCEEDED, None),
             Err(NativeTrap::Return(flags, bytes)) => {
-                let ptr = NonNull::new(alloc(bytes.len(), alloc_ctx.cast_mut()));
-                if let Some(output_ptr) = ptr {
-                    unsafe {
-                        ptr::copy_nonoverlapping(bytes.as_ptr(), output_ptr.as_ptr(), bytes.len());
-                    }
-                }
-
                 if flags.contains(ReturnFlags::REVERT) {
-                    Ok(CALLEE_REVERTED)
+                    (CALLEE_REVERTED, Some(bytes))
                 } else {
-                    Ok(CALLEE_SUCCEEDED)
+                    (CALLEE_SUCCEEDED, Some(bytes))
                 }
             }
-            Err(NativeTrap::Panic(panic)) => {
-                eprintln!("Panic {panic:?}");
-                Ok(CALLEE_TRAPPED)
+            Err(NativeTrap::Panic(_panic)) => (CALLEE_TRAPPED, None),
+            // As on chain, the error is passed along in place of the output of the call.
+            Err(NativeTrap::Abort(message)) => {
+                let host_error = borsh::to_vec(&HostError::Aborted(message))
+                    .expect("Host error should serialize");
+                (CALLEE_ABORTED, Some(Bytes::from(host_error)))
+            }
+        };
+
+        if let Some(bytes) = output {
+            let ptr = NonNull::new(alloc(bytes.len(), alloc_ctx.cast_mut()));
+            if let Some(output_ptr) = ptr {
+                unsafe {
+                    ptr::copy_nonoverlapping(bytes.as_ptr(), output_ptr.as_ptr(), bytes.len());
+                }
             }
         }
+
+        if result != CALLEE_SUCCEEDED {
+            // The callee failed, so the value attached to the call goes back to the caller.
+            *self.balances.write().unwrap() = balances;
+        }
+
+        Ok(result)
     }
 
     #[doc = r"Obtain data from the blockchain environemnt of current wasm invocation.
@@ -576,6 +887,227 @@ Example paths:
         todo!()
     }
 
+    fn casper_transfer(
+        &self,
+        entity_addr_ptr: *const u8,
+        entity_addr_len: usize,
+        amount: *const core::ffi::c_void,
+        id: Option<u64>,
+    ) -> Result<u32, NativeTrap> {
+        let Ok(to) =
+            <Address>::try_from(unsafe { slice::from_raw_parts(entity_addr_ptr, entity_addr_len) })
+        else {
+            return Ok(CALLEE_NOT_CALLABLE);
+        };
+        let amount = unsafe { ptr::read_unaligned(amount as *const u64) };
+
+        let target = if self.contracts.read().unwrap().contains(&to) {
+            Entity::Contract(to)
+        } else {
+            Entity::Account(to)
+        };
+        // Insufficient funds are reported the same way the mint failure is on chain.
+        if !self.transfer_value(target, amount) {
+            return Ok(CALLEE_REVERTED);
+        }
+
+        self.transfers.write().unwrap().push(NativeTransfer {
+            from: self.callee,
+            to,
+            amount,
+            id,
+        });
+        Ok(HOST_ERROR_SUCCESS)
+    }
+
+    fn casper_random_bytes(&self, out_ptr: *mut u8, out_size: usize) -> Result<u32, NativeTrap> {
+        if out_size > chain_utils::MAX_RANDOM_BYTES {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+        let counter = {
+            let mut random_counter = self.random_counter.write().unwrap();
+            let counter = *random_counter;
+            *random_counter += 1;
+            counter
+        };
+        let output = unsafe { slice::from_raw_parts_mut(out_ptr, out_size) };
+        chain_utils::fill_pseudo_random_bytes(
+            self.transaction_hash,
+            *self.callee.address(),
+            counter,
+            output,
+        );
+        Ok(HOST_ERROR_SUCCESS)
+    }
+
+    fn casper_emit(
+        &self,
+        topic_ptr: *const u8,
+        topic_size: usize,
+        _data_ptr: *const u8,
+        data_size: usize,
+    ) -> Result<u32, NativeTrap> {
+        match self.emit_message(topic_ptr, topic_size, data_size) {
+            Ok(_topic_message_index) => Ok(HOST_ERROR_SUCCESS),
+            Err(host_error) => Ok(host_error),
+        }
+    }
+
+    fn casper_emit_with_index(
+        &self,
+        topic_ptr: *const u8,
+        topic_size: usize,
+        _data_ptr: *const u8,
+        data_size: usize,
+        out_index_ptr: *mut u64,
+    ) -> Result<u32, NativeTrap> {
+        match self.emit_message(topic_ptr, topic_size, data_size) {
+            Ok(topic_message_index) => {
+                unsafe { out_index_ptr.write_unaligned(topic_message_index) };
+                Ok(HOST_ERROR_SUCCESS)
+            }
+            Err(host_error) => Ok(host_error),
+        }
+    }
+
+    /// Emits a message, returning the index it was assigned among the messages emitted under its
+    /// topic in the current block, or the host error code to report to the contract.
+    fn emit_message(
+        &self,
+        topic_ptr: *const u8,
+        topic_size: usize,
+        data_size: usize,
+    ) -> Result<u64, u32> {
+        if topic_size > self.message_limits.max_topic_name_size {
+            return Err(HOST_ERROR_TOPIC_TOO_LONG);
+        }
+        if data_size > self.message_limits.max_message_size {
+            return Err(HOST_ERROR_PAYLOAD_TOO_LONG);
+        }
+        let topic = unsafe { slice::from_raw_parts(topic_ptr, topic_size) };
+        let Ok(topic) = std::str::from_utf8(topic) else {
+            return Err(HOST_ERROR_INVALID_DATA);
+        };
+
+        // Topics are created lazily by the first message emitted under them.
+        let ret = self.add_message_topic(topic);
+        if ret != HOST_ERROR_SUCCESS {
+            return Err(ret);
+        }
+
+        let mut message_topics = self.message_topics.write().unwrap();
+        let message_topic = message_topics
+            .get_mut(&self.callee)
+            .and_then(|topics| topics.get_mut(topic))
+            .expect("topic should exist");
+        if message_topic.block_time != self.block_time {
+            message_topic.block_time = self.block_time;
+            message_topic.message_count = 0;
+        }
+        let topic_message_index = message_topic.message_count;
+        message_topic.message_count += 1;
+        Ok(topic_message_index)
+    }
+
+    fn casper_register_topic(
+        &self,
+        topic_ptr: *const u8,
+        topic_size: usize,
+    ) -> Result<u32, NativeTrap> {
+        if topic_size > self.message_limits.max_topic_name_size {
+            return Ok(HOST_ERROR_TOPIC_TOO_LONG);
+        }
+        let topic = unsafe { slice::from_raw_parts(topic_ptr, topic_size) };
+        let Ok(topic) = std::str::from_utf8(topic) else {
+            return Ok(HOST_ERROR_INVALID_DATA);
+        };
+        Ok(self.add_message_topic(topic))
+    }
+
+    /// Adds `topic` to the callee's topics unless it already exists.
+    fn add_message_topic(&self, topic: &str) -> u32 {
+        let mut message_topics = self.message_topics.write().unwrap();
+        let topics = message_topics.entry(self.callee).or_default();
+        if !topics.contains_key(topic) {
+            if topics.len() >= self.message_limits.max_topics_per_contract {
+                return HOST_ERROR_TOO_MANY_TOPICS;
+            }
+            let message_topic = NativeMessageTopic {
+                block_time: self.block_time,
+                message_count: 0,
+            };
+            topics.insert(topic.to_string(), message_topic);
+        }
+        HOST_ERROR_SUCCESS
+    }
+
+    fn casper_message_count(
+        &self,
+        topic_ptr: *const u8,
+        topic_size: usize,
+        out_count_ptr: *mut u64,
+    ) -> Result<u32, NativeTrap> {
+        let topic = unsafe { slice::from_raw_parts(topic_ptr, topic_size) };
+        let Ok(topic) = std::str::from_utf8(topic) else {
+            return Ok(HOST_ERROR_NOT_FOUND);
+        };
+        let message_topics = self.message_topics.read().unwrap();
+        let Some(message_topic) = message_topics
+            .get(&self.callee)
+            .and_then(|topics| topics.get(topic))
+        else {
+            return Ok(HOST_ERROR_NOT_FOUND);
+        };
+        let message_count = if message_topic.block_time == self.block_time {
+            message_topic.message_count
+        } else {
+            0
+        };
+        unsafe { out_count_ptr.write_unaligned(message_count) };
+        Ok(HOST_ERROR_SUCCESS)
+    }
+
+    fn casper_self_code_hash(&self, out_ptr: *mut u8, out_size: usize) -> Result<u32, NativeTrap> {
+        let Entity::Contract(address) = self.callee else {
+            return Ok(HOST_ERROR_NOT_FOUND);
+        };
+        let Some(code_hash) = self.code_hashes.get(&address) else {
+            return Ok(HOST_ERROR_NOT_FOUND);
+        };
+        if out_size != code_hash.len() {
+            return Ok(HOST_ERROR_INVALID_INPUT);
+        }
+        let output = unsafe { slice::from_raw_parts_mut(out_ptr, out_size) };
+        output.copy_from_slice(code_hash);
+        Ok(HOST_ERROR_SUCCESS)
+    }
+
+    fn casper_env_balance(
+        &self,
+        entity_kind: u32,
+        entity_addr_ptr: *const u8,
+        entity_addr_len: usize,
+        output_ptr: *mut core::ffi::c_void,
+    ) -> Result<u32, NativeTrap> {
+        let Ok(address) =
+            <Address>::try_from(unsafe { slice::from_raw_parts(entity_addr_ptr, entity_addr_len) })
+        else {
+            return Ok(0);
+        };
+        let entity = match entity_kind {
+            0 => Entity::Account(address),
+            1 => Entity::Contract(address),
+            _ => return Ok(0),
+        };
+        match self.balance_of(&entity) {
+            Some(balance) => {
+                unsafe { ptr::write_unaligned(output_ptr as *mut u64, balance) };
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
     fn casper_env_info(&self, info_ptr: *const u8, info_size: u32) -> Result<u32, NativeTrap> {
         assert_eq!(info_size as usize, size_of::<EnvInfo>());
         let mut env_info = NonNull::new(info_ptr as *mut u8)
@@ -583,12 +1115,13 @@ Example paths:
             .cast::<EnvInfo>();
         let env_info = unsafe { env_info.as_mut() };
         *env_info = EnvInfo {
-            block_time: 0,
-            transferred_value: 0,
+            block_time: self.block_time,
+            transferred_value: self.transferred_value,
             caller_addr: *self.caller.address(),
             caller_kind: self.caller.tag(),
             callee_addr: *self.callee.address(),
             callee_kind: self.callee.tag(),
+            phase: self.phase.tag(),
         };
         Ok(HOST_ERROR_SUCCESS)
     }
@@ -617,6 +1150,84 @@ pub fn current_environment() -> Environment {
     with_current_environment(|env| env)
 }
 
+/// Reads and decodes the state of the smart contract at `address` from the current environment.
+///
+/// This lets tests inspect a contract's state without calling one of its entry points. Returns
+/// `None` if the contract has no state stored.
+///
+/// # Panics
+///
+/// Panics if the stored state can't be decoded as `T`.
+pub fn read_contract_state<T: BorshDeserialize>(address: &Address) -> Option<T> {
+    with_current_environment(|env| {
+        let key = env.with_callee(Entity::Contract(*address)).key_prefix(&[]);
+        let db = env.db.read().expect("should lock db");
+        let state = db.get(&(KeyspaceTag::State as u64))?.get(key.as_slice())?;
+        Some(borsh::from_slice(state).expect("should decode contract state"))
+    })
+}
+
+/// An owned counterpart of [`Keyspace`], as returned by [`dump_state`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OwnedKeyspace {
+    /// See [`Keyspace::State`].
+    State,
+    /// See [`Keyspace::Context`].
+    Context(Vec<u8>),
+    /// See [`Keyspace::NamedKey`].
+    NamedKey(String),
+    /// See [`Keyspace::PaymentInfo`].
+    PaymentInfo(String),
+}
+
+impl OwnedKeyspace {
+    /// Borrows this keyspace as a [`Keyspace`].
+    #[must_use]
+    pub fn as_keyspace(&self) -> Keyspace<'_> {
+        match self {
+            OwnedKeyspace::State => Keyspace::State,
+            OwnedKeyspace::Context(payload) => Keyspace::Context(payload),
+            OwnedKeyspace::NamedKey(name) => Keyspace::NamedKey(name),
+            OwnedKeyspace::PaymentInfo(name) => Keyspace::PaymentInfo(name),
+        }
+    }
+
+    fn from_parts(key_space: u64, payload: &[u8]) -> Option<Self> {
+        let keyspace = match key_space {
+            tag if tag == KeyspaceTag::State as u64 => OwnedKeyspace::State,
+            tag if tag == KeyspaceTag::Context as u64 => OwnedKeyspace::Context(payload.to_vec()),
+            tag if tag == KeyspaceTag::NamedKey as u64 => {
+                OwnedKeyspace::NamedKey(String::from_utf8(payload.to_vec()).ok()?)
+            }
+            tag if tag == KeyspaceTag::PaymentInfo as u64 => {
+                OwnedKeyspace::PaymentInfo(String::from_utf8(payload.to_vec()).ok()?)
+            }
+            _ => return None,
+        };
+        Some(keyspace)
+    }
+}
+
+/// Returns every value written by the current callee in the current environment, keyed by the
+/// keyspace it was written under.
+///
+/// This is meant for debugging tests which leave unexpected data in storage.
+pub fn dump_state() -> BTreeMap<OwnedKeyspace, Vec<u8>> {
+    with_current_environment(|env| {
+        let prefix = env.key_prefix(&[]);
+        let db = env.db.read().expect("should lock db");
+        db.iter()
+            .flat_map(|(key_space, values)| {
+                values.iter().filter_map(|(key, value)| {
+                    let payload = key.strip_prefix(prefix.as_slice())?;
+                    let keyspace = OwnedKeyspace::from_parts(*key_space, payload)?;
+                    Some((keyspace, value.to_vec()))
+                })
+            })
+            .collect()
+    })
+}
+
 fn handle_ret_with<T>(value: Result<T, NativeTrap>, ret: impl FnOnce() -> T) -> T {
     match value {
         Ok(result) => {
@@ -631,7 +1242,7 @@ fn handle_ret_with<T>(value: Result<T, NativeTrap>, ret: impl FnOnce() -> T) ->
     }
 }
 
-fn dispatch_export_call<F>(func: F) -> Result<(), NativeTrap>
+pub(crate) fn dispatch_export_call<F>(func: F) -> Result<(), NativeTrap>
 where
     F: FnOnce() + Send + UnwindSafe,
 {
@@ -646,10 +1257,25 @@ where
                 None => Ok(()),
             }
         }
-        Err(error) => Err(NativeTrap::Panic(error)),
+        Err(error) => {
+            // An abort unwinds the stack to stop the execution, and a return or revert is
+            // followed by an `unreachable!()` as it does not diverge in native mode. In both
+            // cases the trap recorded before the panic takes precedence over the panic payload.
+            let last_trap = LAST_TRAP.with(|last_trap| last_trap.borrow_mut().take());
+            match last_trap {
+                Some(trap) => Err(trap),
+                None => Err(NativeTrap::Panic(error)),
+            }
+        }
     }
 }
 
+/// Unwinds the stack of an aborted contract, as in Wasm the execution would not continue past the
+/// abort.
+pub(crate) fn unwind_abort() -> ! {
+    panic::resume_unwind(Box::new("casper_abort"))
+}
+
 fn handle_ret<T: Default>(value: Result<T, NativeTrap>) -> T {
     handle_ret_with(value, || T::default())
 }
@@ -690,19 +1316,101 @@ pub fn dispatch_with<T>(stub: Environment, f: impl FnOnce() -> T) -> Result<T, N
     result
 }
 
-mod symbols {
-    // TODO: Figure out how to use for_each_host_function macro here and deal with never type in
-    // casper_return
-    #[no_mangle]
-    /// Read value from a storage available for caller's entity address.
-    pub extern "C" fn casper_read(
-        key_space: u64,
-        key_ptr: *const u8,
-        key_size: usize,
-        info: *mut ::casper_sdk_sys::ReadInfo,
-        alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
-        alloc_ctx: *const core::ffi::c_void,
-    ) -> u32 {
+/// Environment of a test declared with [`contract_test`](crate::macros::contract_test).
+///
+/// The test body runs within [`dispatch_with`], and accounts created here share the global state
+/// of the test.
+pub struct TestEnv {
+    environment: Environment,
+    account_count: RefCell<u64>,
+}
+
+impl Default for TestEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestEnv {
+    /// Creates a test environment with a default [`Environment`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_environment(Environment::default())
+    }
+
+    /// Creates a test environment on top of `environment`.
+    #[must_use]
+    pub fn with_environment(environment: Environment) -> Self {
+        Self {
+            environment,
+            account_count: RefCell::new(0),
+        }
+    }
+
+    /// Returns the environment the test body runs in.
+    #[must_use]
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    /// Creates a new account with the given balance.
+    ///
+    /// Account addresses are derived from a counter, so they are stable across test runs.
+    #[must_use]
+    pub fn create_account(&self, balance: u64) -> Entity {
+        let mut account_count = self.account_count.borrow_mut();
+        *account_count += 1;
+        let mut address = [0xAC; 32];
+        address[24..].copy_from_slice(&account_count.to_be_bytes());
+        let account = Entity::Account(address);
+        self.environment.set_balance(account, balance);
+        account
+    }
+
+    /// Adds `amount` to the balance of `entity`.
+    pub fn fund(&self, entity: Entity, amount: u64) {
+        let balance = self.balance_of(&entity);
+        self.environment.set_balance(entity, balance + amount);
+    }
+
+    /// Returns the balance of `entity`.
+    #[must_use]
+    pub fn balance_of(&self, entity: &Entity) -> u64 {
+        self.environment.balance_of(entity).unwrap_or_default()
+    }
+
+    /// Runs `f` as a session of `account`.
+    pub fn as_account<T>(&self, account: Entity, f: impl FnOnce() -> T) -> Result<T, NativeTrap> {
+        dispatch_with(self.environment.session(account), f)
+    }
+
+    /// Runs the body of a test.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body traps, e.g. by reverting outside of a contract call.
+    pub fn run<T>(self, f: impl FnOnce(&TestEnv) -> T) -> T {
+        let environment = self.environment.clone();
+        match dispatch_with(environment, || f(&self)) {
+            Ok(result) => result,
+            Err(trap) => panic!("test body trapped: {trap:?}"),
+        }
+    }
+}
+
+mod symbols {
+    // TODO: Figure out how to use for_each_host_function macro here and deal with never type in
+    // casper_return
+    #[no_mangle]
+    /// Read value from a storage available for caller's entity address.
+    pub extern "C" fn casper_read(
+        key_space: u64,
+        key_ptr: *const u8,
+        key_size: usize,
+        info: *mut ::casper_sdk_sys::ReadInfo,
+        alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
+        alloc_ctx: *const core::ffi::c_void,
+    ) -> u32 {
         let _name = "casper_read";
         let _args = (&key_space, &key_ptr, &key_size, &info, &alloc, &alloc_ctx);
         let _call_result = with_current_environment(|stub| {
@@ -744,8 +1452,6 @@ mod symbols {
         crate::casper::native::handle_ret(_call_result);
     }
 
-    use casper_executor_wasm_common::error::HOST_ERROR_SUCCESS;
-
     use crate::casper::native::LAST_TRAP;
 
     #[no_mangle]
@@ -758,6 +1464,15 @@ mod symbols {
         LAST_TRAP.with(|last_trap| last_trap.borrow_mut().replace(err));
     }
 
+    #[no_mangle]
+    pub extern "C" fn casper_abort(msg_ptr: *const u8, msg_size: usize) {
+        let _name = "casper_abort";
+        let _args = (&msg_ptr, &msg_size);
+        let _call_result = with_current_environment(|stub| stub.casper_abort(msg_ptr, msg_size));
+        let err = _call_result.unwrap_err(); // SAFE
+        LAST_TRAP.with(|last_trap| last_trap.borrow_mut().replace(err));
+    }
+
     #[no_mangle]
     pub extern "C" fn casper_copy_input(
         alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8,
@@ -800,6 +1515,42 @@ mod symbols {
         crate::casper::native::handle_ret(_call_result)
     }
 
+    #[no_mangle]
+    pub extern "C" fn casper_try_create(
+        code_ptr: *const u8,
+        code_size: usize,
+        transferred_value: u64,
+        constructor_ptr: *const u8,
+        constructor_size: usize,
+        input_ptr: *const u8,
+        input_size: usize,
+        seed_ptr: *const u8,
+        seed_size: usize,
+        result_ptr: *mut casper_sdk_sys::CreateResult,
+        alloc: extern "C" fn(usize, *mut core::ffi::c_void) -> *mut u8, /* For capturing
+                                                                         * constructor revert
+                                                                         * data */
+        alloc_ctx: *const core::ffi::c_void,
+    ) -> u32 {
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_try_create(
+                code_ptr,
+                code_size,
+                transferred_value,
+                constructor_ptr,
+                constructor_size,
+                input_ptr,
+                input_size,
+                seed_ptr,
+                seed_size,
+                result_ptr,
+                alloc,
+                alloc_ctx,
+            )
+        });
+        crate::casper::native::handle_ret(_call_result)
+    }
+
     #[no_mangle]
     pub extern "C" fn casper_call(
         address_ptr: *const u8,
@@ -841,7 +1592,6 @@ mod symbols {
         todo!()
     }
 
-    use core::slice;
     use std::ptr;
 
     use super::with_current_environment;
@@ -862,20 +1612,38 @@ mod symbols {
     }
     #[no_mangle]
     pub extern "C" fn casper_env_balance(
-        _entity_kind: u32,
-        _entity_addr_ptr: *const u8,
-        _entity_addr_len: usize,
-    ) -> u64 {
-        todo!()
+        entity_kind: u32,
+        entity_addr_ptr: *const u8,
+        entity_addr_len: usize,
+        output_ptr: *mut core::ffi::c_void,
+    ) -> u32 {
+        let ret = with_current_environment(|env| {
+            env.casper_env_balance(entity_kind, entity_addr_ptr, entity_addr_len, output_ptr)
+        });
+        crate::casper::native::handle_ret(ret)
     }
     #[no_mangle]
     pub extern "C" fn casper_transfer(
-        _entity_kind: u32,
-        _entity_addr_ptr: *const u8,
-        _entity_addr_len: usize,
-        _amount: u64,
+        entity_addr_ptr: *const u8,
+        entity_addr_len: usize,
+        amount: *const core::ffi::c_void,
     ) -> u32 {
-        todo!()
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_transfer(entity_addr_ptr, entity_addr_len, amount, None)
+        });
+        crate::casper::native::handle_ret(_call_result)
+    }
+    #[no_mangle]
+    pub extern "C" fn casper_transfer_with_id(
+        entity_addr_ptr: *const u8,
+        entity_addr_len: usize,
+        amount: *const core::ffi::c_void,
+        id: u64,
+    ) -> u32 {
+        let _call_result = with_current_environment(|stub| {
+            stub.casper_transfer(entity_addr_ptr, entity_addr_len, amount, Some(id))
+        });
+        crate::casper::native::handle_ret(_call_result)
     }
     #[no_mangle]
     pub extern "C" fn casper_emit(
@@ -884,11 +1652,42 @@ mod symbols {
         data_ptr: *const u8,
         data_size: usize,
     ) -> u32 {
-        let topic = unsafe { slice::from_raw_parts(topic_ptr, topic_size) };
-        let data = unsafe { slice::from_raw_parts(data_ptr, data_size) };
-        let topic = std::str::from_utf8(topic).expect("Valid UTF-8 string");
-        println!("Emitting event with topic: {topic:?} and data: {data:?}");
-        HOST_ERROR_SUCCESS
+        let ret = with_current_environment(|env| {
+            env.casper_emit(topic_ptr, topic_size, data_ptr, data_size)
+        });
+        crate::casper::native::handle_ret(ret)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_register_topic(topic_ptr: *const u8, topic_size: usize) -> u32 {
+        let ret = with_current_environment(|env| env.casper_register_topic(topic_ptr, topic_size));
+        crate::casper::native::handle_ret(ret)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_message_count(
+        topic_ptr: *const u8,
+        topic_size: usize,
+        out_count_ptr: *mut u64,
+    ) -> u32 {
+        let ret = with_current_environment(|env| {
+            env.casper_message_count(topic_ptr, topic_size, out_count_ptr)
+        });
+        crate::casper::native::handle_ret(ret)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_emit_with_index(
+        topic_ptr: *const u8,
+        topic_size: usize,
+        data_ptr: *const u8,
+        data_size: usize,
+        out_index_ptr: *mut u64,
+    ) -> u32 {
+        let ret = with_current_environment(|env| {
+            env.casper_emit_with_index(topic_ptr, topic_size, data_ptr, data_size, out_index_ptr)
+        });
+        crate::casper::native::handle_ret(ret)
     }
 
     #[no_mangle]
@@ -896,13 +1695,30 @@ mod symbols {
         let ret = with_current_environment(|env| env.casper_env_info(info_ptr, info_size));
         crate::casper::native::handle_ret(ret)
     }
+
+    #[no_mangle]
+    pub extern "C" fn casper_self_code_hash(out_ptr: *mut u8, out_size: usize) -> u32 {
+        let ret = with_current_environment(|env| env.casper_self_code_hash(out_ptr, out_size));
+        crate::casper::native::handle_ret(ret)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn casper_random_bytes(out_ptr: *mut u8, out_size: usize) -> u32 {
+        let ret = with_current_environment(|env| env.casper_random_bytes(out_ptr, out_size));
+        crate::casper::native::handle_ret(ret)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use casper_executor_wasm_common::keyspace::Keyspace;
+    use casper_executor_wasm_common::{error::CommonResult, keyspace::Keyspace};
 
-    use crate::casper;
+    use crate::{
+        casper,
+        serializers::borsh::{self, BorshSerialize},
+        types::{CallError, NamedArgError, Timestamp},
+        Message,
+    };
 
     use super::*;
 
@@ -943,6 +1759,81 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn dump_state_should_list_callee_writes() {
+        dispatch(|| {
+            casper::write(Keyspace::Context(b"account"), b"ignored").unwrap();
+
+            let contract =
+                with_current_environment(|stub| stub.smart_contract(Entity::Contract([1; 32])));
+            dispatch_with(contract, || {
+                casper::write(Keyspace::State, b"state").unwrap();
+                casper::write(Keyspace::Context(b"first"), b"value 1").unwrap();
+                casper::write(Keyspace::Context(b"second"), b"value 2").unwrap();
+                casper::write(Keyspace::NamedKey("name"), b"named").unwrap();
+
+                let dump = dump_state();
+                let expected = BTreeMap::from([
+                    (OwnedKeyspace::State, b"state".to_vec()),
+                    (
+                        OwnedKeyspace::Context(b"first".to_vec()),
+                        b"value 1".to_vec(),
+                    ),
+                    (
+                        OwnedKeyspace::Context(b"second".to_vec()),
+                        b"value 2".to_vec(),
+                    ),
+                    (
+                        OwnedKeyspace::NamedKey("name".to_string()),
+                        b"named".to_vec(),
+                    ),
+                ]);
+                assert_eq!(dump, expected);
+
+                for (keyspace, value) in &dump {
+                    assert_eq!(
+                        casper::read_into_vec(keyspace.as_keyspace()),
+                        Ok(Some(value.clone()))
+                    );
+                }
+            })
+            .unwrap();
+
+            assert_eq!(
+                dump_state(),
+                BTreeMap::from([(
+                    OwnedKeyspace::Context(b"account".to_vec()),
+                    b"ignored".to_vec()
+                )])
+            );
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn caller_should_follow_call_frames() {
+        dispatch(|| {
+            assert_eq!(casper::get_caller(), DEFAULT_ADDRESS);
+
+            let first_contract = Entity::Contract([1; 32]);
+            let first_frame = with_current_environment(|stub| stub.smart_contract(first_contract));
+            dispatch_with(first_frame, || {
+                assert_eq!(casper::get_caller(), DEFAULT_ADDRESS);
+                assert_eq!(casper::get_callee(), first_contract);
+
+                let second_frame =
+                    with_current_environment(|stub| stub.smart_contract(Entity::Contract([2; 32])));
+                dispatch_with(second_frame, || {
+                    assert_eq!(casper::get_caller(), first_contract);
+                })
+                .unwrap();
+            })
+            .unwrap();
+        })
+        .unwrap();
+    }
+
     #[test]
     fn test() {
         dispatch_with(Environment::default(), || {
@@ -960,4 +1851,365 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn should_branch_on_phase() {
+        fn write_marker() {
+            let marker: &[u8] = match casper::get_phase() {
+                Phase::Payment => b"payment",
+                Phase::Session => b"session",
+                Phase::System | Phase::FinalizePayment => b"other",
+            };
+            casper::write(Keyspace::Context(b"phase"), marker).unwrap();
+        }
+
+        dispatch(|| {
+            assert_eq!(casper::get_phase(), Phase::Session);
+            write_marker();
+            assert_eq!(
+                casper::read_into_vec(Keyspace::Context(b"phase")),
+                Ok(Some(b"session".to_vec()))
+            );
+
+            let payment_env = with_current_environment(|stub| stub.with_phase(Phase::Payment));
+            dispatch_with(payment_env, || {
+                assert_eq!(casper::get_phase(), Phase::Payment);
+                write_marker();
+                assert_eq!(
+                    casper::read_into_vec(Keyspace::Context(b"phase")),
+                    Ok(Some(b"payment".to_vec()))
+                );
+            })
+            .unwrap();
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_derive_random_bytes_from_transaction_hash() {
+        let transaction = || Environment::default().with_transaction_hash([7; 32]);
+
+        let first_run = dispatch_with(transaction(), || {
+            let first = casper::random_bytes(48);
+            let second = casper::random_bytes(48);
+            assert_eq!(first.len(), 48);
+            assert_ne!(first, second);
+            (first, second)
+        })
+        .unwrap();
+
+        // Re-executing the same transaction reproduces the same bytes.
+        let second_run = dispatch_with(transaction(), || {
+            (casper::random_bytes(48), casper::random_bytes(48))
+        })
+        .unwrap();
+        assert_eq!(first_run, second_run);
+
+        let other_transaction = Environment::default().with_transaction_hash([8; 32]);
+        let other_run = dispatch_with(other_transaction, || casper::random_bytes(48)).unwrap();
+        assert_ne!(other_run, first_run.0);
+
+        // Contracts executed within the same transaction draw different bytes.
+        let other_contract = transaction().smart_contract(Entity::Contract([9; 32]));
+        let other_contract_run =
+            dispatch_with(other_contract, || casper::random_bytes(48)).unwrap();
+        assert_ne!(other_contract_run, first_run.0);
+    }
+
+    #[test]
+    fn should_report_distinct_code_hashes_of_deployed_versions() {
+        const V1_ADDRESS: Address = [1; 32];
+        const V2_ADDRESS: Address = [2; 32];
+        let env = Environment::default()
+            .with_code_hash(V1_ADDRESS, [11; 32])
+            .with_code_hash(V2_ADDRESS, [22; 32]);
+
+        let v1_code_hash = dispatch_with(
+            env.smart_contract(Entity::Contract(V1_ADDRESS)),
+            casper::self_code_hash,
+        )
+        .unwrap();
+        let v2_code_hash = dispatch_with(
+            env.smart_contract(Entity::Contract(V2_ADDRESS)),
+            casper::self_code_hash,
+        )
+        .unwrap();
+
+        assert_eq!(v1_code_hash, [11; 32]);
+        assert_eq!(v2_code_hash, [22; 32]);
+        assert_ne!(v1_code_hash, v2_code_hash);
+    }
+
+    #[test]
+    fn should_read_block_time_as_timestamp() {
+        let env = Environment::default().with_block_time(1_700_000_000_000);
+        dispatch_with(env, || {
+            assert_eq!(casper::get_block_time(), 1_700_000_000_000);
+            assert_eq!(
+                casper::block_timestamp(),
+                Timestamp::from_millis(1_700_000_000_000)
+            );
+        })
+        .unwrap();
+    }
+
+    #[derive(BorshSerialize)]
+    #[borsh(crate = "crate::serializers::borsh")]
+    struct Transfer;
+
+    impl Message for Transfer {
+        const TOPIC: &'static str = "transfer";
+
+        fn payload(&self) -> Vec<u8> {
+            Vec::new()
+        }
+    }
+
+    #[derive(BorshSerialize)]
+    #[borsh(crate = "crate::serializers::borsh")]
+    struct Approval;
+
+    impl Message for Approval {
+        const TOPIC: &'static str = "approval";
+
+        fn payload(&self) -> Vec<u8> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn should_emit_unregistered_topics() {
+        dispatch(|| {
+            assert_eq!(casper::emit(Transfer), Ok(()));
+            assert_eq!(casper::emit(Approval), Ok(()));
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_reject_topic_that_is_not_valid_utf8() {
+        dispatch(|| {
+            let topic = [0xff, 0xfe];
+            let payload = [0u8; 4];
+            let ret = unsafe {
+                casper_sdk_sys::casper_emit(
+                    topic.as_ptr(),
+                    topic.len(),
+                    payload.as_ptr(),
+                    payload.len(),
+                )
+            };
+            assert_eq!(ret, HOST_ERROR_INVALID_DATA);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_count_registered_topics_towards_the_limit() {
+        let env = Environment::default().with_message_limits(MessageLimits {
+            max_topics_per_contract: 1,
+            ..Default::default()
+        });
+        dispatch_with(env, || {
+            assert_eq!(casper::register_topic(Transfer::TOPIC), Ok(()));
+            // Registering the same topic twice is a no-op.
+            assert_eq!(casper::register_topic(Transfer::TOPIC), Ok(()));
+
+            assert_eq!(casper::emit(Transfer), Ok(()));
+            assert_eq!(casper::emit(Approval), Err(CommonResult::TooManyTopics));
+
+            // Topics are registered per contract.
+            let other_contract =
+                with_current_environment(|stub| stub.smart_contract(Entity::Contract([1; 32])));
+            dispatch_with(other_contract, || {
+                assert_eq!(casper::emit(Approval), Ok(()));
+            })
+            .unwrap();
+        })
+        .unwrap();
+    }
+
+    fn named_args(args: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let args: std::collections::BTreeMap<String, Vec<u8>> = args
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        borsh::to_vec(&args).unwrap()
+    }
+
+    #[test]
+    fn should_read_present_named_arg() {
+        let input = named_args(&[("amount", borsh::to_vec(&42u64).unwrap())]);
+        let env = Environment::default().with_input_data(input);
+        dispatch_with(env, || {
+            assert_eq!(casper::try_named_arg::<u64>("amount"), Some(42));
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_return_none_for_absent_named_arg() {
+        let input = named_args(&[("amount", borsh::to_vec(&42u64).unwrap())]);
+        let env = Environment::default().with_input_data(input);
+        dispatch_with(env, || {
+            assert_eq!(casper::try_named_arg::<u64>("memo"), None);
+        })
+        .unwrap();
+
+        // Without any input every argument is absent.
+        dispatch(|| {
+            assert_eq!(casper::try_named_arg::<u64>("amount"), None);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_read_present_optional_named_arg() {
+        let input = named_args(&[("memo", borsh::to_vec("hello").unwrap())]);
+        let env = Environment::default().with_input_data(input);
+        dispatch_with(env, || {
+            let memo: Option<String> = casper::try_named_arg("memo");
+            assert_eq!(memo.as_deref(), Some("hello"));
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_revert_on_named_arg_of_wrong_type() {
+        let input = named_args(&[("memo", borsh::to_vec(&42u8).unwrap())]);
+        let env = Environment::default().with_input_data(input);
+        let result = dispatch_with(env, || {
+            dispatch_export_call(|| {
+                let _memo: Option<String> = casper::try_named_arg("memo");
+            })
+        })
+        .unwrap();
+
+        let Err(NativeTrap::Return(flags, data)) = result else {
+            panic!("expected a revert, got {result:?}");
+        };
+        assert_eq!(flags, ReturnFlags::REVERT);
+        let error: NamedArgError = borsh::from_slice(&data).unwrap();
+        assert_eq!(error.name, "memo");
+    }
+
+    #[test]
+    fn should_count_emitted_messages_per_topic() {
+        dispatch(|| {
+            assert_eq!(casper::message_count(Transfer::TOPIC), Ok(0));
+
+            assert_eq!(casper::emit(Transfer), Ok(()));
+            assert_eq!(casper::emit(Transfer), Ok(()));
+            assert_eq!(casper::emit_raw(Transfer::TOPIC, b"raw"), Ok(()));
+            assert_eq!(casper::emit(Approval), Ok(()));
+
+            assert_eq!(casper::message_count(Transfer::TOPIC), Ok(3));
+            assert_eq!(casper::message_count(Approval::TOPIC), Ok(1));
+            assert_eq!(casper::message_count("unknown"), Ok(0));
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_assign_increasing_message_indices() {
+        dispatch(|| {
+            assert_eq!(casper::emit_with_index(Transfer), Ok(0));
+            assert_eq!(casper::emit_with_index(Transfer), Ok(1));
+            assert_eq!(casper::emit(Transfer), Ok(()));
+            assert_eq!(casper::emit_with_index(Transfer), Ok(3));
+
+            // Indices are assigned per topic.
+            assert_eq!(casper::emit_with_index(Approval), Ok(0));
+            assert_eq!(casper::message_count(Transfer::TOPIC), Ok(4));
+
+            // Indices start over in the next block.
+            let next_block = with_current_environment(|stub| stub.with_block_time(1));
+            dispatch_with(next_block, || {
+                assert_eq!(casper::emit_with_index(Transfer), Ok(0));
+            })
+            .unwrap();
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_record_transfer_id() {
+        let target = [1; 32];
+        let transfers = dispatch(|| {
+            current_environment().set_balance(DEFAULT_ADDRESS, 300);
+            assert_eq!(casper::transfer(&target, 100), Ok(()));
+            assert_eq!(casper::transfer_with_id(&target, 200, 42), Ok(()));
+            current_environment().transfers()
+        })
+        .unwrap();
+
+        assert_eq!(
+            transfers,
+            vec![
+                NativeTransfer {
+                    from: DEFAULT_ADDRESS,
+                    to: target,
+                    amount: 100,
+                    id: None,
+                },
+                NativeTransfer {
+                    from: DEFAULT_ADDRESS,
+                    to: target,
+                    amount: 200,
+                    id: Some(42),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_move_balances_on_transfer() {
+        let target = [1; 32];
+        dispatch(|| {
+            let env = current_environment();
+            env.set_balance(DEFAULT_ADDRESS, 100);
+
+            assert_eq!(casper::transfer(&target, 60), Ok(()));
+            assert_eq!(env.balance_of(&DEFAULT_ADDRESS), Some(40));
+            assert_eq!(env.balance_of(&Entity::Account(target)), Some(60));
+
+            assert_eq!(
+                casper::transfer(&target, 50),
+                Err(CallError::CalleeReverted)
+            );
+            assert_eq!(env.balance_of(&DEFAULT_ADDRESS), Some(40));
+            assert_eq!(env.transfers().len(), 1);
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_fail_to_register_topics_exceeding_limits() {
+        let message_limits = MessageLimits {
+            max_topic_name_size: 16,
+            max_topics_per_contract: 4,
+            ..Default::default()
+        };
+        let env = Environment::default().with_message_limits(message_limits);
+        dispatch_with(env, || {
+            let long_topic = "a".repeat(message_limits.max_topic_name_size + 1);
+            assert_eq!(
+                casper::register_topic(&long_topic),
+                Err(CommonResult::TopicTooLong)
+            );
+            let max_topic = "a".repeat(message_limits.max_topic_name_size);
+            assert_eq!(casper::register_topic(&max_topic), Ok(()));
+
+            for i in 1..message_limits.max_topics_per_contract {
+                assert_eq!(casper::register_topic(&format!("topic{i}")), Ok(()));
+            }
+            assert_eq!(
+                casper::register_topic("one_too_many"),
+                Err(CommonResult::TooManyTopics)
+            );
+            // Already registered topics are still accepted once the limit is reached.
+            assert_eq!(casper::register_topic("topic1"), Ok(()));
+        })
+        .unwrap();
+    }
 }
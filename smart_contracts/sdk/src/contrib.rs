@@ -2,3 +2,6 @@ pub mod access_control;
 pub mod cep18;
 pub mod ownable;
 pub mod pausable;
+pub mod reentrancy_guard;
+mod set_index;
+pub mod versioned_state;
@@ -3,9 +3,11 @@ use crate as casper_sdk; // Worakround for absolute crate path in derive CasperA
 
 use casper_macros::casper;
 
+use super::set_index::SetIndex;
 use crate::{
     casper::{self, Entity},
     collections::{sorted_vector::SortedVector, Map},
+    macros::blake2b256,
 };
 
 /// A role is a unique identifier for a specific permission or set of permissions.
@@ -16,6 +18,19 @@ pub type Role = [u8; 32];
 /// A role is a unique identifier for a specific permission or set of permissions.
 const ROLES_PREFIX: &str = "roles";
 
+/// Role of the administrators of a contract.
+///
+/// Administrators may index the holders of roles with [`EnumerableRoles::index_role_members`].
+pub const ADMIN_ROLE: Role = blake2b256!("admin");
+
+/// Prefix of the holders of each role, filled in as roles are granted and revoked.
+const MEMBERS_PREFIX: &str = "members";
+
+/// Entities holding each role, which [`AccessControlState`] only stores per entity.
+fn members() -> SetIndex<Role, Entity> {
+    SetIndex::new(MEMBERS_PREFIX)
+}
+
 /// The state of the access control contract, which contains a mapping of entities to their roles.
 #[casper(path = "crate")]
 pub struct AccessControlState {
@@ -54,7 +69,8 @@ pub enum AccessControlError {
 /// `SortedVector` of roles.
 ///
 /// None of these methods are turned into smart contract entry points, so they are not exposed
-/// accidentally.
+/// accidentally. Contracts which want other contracts to enumerate roles and their members opt in
+/// by also implementing [`EnumerableRoles`].
 ///
 /// The `AccessControl` trait is designed to be used with the `casper` macro, which generates
 /// the necessary boilerplate code for the contract.
@@ -87,31 +103,32 @@ pub trait AccessControl {
     /// Grants a role to an account. If the account already has the role, it does nothing.
     #[casper(private)]
     fn grant_role(&mut self, entity: Entity, role: Role) {
-        match self.state_mut().roles.get(&entity) {
-            Some(mut roles) => {
+        let mut roles = match self.state().roles.get(&entity) {
+            Some(roles) => {
                 if roles.contains(&role) {
                     return;
                 }
-                roles.push(role);
-            }
-            None => {
-                let mut roles = SortedVector::new(format!(
-                    "{ROLES_PREFIX}-{:02x}{}",
-                    entity.tag(),
-                    base16::encode_lower(&entity.address())
-                ));
-                roles.push(role);
-                self.state_mut().roles.insert(&entity, &roles);
+                roles
             }
-        }
+            None => SortedVector::new(format!(
+                "{ROLES_PREFIX}-{:02x}{}",
+                entity.tag(),
+                base16::encode_lower(&entity.address())
+            )),
+        };
+        roles.push(role);
+        self.state_mut().roles.insert(&entity, &roles);
+        members().update(&role, &entity, true);
     }
 
     /// Revokes a role from an account. If the account does not have the role, it does nothing.
     #[casper(private)]
     fn revoke_role(&mut self, entity: Entity, role: Role) {
-        if let Some(mut roles) = self.state_mut().roles.get(&entity) {
+        if let Some(mut roles) = self.state().roles.get(&entity) {
             roles.retain(|r| r != &role);
+            self.state_mut().roles.insert(&entity, &roles);
         }
+        members().update(&role, &entity, false);
     }
 
     /// Checks if the caller has the specified role and reverts if not.
@@ -136,3 +153,139 @@ pub trait AccessControl {
         Ok(())
     }
 }
+
+/// Extension exposing the roles of a contract implementing [`AccessControl`] to other contracts.
+///
+/// Implementing this trait adds `roles_of`, `members_of` and `index_role_members` to the entry
+/// points of the contract. The queries only reveal what is already readable from the contract's
+/// global state.
+#[casper(path = "crate", export = true)]
+pub trait EnumerableRoles: AccessControl {
+    /// Returns all roles held by the given account.
+    fn roles_of(&self, entity: Entity) -> Vec<Role> {
+        match self.state().roles.get(&entity) {
+            Some(roles) => roles.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns all accounts holding the given role, in no particular order.
+    ///
+    /// Holders of roles granted by a contract deployed before roles could be enumerated are only
+    /// listed once they are indexed with [`EnumerableRoles::index_role_members`].
+    fn members_of(&self, role: Role) -> Vec<Entity> {
+        let members = members();
+        members.page(&role, 0, members.len(&role))
+    }
+
+    /// Lists each of `entities` as a member of the roles it holds.
+    ///
+    /// Upgraded contracts call this once with the accounts holding roles granted before the
+    /// upgrade. Only callers with [`ADMIN_ROLE`] may do so.
+    #[casper(revert_on_error)]
+    fn index_role_members(&mut self, entities: Vec<Entity>) -> Result<(), AccessControlError> {
+        self.require_role(ADMIN_ROLE)?;
+        for entity in entities {
+            if let Some(roles) = self.state().roles.get(&entity) {
+                for role in roles.iter() {
+                    members().update(&role, &entity, true);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::casper::native::dispatch;
+
+    const ALICE: Entity = Entity::Account([1; 32]);
+    const BOB: Entity = Entity::Account([2; 32]);
+    const MINTER_ROLE: Role = [2; 32];
+    const BURNER_ROLE: Role = [3; 32];
+
+    #[derive(Default)]
+    struct TestContract {
+        state: AccessControlState,
+    }
+
+    impl AccessControl for TestContract {
+        fn state(&self) -> &AccessControlState {
+            &self.state
+        }
+
+        fn state_mut(&mut self) -> &mut AccessControlState {
+            &mut self.state
+        }
+    }
+
+    impl EnumerableRoles for TestContract {}
+
+    #[test]
+    fn should_enumerate_roles_and_members() {
+        dispatch(|| {
+            let mut contract = TestContract::default();
+            assert!(contract.roles_of(ALICE).is_empty());
+            assert!(contract.members_of(ADMIN_ROLE).is_empty());
+
+            contract.grant_role(ALICE, MINTER_ROLE);
+            contract.grant_role(ALICE, ADMIN_ROLE);
+            contract.grant_role(ALICE, BURNER_ROLE);
+            contract.grant_role(BOB, ADMIN_ROLE);
+            // Granting a role twice is a no-op.
+            contract.grant_role(BOB, ADMIN_ROLE);
+
+            let mut alice_roles = vec![ADMIN_ROLE, MINTER_ROLE, BURNER_ROLE];
+            alice_roles.sort();
+            assert_eq!(contract.roles_of(ALICE), alice_roles);
+            assert_eq!(contract.roles_of(BOB), vec![ADMIN_ROLE]);
+            assert_eq!(contract.members_of(ADMIN_ROLE), vec![ALICE, BOB]);
+            assert_eq!(contract.members_of(MINTER_ROLE), vec![ALICE]);
+
+            contract.revoke_role(ALICE, ADMIN_ROLE);
+            alice_roles.retain(|role| role != &ADMIN_ROLE);
+            assert_eq!(contract.roles_of(ALICE), alice_roles);
+            assert_eq!(contract.members_of(ADMIN_ROLE), vec![BOB]);
+            assert!(contract.has_role(BOB, ADMIN_ROLE));
+            assert!(!contract.has_role(ALICE, ADMIN_ROLE));
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_let_admins_list_holders_of_roles_stored_without_members() {
+        dispatch(|| {
+            let mut contract = TestContract::default();
+
+            // Alice's roles are stored the way contracts predating `EnumerableRoles` stored them.
+            let mut roles = SortedVector::new(format!(
+                "{ROLES_PREFIX}-{:02x}{}",
+                ALICE.tag(),
+                base16::encode_lower(&ALICE.address())
+            ));
+            roles.push(MINTER_ROLE);
+            roles.push(BURNER_ROLE);
+            contract.state_mut().roles.insert(&ALICE, &roles);
+            contract.grant_role(BOB, MINTER_ROLE);
+            assert_eq!(contract.members_of(MINTER_ROLE), vec![BOB]);
+            assert!(contract.members_of(BURNER_ROLE).is_empty());
+
+            assert_eq!(
+                contract.index_role_members(vec![ALICE]),
+                Err(AccessControlError::NotAuthorized)
+            );
+            assert!(contract.members_of(BURNER_ROLE).is_empty());
+
+            contract.grant_role(casper::get_caller(), ADMIN_ROLE);
+            assert_eq!(contract.index_role_members(vec![ALICE, BOB]), Ok(()));
+            // Bob already was a member, and Alice is not listed twice.
+            assert_eq!(contract.index_role_members(vec![ALICE]), Ok(()));
+
+            assert_eq!(contract.members_of(MINTER_ROLE), vec![BOB, ALICE]);
+            assert_eq!(contract.members_of(BURNER_ROLE), vec![ALICE]);
+        })
+        .unwrap();
+    }
+}
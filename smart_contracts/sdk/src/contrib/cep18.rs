@@ -60,7 +60,10 @@
 //! ```
 use bnum::types::U256;
 
-use super::access_control::{AccessControl, AccessControlError, Role};
+use super::{
+    access_control::{AccessControl, AccessControlError, Role},
+    set_index::SetIndex,
+};
 #[allow(unused_imports)]
 use crate as casper_sdk;
 use crate::{collections::Map, macros::blake2b256, prelude::*};
@@ -131,7 +134,7 @@ pub struct Approve {
     pub amount: U256,
 }
 
-pub const ADMIN_ROLE: Role = blake2b256!("admin");
+pub use super::access_control::ADMIN_ROLE;
 pub const MINTER_ROLE: Role = blake2b256!("minter");
 
 #[casper(path = crate)]
@@ -172,6 +175,23 @@ impl CEP18State {
         self.balances.insert(recipient, &new_recipient_balance);
         Ok(())
     }
+
+    /// Sets the allowance of `spender` over tokens of `owner`, keeping the index of spenders in
+    /// sync so allowances can be enumerated.
+    fn set_allowance(&mut self, owner: &Entity, spender: &Entity, amount: U256) {
+        self.allowances.insert(&(*owner, *spender), &amount);
+        allowance_spenders(&self.allowances).update(owner, spender, !amount.is_zero());
+    }
+}
+
+/// Spenders holding a non-zero allowance over the tokens of each owner.
+///
+/// CEP-18 only stores allowances by `(owner, spender)` pair, so a wallet can't tell which
+/// allowances an owner has granted. The spenders are listed under keys derived from the name of
+/// the allowances map, which leaves the layout of [`CEP18State`] of already deployed tokens as it
+/// is and keeps the lists of two tokens apart.
+fn allowance_spenders(allowances: &Map<(Entity, Entity), U256>) -> SetIndex<Entity, Entity> {
+    SetIndex::new(format!("{}-spenders", allowances.name))
 }
 
 impl CEP18State {
@@ -229,8 +249,7 @@ pub trait CEP18 {
         if owner == spender {
             return Err(Cep18Error::CannotTargetSelfUser);
         }
-        let lookup_key = (owner, spender);
-        self.state_mut().allowances.insert(&lookup_key, &amount);
+        self.state_mut().set_allowance(&owner, &spender, amount);
         casper::emit(Approve {
             owner,
             spender,
@@ -249,7 +268,7 @@ pub trait CEP18 {
         let lookup_key = (owner, spender);
         let allowance = self.state().allowances.get(&lookup_key).unwrap_or_default();
         let allowance = allowance.saturating_sub(amount);
-        self.state_mut().allowances.insert(&lookup_key, &allowance);
+        self.state_mut().set_allowance(&owner, &spender, allowance);
         Ok(())
     }
 
@@ -262,7 +281,7 @@ pub trait CEP18 {
         let lookup_key = (owner, spender);
         let allowance = self.state().allowances.get(&lookup_key).unwrap_or_default();
         let allowance = allowance.saturating_add(amount);
-        self.state_mut().allowances.insert(&lookup_key, &allowance);
+        self.state_mut().set_allowance(&owner, &spender, allowance);
         Ok(())
     }
 
@@ -317,8 +336,7 @@ pub trait CEP18 {
             .transfer_balance(&owner, &recipient, amount)?;
 
         self.state_mut()
-            .allowances
-            .insert(&(owner, spender), &new_spender_allowance);
+            .set_allowance(&owner, &spender, new_spender_allowance);
 
         casper::emit(Transfer {
             from: Some(owner),
@@ -331,6 +349,53 @@ pub trait CEP18 {
     }
 }
 
+/// Maximum number of allowances returned by a single [`EnumerableAllowances::allowances_of`] call.
+pub const MAX_ALLOWANCES_PER_PAGE: u32 = 100;
+
+/// Extension listing the allowances granted by an owner.
+///
+/// `approve`, `increase_allowance`, `decrease_allowance` and `transfer_from` record which
+/// spenders an owner has approved. Tokens that approved spenders before they implemented this trait list those
+/// approvals after passing their spenders to [`EnumerableAllowances::index_allowances`].
+#[casper(path = crate, export = true)]
+pub trait EnumerableAllowances: CEP18 {
+    /// Returns up to `limit` spenders holding a non-zero allowance over tokens of `owner`,
+    /// together with the allowed amount, starting at `offset`.
+    ///
+    /// At most [`MAX_ALLOWANCES_PER_PAGE`] allowances are returned, so the cost of a call is
+    /// bounded. Spenders are listed in no particular order, and the order changes as allowances
+    /// drop to zero.
+    fn allowances_of(&self, owner: Entity, offset: u32, limit: u32) -> Vec<(Entity, U256)> {
+        allowance_spenders(&self.state().allowances)
+            .page(&owner, offset, limit.min(MAX_ALLOWANCES_PER_PAGE))
+            .into_iter()
+            .map(|spender| {
+                let amount = self
+                    .state()
+                    .allowances
+                    .get(&(owner, spender))
+                    .unwrap_or_default();
+                (spender, amount)
+            })
+            .collect()
+    }
+
+    /// Lists those of `spenders` holding a non-zero allowance over tokens of `owner`.
+    ///
+    /// Tokens upgraded to implement this trait call this for the approvals made before the
+    /// upgrade, which [`EnumerableAllowances::allowances_of`] can't find on its own. It only
+    /// copies what the allowances map already holds, so it is open to anyone, and a spender with
+    /// a zero allowance is dropped from the list.
+    fn index_allowances(&mut self, owner: Entity, spenders: Vec<Entity>) {
+        let allowances = &self.state().allowances;
+        let index = allowance_spenders(allowances);
+        for spender in spenders {
+            let amount = allowances.get(&(owner, spender)).unwrap_or_default();
+            index.update(&owner, &spender, !amount.is_zero());
+        }
+    }
+}
+
 #[casper(path = crate, export = true)]
 pub trait Mintable: CEP18 + AccessControl {
     #[casper(revert_on_error)]
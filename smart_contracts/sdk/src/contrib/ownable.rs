@@ -82,3 +82,23 @@ pub trait Ownable {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToCallData;
+
+    #[test]
+    fn call_data_should_implement_debug() {
+        let new_owner = Entity::Account([1; 32]);
+        let call_data = OwnableRef.transfer_ownership(new_owner);
+        assert_eq!(call_data.entry_point(), "transfer_ownership");
+        assert_eq!(
+            format!("{call_data:?}"),
+            format!("CallData {{ new_owner: {new_owner:?} }}")
+        );
+
+        let call_data = OwnableRef.renounce_ownership();
+        assert_eq!(format!("{call_data:?}"), "CallData");
+    }
+}
@@ -0,0 +1,71 @@
+//! This module provides a reentrancy guard for smart contracts.
+//!
+//! A contract that calls into other contracts may be called back before the outer call has
+//! finished. The `NonReentrant` trait rejects such nested calls for the duration of a guarded
+//! section, which protects flows like withdrawals from observing a half-updated state.
+//!
+//! Nested calls load their own copy of the contract state, so the guard flag is persisted in the
+//! contract's context storage as soon as the guarded section is entered rather than kept in the
+//! state struct.
+use casper_executor_wasm_common::keyspace::Keyspace;
+
+#[allow(unused_imports)]
+use crate as casper_sdk;
+use crate::{casper, macros::casper};
+
+/// Context key reserved for the guard flag while a guarded section is running.
+///
+/// Contracts implementing [`NonReentrant`] must not store their own data under this key, as doing
+/// so would either trip or silently clear the guard.
+pub const REENTRANCY_GUARD_KEY: &[u8] = b"__casper_reentrancy_guard";
+
+/// Represents the possible errors that can occur when entering a guarded section.
+#[casper(path = crate)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum ReentrancyGuardError {
+    /// The contract was called again while a guarded section was still running.
+    ReentrantCall,
+}
+
+/// NonReentrant is a trait that rejects calls made back into a contract while one of its guarded
+/// sections is running.
+#[casper(path = crate, export = true)]
+pub trait NonReentrant {
+    /// Checks if a guarded section is currently running.
+    #[casper(private)]
+    fn entered(&self) -> bool {
+        casper::read_into_vec(Keyspace::Context(REENTRANCY_GUARD_KEY))
+            .expect("Read")
+            .is_some()
+    }
+
+    /// Marks the start of a guarded section.
+    ///
+    /// Fails if another guarded section of this contract is already running.
+    #[casper(private)]
+    fn enter(&mut self) -> Result<(), ReentrancyGuardError> {
+        if self.entered() {
+            return Err(ReentrancyGuardError::ReentrantCall);
+        }
+        casper::write(Keyspace::Context(REENTRANCY_GUARD_KEY), &[1]).expect("Write");
+        Ok(())
+    }
+
+    /// Marks the end of a guarded section.
+    #[casper(private)]
+    fn exit(&mut self) {
+        casper::remove(Keyspace::Context(REENTRANCY_GUARD_KEY)).expect("Remove");
+    }
+
+    /// Runs `f` inside a guarded section.
+    #[casper(private)]
+    fn guarded<T, F: FnOnce(&mut Self) -> T>(&mut self, f: F) -> Result<T, ReentrancyGuardError>
+    where
+        Self: Sized,
+    {
+        self.enter()?;
+        let ret = f(self);
+        self.exit();
+        Ok(ret)
+    }
+}
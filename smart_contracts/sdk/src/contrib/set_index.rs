@@ -0,0 +1,89 @@
+//! Enumerable sets of values kept per key, for contrib modules which need to list entries of a
+//! [`Map`] by part of their key.
+use core::marker::PhantomData;
+
+use crate::{
+    collections::Map,
+    prelude::*,
+    serializers::borsh::{BorshDeserialize, BorshSerialize},
+};
+
+/// Sets of values kept per key, stored under keys derived from a prefix.
+///
+/// Each set is stored as a dense array of its members together with the position of each member,
+/// so adding or removing a member touches a constant number of keys however large the set grows.
+/// A removed member is replaced by the last one, so members are listed in no particular order.
+pub(crate) struct SetIndex<K, V> {
+    prefix: String,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> SetIndex<K, V>
+where
+    K: BorshSerialize + Copy,
+    V: BorshSerialize + BorshDeserialize + Copy,
+{
+    /// Sets stored under keys starting with `prefix`.
+    pub(crate) fn new(prefix: impl Into<String>) -> Self {
+        SetIndex {
+            prefix: prefix.into(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of members of the set of each key.
+    fn lengths(&self) -> Map<K, u32> {
+        Map::new(format!("{}-len", self.prefix))
+    }
+
+    /// Member at a position of the set of a key.
+    fn members(&self) -> Map<(K, u32), V> {
+        Map::new(format!("{}-at", self.prefix))
+    }
+
+    /// Position of a member in the set of a key.
+    fn positions(&self) -> Map<(K, V), u32> {
+        Map::new(format!("{}-position", self.prefix))
+    }
+
+    /// Returns the number of members of the set of `key`.
+    pub(crate) fn len(&self, key: &K) -> u32 {
+        self.lengths().get(key).unwrap_or_default()
+    }
+
+    /// Returns up to `limit` members of the set of `key`, starting at `offset`.
+    pub(crate) fn page(&self, key: &K, offset: u32, limit: u32) -> Vec<V> {
+        let end = offset.saturating_add(limit).min(self.len(key));
+        (offset..end)
+            .filter_map(|position| self.members().get(&(*key, position)))
+            .collect()
+    }
+
+    /// Adds `value` to, or removes it from, the set of `key`.
+    ///
+    /// Adding a member or removing a non-member has no effect, so the index can be rebuilt from the
+    /// data it mirrors by calling this again for every entry.
+    pub(crate) fn update(&self, key: &K, value: &V, member: bool) {
+        let position = self.positions().get(&(*key, *value));
+        match (position, member) {
+            (None, true) => {
+                let len = self.len(key);
+                self.members().insert(&(*key, len), value);
+                self.positions().insert(&(*key, *value), &len);
+                self.lengths().insert(key, &(len + 1));
+            }
+            (Some(position), false) => {
+                let last = self.len(key) - 1;
+                if position != last {
+                    let last_member = self.members().get(&(*key, last)).expect("indexed member");
+                    self.members().insert(&(*key, position), &last_member);
+                    self.positions().insert(&(*key, last_member), &position);
+                }
+                self.members().remove(&(*key, last));
+                self.positions().remove(&(*key, *value));
+                self.lengths().insert(key, &last);
+            }
+            _ => {}
+        }
+    }
+}
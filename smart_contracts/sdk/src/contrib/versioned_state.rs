@@ -0,0 +1,153 @@
+//! This module provides helpers for evolving the layout of a contract's stored state across
+//! upgrades.
+//!
+//! State written through [`write_versioned`] is prefixed with the index of its layout, so that an
+//! upgraded contract can still decode state written by a previous version and migrate it to the
+//! latest layout with [`read_and_migrate`].
+use casper_executor_wasm_common::{error::CommonResult, keyspace::Keyspace};
+
+use crate::{
+    casper,
+    serializers::borsh::{self, BorshDeserialize, BorshSerialize},
+};
+
+/// Converts state stored in a previous layout into this one.
+pub trait Migrate<Previous> {
+    /// Builds the new layout out of the `previous` one.
+    fn migrate(previous: Previous) -> Self;
+}
+
+/// A state that may be stored in any of several layouts.
+pub trait Versioned: BorshSerialize + BorshDeserialize {
+    /// The most recent layout of the state.
+    type Latest;
+
+    /// Returns `true` if the state is already stored in the latest layout.
+    fn is_latest(&self) -> bool;
+
+    /// Migrates the state to the latest layout.
+    fn into_latest(self) -> Self::Latest;
+
+    /// Wraps the latest layout of the state.
+    fn from_latest(latest: Self::Latest) -> Self;
+}
+
+/// A contract state stored in either its first or its second layout.
+///
+/// The variant index is serialized in front of the state and acts as the version tag, so new
+/// layouts must only ever be appended. Contracts going through more layouts can define their own
+/// enum and implement [`Versioned`] for it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+#[borsh(crate = "crate::serializers::borsh")]
+pub enum VersionedState<V1, V2> {
+    /// The first layout.
+    V1(V1),
+    /// The second layout.
+    V2(V2),
+}
+
+impl<V1, V2> Versioned for VersionedState<V1, V2>
+where
+    V1: BorshSerialize + BorshDeserialize,
+    V2: BorshSerialize + BorshDeserialize + Migrate<V1>,
+{
+    type Latest = V2;
+
+    fn is_latest(&self) -> bool {
+        matches!(self, VersionedState::V2(_))
+    }
+
+    fn into_latest(self) -> V2 {
+        match self {
+            VersionedState::V1(v1) => V2::migrate(v1),
+            VersionedState::V2(v2) => v2,
+        }
+    }
+
+    fn from_latest(latest: V2) -> Self {
+        VersionedState::V2(latest)
+    }
+}
+
+/// Writes `latest` under `key`, tagged with the version of its layout.
+pub fn write_versioned<T: Versioned>(key: Keyspace, latest: T::Latest) -> Result<(), CommonResult> {
+    let bytes = borsh::to_vec(&T::from_latest(latest)).map_err(|_| CommonResult::InvalidData)?;
+    casper::write(key, &bytes)
+}
+
+/// Reads the versioned state stored under `key` and migrates it to the latest layout.
+///
+/// State found in an older layout is written back in the latest one, so the migration runs only
+/// once. Returns `None` if nothing is stored under `key`.
+pub fn read_and_migrate<T: Versioned>(key: Keyspace) -> Result<Option<T::Latest>, CommonResult>
+where
+    T::Latest: Clone,
+{
+    let Some(bytes) = casper::read_into_vec(key)? else {
+        return Ok(None);
+    };
+    let versioned: T = borsh::from_slice(&bytes).map_err(|_| CommonResult::InvalidData)?;
+    if versioned.is_latest() {
+        return Ok(Some(versioned.into_latest()));
+    }
+
+    let latest = versioned.into_latest();
+    write_versioned::<T>(key, latest.clone())?;
+    Ok(Some(latest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::casper::native::dispatch;
+
+    const COUNTER_KEY: Keyspace = Keyspace::Context(b"counter");
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+    #[borsh(crate = "crate::serializers::borsh")]
+    struct CounterV1 {
+        value: u64,
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+    #[borsh(crate = "crate::serializers::borsh")]
+    struct CounterV2 {
+        value: u64,
+        step: u64,
+    }
+
+    impl Migrate<CounterV1> for CounterV2 {
+        fn migrate(previous: CounterV1) -> Self {
+            CounterV2 {
+                value: previous.value,
+                step: 1,
+            }
+        }
+    }
+
+    type CounterState = VersionedState<CounterV1, CounterV2>;
+
+    #[test]
+    fn should_migrate_counter_state_to_latest_layout() {
+        dispatch(|| {
+            assert_eq!(read_and_migrate::<CounterState>(COUNTER_KEY), Ok(None));
+
+            let v1_bytes = borsh::to_vec(&CounterState::V1(CounterV1 { value: 42 })).unwrap();
+            casper::write(COUNTER_KEY, &v1_bytes).unwrap();
+
+            let expected = CounterV2 { value: 42, step: 1 };
+            assert_eq!(
+                read_and_migrate::<CounterState>(COUNTER_KEY),
+                Ok(Some(expected.clone()))
+            );
+
+            // The migrated state was written back in the latest layout.
+            let stored = casper::read_into_vec(COUNTER_KEY).unwrap().unwrap();
+            assert_eq!(
+                borsh::from_slice::<CounterState>(&stored).unwrap(),
+                CounterState::V2(expected)
+            );
+        })
+        .unwrap();
+    }
+}
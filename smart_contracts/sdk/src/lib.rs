@@ -19,11 +19,12 @@ pub mod contrib;
 pub mod schema;
 pub mod types;
 
-use crate::prelude::{marker::PhantomData, ptr::NonNull};
+use crate::prelude::{marker::PhantomData, ptr::NonNull, String};
 
 use crate::serializers::borsh::{BorshDeserialize, BorshSerialize};
 use casper::{CallResult, Entity};
 pub use casper_executor_wasm_common;
+use casper_executor_wasm_common::error::CommonResult;
 pub use casper_macros as macros;
 pub use casper_sdk_sys as sys;
 use types::{Address, CallError};
@@ -60,6 +61,28 @@ pub fn reserve_vec_space(vec: &mut Vec<u8>, size: usize) -> Option<NonNull<u8>>
     }
 }
 
+/// Predicts the address of a contract before it is created.
+///
+/// Uses the same computation as the host: the address is derived from the chain name, the
+/// address of the entity creating the contract, the hash of its Wasm bytecode and the optional
+/// seed passed to [`ContractBuilder::with_seed`]. This can be run off-chain to learn where a
+/// contract will live ahead of its installation.
+pub fn predict_address(
+    chain_name: &str,
+    initiator: &Address,
+    bytecode: &[u8],
+    seed: Option<&[u8; 32]>,
+) -> Address {
+    let bytecode_hash =
+        casper_executor_wasm_common::chain_utils::compute_wasm_bytecode_hash(bytecode);
+    casper_executor_wasm_common::chain_utils::compute_predictable_address(
+        chain_name,
+        *initiator,
+        bytecode_hash,
+        seed.copied(),
+    )
+}
+
 pub trait ContractRef {
     fn new() -> Self;
 }
@@ -72,19 +95,75 @@ pub trait ToCallData {
     fn input_data(&self) -> Option<crate::prelude::Vec<u8>>;
 }
 
-/// To derive this contract you have to use `#[casper]` macro on top of impl block.
+/// To derive this contract you have to use `#[casper(contract_state)]` macro on top of the struct
+/// holding the contract's state.
 ///
 /// This proc macro handles generation of a manifest.
 pub trait Contract {
     type Ref: ContractRef;
 
+    /// Name of the contract state struct.
     fn name() -> &'static str;
+
+    /// Version declared with `#[casper(contract_state, version = "...")]`, or otherwise the
+    /// version of the contract's crate.
+    fn version() -> &'static str;
+
+    /// Metadata of the contract, as returned by its `__casper_metadata` export.
+    fn metadata() -> ContractMetadata {
+        ContractMetadata {
+            name: Self::name().into(),
+            version: Self::version().into(),
+        }
+    }
+
+    /// Creates a new instance of the contract from the code being executed, calling the
+    /// constructor described by `call_data` and transferring `value` to it.
     fn create<T: ToCallData>(
         value: u64,
         call_data: T,
-    ) -> Result<ContractHandle<Self::Ref>, CallError>;
-    fn default_create() -> Result<ContractHandle<Self::Ref>, CallError>;
-    fn upgrade<T: ToCallData>(code: Option<&[u8]>, call_data: T) -> Result<(), CallError>;
+    ) -> Result<ContractHandle<Self::Ref>, CallError> {
+        let input_data = call_data.input_data();
+        let create_result = casper::create(
+            None,
+            value,
+            Some(call_data.entry_point()),
+            input_data.as_deref(),
+            None,
+        )?;
+        Ok(ContractHandle::from_address(create_result.contract_address))
+    }
+
+    /// Creates a new instance of the contract from the code being executed, without calling a
+    /// constructor.
+    fn default_create() -> Result<ContractHandle<Self::Ref>, CallError> {
+        let create_result = casper::create(None, 0, None, None, None)?;
+        Ok(ContractHandle::from_address(create_result.contract_address))
+    }
+
+    /// Upgrades the contract being executed to `code`, calling the entry point described by
+    /// `call_data` afterwards.
+    ///
+    /// The host has no way to reuse the code being executed, so `None` fails with
+    /// [`CallError::NotCallable`].
+    fn upgrade<T: ToCallData>(code: Option<&[u8]>, call_data: T) -> Result<(), CallError> {
+        let Some(code) = code else {
+            return Err(CallError::NotCallable);
+        };
+        let input_data = call_data.input_data();
+        casper::upgrade(code, Some(call_data.entry_point()), input_data.as_deref())
+    }
+}
+
+/// Metadata of a contract, as returned by its `__casper_metadata` export.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "crate::serializers::borsh")]
+pub struct ContractMetadata {
+    /// Name of the contract state struct.
+    pub name: String,
+    /// Version declared with `#[casper(contract_state, version = "...")]`, or otherwise the
+    /// version of the contract's crate.
+    pub version: String,
 }
 
 #[derive(Debug)]
@@ -132,6 +211,29 @@ macro_rules! revert {
     }};
 }
 
+/// Reverts the execution with a serialized error if the condition does not hold.
+///
+/// Unlike `assert!`, which aborts the execution with a trap, this returns `$err` from the
+/// enclosing function after reverting with its serialized value, so the caller receives
+/// structured error data. `$err` has to match the return type of the enclosing function, i.e.
+/// entry points returning `Result<T, E>` should pass `Err(error)`.
+///
+/// ```ignore
+/// fn withdraw(&mut self, amount: u64) -> Result<(), WithdrawError> {
+///     casper_assert!(amount <= self.balance, Err(WithdrawError::InsufficientBalance));
+///     self.balance -= amount;
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! casper_assert {
+    ($cond:expr, $err:expr $(,)?) => {{
+        if !$cond {
+            return $crate::revert!($err);
+        }
+    }};
+}
+
 pub trait UnwrapOrRevert<T> {
     /// Unwraps the value into its inner type or calls [`crate::casper::ret`] with a
     /// predetermined error code on failure.
@@ -154,6 +256,23 @@ where
     }
 }
 
+/// Propagates a failed host function call as a revert.
+///
+/// This is [`UnwrapOrRevert`] for the [`CommonResult`] returned by host function wrappers such as
+/// [`casper::remove`] or [`casper::emit_raw`]. The caller receives the serialized
+/// [`CommonResult`], whose tag is the host error code.
+pub trait UnwrapOrRevertHost<T> {
+    /// Unwraps the result of a host function, or calls [`crate::casper::ret`] with the
+    /// [`CommonResult`] the host function failed with.
+    fn unwrap_or_revert_host(self) -> T;
+}
+
+impl<T> UnwrapOrRevertHost<T> for Result<T, CommonResult> {
+    fn unwrap_or_revert_host(self) -> T {
+        self.unwrap_or_revert()
+    }
+}
+
 #[derive(Debug)]
 pub struct ContractHandle<T: ContractRef> {
     contract_address: Address,
@@ -278,6 +397,43 @@ impl<T: ContractRef> CallBuilder<T> {
     }
 }
 
+/// Result of a contract creation that keeps the data returned by a reverted constructor.
+#[derive(Debug)]
+pub struct CreateResult<T: ContractRef> {
+    /// Data returned by the constructor.
+    ///
+    /// When the constructor reverts this holds the borsh encoded revert value, which
+    /// [`CreateResult::revert_data`] decodes. `None` if the constructor returned no data.
+    pub data: Option<Vec<u8>>,
+    /// Handle to the created contract, or the error the creation failed with.
+    pub result: Result<ContractHandle<T>, CallError>,
+}
+
+impl<T: ContractRef> CreateResult<T> {
+    /// Returns the handle to the created contract, discarding the returned data.
+    pub fn into_result(self) -> Result<ContractHandle<T>, CallError> {
+        self.result
+    }
+
+    /// Returns `true` if the constructor reverted.
+    pub fn did_revert(&self) -> bool {
+        matches!(self.result, Err(CallError::CalleeReverted))
+    }
+
+    /// Decodes the data the constructor reverted with, if it did revert.
+    ///
+    /// Returns an error if the data does not decode as `E`.
+    pub fn revert_data<E: BorshDeserialize>(
+        &self,
+    ) -> Result<Option<E>, serializers::borsh::io::Error> {
+        if !self.did_revert() {
+            return Ok(None);
+        }
+        let data = self.data.as_deref().unwrap_or_default();
+        serializers::borsh::from_slice(data).map(Some)
+    }
+}
+
 pub struct ContractBuilder<'a, T: ContractRef> {
     transferred_value: Option<u64>,
     code: Option<&'a [u8]>,
@@ -341,6 +497,36 @@ impl<'a, T: ContractRef> ContractBuilder<'a, T> {
         Ok(ContractHandle::from_address(create_result.contract_address))
     }
 
+    /// Creates the contract like [`Self::create`], but keeps the data returned by a reverted
+    /// constructor so the caller can decode why it failed.
+    pub fn try_create<CallData: ToCallData>(
+        &self,
+        func: impl FnOnce() -> CallData,
+    ) -> Result<CreateResult<T>, CallError> {
+        let value = self.transferred_value.unwrap_or(0);
+        let call_data = func();
+        let input_data = call_data.input_data();
+        let seed = self.seed;
+        let (data, create_result) = casper::try_create(
+            self.code,
+            value,
+            Some(call_data.entry_point()),
+            input_data.as_deref(),
+            seed,
+        );
+        match create_result {
+            Ok(create_result) => Ok(CreateResult {
+                data,
+                result: Ok(ContractHandle::from_address(create_result.contract_address)),
+            }),
+            Err(CallError::CalleeReverted) => Ok(CreateResult {
+                data,
+                result: Err(CallError::CalleeReverted),
+            }),
+            Err(error) => Err(error),
+        }
+    }
+
     pub fn default_create(&self) -> Result<ContractHandle<T>, CallError> {
         if self.transferred_value.is_some() {
             panic!("Value should not be set for default create");
@@ -353,6 +539,39 @@ impl<'a, T: ContractRef> ContractBuilder<'a, T> {
     }
 }
 
+/// Generates deterministic seeds for [`ContractBuilder::with_seed`].
+///
+/// Seeds are consecutive big-endian 256-bit integers starting at the base seed, so the same base
+/// always yields the same sequence of contract addresses.
+#[derive(Debug, Default, Clone)]
+pub struct SeedGenerator {
+    next_seed: [u8; 32],
+}
+
+impl SeedGenerator {
+    /// Creates a generator whose first seed is `base`.
+    #[must_use]
+    pub fn from_base(base: &[u8; 32]) -> Self {
+        SeedGenerator { next_seed: *base }
+    }
+}
+
+impl Iterator for SeedGenerator {
+    type Item = [u8; 32];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let seed = self.next_seed;
+        for byte in self.next_seed.iter_mut().rev() {
+            let (value, overflow) = byte.overflowing_add(1);
+            *byte = value;
+            if !overflow {
+                break;
+            }
+        }
+        Some(seed)
+    }
+}
+
 /// Trait for converting a message data to a string.
 pub trait Message: BorshSerialize {
     const TOPIC: &'static str;
@@ -362,6 +581,100 @@ pub trait Message: BorshSerialize {
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        casper::{
+            self,
+            native::{dispatch, dispatch_export_call, MessageLimits, NativeTrap},
+        },
+        casper_executor_wasm_common::{
+            error::CommonResult, flags::ReturnFlags, keyspace::Keyspace,
+        },
+        serializers::borsh::{self, BorshSerialize},
+        SeedGenerator, UnwrapOrRevertHost,
+    };
+
+    #[derive(BorshSerialize, Debug, PartialEq)]
+    #[borsh(crate = "crate::serializers::borsh")]
+    enum DivisionError {
+        DivisionByZero,
+    }
+
+    fn checked_div(lhs: u64, rhs: u64) -> Result<u64, DivisionError> {
+        casper_assert!(rhs != 0, Err(DivisionError::DivisionByZero));
+        Ok(lhs / rhs)
+    }
+
     #[test]
     fn test_call_builder() {}
+
+    #[test]
+    fn seed_generator_should_increment_from_base() {
+        let mut base = [0; 32];
+        base[31] = 0xff;
+        let seeds: Vec<_> = SeedGenerator::from_base(&base).take(2).collect();
+
+        let mut expected_next = [0; 32];
+        expected_next[30] = 1;
+        assert_eq!(seeds, vec![base, expected_next]);
+    }
+
+    #[test]
+    fn casper_assert_should_pass_through() {
+        assert_eq!(dispatch(|| checked_div(10, 2)).unwrap(), Ok(5));
+    }
+
+    #[test]
+    fn casper_assert_should_revert_with_data() {
+        let trap = dispatch(|| checked_div(10, 0)).unwrap_err();
+        let expected_data = borsh::to_vec(&Err::<u64, _>(DivisionError::DivisionByZero)).unwrap();
+        match trap {
+            NativeTrap::Return(flags, data) => {
+                assert_eq!(flags, ReturnFlags::REVERT);
+                assert_eq!(data.as_ref(), expected_data.as_slice());
+            }
+            NativeTrap::Panic(_) | NativeTrap::Abort(_) => panic!("expected a revert"),
+        }
+    }
+
+    /// Runs `f` as an exported entry point and returns the host error it reverted with.
+    fn reverted_host_error(f: impl FnOnce() + Send + std::panic::UnwindSafe) -> CommonResult {
+        match dispatch(|| dispatch_export_call(f)).unwrap() {
+            Err(NativeTrap::Return(flags, data)) => {
+                assert_eq!(flags, ReturnFlags::REVERT);
+                borsh::from_slice(&data).expect("should decode host error")
+            }
+            other => panic!("expected a revert, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unwrap_or_revert_host_should_revert_with_not_found_from_remove() {
+        let host_error = reverted_host_error(|| {
+            casper::remove(Keyspace::Context(b"missing")).unwrap_or_revert_host();
+        });
+        assert_eq!(host_error, CommonResult::NotFound);
+    }
+
+    #[test]
+    fn unwrap_or_revert_host_should_revert_with_too_many_topics_from_emit_raw() {
+        let host_error = reverted_host_error(|| {
+            for index in 0..MessageLimits::default().max_topics_per_contract {
+                casper::emit_raw(&format!("topic_{index}"), b"payload").unwrap_or_revert_host();
+            }
+            // Emitting under an existing topic is still possible.
+            casper::emit_raw("topic_0", b"payload").unwrap_or_revert_host();
+            assert_eq!(
+                casper::emit_raw("one_too_many", b"payload"),
+                Err(CommonResult::TooManyTopics)
+            );
+            casper::emit_raw("one_too_many", b"payload").unwrap_or_revert_host();
+        });
+        assert_eq!(host_error, CommonResult::TooManyTopics);
+    }
+
+    #[test]
+    fn unwrap_or_revert_host_should_pass_through_success() {
+        let value = dispatch(|| Ok::<_, CommonResult>(42).unwrap_or_revert_host()).unwrap();
+        assert_eq!(value, 42);
+    }
 }
@@ -47,6 +47,9 @@ pub struct SchemaEntryPoint {
         deserialize_with = "deserialize_bits"
     )]
     pub flags: EntryPointFlags,
+    /// Documentation of the entry point, collected from its doc comments.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub docs: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -64,6 +67,13 @@ pub struct SchemaMessage {
     pub decl: Declaration,
 }
 
+/// An associated const of the contract declared with `#[casper(const)]`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct SchemaConstant {
+    pub name: String,
+    pub decl: Declaration,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Schema {
     pub name: String,
@@ -73,6 +83,8 @@ pub struct Schema {
     pub definitions: Definitions,
     pub entry_points: Vec<SchemaEntryPoint>,
     pub messages: Vec<SchemaMessage>,
+    #[serde(default)]
+    pub constants: Vec<SchemaConstant>,
 }
 
 #[derive(Debug)]
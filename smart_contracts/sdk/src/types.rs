@@ -1,16 +1,93 @@
 use casper_executor_wasm_common::error::{
-    CALLEE_GAS_DEPLETED, CALLEE_NOT_CALLABLE, CALLEE_REVERTED, CALLEE_TRAPPED,
+    CALLEE_ABORTED, CALLEE_GAS_DEPLETED, CALLEE_NOT_CALLABLE, CALLEE_NOT_PAYABLE, CALLEE_REVERTED,
+    CALLEE_TRAPPED,
 };
 
 use crate::{
     abi::{CasperABI, Declaration, Definition, EnumVariant},
-    prelude::fmt,
+    prelude::{fmt, string::String},
     serializers::borsh::{BorshDeserialize, BorshSerialize},
 };
 
 pub type Address = [u8; 32];
 pub use bnum::types::U256;
 
+/// Hash identifying an account.
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[borsh(crate = "crate::serializers::borsh")]
+pub struct AccountHash(pub [u8; 32]);
+
+impl AccountHash {
+    /// Constructs a new `AccountHash` from raw bytes.
+    #[must_use]
+    pub const fn new(value: [u8; 32]) -> Self {
+        AccountHash(value)
+    }
+
+    /// Returns the raw bytes of the account hash.
+    #[must_use]
+    pub fn value(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// A point in time, expressed as milliseconds since the Unix epoch.
+#[derive(
+    BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[borsh(crate = "crate::serializers::borsh")]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// Constructs a new `Timestamp` from milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn from_millis(millis: u64) -> Self {
+        Timestamp(millis)
+    }
+
+    /// Returns the number of milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn millis(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for Timestamp {
+    fn from(millis: u64) -> Self {
+        Timestamp(millis)
+    }
+}
+
+impl From<Timestamp> for u64 {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+/// Error an entry point reverts with when one of its named arguments can't be decoded.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+#[borsh(crate = "crate::serializers::borsh")]
+pub struct NamedArgError {
+    /// Name of the argument.
+    pub name: String,
+    /// Name of the type the argument was expected to decode as.
+    pub expected_type: String,
+}
+
+impl fmt::Display for NamedArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "named arg `{}` can't be decoded as `{}`",
+            self.name, self.expected_type
+        )
+    }
+}
+
+pub use casper_executor_wasm_common::error::HostError;
+
 // Keep in sync with [`casper_executor_wasm_common::error::CallError`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 #[borsh(crate = "crate::serializers::borsh")]
@@ -19,6 +96,12 @@ pub enum CallError {
     CalleeTrapped,
     CalleeGasDepleted,
     NotCallable,
+    /// The callee aborted.
+    ///
+    /// The [`HostError::Aborted`] it raised is available through
+    /// [`crate::casper::CallResult::host_error`].
+    CalleeAborted,
+    NotPayable,
 }
 
 impl fmt::Display for CallError {
@@ -28,6 +111,8 @@ impl fmt::Display for CallError {
             CallError::CalleeTrapped => write!(f, "callee trapped"),
             CallError::CalleeGasDepleted => write!(f, "callee gas depleted"),
             CallError::NotCallable => write!(f, "not callable"),
+            CallError::CalleeAborted => write!(f, "callee aborted"),
+            CallError::NotPayable => write!(f, "not payable"),
         }
     }
 }
@@ -41,6 +126,8 @@ impl TryFrom<u32> for CallError {
             CALLEE_TRAPPED => Ok(Self::CalleeTrapped),
             CALLEE_GAS_DEPLETED => Ok(Self::CalleeGasDepleted),
             CALLEE_NOT_CALLABLE => Ok(Self::NotCallable),
+            CALLEE_ABORTED => Ok(Self::CalleeAborted),
+            CALLEE_NOT_PAYABLE => Ok(Self::NotPayable),
             _ => Err(()),
         }
     }
@@ -76,6 +163,16 @@ impl CasperABI for CallError {
                     discriminant: 3,
                     decl: <()>::declaration(),
                 },
+                EnumVariant {
+                    name: "CalleeAborted".into(),
+                    discriminant: 4,
+                    decl: <()>::declaration(),
+                },
+                EnumVariant {
+                    name: "NotPayable".into(),
+                    discriminant: 5,
+                    decl: <()>::declaration(),
+                },
             ],
         }
     }
@@ -0,0 +1,73 @@
+use casper_sdk::{
+    casper::{self, native::dispatch},
+    prelude::*,
+    types::{CallError, HostError},
+    ContractBuilder,
+};
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Counter {
+    value: u64,
+}
+
+#[casper]
+impl Counter {
+    #[casper(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn increment_then_abort(&mut self) {
+        self.value += 1;
+        casper::abort("counter is frozen");
+    }
+
+    /// Calls back into the currently executing contract and reports the message the nested call
+    /// aborted with.
+    pub fn call_self(&self) -> Option<String> {
+        let callee = casper::get_callee();
+        casper::call(callee.address(), 0, CounterRef.increment_then_abort())
+            .expect("Should call")
+            .abort_message()
+            .map(ToOwned::to_owned)
+    }
+}
+
+#[test]
+fn caller_should_observe_callee_abort() {
+    dispatch(|| {
+        let counter = ContractBuilder::<CounterRef>::new()
+            .create(CounterRef::new)
+            .expect("Should create");
+
+        let message = counter
+            .call(|counter| counter.call_self())
+            .expect("Should call");
+        assert_eq!(message.as_deref(), Some("counter is frozen"));
+
+        let call_result = counter
+            .try_call(|counter| counter.increment_then_abort())
+            .expect("Should call");
+        assert_eq!(call_result.result, Err(CallError::CalleeAborted));
+        assert_eq!(
+            call_result.host_error,
+            Some(HostError::Aborted("counter is frozen".into()))
+        );
+        assert_eq!(call_result.data, None);
+
+        let result = counter.call(|counter| counter.increment_then_abort());
+        assert_eq!(result, Err(CallError::CalleeAborted));
+        assert_eq!(
+            counter
+                .call(|counter| counter.value())
+                .expect("Should call"),
+            0
+        );
+    })
+    .unwrap();
+}
@@ -0,0 +1,41 @@
+use casper_sdk::{prelude::*, Contract, ContractMetadata};
+
+#[casper(contract_state, version = "1.2.3")]
+#[derive(Default)]
+pub struct Versioned {
+    value: u64,
+}
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Unversioned {
+    value: u64,
+}
+
+#[test]
+fn metadata_should_carry_declared_name_and_version() {
+    assert_eq!(
+        Versioned::metadata(),
+        ContractMetadata {
+            name: "Versioned".to_string(),
+            version: "1.2.3".to_string(),
+        }
+    );
+}
+
+#[test]
+fn metadata_version_should_default_to_crate_version() {
+    assert_eq!(
+        Unversioned::metadata(),
+        ContractMetadata {
+            name: "Unversioned".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
+}
+
+#[test]
+fn version_should_match_metadata() {
+    assert_eq!(Versioned::version(), "1.2.3");
+    assert_eq!(Unversioned::version(), env!("CARGO_PKG_VERSION"));
+}
@@ -0,0 +1,196 @@
+use casper_sdk::{
+    casper::native::TestEnv, macros::contract_test, prelude::*, types::CallError, ContractBuilder,
+};
+
+#[casper(contract_state)]
+pub struct Registry {
+    owner: Entity,
+    greeting: String,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        panic!("Unable to instantiate contract without a constructor");
+    }
+}
+
+#[casper]
+impl Registry {
+    #[casper(constructor)]
+    pub fn new(greeting: String) -> Self {
+        Self {
+            owner: casper::get_caller(),
+            greeting,
+        }
+    }
+
+    #[casper(constructor, payable)]
+    pub fn with_deposit(greeting: String) -> Self {
+        Self::new(greeting)
+    }
+
+    #[casper(constructor, payable)]
+    pub fn with_failing_deposit(_greeting: String) -> Self {
+        panic!("Deposit rejected");
+    }
+
+    #[casper(payable)]
+    pub fn deposit(&self) {}
+
+    #[casper(payable, revert_on_error)]
+    pub fn deposit_at_most(&self, limit: u64) -> Result<(), String> {
+        if casper::transferred_value() > limit {
+            return Err("Deposit exceeds the limit".into());
+        }
+        Ok(())
+    }
+
+    pub fn owner(&self) -> Entity {
+        self.owner
+    }
+
+    pub fn greeting(&self) -> String {
+        self.greeting.clone()
+    }
+
+    pub fn owner_balance(&self) -> u64 {
+        casper::get_balance_of(&self.owner)
+    }
+}
+
+#[contract_test]
+fn should_create_and_call_contract_as_funded_account(env: &TestEnv) {
+    let alice = env.create_account(1000);
+    let bob = env.create_account(0);
+    assert_ne!(alice, bob);
+
+    env.fund(alice, 500);
+    assert_eq!(env.balance_of(&alice), 1500);
+    assert_eq!(env.balance_of(&bob), 0);
+
+    let registry = env
+        .as_account(alice, || {
+            ContractBuilder::<RegistryRef>::new()
+                .create(|| RegistryRef::new("Hello".into()))
+                .expect("Should create")
+        })
+        .expect("Should not trap");
+
+    assert_eq!(registry.call(|registry| registry.owner()), Ok(alice));
+    assert_eq!(
+        registry.call(|registry| registry.greeting()),
+        Ok("Hello".to_string())
+    );
+    assert_eq!(registry.call(|registry| registry.owner_balance()), Ok(1500));
+}
+
+#[contract_test]
+fn should_run_without_test_env() {
+    let registry = ContractBuilder::<RegistryRef>::new()
+        .create(|| RegistryRef::new("Hi".into()))
+        .expect("Should create");
+    assert_eq!(
+        registry.call(|registry| registry.greeting()),
+        Ok("Hi".to_string())
+    );
+}
+
+#[contract_test]
+fn should_report_value_transferred_to_contract(env: &TestEnv) {
+    let alice = env.create_account(1000);
+
+    let registry = env
+        .as_account(alice, || {
+            let registry = ContractBuilder::<RegistryRef>::new()
+                .with_transferred_value(300)
+                .create(|| RegistryRef::with_deposit("Hello".into()))
+                .expect("Should create");
+            registry
+                .build_call()
+                .with_transferred_value(200)
+                .call(|registry| registry.deposit())
+                .expect("Should deposit");
+            registry
+        })
+        .expect("Should not trap");
+
+    assert_eq!(registry.balance(), 500);
+    assert_eq!(env.balance_of(&alice), 500);
+}
+
+#[contract_test]
+fn should_revert_when_transferred_value_exceeds_balance(env: &TestEnv) {
+    let alice = env.create_account(100);
+
+    let result = env
+        .as_account(alice, || {
+            ContractBuilder::<RegistryRef>::new()
+                .with_transferred_value(300)
+                .create(|| RegistryRef::with_deposit("Hello".into()))
+        })
+        .expect("Should not trap");
+
+    assert_eq!(result.err(), Some(CallError::CalleeReverted));
+    assert_eq!(env.balance_of(&alice), 100);
+}
+
+#[contract_test]
+fn should_reject_value_transferred_to_non_payable_entry_point(env: &TestEnv) {
+    let alice = env.create_account(1000);
+
+    let (registry, call_result) = env
+        .as_account(alice, || {
+            let registry = ContractBuilder::<RegistryRef>::new()
+                .create(|| RegistryRef::new("Hello".into()))
+                .expect("Should create");
+            let call_result = registry
+                .build_call()
+                .with_transferred_value(100)
+                .try_call(|registry| registry.greeting());
+            (registry, call_result)
+        })
+        .expect("Should not trap");
+
+    assert_eq!(call_result.err(), Some(CallError::NotPayable));
+    assert_eq!(registry.balance(), 0);
+    assert_eq!(env.balance_of(&alice), 1000);
+}
+
+#[contract_test]
+fn should_refund_value_when_callee_reverts(env: &TestEnv) {
+    let alice = env.create_account(1000);
+
+    let (registry, call_result) = env
+        .as_account(alice, || {
+            let registry = ContractBuilder::<RegistryRef>::new()
+                .create(|| RegistryRef::new("Hello".into()))
+                .expect("Should create");
+            let call_result = registry
+                .build_call()
+                .with_transferred_value(100)
+                .try_call(|registry| registry.deposit_at_most(50))
+                .expect("Should call");
+            (registry, call_result)
+        })
+        .expect("Should not trap");
+
+    assert!(call_result.did_revert());
+    assert_eq!(registry.balance(), 0);
+    assert_eq!(env.balance_of(&alice), 1000);
+}
+
+#[contract_test]
+fn should_refund_value_when_constructor_traps(env: &TestEnv) {
+    let alice = env.create_account(1000);
+
+    let result = env
+        .as_account(alice, || {
+            ContractBuilder::<RegistryRef>::new()
+                .with_transferred_value(300)
+                .create(|| RegistryRef::with_failing_deposit("Hello".into()))
+        })
+        .expect("Should not trap");
+
+    assert_eq!(result.err(), Some(CallError::CalleeTrapped));
+    assert_eq!(env.balance_of(&alice), 1000);
+}
@@ -0,0 +1,57 @@
+use casper_sdk::{casper::native::dispatch, prelude::*, ContractBuilder};
+
+#[casper(contract_state)]
+pub struct Counter {
+    value: u64,
+    step: u64,
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        panic!("Unable to instantiate contract without a constructor");
+    }
+}
+
+#[casper]
+impl Counter {
+    #[casper(constructor)]
+    pub fn new(value: u64, #[casper(default = 1)] step: u64) -> Self {
+        Self { value, step }
+    }
+
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+#[test]
+fn constructor_should_use_default_for_omitted_argument() {
+    dispatch(|| {
+        let handle = ContractBuilder::<CounterRef>::new()
+            .try_create(|| CounterRef::new_with_defaults(10))
+            .expect("Should create")
+            .into_result()
+            .expect("Should be created");
+        assert_eq!(handle.call(|counter| counter.value()), Ok(10));
+        assert_eq!(handle.call(|counter| counter.step()), Ok(1));
+    })
+    .unwrap();
+}
+
+#[test]
+fn constructor_should_use_passed_optional_argument() {
+    dispatch(|| {
+        let handle = ContractBuilder::<CounterRef>::new()
+            .try_create(|| CounterRef::new(10, 5))
+            .expect("Should create")
+            .into_result()
+            .expect("Should be created");
+        assert_eq!(handle.call(|counter| counter.value()), Ok(10));
+        assert_eq!(handle.call(|counter| counter.step()), Ok(5));
+    })
+    .unwrap();
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use casper_sdk::{
+    casper::native::{dispatch_with, Environment, NativeTrap},
+    casper_executor_wasm_common::flags::ReturnFlags,
+    macros::entry_point,
+    serializers::borsh,
+    types::NamedArgError,
+};
+
+#[entry_point]
+fn transfer(amount: u64, memo: Option<String>) -> Result<(), ()> {
+    assert_eq!(amount, 42);
+    assert_eq!(memo, None);
+    Ok(())
+}
+
+#[entry_point]
+fn transfer_with_memo(amount: u64, memo: Option<String>) -> Result<(), ()> {
+    assert_eq!(amount, 42);
+    assert_eq!(memo.as_deref(), Some("hello"));
+    Ok(())
+}
+
+fn named_args(args: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let args: BTreeMap<String, Vec<u8>> = args
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect();
+    borsh::to_vec(&args).unwrap()
+}
+
+#[test]
+fn entry_point_should_treat_absent_optional_arg_as_none() {
+    let input = named_args(&[("amount", borsh::to_vec(&42u64).unwrap())]);
+    dispatch_with(Environment::default().with_input_data(input), transfer).unwrap();
+}
+
+#[test]
+fn entry_point_should_decode_present_optional_arg() {
+    let input = named_args(&[
+        ("amount", borsh::to_vec(&42u64).unwrap()),
+        ("memo", borsh::to_vec("hello").unwrap()),
+    ]);
+    dispatch_with(
+        Environment::default().with_input_data(input),
+        transfer_with_memo,
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "should get named arg")]
+fn entry_point_should_panic_on_absent_required_arg() {
+    let input = named_args(&[("memo", borsh::to_vec("hello").unwrap())]);
+    let _ = dispatch_with(
+        Environment::default().with_input_data(input),
+        transfer_with_memo,
+    );
+}
+
+#[test]
+fn entry_point_should_revert_on_wrong_typed_arg() {
+    let input = named_args(&[("amount", borsh::to_vec("forty two").unwrap())]);
+    let trap = dispatch_with(Environment::default().with_input_data(input), transfer).unwrap_err();
+    match trap {
+        NativeTrap::Return(flags, data) => {
+            assert_eq!(flags, ReturnFlags::REVERT);
+            let error: NamedArgError = borsh::from_slice(&data).unwrap();
+            assert_eq!(
+                error,
+                NamedArgError {
+                    name: "amount".to_string(),
+                    expected_type: "u64".to_string(),
+                }
+            );
+            assert_eq!(
+                error.to_string(),
+                "named arg `amount` can't be decoded as `u64`"
+            );
+        }
+        NativeTrap::Panic(_) | NativeTrap::Abort(_) => panic!("expected a revert"),
+    }
+}
@@ -0,0 +1,36 @@
+use casper_sdk::prelude::*;
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Counter {
+    value: u64,
+}
+
+#[casper]
+impl Counter {
+    #[casper(constructor)]
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+
+    pub fn get(&self) -> u64 {
+        self.value
+    }
+
+    pub fn increment(&mut self) {
+        self.value = self.increment_by();
+    }
+
+    fn increment_by(&self) -> u64 {
+        self.value + 1
+    }
+
+    pub fn reset(&mut self) {
+        self.value = 0;
+    }
+}
+
+#[test]
+fn entry_point_names_should_list_exported_methods() {
+    assert_eq!(Counter::ENTRY_POINT_NAMES, &["new", "get", "increment", "reset"]);
+}
@@ -0,0 +1,34 @@
+use casper_sdk::{
+    casper::native::{dispatch, NativeTrap},
+    casper_executor_wasm_common::flags::ReturnFlags,
+    prelude::*,
+    serializers::borsh,
+    ContractMetadata,
+};
+
+#[casper(contract_state, version = "1.2.3", export_metadata)]
+#[derive(Default)]
+pub struct Versioned {
+    value: u64,
+}
+
+#[test]
+fn metadata_export_should_return_name_and_version() {
+    // `extern "C"` functions don't implement `FnOnce`, hence the closure.
+    #[allow(clippy::redundant_closure)]
+    let result = dispatch(|| __casper_metadata());
+    match result {
+        Err(NativeTrap::Return(flags, bytes)) => {
+            assert_eq!(flags, ReturnFlags::empty());
+            let metadata: ContractMetadata = borsh::from_slice(&bytes).unwrap();
+            assert_eq!(
+                metadata,
+                ContractMetadata {
+                    name: "Versioned".to_string(),
+                    version: "1.2.3".to_string(),
+                }
+            );
+        }
+        other => panic!("expected metadata to be returned, got {other:?}"),
+    }
+}
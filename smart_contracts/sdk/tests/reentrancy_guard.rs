@@ -0,0 +1,64 @@
+use casper_sdk::{
+    casper::{self, native::dispatch},
+    contrib::reentrancy_guard::{NonReentrant, NonReentrantExt, ReentrancyGuardError},
+    prelude::*,
+    ContractBuilder, ToCallData,
+};
+
+/// Calls back into the currently executing contract and reports whether the nested call reverted.
+fn call_self<T: ToCallData>(call_data: T) -> bool {
+    let callee = casper::get_callee();
+    casper::call(callee.address(), 0, call_data)
+        .expect("Should call")
+        .did_revert()
+}
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Vault;
+
+#[casper]
+impl Vault {
+    #[casper(constructor)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[casper(revert_on_error)]
+    pub fn guarded_withdraw(&mut self, reenter: bool) -> Result<bool, ReentrancyGuardError> {
+        self.guarded(|_| reenter && call_self(VaultRef.guarded_withdraw(false)))
+    }
+
+    pub fn unguarded_withdraw(&mut self, reenter: bool) -> bool {
+        reenter && call_self(VaultRef.unguarded_withdraw(false))
+    }
+}
+
+#[casper(path = casper_sdk::contrib::reentrancy_guard)]
+impl NonReentrant for Vault {}
+
+#[test]
+fn guarded_reentrant_call_should_revert() {
+    dispatch(|| {
+        let vault = ContractBuilder::<VaultRef>::new()
+            .create(VaultRef::new)
+            .expect("Should create");
+
+        let nested_reverted = vault
+            .call(|vault| vault.guarded_withdraw(true))
+            .expect("Should call");
+        assert_eq!(nested_reverted, Ok(true));
+
+        // The guard is released once the outer call finishes.
+        let nested_reverted = vault
+            .call(|vault| vault.guarded_withdraw(true))
+            .expect("Should call");
+        assert_eq!(nested_reverted, Ok(true));
+
+        let nested_reverted = vault
+            .call(|vault| vault.unguarded_withdraw(true))
+            .expect("Should call");
+        assert!(!nested_reverted);
+    })
+    .unwrap();
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "__abi_generator")]
+
+use casper_sdk::{abi::CasperABI, prelude::*};
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Token {
+    total_supply: u64,
+}
+
+#[casper]
+impl Token {
+    #[casper(const)]
+    pub const NAME: &'static str = "Token";
+
+    #[casper(const)]
+    pub const DECIMALS: u8 = 9;
+
+    pub const INITIAL_SUPPLY: u64 = 0;
+
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+}
+
+#[test]
+fn schema_should_carry_declared_constants() {
+    let name = Token::__casper_schema_constant_NAME();
+    assert_eq!(name.name, "NAME");
+    assert_eq!(name.decl, <&str as CasperABI>::declaration());
+
+    let decimals = Token::__casper_schema_constant_DECIMALS();
+    assert_eq!(decimals.name, "DECIMALS");
+    assert_eq!(decimals.decl, <u8 as CasperABI>::declaration());
+
+    // The consts themselves are left intact.
+    assert_eq!(Token::NAME, "Token");
+    assert_eq!(Token::DECIMALS, 9);
+    assert_eq!(Token::INITIAL_SUPPLY, 0);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "__abi_generator")]
+
+use casper_sdk::prelude::*;
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Greeter {
+    greeting: String,
+}
+
+#[casper]
+impl Greeter {
+    /// Returns the current greeting.
+    ///
+    /// The greeting is empty until one is set.
+    pub fn greeting(&self) -> String {
+        self.greeting.clone()
+    }
+
+    pub fn set_greeting(&mut self, greeting: String) {
+        self.greeting = greeting;
+    }
+}
+
+#[test]
+fn schema_entry_point_should_carry_doc_comments() {
+    let documented = Greeter::__casper_schema_entry_point_greeting();
+    assert_eq!(
+        documented.docs,
+        "Returns the current greeting.\n\nThe greeting is empty until one is set."
+    );
+
+    let undocumented = Greeter::__casper_schema_entry_point_set_greeting();
+    assert_eq!(undocumented.docs, "");
+}
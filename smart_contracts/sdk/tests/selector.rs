@@ -0,0 +1,10 @@
+use casper_executor_wasm_common::chain_utils::compute_selector;
+use casper_sdk::macros::selector;
+
+const TRANSFER_SELECTOR: u32 = selector!("transfer");
+
+#[test]
+fn selector_should_match_runtime_computation() {
+    assert_eq!(TRANSFER_SELECTOR, compute_selector("transfer"));
+    assert_ne!(selector!("transfer"), selector!("transfer_from"));
+}
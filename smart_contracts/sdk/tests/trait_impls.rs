@@ -0,0 +1,112 @@
+use casper_sdk::{
+    casper::native::{dispatch, TestEnv},
+    macros::contract_test,
+    prelude::*,
+    types::CallError,
+    ContractBuilder,
+};
+
+#[casper]
+pub trait Greeter {
+    fn greet(&self, who: String) -> String {
+        format!("Hello, {who}!")
+    }
+
+    #[casper(payable)]
+    fn tip(&self) {}
+}
+
+#[casper]
+pub trait Adder {
+    fn add(&self, lhs: u64, rhs: u64) -> u64 {
+        lhs + rhs
+    }
+}
+
+#[casper]
+pub trait Versioned {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn is_latest(&self, version: u32) -> bool {
+        version == self.version()
+    }
+}
+
+#[casper(
+    contract_state,
+    impls(
+        Greeter,
+        Adder,
+        Versioned,
+        casper_sdk::contrib::reentrancy_guard::NonReentrant
+    )
+)]
+#[derive(Default)]
+pub struct Composed;
+
+#[casper]
+impl Composed {
+    #[casper(constructor)]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[test]
+fn entry_points_of_all_listed_traits_should_export() {
+    dispatch(|| {
+        let composed = ContractBuilder::<ComposedRef>::new()
+            .create(ComposedRef::new)
+            .expect("Should create");
+
+        let greeting = composed
+            .call(|composed| composed.greet("world".to_string()))
+            .expect("Should call");
+        assert_eq!(greeting, "Hello, world!");
+
+        let sum = composed
+            .call(|composed| composed.add(2, 3))
+            .expect("Should call");
+        assert_eq!(sum, 5);
+
+        let version = composed
+            .call(|composed| composed.version())
+            .expect("Should call");
+        assert_eq!(version, 1);
+
+        let is_latest = composed
+            .call(|composed| composed.is_latest(2))
+            .expect("Should call");
+        assert!(!is_latest);
+    })
+    .unwrap();
+}
+
+#[contract_test]
+fn trait_entry_points_should_only_accept_value_when_payable(env: &TestEnv) {
+    let alice = env.create_account(1000);
+
+    let (composed, greet_result, tip_result) = env
+        .as_account(alice, || {
+            let composed = ContractBuilder::<ComposedRef>::new()
+                .create(ComposedRef::new)
+                .expect("Should create");
+            let greet_result = composed
+                .build_call()
+                .with_transferred_value(100)
+                .try_call(|composed| composed.greet("world".to_string()));
+            let tip_result = composed
+                .build_call()
+                .with_transferred_value(100)
+                .try_call(|composed| composed.tip());
+            (composed, greet_result, tip_result)
+        })
+        .expect("Should not trap");
+
+    assert_eq!(greet_result.err(), Some(CallError::NotPayable));
+    assert!(!tip_result.expect("Should call").did_revert());
+    assert_eq!(composed.balance(), 100);
+    assert_eq!(env.balance_of(&alice), 900);
+}
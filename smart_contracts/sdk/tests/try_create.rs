@@ -0,0 +1,70 @@
+use casper_sdk::{
+    casper::{self, native::dispatch},
+    casper_executor_wasm_common::flags::ReturnFlags,
+    prelude::*,
+    serializers::borsh::{self, BorshDeserialize, BorshSerialize},
+    types::CallError,
+    ContractBuilder,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+#[borsh(crate = "casper_sdk::serializers::borsh")]
+enum SupplyError {
+    ZeroSupply,
+}
+
+#[casper(contract_state)]
+pub struct Supply {
+    total: u64,
+}
+
+impl Default for Supply {
+    fn default() -> Self {
+        panic!("Unable to instantiate contract without a constructor");
+    }
+}
+
+#[casper]
+impl Supply {
+    #[casper(constructor)]
+    pub fn new(total: u64) -> Self {
+        if total == 0 {
+            let data = borsh::to_vec(&SupplyError::ZeroSupply).unwrap();
+            casper::ret(ReturnFlags::REVERT, Some(&data));
+            unreachable!();
+        }
+        Self { total }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+#[test]
+fn try_create_should_surface_constructor_revert_data() {
+    dispatch(|| {
+        let create_result = ContractBuilder::<SupplyRef>::new()
+            .try_create(|| SupplyRef::new(0))
+            .expect("Should create");
+        assert!(create_result.did_revert());
+        assert_eq!(
+            create_result.revert_data::<SupplyError>().unwrap(),
+            Some(SupplyError::ZeroSupply)
+        );
+        assert!(create_result.revert_data::<String>().is_err());
+        assert!(matches!(
+            create_result.into_result(),
+            Err(CallError::CalleeReverted)
+        ));
+
+        let create_result = ContractBuilder::<SupplyRef>::new()
+            .try_create(|| SupplyRef::new(1000))
+            .expect("Should create");
+        assert!(!create_result.did_revert());
+        assert_eq!(create_result.revert_data::<SupplyError>().unwrap(), None);
+        let handle = create_result.into_result().expect("Should be created");
+        assert_eq!(handle.call(|supply| supply.total()), Ok(1000));
+    })
+    .unwrap();
+}
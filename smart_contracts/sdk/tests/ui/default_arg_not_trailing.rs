@@ -0,0 +1,18 @@
+use casper_sdk::prelude::*;
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Counter {
+    value: u64,
+    step: u64,
+}
+
+#[casper]
+impl Counter {
+    #[casper(constructor)]
+    pub fn new(#[casper(default = 1)] step: u64, value: u64) -> Self {
+        Self { value, step }
+    }
+}
+
+fn main() {}
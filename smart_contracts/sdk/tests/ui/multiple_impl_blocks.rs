@@ -0,0 +1,23 @@
+use casper_sdk::prelude::*;
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Counter {
+    value: u64,
+}
+
+#[casper]
+impl Counter {
+    pub fn get(&self) -> u64 {
+        self.value
+    }
+}
+
+#[casper]
+impl Counter {
+    pub fn reset(&mut self) {
+        self.value = 0;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use casper_sdk::prelude::*;
+
+#[casper(contract_state)]
+#[derive(Default)]
+pub struct Vault;
+
+#[casper]
+impl Vault {
+    #[casper(constructor)]
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[casper(payable)]
+    fn deposit(&self) {}
+}
+
+fn main() {}
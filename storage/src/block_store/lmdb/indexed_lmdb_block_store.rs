@@ -872,6 +872,22 @@ impl DataReader<TransactionHash, ExecutionResult> for IndexedLmdbBlockStoreReadT
     }
 }
 
+impl DataReader<TransactionHash, Vec<Transfer>> for IndexedLmdbBlockStoreReadTransaction<'_> {
+    fn read(&self, key: TransactionHash) -> Result<Option<Vec<Transfer>>, BlockStoreError> {
+        self.block_store
+            .block_store
+            .get_transaction_transfers(&self.txn, &key)
+    }
+
+    fn exists(&self, key: TransactionHash) -> Result<bool, BlockStoreError> {
+        self.block_store
+            .block_store
+            .execution_result_dbs
+            .exists(&self.txn, &key)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
+}
+
 impl DataReader<StateStoreKey, Vec<u8>> for IndexedLmdbBlockStoreReadTransaction<'_> {
     fn read(&self, StateStoreKey(key): StateStoreKey) -> Result<Option<Vec<u8>>, BlockStoreError> {
         self.block_store
@@ -1234,6 +1250,19 @@ impl DataReader<TransactionHash, ExecutionResult> for IndexedLmdbBlockStoreRWTra
     }
 }
 
+impl DataReader<TransactionHash, Vec<Transfer>> for IndexedLmdbBlockStoreRWTransaction<'_> {
+    fn read(&self, key: TransactionHash) -> Result<Option<Vec<Transfer>>, BlockStoreError> {
+        self.block_store.get_transaction_transfers(&self.txn, &key)
+    }
+
+    fn exists(&self, key: TransactionHash) -> Result<bool, BlockStoreError> {
+        self.block_store
+            .execution_result_dbs
+            .exists(&self.txn, &key)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
+}
+
 impl DataReader<BlockHash, Vec<Transfer>> for IndexedLmdbBlockStoreRWTransaction<'_> {
     fn read(&self, key: BlockHash) -> Result<Option<Vec<Transfer>>, BlockStoreError> {
         self.block_store.get_transfers(&self.txn, &key)
@@ -214,6 +214,20 @@ impl LmdbBlockStore {
             .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
     }
 
+    /// Returns the transfers recorded by the successful execution of the given transaction, or
+    /// `None` if no execution result is stored for it.
+    pub(crate) fn get_transaction_transfers<Tx: lmdb::Transaction>(
+        &self,
+        txn: &Tx,
+        transaction_hash: &TransactionHash,
+    ) -> Result<Option<Vec<Transfer>>, BlockStoreError> {
+        Ok(self
+            .execution_result_dbs
+            .get(txn, transaction_hash)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))?
+            .map(|execution_result| successful_transfers(&execution_result)))
+    }
+
     pub(crate) fn read_state_store<K: AsRef<[u8]>, Tx: lmdb::Transaction>(
         &self,
         txn: &Tx,
@@ -776,6 +790,22 @@ where
     }
 }
 
+impl<T> DataReader<TransactionHash, Vec<Transfer>> for LmdbBlockStoreTransaction<'_, T>
+where
+    T: LmdbTransaction,
+{
+    fn read(&self, key: TransactionHash) -> Result<Option<Vec<Transfer>>, BlockStoreError> {
+        self.block_store.get_transaction_transfers(&self.txn, &key)
+    }
+
+    fn exists(&self, key: TransactionHash) -> Result<bool, BlockStoreError> {
+        self.block_store
+            .execution_result_dbs
+            .exists(&self.txn, &key)
+            .map_err(|err| BlockStoreError::InternalStorage(Box::new(err)))
+    }
+}
+
 impl<T, K> DataReader<K, Vec<u8>> for LmdbBlockStoreTransaction<'_, T>
 where
     K: AsRef<[u8]>,
@@ -939,3 +969,79 @@ impl<'t> DataWriter<BlockHashHeightAndEra, BlockExecutionResults>
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        execution::{Effects, ExecutionResultV2},
+        testing::TestRng,
+        AccessRights, EraId, Gas, InitiatorAddr, TransactionV1Hash, TransferV2, URef, U512,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_read_transfers_by_transaction_hash() {
+        let rng = &mut TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut block_store = LmdbBlockStore::new(tempdir.path(), 50 * 1024 * 1024).unwrap();
+
+        let transaction_hash = TransactionHash::V1(TransactionV1Hash::random(rng));
+        let failed_transaction_hash = TransactionHash::V1(TransactionV1Hash::random(rng));
+        let initiator = InitiatorAddr::random(rng);
+        let transfer = Transfer::V2(TransferV2::new(
+            transaction_hash,
+            initiator.clone(),
+            None,
+            URef::new([1; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([2; 32], AccessRights::READ_ADD_WRITE),
+            U512::from(2_500_000_000u64),
+            Gas::new(100_000_000),
+            Some(7),
+        ));
+        let execution_result = |transfers: Vec<Transfer>, error_message: Option<String>| {
+            ExecutionResult::V2(Box::new(ExecutionResultV2 {
+                initiator: initiator.clone(),
+                error_message,
+                current_price: 1,
+                limit: Gas::new(100_000_000),
+                consumed: Gas::new(100_000_000),
+                cost: U512::from(100_000_000),
+                refund: U512::zero(),
+                transfers,
+                size_estimate: 0,
+                effects: Effects::new(),
+            }))
+        };
+        let exec_results = HashMap::from([
+            (
+                transaction_hash,
+                execution_result(vec![transfer.clone()], None),
+            ),
+            (
+                failed_transaction_hash,
+                execution_result(vec![transfer.clone()], Some("failed".to_string())),
+            ),
+        ]);
+
+        let mut txn = block_store.checkout_rw().unwrap();
+        txn.write(&BlockExecutionResults {
+            block_info: BlockHashHeightAndEra::new(BlockHash::random(rng), 1, EraId::new(0)),
+            exec_results,
+        })
+        .unwrap();
+        txn.commit().unwrap();
+
+        let txn = block_store.checkout_ro().unwrap();
+        let transfers: Option<Vec<Transfer>> = txn.read(transaction_hash).unwrap();
+        assert_eq!(transfers, Some(vec![transfer]));
+
+        // Transfers of failed executions are not reported.
+        let transfers: Option<Vec<Transfer>> = txn.read(failed_transaction_hash).unwrap();
+        assert_eq!(transfers, Some(vec![]));
+
+        let unknown_transaction_hash = TransactionHash::V1(TransactionV1Hash::random(rng));
+        let transfers: Option<Vec<Transfer>> = txn.read(unknown_transaction_hash).unwrap();
+        assert_eq!(transfers, None);
+    }
+}
@@ -36,6 +36,10 @@ mod key_prefix;
 pub mod message_topics;
 /// Mint provider.
 pub mod mint;
+/// Named keys provider.
+pub mod named_keys;
+/// Package versions provider.
+pub mod package_versions;
 /// Prefixed values provider.
 pub mod prefixed_values;
 mod protocol_upgrade;
@@ -57,15 +61,17 @@ pub use addressable_entity::{AddressableEntityRequest, AddressableEntityResult};
 pub use auction::{AuctionMethod, BiddingRequest, BiddingResult};
 pub use balance::{
     BalanceHolds, BalanceHoldsWithProof, BalanceIdentifier, BalanceRequest, BalanceResult,
-    GasHoldBalanceHandling, ProofHandling, ProofsResult,
+    GasHoldBalanceHandling, HoldsBreakdownHandling, ProofHandling, ProofsResult,
 };
 pub use balance_hold::{
     BalanceHoldError, BalanceHoldKind, BalanceHoldMode, BalanceHoldRequest, BalanceHoldResult,
     InsufficientBalanceHandling,
 };
 pub use balance_identifier_purse::{BalanceIdentifierPurseRequest, BalanceIdentifierPurseResult};
-pub use bids::{BidsRequest, BidsResult};
-pub use block_global::{BlockGlobalKind, BlockGlobalRequest, BlockGlobalResult};
+pub use bids::{BidsPage, BidsRequest, BidsResult};
+pub use block_global::{
+    BlockGlobalKind, BlockGlobalReadResult, BlockGlobalRequest, BlockGlobalResult,
+};
 pub use block_rewards::{BlockRewardsError, BlockRewardsRequest, BlockRewardsResult};
 pub use contract::{ContractRequest, ContractResult};
 pub use entry_points::{
@@ -84,6 +90,8 @@ pub use handle_refund::{HandleRefundMode, HandleRefundRequest, HandleRefundResul
 pub use key_prefix::KeyPrefix;
 pub use message_topics::{MessageTopicsRequest, MessageTopicsResult};
 pub use mint::{TransferRequest, TransferResult};
+pub use named_keys::{NamedKeysRequest, NamedKeysResult};
+pub use package_versions::{PackageVersionsRequest, PackageVersionsResult};
 pub use protocol_upgrade::{ProtocolUpgradeRequest, ProtocolUpgradeResult};
 pub use prune::{PruneRequest, PruneResult};
 pub use query::{QueryRequest, QueryResult};
@@ -45,6 +45,19 @@ pub enum ProofHandling {
     Proofs,
 }
 
+/// Balance hold breakdown handling options.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum HoldsBreakdownHandling {
+    /// Do not report held amounts per hold kind.
+    #[default]
+    NoBreakdown,
+    /// Report held amounts per hold kind.
+    ///
+    /// Holds are only read when using [`BalanceHandling::Available`], otherwise the breakdown is
+    /// empty.
+    Breakdown,
+}
+
 /// Represents a way to make a balance inquiry.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BalanceIdentifier {
@@ -283,6 +296,7 @@ pub struct BalanceRequest {
     identifier: BalanceIdentifier,
     balance_handling: BalanceHandling,
     proof_handling: ProofHandling,
+    holds_breakdown_handling: HoldsBreakdownHandling,
 }
 
 impl BalanceRequest {
@@ -300,6 +314,7 @@ impl BalanceRequest {
             identifier,
             balance_handling,
             proof_handling,
+            holds_breakdown_handling: HoldsBreakdownHandling::NoBreakdown,
         }
     }
 
@@ -317,6 +332,7 @@ impl BalanceRequest {
             identifier: BalanceIdentifier::Purse(purse_uref),
             balance_handling,
             proof_handling,
+            holds_breakdown_handling: HoldsBreakdownHandling::NoBreakdown,
         }
     }
 
@@ -334,6 +350,7 @@ impl BalanceRequest {
             identifier: BalanceIdentifier::Public(public_key),
             balance_handling,
             proof_handling,
+            holds_breakdown_handling: HoldsBreakdownHandling::NoBreakdown,
         }
     }
 
@@ -351,6 +368,7 @@ impl BalanceRequest {
             identifier: BalanceIdentifier::Account(account_hash),
             balance_handling,
             proof_handling,
+            holds_breakdown_handling: HoldsBreakdownHandling::NoBreakdown,
         }
     }
 
@@ -368,6 +386,7 @@ impl BalanceRequest {
             identifier: BalanceIdentifier::Entity(entity_addr),
             balance_handling,
             proof_handling,
+            holds_breakdown_handling: HoldsBreakdownHandling::NoBreakdown,
         }
     }
 
@@ -385,6 +404,7 @@ impl BalanceRequest {
             identifier: BalanceIdentifier::Internal(balance_addr),
             balance_handling,
             proof_handling,
+            holds_breakdown_handling: HoldsBreakdownHandling::NoBreakdown,
         }
     }
 
@@ -412,6 +432,20 @@ impl BalanceRequest {
     pub fn proof_handling(&self) -> ProofHandling {
         self.proof_handling
     }
+
+    /// Returns holds breakdown handling.
+    pub fn holds_breakdown_handling(&self) -> HoldsBreakdownHandling {
+        self.holds_breakdown_handling
+    }
+
+    /// Sets holds breakdown handling.
+    pub fn with_holds_breakdown_handling(
+        mut self,
+        holds_breakdown_handling: HoldsBreakdownHandling,
+    ) -> Self {
+        self.holds_breakdown_handling = holds_breakdown_handling;
+        self
+    }
 }
 
 /// Available balance checker.
@@ -428,6 +462,39 @@ pub trait AvailableBalanceChecker {
             return Ok(total_balance);
         }
 
+        let held = self
+            .held_amount_by_tag(
+                block_time,
+                gas_hold_balance_handling,
+                processing_hold_balance_handling,
+            )?
+            .into_values()
+            .fold(U512::zero(), |acc, held| acc.saturating_add(held));
+
+        if held > total_balance {
+            return Ok(U512::zero());
+        }
+
+        debug_assert!(
+            total_balance >= held,
+            "it should not be possible to hold more than the total available"
+        );
+        match total_balance.checked_sub(held) {
+            Some(available_balance) => Ok(available_balance),
+            None => {
+                error!(%held, %total_balance, "held amount exceeds total balance, which should never occur.");
+                Err(BalanceFailure::HeldExceedsTotal)
+            }
+        }
+    }
+
+    /// Calculate and return the held amount per hold kind, honoring each kind's handling.
+    fn held_amount_by_tag(
+        &self,
+        block_time: BlockTime,
+        gas_hold_balance_handling: GasHoldBalanceHandling,
+        processing_hold_balance_handling: ProcessingHoldBalanceHandling,
+    ) -> Result<BTreeMap<BalanceHoldAddrTag, U512>, BalanceFailure> {
         let gas_held = match gas_hold_balance_handling.handling() {
             HoldBalanceHandling::Accrued => self.accrued(BalanceHoldAddrTag::Gas),
             HoldBalanceHandling::Amortized => {
@@ -444,23 +511,14 @@ pub trait AvailableBalanceChecker {
             }
         };
 
-        let held = gas_held.saturating_add(processing_held);
-
-        if held > total_balance {
-            return Ok(U512::zero());
+        let mut ret = BTreeMap::new();
+        if !gas_held.is_zero() {
+            ret.insert(BalanceHoldAddrTag::Gas, gas_held);
         }
-
-        debug_assert!(
-            total_balance >= held,
-            "it should not be possible to hold more than the total available"
-        );
-        match total_balance.checked_sub(held) {
-            Some(available_balance) => Ok(available_balance),
-            None => {
-                error!(%held, %total_balance, "held amount exceeds total balance, which should never occur.");
-                Err(BalanceFailure::HeldExceedsTotal)
-            }
+        if !processing_held.is_zero() {
+            ret.insert(BalanceHoldAddrTag::Processing, processing_held);
         }
+        Ok(ret)
     }
 
     /// Calculates amortization.
@@ -653,6 +711,43 @@ impl ProofsResult {
         }
     }
 
+    /// Returns the held amount per hold kind, calculated the same way as the available balance.
+    ///
+    /// Holds older than the gas hold interval are ignored; unlike the holds read without proofs,
+    /// the holds read with proofs are not pre-filtered by age.
+    pub fn held_amount_by_tag(
+        &self,
+        block_time: BlockTime,
+        gas_hold_balance_handling: GasHoldBalanceHandling,
+        processing_hold_balance_handling: ProcessingHoldBalanceHandling,
+    ) -> Result<BTreeMap<BalanceHoldAddrTag, U512>, BalanceFailure> {
+        match self {
+            ProofsResult::NotRequested { balance_holds } => balance_holds.held_amount_by_tag(
+                block_time,
+                gas_hold_balance_handling,
+                processing_hold_balance_handling,
+            ),
+            ProofsResult::Proofs { balance_holds, .. } => {
+                let holds_epoch = block_time
+                    .value()
+                    .saturating_sub(gas_hold_balance_handling.interval().millis());
+                let active_holds: BTreeMap<BlockTime, BalanceHolds> = balance_holds
+                    .iter()
+                    .filter(|(hold_time, _)| hold_time.value() >= holds_epoch)
+                    .map(|(hold_time, holds)| {
+                        let holds = holds.iter().map(|(tag, (held, _))| (*tag, *held)).collect();
+                        (*hold_time, holds)
+                    })
+                    .collect();
+                active_holds.held_amount_by_tag(
+                    block_time,
+                    gas_hold_balance_handling,
+                    processing_hold_balance_handling,
+                )
+            }
+        }
+    }
+
     /// Returns the available balance, calculated using imputed values.
     #[allow(clippy::result_unit_err)]
     pub fn available_balance(
@@ -722,6 +817,8 @@ pub enum BalanceResult {
         available_balance: U512,
         /// Proofs result.
         proofs_result: ProofsResult,
+        /// Held amounts per hold kind, if requested via [`HoldsBreakdownHandling::Breakdown`].
+        holds_breakdown: Option<BTreeMap<BalanceHoldAddrTag, U512>>,
     },
     /// Failure.
     Failure(TrackingCopyError),
@@ -754,6 +851,17 @@ impl BalanceResult {
         }
     }
 
+    /// Returns the held amounts per hold kind for a [`BalanceResult::Success`] variant, if
+    /// requested.
+    pub fn holds_breakdown(&self) -> Option<&BTreeMap<BalanceHoldAddrTag, U512>> {
+        match self {
+            BalanceResult::Success {
+                holds_breakdown, ..
+            } => holds_breakdown.as_ref(),
+            _ => None,
+        }
+    }
+
     /// Returns the Merkle proofs, if any.
     pub fn proofs_result(self) -> Option<ProofsResult> {
         match self {
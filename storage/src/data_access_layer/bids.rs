@@ -7,18 +7,58 @@ use casper_types::{system::auction::BidKind, Digest};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BidsRequest {
     state_hash: Digest,
+    page: Option<BidsPage>,
 }
 
 impl BidsRequest {
-    /// Creates new request.
+    /// Creates new request for all bids.
     pub fn new(state_hash: Digest) -> Self {
-        BidsRequest { state_hash }
+        BidsRequest {
+            state_hash,
+            page: None,
+        }
+    }
+
+    /// Creates new request for at most `limit` bids, skipping the first `offset` bid keys.
+    ///
+    /// Bid keys are visited in trie order, so the `next_offset` returned with a page can be passed
+    /// as `offset` to request the following page. A `limit` of zero yields an empty page without
+    /// a `next_offset`.
+    pub fn paged(state_hash: Digest, offset: usize, limit: usize) -> Self {
+        BidsRequest {
+            state_hash,
+            page: Some(BidsPage { offset, limit }),
+        }
     }
 
     /// Returns state root hash.
     pub fn state_hash(&self) -> Digest {
         self.state_hash
     }
+
+    /// Returns the requested page, or `None` if all bids are requested.
+    pub fn page(&self) -> Option<BidsPage> {
+        self.page
+    }
+}
+
+/// A page of bids requested with [`BidsRequest::paged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BidsPage {
+    offset: usize,
+    limit: usize,
+}
+
+impl BidsPage {
+    /// Returns the number of bid keys to skip.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the maximum number of bid keys to visit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
 }
 
 /// Represents a result of a `get_bids` request.
@@ -30,6 +70,8 @@ pub enum BidsResult {
     Success {
         /// Current bids.
         bids: Vec<BidKind>,
+        /// Offset of the next page of bids, if the request was paged and more bids remain.
+        next_offset: Option<usize>,
     },
     /// Failure.
     Failure(TrackingCopyError),
@@ -38,7 +80,7 @@ pub enum BidsResult {
 impl BidsResult {
     /// Returns wrapped [`Vec<BidKind>`] if this represents a successful query result.
     pub fn into_option(self) -> Option<Vec<BidKind>> {
-        if let Self::Success { bids } = self {
+        if let Self::Success { bids, .. } = self {
             Some(bids)
         } else {
             None
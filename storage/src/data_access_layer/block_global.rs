@@ -112,3 +112,19 @@ impl Display for BlockGlobalResult {
         }
     }
 }
+
+/// Block global read result.
+#[derive(Debug, Clone)]
+pub enum BlockGlobalReadResult {
+    /// Returned if a passed state root hash is not found.
+    RootNotFound,
+    /// Returned if nothing is stored under the requested block global address.
+    ValueNotFound,
+    /// Failed to read or decode the block global value.
+    Failure(TrackingCopyError),
+    /// Successfully read the block global value.
+    Success {
+        /// The decoded block global value.
+        value: BlockGlobalKind,
+    },
+}
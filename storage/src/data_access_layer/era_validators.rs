@@ -1,7 +1,7 @@
 //! Support for querying era validators.
 
 use crate::tracking_copy::TrackingCopyError;
-use casper_types::{system::auction::EraValidators, Digest};
+use casper_types::{system::auction::EraValidators, Digest, PublicKey, U512};
 use std::fmt::{Display, Formatter};
 
 /// Request for era validators.
@@ -46,6 +46,30 @@ impl EraValidatorsResult {
         matches!(self, EraValidatorsResult::Success { .. })
     }
 
+    /// Returns the validators of the earliest era in the result along with their weights, sorted
+    /// by descending weight.
+    ///
+    /// Validators of equal weight are ordered by the raw bytes of their public keys, regardless of
+    /// the key algorithm. Returns an empty vec if the request did not succeed.
+    pub fn sorted_by_weight(&self) -> Vec<(PublicKey, U512)> {
+        let EraValidatorsResult::Success { era_validators } = self else {
+            return Vec::new();
+        };
+        let Some(validator_weights) = era_validators.values().next() else {
+            return Vec::new();
+        };
+        let mut sorted: Vec<(PublicKey, U512)> = validator_weights
+            .iter()
+            .map(|(public_key, weight)| (public_key.clone(), *weight))
+            .collect();
+        sorted.sort_by(|(lhs_key, lhs_weight), (rhs_key, rhs_weight)| {
+            rhs_weight
+                .cmp(lhs_weight)
+                .then_with(|| Vec::<u8>::from(lhs_key).cmp(&Vec::<u8>::from(rhs_key)))
+        });
+        sorted
+    }
+
     /// Takes era validators.
     pub fn take_era_validators(self) -> Option<EraValidators> {
         match self {
@@ -71,3 +95,84 @@ impl Display for EraValidatorsResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{system::auction::ValidatorWeights, EraId, SecretKey};
+
+    use super::*;
+
+    fn public_key(seed: u8) -> PublicKey {
+        PublicKey::from(&SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap())
+    }
+
+    fn secp256k1_public_key(seed: u8) -> PublicKey {
+        PublicKey::from(
+            &SecretKey::secp256k1_from_bytes([seed; SecretKey::SECP256K1_LENGTH]).unwrap(),
+        )
+    }
+
+    #[test]
+    fn should_sort_validators_by_weight_then_public_key() {
+        let (key_1, key_2, key_3, key_4) =
+            (public_key(1), public_key(2), public_key(3), public_key(4));
+        let (low_tie_key, high_tie_key) = if Vec::<u8>::from(&key_2) < Vec::<u8>::from(&key_3) {
+            (key_2.clone(), key_3.clone())
+        } else {
+            (key_3.clone(), key_2.clone())
+        };
+
+        let current_era_weights = ValidatorWeights::from([
+            (key_1.clone(), U512::from(10)),
+            (key_2, U512::from(50)),
+            (key_3, U512::from(50)),
+            (key_4.clone(), U512::from(100)),
+        ]);
+        let next_era_weights = ValidatorWeights::from([(key_1.clone(), U512::from(1_000))]);
+        let era_validators = EraValidators::from([
+            (EraId::new(5), current_era_weights),
+            (EraId::new(6), next_era_weights),
+        ]);
+
+        let result = EraValidatorsResult::Success { era_validators };
+        assert_eq!(
+            result.sorted_by_weight(),
+            vec![
+                (key_4, U512::from(100)),
+                (low_tie_key, U512::from(50)),
+                (high_tie_key, U512::from(50)),
+                (key_1, U512::from(10)),
+            ]
+        );
+
+        assert!(EraValidatorsResult::RootNotFound
+            .sorted_by_weight()
+            .is_empty());
+    }
+
+    #[test]
+    fn should_break_ties_on_key_bytes_rather_than_algorithm() {
+        // A compressed secp256k1 key starts with 0x02 or 0x03, so an Ed25519 key whose first
+        // byte is higher sorts after it by bytes, although its algorithm tag is lower.
+        let secp256k1_key = secp256k1_public_key(1);
+        let ed25519_key = (1..=u8::MAX)
+            .map(public_key)
+            .find(|key| Vec::<u8>::from(key)[0] > 0x03)
+            .expect("should find an Ed25519 key with a high first byte");
+        assert!(ed25519_key < secp256k1_key);
+
+        let era_validators = EraValidators::from([(
+            EraId::new(1),
+            ValidatorWeights::from([
+                (ed25519_key.clone(), U512::from(7)),
+                (secp256k1_key.clone(), U512::from(7)),
+            ]),
+        )]);
+
+        let result = EraValidatorsResult::Success { era_validators };
+        assert_eq!(
+            result.sorted_by_weight(),
+            vec![(secp256k1_key, U512::from(7)), (ed25519_key, U512::from(7))]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Support for obtaining the named keys of an entity in pages.
+use crate::tracking_copy::TrackingCopyError;
+
+use casper_types::{contracts::NamedKeys, Digest, EntityAddr};
+
+/// Represents a request to obtain a page of the named keys stored under `Key::NamedKey` entries
+/// of an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedKeysRequest {
+    state_hash: Digest,
+    entity_addr: EntityAddr,
+    offset: usize,
+    limit: usize,
+}
+
+impl NamedKeysRequest {
+    /// Creates new request for at most `limit` named keys, skipping the first `offset` named key
+    /// entries.
+    ///
+    /// Named key entries are visited in trie order, so the `next_offset` returned with a page can
+    /// be passed as `offset` to request the following page. A `limit` of zero yields an empty
+    /// page without a `next_offset`.
+    pub fn new(state_hash: Digest, entity_addr: EntityAddr, offset: usize, limit: usize) -> Self {
+        NamedKeysRequest {
+            state_hash,
+            entity_addr,
+            offset,
+            limit,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the address of the entity owning the named keys.
+    pub fn entity_addr(&self) -> EntityAddr {
+        self.entity_addr
+    }
+
+    /// Returns the number of named key entries to skip.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the maximum number of named key entries to visit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+/// Represents a result of a `named_keys` request.
+#[derive(Debug)]
+pub enum NamedKeysResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains a page of named keys returned from the global state.
+    Success {
+        /// Named keys of the requested page.
+        named_keys: NamedKeys,
+        /// Offset of the next page of named keys, if more named keys remain.
+        next_offset: Option<usize>,
+    },
+    /// Failure.
+    Failure(TrackingCopyError),
+}
@@ -0,0 +1,60 @@
+//! Support for obtaining the versions of a package along with their enabled status.
+use std::collections::BTreeMap;
+
+use crate::tracking_copy::TrackingCopyError;
+use casper_types::{Digest, EntityVersionKey, Key};
+
+/// Represents a request to obtain the versions of a package along with their enabled status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageVersionsRequest {
+    state_hash: Digest,
+    key: Key,
+}
+
+impl PackageVersionsRequest {
+    /// Creates new request.
+    ///
+    /// `key` may address either a [`casper_types::Package`] or a legacy
+    /// [`casper_types::contracts::ContractPackage`].
+    pub fn new(state_hash: Digest, key: Key) -> Self {
+        PackageVersionsRequest { state_hash, key }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns key.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+}
+
+/// Represents a result of a `package_versions` request.
+#[derive(Debug)]
+pub enum PackageVersionsResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Value not found.
+    ValueNotFound(String),
+    /// Contains every version of the package.
+    Success {
+        /// Each version of the package mapped to `true` if it is enabled, or `false` if it has
+        /// been disabled.
+        versions: BTreeMap<EntityVersionKey, bool>,
+    },
+    /// Failure.
+    Failure(TrackingCopyError),
+}
+
+impl PackageVersionsResult {
+    /// Returns the versions if this represents a successful query result.
+    pub fn into_option(self) -> Option<BTreeMap<EntityVersionKey, bool>> {
+        if let Self::Success { versions } = self {
+            Some(versions)
+        } else {
+            None
+        }
+    }
+}
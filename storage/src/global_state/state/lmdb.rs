@@ -220,6 +220,32 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn keys_with_prefix_paged(
+        &self,
+        prefix: &[u8],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = keys_with_prefix::<Key, StoredValue, _, _>(
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let mut ret = Vec::new();
+        // Keys before `offset` are still visited so that errors reading them are not swallowed.
+        for (index, result) in keys_iter.enumerate().take(offset.saturating_add(limit)) {
+            match result {
+                Ok(key) if index >= offset => ret.push(key),
+                Ok(_) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl CommitProvider for LmdbGlobalState {
@@ -539,9 +565,30 @@ pub fn make_temporary_global_state(
 
 #[cfg(test)]
 mod tests {
-    use casper_types::{account::AccountHash, execution::TransformKindV2, CLValue, Digest};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use casper_types::{
+        account::AccountHash,
+        addressable_entity::{NamedKeyAddr, NamedKeyValue},
+        bytesrepr::ToBytes,
+        contracts::NamedKeys,
+        execution::{TransformKindV2, TransformV2},
+        system::auction::{BidKind, ValidatorBid},
+        AccessRights, BlockGlobalAddr, BlockTime, CLValue, ChecksumRegistry, Digest, EntityAddr,
+        EntityVersions, Groups, Package, PackageStatus, ProtocolVersion, PublicKey, SecretKey,
+        URef, U512,
+    };
 
-    use crate::global_state::state::scratch::tests::TestPair;
+    use crate::{
+        data_access_layer::{
+            balance::BalanceHandling, BalanceIdentifier, BalanceRequest, BalanceResult,
+            BidsRequest, BidsResult, BlockGlobalKind, BlockGlobalReadResult, BlockGlobalRequest,
+            BlockGlobalResult, ExecutionResultsChecksumResult, NamedKeysRequest, NamedKeysResult,
+            PackageVersionsRequest, PackageVersionsResult, ProofHandling, QueryRequest,
+            QueryResult, EXECUTION_RESULTS_CHECKSUM_NAME,
+        },
+        global_state::state::scratch::tests::TestPair,
+    };
 
     use super::*;
 
@@ -585,6 +632,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reads_from_a_snapshot_match_direct_queries() {
+        let test_pairs = create_test_pairs();
+        let (state, root_hash, _tempdir) = make_temporary_global_state(test_pairs.clone());
+        let snapshot = state.snapshot(root_hash).unwrap().unwrap();
+        assert_eq!(snapshot.state_hash(), root_hash);
+
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for (key, value) in test_pairs {
+            assert_eq!(Some(value.clone()), snapshot.read(&key).unwrap());
+
+            let direct = state.query(QueryRequest::new(root_hash, key, vec![]));
+            match (snapshot.query(key, &[]), direct) {
+                (
+                    QueryResult::Success {
+                        value: snapshot_value,
+                        proofs: snapshot_proofs,
+                    },
+                    QueryResult::Success {
+                        value: direct_value,
+                        proofs: direct_proofs,
+                    },
+                ) => {
+                    assert_eq!(*snapshot_value, value);
+                    assert_eq!(snapshot_value, direct_value);
+                    assert_eq!(snapshot_proofs, direct_proofs);
+                }
+                other => panic!("expected both queries to succeed, got {:?}", other),
+            }
+
+            let prefix = key.to_bytes().unwrap();
+            assert_eq!(
+                snapshot.keys_with_prefix(&prefix).unwrap(),
+                checkout.keys_with_prefix(&prefix).unwrap()
+            );
+        }
+
+        let missing_key = Key::Account(AccountHash::new([3_u8; 32]));
+        assert_eq!(snapshot.read(&missing_key).unwrap(), None);
+        assert!(matches!(
+            snapshot.query(missing_key, &[]),
+            QueryResult::ValueNotFound(_)
+        ));
+    }
+
+    #[test]
+    fn snapshot_is_none_if_unknown_hash_is_given() {
+        let (state, _, _tempdir) = make_temporary_global_state(create_test_pairs());
+        let fake_hash: Digest = Digest::hash([1u8; 32]);
+        assert!(state.snapshot(fake_hash).unwrap().is_none());
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _, _tempdir) = make_temporary_global_state(create_test_pairs());
@@ -648,4 +747,338 @@ mod tests {
             original_checkout.read(&test_pairs_updated[2].key).unwrap()
         );
     }
+
+    #[test]
+    fn paging_over_bids_matches_full_scan() {
+        let bid_pairs: Vec<(Key, StoredValue)> = (1..=5u8)
+            .map(|seed| {
+                let secret_key = SecretKey::ed25519_from_bytes([seed; 32]).unwrap();
+                let public_key = PublicKey::from(&secret_key);
+                let bonding_purse = URef::new([seed; 32], AccessRights::READ_ADD_WRITE);
+                let bid_kind =
+                    BidKind::Validator(Box::new(ValidatorBid::empty(public_key, bonding_purse)));
+                (
+                    Key::BidAddr(bid_kind.bid_addr()),
+                    StoredValue::BidKind(bid_kind),
+                )
+            })
+            .collect();
+        let (state, root_hash, _tempdir) = make_temporary_global_state(bid_pairs);
+
+        let all_bids = match state.bids(BidsRequest::new(root_hash)) {
+            BidsResult::Success { bids, next_offset } => {
+                assert_eq!(next_offset, None);
+                bids
+            }
+            other => panic!("expected bids, got {:?}", other),
+        };
+        assert_eq!(all_bids.len(), 5);
+
+        let mut paged_bids = vec![];
+        let mut offset = 0;
+        loop {
+            match state.bids(BidsRequest::paged(root_hash, offset, 2)) {
+                BidsResult::Success { bids, next_offset } => {
+                    assert!(bids.len() <= 2);
+                    paged_bids.extend(bids);
+                    match next_offset {
+                        Some(next_offset) => offset = next_offset,
+                        None => break,
+                    }
+                }
+                other => panic!("expected bids, got {:?}", other),
+            }
+        }
+        assert_eq!(paged_bids, all_bids);
+
+        for (offset, limit) in [(10, 2), (0, 0)] {
+            match state.bids(BidsRequest::paged(root_hash, offset, limit)) {
+                BidsResult::Success { bids, next_offset } => {
+                    assert!(bids.is_empty());
+                    assert_eq!(next_offset, None);
+                }
+                other => panic!("expected bids, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn paging_over_named_keys_matches_full_read() {
+        let entity_addr = EntityAddr::new_smart_contract([7; 32]);
+        let other_entity_addr = EntityAddr::new_smart_contract([8; 32]);
+        let named_key_pairs: Vec<(Key, StoredValue)> = (0..50u8)
+            .flat_map(|index| {
+                [entity_addr, other_entity_addr].map(|owner| {
+                    let name = format!("key-{}", index);
+                    let named_key_addr =
+                        NamedKeyAddr::new_from_string(owner, name.clone()).unwrap();
+                    let value = NamedKeyValue::from_concrete_values(
+                        Key::URef(URef::new([index; 32], AccessRights::READ)),
+                        name,
+                    )
+                    .unwrap();
+                    (Key::NamedKey(named_key_addr), StoredValue::NamedKey(value))
+                })
+            })
+            .collect();
+        let (state, root_hash, _tempdir) = make_temporary_global_state(named_key_pairs);
+
+        let all_named_keys =
+            match state.named_keys(NamedKeysRequest::new(root_hash, entity_addr, 0, usize::MAX)) {
+                NamedKeysResult::Success {
+                    named_keys,
+                    next_offset,
+                } => {
+                    assert_eq!(next_offset, None);
+                    named_keys
+                }
+                other => panic!("expected named keys, got {:?}", other),
+            };
+        assert_eq!(all_named_keys.len(), 50);
+
+        let mut paged_named_keys = NamedKeys::new();
+        let mut offset = 0;
+        loop {
+            match state.named_keys(NamedKeysRequest::new(root_hash, entity_addr, offset, 7)) {
+                NamedKeysResult::Success {
+                    named_keys,
+                    next_offset,
+                } => {
+                    assert!(named_keys.len() <= 7);
+                    paged_named_keys.append(named_keys);
+                    match next_offset {
+                        Some(next_offset) => offset = next_offset,
+                        None => break,
+                    }
+                }
+                other => panic!("expected named keys, got {:?}", other),
+            }
+        }
+        assert_eq!(paged_named_keys, all_named_keys);
+
+        for (offset, limit) in [(50, 7), (0, 0)] {
+            match state.named_keys(NamedKeysRequest::new(root_hash, entity_addr, offset, limit)) {
+                NamedKeysResult::Success {
+                    named_keys,
+                    next_offset,
+                } => {
+                    assert!(named_keys.is_empty());
+                    assert_eq!(next_offset, None);
+                }
+                other => panic!("expected named keys, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn should_read_balance_at_historical_state_root() {
+        let purse = URef::new([9; 32], AccessRights::READ_ADD_WRITE);
+        let balance_key = Key::Balance(purse.addr());
+        let (state, earlier_hash, _tempdir) = make_temporary_global_state([(
+            balance_key,
+            StoredValue::CLValue(CLValue::from_t(U512::from(100)).unwrap()),
+        )]);
+
+        let mut effects = Effects::new();
+        effects.push(TransformV2::new(
+            balance_key,
+            TransformKindV2::Write(StoredValue::CLValue(
+                CLValue::from_t(U512::from(40)).unwrap(),
+            )),
+        ));
+        let latest_hash = state.commit_effects(earlier_hash, effects).unwrap();
+
+        let balance_request = |state_hash| {
+            BalanceRequest::new(
+                state_hash,
+                ProtocolVersion::V2_0_0,
+                BalanceIdentifier::Purse(purse),
+                BalanceHandling::Available,
+                ProofHandling::NoProofs,
+            )
+        };
+        let available_balance_at = |state_hash| match state.balance(balance_request(state_hash)) {
+            BalanceResult::Success {
+                total_balance,
+                available_balance,
+                ..
+            } => {
+                assert_eq!(total_balance, available_balance);
+                available_balance
+            }
+            other => panic!("expected balance, got {:?}", other),
+        };
+        assert_eq!(available_balance_at(earlier_hash), U512::from(100));
+        assert_eq!(available_balance_at(latest_hash), U512::from(40));
+
+        match state.balance(balance_request(Digest::hash(b"missing root"))) {
+            BalanceResult::RootNotFound => {}
+            other => panic!("expected root not found, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_report_enabled_and_disabled_package_versions() {
+        let mut package = Package::new(
+            EntityVersions::new(),
+            BTreeSet::new(),
+            Groups::new(),
+            PackageStatus::Unlocked,
+        );
+        let first_entity = EntityAddr::new_smart_contract([1; 32]);
+        let second_entity = EntityAddr::new_smart_contract([2; 32]);
+        let first_version = package.insert_entity_version(2, first_entity);
+        let second_version = package.insert_entity_version(2, second_entity);
+        package.disable_entity_version(first_entity).unwrap();
+
+        let package_key = Key::SmartContract([3; 32]);
+        let (state, root_hash, _tempdir) =
+            make_temporary_global_state([(package_key, StoredValue::SmartContract(package))]);
+
+        match state.package_versions(PackageVersionsRequest::new(root_hash, package_key)) {
+            PackageVersionsResult::Success { versions } => {
+                assert_eq!(
+                    versions,
+                    BTreeMap::from([(first_version, false), (second_version, true)])
+                );
+            }
+            other => panic!("expected package versions, got {:?}", other),
+        }
+
+        match state.package_versions(PackageVersionsRequest::new(
+            root_hash,
+            Key::SmartContract([4; 32]),
+        )) {
+            PackageVersionsResult::ValueNotFound(_) => {}
+            other => panic!("expected value not found, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_read_execution_results_checksum_after_commit() {
+        let (state, root_hash, _tempdir) = make_temporary_global_state(create_test_pairs());
+
+        match state.execution_results_checksum(root_hash) {
+            ExecutionResultsChecksumResult::RegistryNotFound => {}
+            other => panic!("expected registry not found, got {:?}", other),
+        }
+
+        let checksum = Digest::hash(b"execution results");
+        let mut checksum_registry = ChecksumRegistry::new();
+        checksum_registry.insert(EXECUTION_RESULTS_CHECKSUM_NAME, checksum);
+        let mut effects = Effects::new();
+        effects.push(TransformV2::new(
+            Key::ChecksumRegistry,
+            TransformKindV2::Write(CLValue::from_t(checksum_registry).unwrap().into()),
+        ));
+        let post_state_hash = state.commit_effects(root_hash, effects).unwrap();
+
+        match state.execution_results_checksum(post_state_hash) {
+            ExecutionResultsChecksumResult::Success {
+                checksum: stored_checksum,
+            } => assert_eq!(stored_checksum, checksum),
+            other => panic!("expected checksum, got {:?}", other),
+        }
+
+        match state.execution_results_checksum(Digest::hash(b"missing root")) {
+            ExecutionResultsChecksumResult::RootNotFound => {}
+            other => panic!("expected root not found, got {:?}", other),
+        }
+    }
+
+    fn commit_block_global(state: &LmdbGlobalState, request: BlockGlobalRequest) -> Digest {
+        match state.block_global(request) {
+            BlockGlobalResult::Success {
+                post_state_hash, ..
+            } => post_state_hash,
+            other => panic!("expected block global to be written, got {:?}", other),
+        }
+    }
+
+    fn read_block_global(
+        state: &LmdbGlobalState,
+        state_hash: Digest,
+        addr: BlockGlobalAddr,
+    ) -> BlockGlobalKind {
+        match state.block_global_read(state_hash, addr) {
+            BlockGlobalReadResult::Success { value } => value,
+            other => panic!("expected block global {:?}, got {:?}", addr, other),
+        }
+    }
+
+    #[test]
+    fn should_read_back_block_globals() {
+        let (state, root_hash, _tempdir) = make_temporary_global_state(create_test_pairs());
+        let protocol_version = ProtocolVersion::from_parts(2, 1, 0);
+
+        match state.block_global_read(root_hash, BlockGlobalAddr::BlockTime) {
+            BlockGlobalReadResult::ValueNotFound => {}
+            other => panic!("expected value not found, got {:?}", other),
+        }
+
+        let block_time = BlockTime::new(1_700_000_000_000);
+        let root_hash = commit_block_global(
+            &state,
+            BlockGlobalRequest::block_time(root_hash, protocol_version, block_time),
+        );
+        let root_hash = commit_block_global(
+            &state,
+            BlockGlobalRequest::set_protocol_version(root_hash, protocol_version),
+        );
+        let root_hash = commit_block_global(
+            &state,
+            BlockGlobalRequest::set_addressable_entity(root_hash, protocol_version, true),
+        );
+
+        assert_eq!(
+            read_block_global(&state, root_hash, BlockGlobalAddr::BlockTime),
+            BlockGlobalKind::BlockTime(block_time)
+        );
+        assert_eq!(
+            read_block_global(&state, root_hash, BlockGlobalAddr::ProtocolVersion),
+            BlockGlobalKind::ProtocolVersion(protocol_version)
+        );
+        assert_eq!(
+            read_block_global(&state, root_hash, BlockGlobalAddr::AddressableEntity),
+            BlockGlobalKind::AddressableEntity(true)
+        );
+
+        match state.block_global_read(Digest::hash(b"missing root"), BlockGlobalAddr::BlockTime) {
+            BlockGlobalReadResult::RootNotFound => {}
+            other => panic!("expected root not found, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_read_back_block_message_count() {
+        let (state, root_hash, _tempdir) = make_temporary_global_state(create_test_pairs());
+
+        // The message count is written by `block_global` as a bare count.
+        let mut effects = Effects::new();
+        effects.push(TransformV2::new(
+            Key::BlockGlobal(BlockGlobalAddr::MessageCount),
+            TransformKindV2::Write(CLValue::from_t(3_u64).unwrap().into()),
+        ));
+        let root_hash = state.commit_effects(root_hash, effects).unwrap();
+        assert_eq!(
+            read_block_global(&state, root_hash, BlockGlobalAddr::MessageCount),
+            BlockGlobalKind::MessageCount(3)
+        );
+
+        // Emitting messages pairs the count with the time of the block it belongs to.
+        let mut effects = Effects::new();
+        effects.push(TransformV2::new(
+            Key::BlockGlobal(BlockGlobalAddr::MessageCount),
+            TransformKindV2::Write(
+                CLValue::from_t((BlockTime::new(1_000), 5_u64))
+                    .unwrap()
+                    .into(),
+            ),
+        ));
+        let root_hash = state.commit_effects(root_hash, effects).unwrap();
+        assert_eq!(
+            read_block_global(&state, root_hash, BlockGlobalAddr::MessageCount),
+            BlockGlobalKind::MessageCount(5)
+        );
+    }
 }
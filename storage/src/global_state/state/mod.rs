@@ -6,6 +6,9 @@ pub mod lmdb;
 /// Lmdb implementation of global state with cache.
 pub mod scratch;
 
+/// Read-only view of global state pinned to a state root.
+pub mod snapshot;
+
 use num_rational::Ratio;
 use parking_lot::RwLock;
 use std::{
@@ -36,13 +39,15 @@ use casper_types::{
         },
         AUCTION, HANDLE_PAYMENT, MINT,
     },
-    Account, AddressableEntity, BlockGlobalAddr, CLValue, Digest, EntityAddr, EntityEntryPoint,
-    EntryPointAddr, EntryPointValue, HoldsEpoch, Key, KeyTag, Phase, PublicKey, RuntimeArgs,
-    StoredValue, SystemHashRegistry, U512,
+    Account, AddressableEntity, BlockGlobalAddr, BlockTime, CLType, CLValue, Digest, EntityAddr,
+    EntityEntryPoint, EntryPointAddr, EntryPointValue, HoldsEpoch, Key, KeyTag, Phase,
+    ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, StoredValueTypeMismatch,
+    SystemHashRegistry, U512,
 };
 
 #[cfg(test)]
 pub use self::lmdb::make_temporary_global_state;
+use self::snapshot::Snapshot;
 
 use super::trie_store::{operations::batch_write, TrieStoreCacheError};
 use crate::{
@@ -60,20 +65,21 @@ use crate::{
         AddressableEntityRequest, AddressableEntityResult, AuctionMethod, BalanceHoldError,
         BalanceHoldKind, BalanceHoldMode, BalanceHoldRequest, BalanceHoldResult, BalanceIdentifier,
         BalanceIdentifierPurseRequest, BalanceIdentifierPurseResult, BalanceRequest, BalanceResult,
-        BidsRequest, BidsResult, BlockGlobalKind, BlockGlobalRequest, BlockGlobalResult,
-        BlockRewardsError, BlockRewardsRequest, BlockRewardsResult, ContractRequest,
-        ContractResult, EntryPointExistsRequest, EntryPointExistsResult, EntryPointRequest,
-        EntryPointResult, EraValidatorsRequest, ExecutionResultsChecksumRequest,
+        BidsRequest, BidsResult, BlockGlobalKind, BlockGlobalReadResult, BlockGlobalRequest,
+        BlockGlobalResult, BlockRewardsError, BlockRewardsRequest, BlockRewardsResult,
+        ContractRequest, ContractResult, EntryPointExistsRequest, EntryPointExistsResult,
+        EntryPointRequest, EntryPointResult, EraValidatorsRequest, ExecutionResultsChecksumRequest,
         ExecutionResultsChecksumResult, FeeError, FeeRequest, FeeResult, FlushRequest, FlushResult,
         GenesisRequest, GenesisResult, HandleRefundMode, HandleRefundRequest, HandleRefundResult,
-        InsufficientBalanceHandling, MessageTopicsRequest, MessageTopicsResult, ProofHandling,
-        ProofsResult, ProtocolUpgradeRequest, ProtocolUpgradeResult, PruneRequest, PruneResult,
-        PutTrieRequest, PutTrieResult, QueryRequest, QueryResult, RoundSeigniorageRateRequest,
-        RoundSeigniorageRateResult, SeigniorageRecipientsRequest, SeigniorageRecipientsResult,
-        StepError, StepRequest, StepResult, SystemEntityRegistryPayload,
-        SystemEntityRegistryRequest, SystemEntityRegistryResult, SystemEntityRegistrySelector,
-        TotalSupplyRequest, TotalSupplyResult, TrieRequest, TrieResult,
-        EXECUTION_RESULTS_CHECKSUM_NAME,
+        HoldsBreakdownHandling, InsufficientBalanceHandling, KeyPrefix, MessageTopicsRequest,
+        MessageTopicsResult, NamedKeysRequest, NamedKeysResult, PackageVersionsRequest,
+        PackageVersionsResult, ProofHandling, ProofsResult, ProtocolUpgradeRequest,
+        ProtocolUpgradeResult, PruneRequest, PruneResult, PutTrieRequest, PutTrieResult,
+        QueryRequest, QueryResult, RoundSeigniorageRateRequest, RoundSeigniorageRateResult,
+        SeigniorageRecipientsRequest, SeigniorageRecipientsResult, StepError, StepRequest,
+        StepResult, SystemEntityRegistryPayload, SystemEntityRegistryRequest,
+        SystemEntityRegistryResult, SystemEntityRegistrySelector, TotalSupplyRequest,
+        TotalSupplyResult, TrieRequest, TrieResult, EXECUTION_RESULTS_CHECKSUM_NAME,
     },
     global_state::{
         error::Error as GlobalStateError,
@@ -112,6 +118,48 @@ pub trait StateReader<K = Key, V = StoredValue>: Sized + Send + Sync {
 
     /// Returns the keys in the trie matching `prefix`.
     fn keys_with_prefix(&self, prefix: &[u8]) -> Result<Vec<K>, Self::Error>;
+
+    /// Returns at most `limit` keys in the trie matching `prefix`, skipping the first `offset`
+    /// matching keys in trie order.
+    fn keys_with_prefix_paged(
+        &self,
+        prefix: &[u8],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<K>, Self::Error> {
+        Ok(self
+            .keys_with_prefix(prefix)?
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect())
+    }
+}
+
+/// Reads a page of at most `limit` keys matching `prefix` directly from the trie, along with the
+/// offset of the following page if more keys remain.
+///
+/// A `limit` of zero yields an empty page without a following page.
+fn keys_page<R>(
+    reader: &R,
+    prefix: &[u8],
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<Key>, Option<usize>), GlobalStateError>
+where
+    R: StateReader<Key, StoredValue, Error = GlobalStateError>,
+{
+    if limit == 0 {
+        return Ok((Vec::new(), None));
+    }
+    // Read one key past the page to learn whether a following page exists.
+    let mut keys = reader.keys_with_prefix_paged(prefix, offset, limit.saturating_add(1))?;
+    if keys.len() > limit {
+        keys.truncate(limit);
+        Ok((keys, Some(offset.saturating_add(limit))))
+    } else {
+        Ok((keys, None))
+    }
 }
 
 /// An error emitted by the execution engine on commit
@@ -273,6 +321,53 @@ pub trait CommitProvider: StateProvider {
         }
     }
 
+    /// Prunes the balance hold records which have expired by `current_block_time`.
+    ///
+    /// A hold expires once it is older than the hold interval configured for its kind, i.e. once
+    /// it no longer reduces the available balance of its purse. Purse balances are not affected.
+    fn prune_expired_holds(
+        &self,
+        state_hash: Digest,
+        current_block_time: BlockTime,
+    ) -> PruneResult {
+        let tc = match self.tracking_copy(state_hash) {
+            Ok(Some(tc)) => tc,
+            Ok(None) => return PruneResult::RootNotFound,
+            Err(err) => return PruneResult::Failure(TrackingCopyError::Storage(err)),
+        };
+
+        let mut keys_to_prune = vec![];
+        for hold_kind in [BalanceHoldAddrTag::Gas, BalanceHoldAddrTag::Processing] {
+            let interval = match tc.get_balance_hold_config(hold_kind) {
+                Ok(Some((_, _, interval))) => interval,
+                // if there is no holds config at this root hash, there can't be any holds
+                Ok(None) => continue,
+                Err(tce) => return PruneResult::Failure(tce),
+            };
+            let holds_epoch = HoldsEpoch::from_millis(current_block_time.value(), interval);
+            let Some(earliest_relevant_timestamp) = holds_epoch.value() else {
+                continue;
+            };
+            let prefix = [KeyTag::BalanceHold as u8, hold_kind as u8];
+            let immut: &_ = &tc;
+            let hold_keys = match immut.keys_with_prefix(&prefix) {
+                Ok(hold_keys) => hold_keys,
+                Err(err) => return PruneResult::Failure(TrackingCopyError::Storage(err)),
+            };
+            for hold_key in hold_keys {
+                let Some(balance_hold_addr) = hold_key.as_balance_hold() else {
+                    return PruneResult::Failure(TrackingCopyError::UnexpectedKeyVariant(hold_key));
+                };
+                // holds with a timestamp >= epoch timestamp still count against the balance
+                if balance_hold_addr.block_time().value() < earliest_relevant_timestamp {
+                    keys_to_prune.push(hold_key);
+                }
+            }
+        }
+
+        self.prune(PruneRequest::new(state_hash, keys_to_prune))
+    }
+
     /// Step auction state at era end.
     fn step(&self, request: StepRequest) -> StepResult {
         let state_hash = request.state_hash();
@@ -656,6 +751,17 @@ pub trait StateProvider: Send + Sync + Sized {
     /// Checkouts a slice of initial state using root state hash.
     fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, GlobalStateError>;
 
+    /// Returns a read-only snapshot of the state under `state_hash`, checking it out only once
+    /// for all subsequent reads.
+    fn snapshot(
+        &self,
+        state_hash: Digest,
+    ) -> Result<Option<Snapshot<Self::Reader>>, GlobalStateError> {
+        Ok(self
+            .tracking_copy(state_hash)?
+            .map(|tracking_copy| Snapshot::new(state_hash, tracking_copy)))
+    }
+
     /// Query state.
     fn query(&self, request: QueryRequest) -> QueryResult {
         match self.tracking_copy(request.state_hash()) {
@@ -682,6 +788,57 @@ pub trait StateProvider: Send + Sync + Sized {
         }
     }
 
+    /// Gets a page of the named keys of an entity.
+    fn named_keys(&self, request: NamedKeysRequest) -> NamedKeysResult {
+        let mut tc = match self.tracking_copy(request.state_hash()) {
+            Ok(Some(tc)) => tc,
+            Ok(None) => return NamedKeysResult::RootNotFound,
+            Err(err) => return NamedKeysResult::Failure(TrackingCopyError::Storage(err)),
+        };
+
+        let prefix = match KeyPrefix::NamedKeysByEntity(request.entity_addr()).to_bytes() {
+            Ok(prefix) => prefix,
+            Err(err) => return NamedKeysResult::Failure(TrackingCopyError::BytesRepr(err)),
+        };
+        let (entry_keys, next_offset) =
+            match keys_page(tc.reader(), &prefix, request.offset(), request.limit()) {
+                Ok(page) => page,
+                Err(err) => return NamedKeysResult::Failure(TrackingCopyError::Storage(err)),
+            };
+
+        let mut named_keys = NamedKeys::new();
+        for entry_key in entry_keys.iter() {
+            match tc.read(entry_key) {
+                Ok(Some(StoredValue::NamedKey(named_key_value))) => {
+                    let entry = named_key_value
+                        .get_name()
+                        .and_then(|name| Ok((name, named_key_value.get_key()?)));
+                    match entry {
+                        Ok((name, key)) => {
+                            named_keys.insert(name, key);
+                        }
+                        Err(error) => {
+                            return NamedKeysResult::Failure(TrackingCopyError::CLValue(error))
+                        }
+                    }
+                }
+                Ok(Some(_)) => {
+                    return NamedKeysResult::Failure(
+                        TrackingCopyError::UnexpectedStoredValueVariant,
+                    );
+                }
+                Ok(None) => {
+                    return NamedKeysResult::Failure(TrackingCopyError::KeyNotFound(*entry_key))
+                }
+                Err(error) => return NamedKeysResult::Failure(error),
+            }
+        }
+        NamedKeysResult::Success {
+            named_keys,
+            next_offset,
+        }
+    }
+
     /// Provides the underlying addr for the imputed balance identifier.
     fn balance_purse(
         &self,
@@ -776,6 +933,12 @@ pub trait StateProvider: Send + Sync + Sized {
             }
         };
 
+        // Without a hold configuration no holds are considered, so the breakdown is empty.
+        let empty_holds_breakdown = match request.holds_breakdown_handling() {
+            HoldsBreakdownHandling::NoBreakdown => None,
+            HoldsBreakdownHandling::Breakdown => Some(BTreeMap::new()),
+        };
+
         let (block_time, gas_hold_handling) = match tc
             .get_balance_hold_config(BalanceHoldAddrTag::Gas)
         {
@@ -786,6 +949,7 @@ pub trait StateProvider: Send + Sync + Sized {
                     total_balance,
                     available_balance: total_balance,
                     proofs_result,
+                    holds_breakdown: empty_holds_breakdown,
                 };
             }
             Err(tce) => return tce.into(),
@@ -800,6 +964,7 @@ pub trait StateProvider: Send + Sync + Sized {
                         total_balance,
                         available_balance: total_balance,
                         proofs_result,
+                        holds_breakdown: empty_holds_breakdown,
                     };
                 }
                 Err(tce) => return tce.into(),
@@ -815,11 +980,24 @@ pub trait StateProvider: Send + Sync + Sized {
             Err(be) => return BalanceResult::Failure(TrackingCopyError::Balance(be.clone())),
         };
 
+        let holds_breakdown = match request.holds_breakdown_handling() {
+            HoldsBreakdownHandling::NoBreakdown => None,
+            HoldsBreakdownHandling::Breakdown => match proofs_result.held_amount_by_tag(
+                block_time,
+                gas_hold_handling,
+                processing_hold_handling,
+            ) {
+                Ok(held_amount_by_tag) => Some(held_amount_by_tag),
+                Err(be) => return BalanceResult::Failure(TrackingCopyError::Balance(be)),
+            },
+        };
+
         BalanceResult::Success {
             purse_addr,
             total_balance,
             available_balance,
             proofs_result,
+            holds_breakdown,
         }
     }
 
@@ -1111,9 +1289,18 @@ pub trait StateProvider: Send + Sync + Sized {
             Err(err) => return BidsResult::Failure(TrackingCopyError::Storage(err)),
         };
 
-        let bid_keys = match tc.get_keys(&KeyTag::BidAddr) {
-            Ok(ret) => ret,
-            Err(err) => return BidsResult::Failure(err),
+        let (bid_keys, next_offset): (Vec<_>, _) = match request.page() {
+            Some(page) => {
+                let prefix = [KeyTag::BidAddr as u8];
+                match keys_page(tc.reader(), &prefix, page.offset(), page.limit()) {
+                    Ok(page) => page,
+                    Err(err) => return BidsResult::Failure(TrackingCopyError::Storage(err)),
+                }
+            }
+            None => match tc.get_keys(&KeyTag::BidAddr) {
+                Ok(ret) => (ret.into_iter().collect(), None),
+                Err(err) => return BidsResult::Failure(err),
+            },
         };
 
         let mut bids = vec![];
@@ -1135,7 +1322,7 @@ pub trait StateProvider: Send + Sync + Sized {
                 Err(error) => return BidsResult::Failure(error),
             }
         }
-        BidsResult::Success { bids }
+        BidsResult::Success { bids, next_offset }
     }
 
     /// Direct auction interaction for all variations of bid management.
@@ -1727,6 +1914,66 @@ pub trait StateProvider: Send + Sync + Sized {
         }
     }
 
+    /// Gets the execution results checksum stored in the checksum registry under `state_hash`.
+    ///
+    /// The checksum is written after a block is executed and covers the execution results of all
+    /// of its transactions, so it can be used to validate execution results received for that
+    /// block against the block's post state hash.
+    fn execution_results_checksum(&self, state_hash: Digest) -> ExecutionResultsChecksumResult {
+        self.execution_result_checksum(ExecutionResultsChecksumRequest::new(state_hash))
+    }
+
+    /// Reads the block global value stored under `addr` in the state under `state_hash`, decoding
+    /// it the way [`CommitProvider::block_global`] writes it.
+    ///
+    /// The message count is also accepted in the `(BlockTime, u64)` form written when messages
+    /// are emitted, in which case only the count is returned.
+    fn block_global_read(
+        &self,
+        state_hash: Digest,
+        addr: BlockGlobalAddr,
+    ) -> BlockGlobalReadResult {
+        let mut tc = match self.tracking_copy(state_hash) {
+            Ok(Some(tracking_copy)) => tracking_copy,
+            Ok(None) => return BlockGlobalReadResult::RootNotFound,
+            Err(gse) => return BlockGlobalReadResult::Failure(TrackingCopyError::Storage(gse)),
+        };
+
+        let cl_value = match tc.read(&Key::BlockGlobal(addr)) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => cl_value,
+            Ok(Some(other)) => {
+                return BlockGlobalReadResult::Failure(TrackingCopyError::TypeMismatch(
+                    StoredValueTypeMismatch::new("CLValue".to_string(), other.type_name()),
+                ))
+            }
+            Ok(None) => return BlockGlobalReadResult::ValueNotFound,
+            Err(tce) => return BlockGlobalReadResult::Failure(tce),
+        };
+
+        let value = match addr {
+            BlockGlobalAddr::BlockTime => cl_value
+                .into_t()
+                .map(|block_time| BlockGlobalKind::BlockTime(BlockTime::new(block_time))),
+            BlockGlobalAddr::MessageCount => match cl_value.cl_type() {
+                CLType::Tuple2(_) => cl_value
+                    .into_t::<(BlockTime, u64)>()
+                    .map(|(_block_time, count)| BlockGlobalKind::MessageCount(count)),
+                _ => cl_value.into_t().map(BlockGlobalKind::MessageCount),
+            },
+            BlockGlobalAddr::ProtocolVersion => cl_value.into_t().map(|(major, minor, patch)| {
+                BlockGlobalKind::ProtocolVersion(ProtocolVersion::from_parts(major, minor, patch))
+            }),
+            BlockGlobalAddr::AddressableEntity => {
+                cl_value.into_t().map(BlockGlobalKind::AddressableEntity)
+            }
+        };
+
+        match value {
+            Ok(value) => BlockGlobalReadResult::Success { value },
+            Err(error) => BlockGlobalReadResult::Failure(TrackingCopyError::CLValue(error)),
+        }
+    }
+
     /// Gets an addressable entity.
     fn addressable_entity(&self, request: AddressableEntityRequest) -> AddressableEntityResult {
         let key = request.key();
@@ -1951,6 +2198,35 @@ pub trait StateProvider: Send + Sync + Sized {
         }
     }
 
+    /// Gets the versions of a package along with their enabled status.
+    fn package_versions(&self, request: PackageVersionsRequest) -> PackageVersionsResult {
+        let query_request = QueryRequest::new(request.state_hash(), request.key(), vec![]);
+
+        match self.query(query_request) {
+            QueryResult::RootNotFound => PackageVersionsResult::RootNotFound,
+            QueryResult::ValueNotFound(msg) => PackageVersionsResult::ValueNotFound(msg),
+            QueryResult::Failure(tce) => PackageVersionsResult::Failure(tce),
+            QueryResult::Success { value, .. } => {
+                let package = match *value {
+                    StoredValue::SmartContract(package) => package,
+                    StoredValue::ContractPackage(contract_package) => contract_package.into(),
+                    _ => {
+                        error!("Expected to get package value received other variant");
+                        return PackageVersionsResult::Failure(
+                            TrackingCopyError::UnexpectedStoredValueVariant,
+                        );
+                    }
+                };
+                let versions = package
+                    .versions()
+                    .keys()
+                    .map(|version_key| (*version_key, package.is_version_enabled(*version_key)))
+                    .collect();
+                PackageVersionsResult::Success { versions }
+            }
+        }
+    }
+
     /// Gets an entry point value.
     fn entry_point_exists(&self, request: EntryPointExistsRequest) -> EntryPointExistsResult {
         match self.entry_point(request.into()) {
@@ -2032,6 +2308,24 @@ pub trait StateProvider: Send + Sync + Sized {
         }
     }
 
+    /// Reads the round seigniorage rate stored by the mint, as a ratio of `u64`s.
+    ///
+    /// Returns `None` if the rate can't be read, or if its numerator or denominator doesn't fit in
+    /// a `u64`.
+    fn round_seigniorage_rate_ratio(
+        &self,
+        request: RoundSeigniorageRateRequest,
+    ) -> Option<Ratio<u64>> {
+        match self.round_seigniorage_rate(request) {
+            RoundSeigniorageRateResult::Success { rate } => {
+                let numer = u64::try_from(*rate.numer()).ok()?;
+                let denom = u64::try_from(*rate.denom()).ok()?;
+                Some(Ratio::new_raw(numer, denom))
+            }
+            _ => None,
+        }
+    }
+
     /// Direct transfer.
     fn transfer(&self, request: TransferRequest) -> TransferResult {
         let state_hash = request.state_hash();
@@ -0,0 +1,53 @@
+use casper_types::{Digest, Key, StoredValue};
+
+use crate::{
+    data_access_layer::QueryResult,
+    global_state::{error::Error as GlobalStateError, state::StateReader},
+    tracking_copy::TrackingCopy,
+};
+
+/// A read-only view of global state pinned to a single state root.
+///
+/// The underlying reader is checked out once when the snapshot is created, which makes a snapshot
+/// a cheaper alternative to repeated [`StateProvider`](super::StateProvider) calls when performing
+/// many reads against the same state root.
+pub struct Snapshot<R> {
+    state_hash: Digest,
+    tracking_copy: TrackingCopy<R>,
+}
+
+impl<R> Snapshot<R>
+where
+    R: StateReader<Key, StoredValue, Error = GlobalStateError>,
+{
+    /// Creates a new snapshot of the state under `state_hash` from its tracking copy.
+    pub fn new(state_hash: Digest, tracking_copy: TrackingCopy<R>) -> Self {
+        Snapshot {
+            state_hash,
+            tracking_copy,
+        }
+    }
+
+    /// Returns the state root hash this snapshot is pinned to.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Queries the value under `key`, following `path` through named keys.
+    pub fn query(&self, key: Key, path: &[String]) -> QueryResult {
+        match self.tracking_copy.query(key, path) {
+            Ok(ret) => ret.into(),
+            Err(err) => QueryResult::Failure(err),
+        }
+    }
+
+    /// Reads the value stored under `key`.
+    pub fn read(&self, key: &Key) -> Result<Option<StoredValue>, GlobalStateError> {
+        self.tracking_copy.reader().read(key)
+    }
+
+    /// Returns the keys in the trie matching `prefix`.
+    pub fn keys_with_prefix(&self, prefix: &[u8]) -> Result<Vec<Key>, GlobalStateError> {
+        self.tracking_copy.reader().keys_with_prefix(prefix)
+    }
+}
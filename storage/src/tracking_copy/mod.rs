@@ -14,7 +14,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
     convert::{From, TryInto},
     fmt::Debug,
-    sync::Arc,
+    sync::{Arc, RwLock},
 };
 
 use linked_hash_map::LinkedHashMap;
@@ -338,6 +338,7 @@ pub struct TrackingCopy<R> {
     max_query_depth: u64,
     messages: Messages,
     enable_addressable_entity: bool,
+    keys_read: Option<Arc<RwLock<BTreeSet<Key>>>>,
 }
 
 /// Result of executing an "add" operation on a value in the state.
@@ -371,6 +372,18 @@ impl From<CLValueError> for AddResult {
 /// A helper type for `TrackingCopy` that represents a key-value pair.
 pub type TrackingCopyParts = (TrackingCopyCache, Effects, Messages);
 
+impl<R> TrackingCopy<R> {
+    /// Records `keys` as read, if recording was enabled.
+    fn insert_reads(&self, keys: impl IntoIterator<Item = Key>) {
+        if let Some(keys_read) = &self.keys_read {
+            keys_read
+                .write()
+                .expect("should lock keys read")
+                .extend(keys);
+        }
+    }
+}
+
 impl<R: StateReader<Key, StoredValue>> TrackingCopy<R>
 where
     R: StateReader<Key, StoredValue, Error = GlobalStateError>,
@@ -389,6 +402,7 @@ where
             max_query_depth,
             messages: Vec::new(),
             enable_addressable_entity,
+            keys_read: None,
         }
     }
 
@@ -435,6 +449,7 @@ where
             max_query_depth: self.max_query_depth,
             messages: self.messages.clone(),
             enable_addressable_entity: self.enable_addressable_entity,
+            keys_read: self.keys_read.clone(),
         }
     }
 
@@ -460,11 +475,36 @@ where
         self.effects.clone()
     }
 
+    /// Returns the number of execution effects cached by this instance.
+    pub fn effects_len(&self) -> usize {
+        self.effects.len()
+    }
+
     /// Returns copy of cache.
     pub fn cache(&self) -> TrackingCopyCache {
         self.cache.clone()
     }
 
+    /// Starts recording the keys read by this instance, including the keys listed by prefix
+    /// reads. Does nothing if recording is already enabled.
+    ///
+    /// The recorded set is shared with instances later created by [`TrackingCopy::fork2`], so
+    /// reads of forks whose changes are discarded are recorded as well.
+    pub fn record_reads(&mut self) {
+        if self.keys_read.is_none() {
+            self.keys_read = Some(Arc::new(RwLock::new(BTreeSet::new())));
+        }
+    }
+
+    /// Returns a copy of the set of keys read so far, which is empty unless recording was enabled
+    /// with [`TrackingCopy::record_reads`].
+    pub fn reads(&self) -> BTreeSet<Key> {
+        match &self.keys_read {
+            Some(keys_read) => keys_read.read().expect("should lock keys read").clone(),
+            None => BTreeSet::new(),
+        }
+    }
+
     /// Destructure cached entries.
     pub fn destructure(self) -> (Vec<(Key, StoredValue)>, BTreeSet<Key>, Effects) {
         let (writes, prunes) = self.cache.into_muts();
@@ -480,6 +520,7 @@ where
 
     /// Get record by key.
     pub fn get(&mut self, key: &Key) -> Result<Option<StoredValue>, TrackingCopyError> {
+        self.insert_reads([*key]);
         if let Some(value) = self.cache.get(key) {
             return Ok(Some(value.to_owned()));
         }
@@ -870,6 +911,7 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
     type Error = R::Error;
 
     fn read(&self, key: &Key) -> Result<Option<StoredValue>, Self::Error> {
+        self.insert_reads([*key]);
         let kb = KeyWithByteRepr::new(*key);
         if let Some(value) = self.cache.muts_cached.get(&kb) {
             return Ok(Some(value.to_owned()));
@@ -891,13 +933,14 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
     fn keys_with_prefix(&self, byte_prefix: &[u8]) -> Result<Vec<Key>, Self::Error> {
         let keys = self.reader.keys_with_prefix(byte_prefix)?;
 
-        let ret = keys
+        let ret: Vec<Key> = keys
             .into_iter()
             // don't include keys marked for pruning
             .filter(|key| !self.cache.is_pruned(key))
             // there may be newly inserted keys which have not been committed yet
             .chain(self.cache.get_muts_cached_by_byte_prefix(byte_prefix))
             .collect();
+        self.insert_reads(ret.iter().copied());
         Ok(ret)
     }
 }
@@ -120,6 +120,46 @@ fn tracking_copy_read() {
     assert_eq!(tc.effects, effects(vec![(k, TransformKindV2::Identity)]));
 }
 
+#[test]
+fn tracking_copy_reads() {
+    let counter = Arc::new(RwLock::new(0));
+    let db = CountingDb::new(Arc::clone(&counter));
+    let mut tc = TrackingCopy::new(db, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_ENABLE_ENTITY);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+    let k3 = Key::Hash([3u8; 32]);
+
+    // reads are only recorded once enabled
+    tc.read(&k2).unwrap().unwrap();
+    assert!(tc.reads().is_empty());
+    tc.record_reads();
+
+    // writes are not reads
+    tc.write(k1, StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()));
+    assert!(tc.reads().is_empty());
+
+    tc.read(&k2).unwrap().unwrap();
+    // cached reads are recorded as well
+    tc.read(&k2).unwrap().unwrap();
+    assert_eq!(tc.reads().into_iter().collect::<Vec<_>>(), vec![k2]);
+
+    // reads of a fork are recorded even if its changes are discarded
+    let mut fork = tc.fork2();
+    fork.read(&k3).unwrap().unwrap();
+    drop(fork);
+    assert_eq!(tc.reads().into_iter().collect::<Vec<_>>(), vec![k2, k3]);
+
+    // keys listed by prefix reads are recorded
+    assert_eq!(
+        tc.get_keys(&KeyTag::Hash)
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![k1]
+    );
+    assert_eq!(tc.reads().into_iter().collect::<Vec<_>>(), vec![k1, k2, k3]);
+}
+
 #[test]
 fn tracking_copy_write() {
     let counter = Arc::new(RwLock::new(0));
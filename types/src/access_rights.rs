@@ -1,8 +1,11 @@
 use alloc::{
     collections::{btree_map::Entry, BTreeMap},
+    string::String,
     vec::Vec,
 };
 use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
 
 #[cfg(any(feature = "testing", test))]
 use rand::{
@@ -17,6 +20,36 @@ pub use private::AccessRights;
 /// The number of bytes in a serialized [`AccessRights`].
 pub const ACCESS_RIGHTS_SERIALIZED_LENGTH: usize = 1;
 
+/// The characters representing each flag of [`AccessRights`], in the order used by
+/// [`AccessRights::to_chars`].
+const ACCESS_RIGHTS_CHARS: [(char, AccessRights); 3] = [
+    ('r', AccessRights::READ),
+    ('w', AccessRights::WRITE),
+    ('a', AccessRights::ADD),
+];
+
+/// Error while parsing [`AccessRights`] from characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FromCharsError {
+    /// The character does not represent any access right.
+    UnknownChar(char),
+    /// The character appears more than once.
+    DuplicateChar(char),
+}
+
+impl Display for FromCharsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FromCharsError::UnknownChar(c) => write!(f, "unknown access right '{}'", c),
+            FromCharsError::DuplicateChar(c) => write!(f, "duplicate access right '{}'", c),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for FromCharsError {}
+
 // Module exists only to restrict the scope of the following `#allow`.
 #[allow(clippy::bad_bit_mask)]
 mod private {
@@ -76,6 +109,44 @@ impl AccessRights {
     pub fn is_none(self) -> bool {
         self == AccessRights::NONE
     }
+
+    /// Returns `true` if every flag set in `other` is also set in `self`.
+    pub fn contains_all(self, other: AccessRights) -> bool {
+        self & other == other
+    }
+
+    /// Parses access rights from characters, where `r` stands for `READ`, `w` for `WRITE` and
+    /// `a` for `ADD`, e.g. `"rwa"`.
+    ///
+    /// The characters may appear in any order, but each at most once. An empty string parses to
+    /// `NONE`.
+    pub fn from_chars(chars: &str) -> Result<AccessRights, FromCharsError> {
+        let mut access_rights = AccessRights::NONE;
+        for c in chars.chars() {
+            let flag = ACCESS_RIGHTS_CHARS
+                .iter()
+                .find(|(flag_char, _)| *flag_char == c)
+                .map(|(_, flag)| *flag)
+                .ok_or(FromCharsError::UnknownChar(c))?;
+            if access_rights.contains(flag) {
+                return Err(FromCharsError::DuplicateChar(c));
+            }
+            access_rights |= flag;
+        }
+        Ok(access_rights)
+    }
+
+    /// Returns the characters representing the set flags, in the order `r`, `w`, `a`.
+    ///
+    /// `NONE` is represented by an empty string. The result always parses back to `self` with
+    /// [`AccessRights::from_chars`].
+    pub fn to_chars(self) -> String {
+        ACCESS_RIGHTS_CHARS
+            .iter()
+            .filter(|(_, flag)| self.contains(*flag))
+            .map(|(flag_char, _)| *flag_char)
+            .collect()
+    }
 }
 
 impl Display for AccessRights {
@@ -289,6 +360,49 @@ mod tests {
     const UREF_READ_ADD: URef = URef::new(UREF_ADDRESS, AccessRights::READ_ADD);
     const UREF_READ_ADD_WRITE: URef = URef::new(UREF_ADDRESS, AccessRights::READ_ADD_WRITE);
 
+    #[test]
+    fn should_round_trip_access_rights_through_chars() {
+        let expected = [
+            (AccessRights::NONE, ""),
+            (AccessRights::READ, "r"),
+            (AccessRights::WRITE, "w"),
+            (AccessRights::ADD, "a"),
+            (AccessRights::READ_WRITE, "rw"),
+            (AccessRights::READ_ADD, "ra"),
+            (AccessRights::ADD_WRITE, "wa"),
+            (AccessRights::READ_ADD_WRITE, "rwa"),
+        ];
+        for (access_rights, chars) in expected {
+            assert_eq!(access_rights.to_chars(), chars);
+            assert_eq!(AccessRights::from_chars(chars), Ok(access_rights));
+        }
+    }
+
+    #[test]
+    fn should_parse_access_rights_chars_in_any_order() {
+        assert_eq!(
+            AccessRights::from_chars("awr"),
+            Ok(AccessRights::READ_ADD_WRITE)
+        );
+        assert_eq!(AccessRights::from_chars("ar"), Ok(AccessRights::READ_ADD));
+    }
+
+    #[test]
+    fn should_reject_invalid_access_rights_chars() {
+        assert_eq!(
+            AccessRights::from_chars("rx"),
+            Err(FromCharsError::UnknownChar('x'))
+        );
+        assert_eq!(
+            AccessRights::from_chars("R"),
+            Err(FromCharsError::UnknownChar('R'))
+        );
+        assert_eq!(
+            AccessRights::from_chars("rwr"),
+            Err(FromCharsError::DuplicateChar('r'))
+        );
+    }
+
     fn test_readable(right: AccessRights, is_true: bool) {
         assert_eq!(right.is_readable(), is_true)
     }
@@ -334,6 +448,17 @@ mod tests {
         test_addable(AccessRights::READ_ADD_WRITE, true);
     }
 
+    #[test]
+    fn test_contains_all() {
+        assert!(AccessRights::READ_ADD_WRITE.contains_all(AccessRights::READ_WRITE));
+        assert!(AccessRights::READ_ADD.contains_all(AccessRights::ADD));
+        assert!(AccessRights::READ.contains_all(AccessRights::READ));
+        assert!(AccessRights::READ.contains_all(AccessRights::NONE));
+        assert!(!AccessRights::READ.contains_all(AccessRights::READ_WRITE));
+        assert!(!AccessRights::ADD_WRITE.contains_all(AccessRights::READ));
+        assert!(!AccessRights::NONE.contains_all(AccessRights::ADD));
+    }
+
     #[test]
     fn should_check_has_access_rights_to_uref() {
         let context_rights = ContextAccessRights::new(ENTITY_HASH, vec![UREF_READ_ADD]);
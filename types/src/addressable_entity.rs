@@ -72,6 +72,7 @@ use crate::{
     Key, NamedKeys, PackageHash, ProtocolVersion, PublicKey, Tagged, BLAKE2B_DIGEST_LENGTH,
     KEY_HASH_LENGTH,
 };
+pub(crate) use entry_points::V1_ENTRY_POINT_PREFIX;
 
 /// Maximum number of distinct user groups.
 pub const MAX_GROUPS: u8 = 10;
@@ -873,6 +874,20 @@ impl EntityAddr {
             _ => None,
         }
     }
+
+    /// Constructs the [`EntityAddr`] of a legacy contract stored under a [`ContractHash`].
+    ///
+    /// Legacy contracts are smart contracts, and their entity shares the bytes of the contract
+    /// hash.
+    pub fn from_legacy_contract_hash(contract_hash: ContractHash) -> Self {
+        Self::SmartContract(contract_hash.value())
+    }
+
+    /// Returns the legacy [`ContractHash`] of a smart contract entity, or `None` for system and
+    /// account entities.
+    pub fn into_legacy_contract_hash(self) -> Option<ContractHash> {
+        self.into_smart_contract().map(ContractHash::new)
+    }
 }
 
 impl ToBytes for EntityAddr {
@@ -1591,6 +1606,12 @@ impl AddressableEntity {
         total_weight >= *self.action_thresholds().key_management()
     }
 
+    /// Checks whether a set of signers carries enough weight to manage the associated keys and
+    /// action thresholds of this entity.
+    pub fn can_manage_keys(&self, keys: &BTreeSet<AccountHash>) -> bool {
+        self.associated_keys.total_weight_for(keys) >= *self.action_thresholds().key_management()
+    }
+
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to upgrade management threshold.
     pub fn can_upgrade_with(&self, authorization_keys: &BTreeSet<AccountHash>) -> bool {
@@ -1855,6 +1876,23 @@ mod tests {
         assert_eq!(decoded, entity_addr);
     }
 
+    #[test]
+    fn entity_addr_legacy_contract_hash_roundtrip() {
+        let contract_hash = ContractHash::new([7; 32]);
+        let entity_addr = EntityAddr::from_legacy_contract_hash(contract_hash);
+        assert_eq!(entity_addr, EntityAddr::SmartContract([7; 32]));
+        assert_eq!(entity_addr.into_legacy_contract_hash(), Some(contract_hash));
+
+        assert_eq!(
+            EntityAddr::Account([7; 32]).into_legacy_contract_hash(),
+            None
+        );
+        assert_eq!(
+            EntityAddr::System([7; 32]).into_legacy_contract_hash(),
+            None
+        );
+    }
+
     #[test]
     fn entity_addr_serialization_roundtrip() {
         for addr in [
@@ -1911,6 +1949,55 @@ mod tests {
         }
     }
 
+    fn multisig_entity() -> AddressableEntity {
+        let mut associated_keys = AssociatedKeys::new(AccountHash::new([1; 32]), Weight::new(1));
+        associated_keys
+            .add_key(AccountHash::new([2; 32]), Weight::new(1))
+            .unwrap();
+        let action_thresholds =
+            ActionThresholds::new(Weight::new(1), Weight::new(2), Weight::new(2)).unwrap();
+        AddressableEntity::new(
+            PackageHash::new([3; 32]),
+            ByteCodeHash::new([4; 32]),
+            ProtocolVersion::V1_0_0,
+            URef::default(),
+            associated_keys,
+            action_thresholds,
+            EntityKind::Account(AccountHash::new([1; 32])),
+        )
+    }
+
+    #[test]
+    fn should_reject_under_threshold_signers() {
+        let entity = multisig_entity();
+        let signers = BTreeSet::from([AccountHash::new([1; 32]), AccountHash::new([5; 32])]);
+
+        assert_eq!(
+            entity.associated_keys().total_weight_for(&signers),
+            Weight::new(1)
+        );
+        assert!(entity.can_authorize(&signers));
+        assert!(entity.can_deploy_with(&signers));
+        assert!(!entity.can_manage_keys(&signers));
+        assert!(!entity.can_upgrade_with(&signers));
+        assert!(!entity.can_authorize(&BTreeSet::from([AccountHash::new([5; 32])])));
+    }
+
+    #[test]
+    fn should_accept_signers_meeting_threshold() {
+        let entity = multisig_entity();
+        let signers = BTreeSet::from([AccountHash::new([1; 32]), AccountHash::new([2; 32])]);
+
+        assert_eq!(
+            entity.associated_keys().total_weight_for(&signers),
+            Weight::new(2)
+        );
+        assert!(entity.can_authorize(&signers));
+        assert!(entity.can_deploy_with(&signers));
+        assert!(entity.can_manage_keys(&signers));
+        assert!(entity.can_upgrade_with(&signers));
+    }
+
     #[test]
     fn should_extract_access_rights() {
         const MAIN_PURSE: URef = URef::new([2; 32], AccessRights::READ_ADD_WRITE);
@@ -125,6 +125,15 @@ impl AssociatedKeys {
         self.calculate_any_keys_weight(authorization_keys.iter())
     }
 
+    /// Returns the total weight of a set of signers.
+    ///
+    /// Signers which are not associated keys weigh nothing, and the sum saturates at the
+    /// maximum weight, so the result can be compared against any of the entity's
+    /// [`ActionThresholds`](super::ActionThresholds).
+    pub fn total_weight_for(&self, keys: &BTreeSet<AccountHash>) -> Weight {
+        self.calculate_any_keys_weight(keys.iter())
+    }
+
     /// Calculates total weight of all authorization keys
     pub fn total_keys_weight(&self) -> Weight {
         self.calculate_any_keys_weight(self.0.keys())
@@ -34,7 +34,7 @@ use crate::{
 
 const V1_ENTRY_POINT_TAG: u8 = 0;
 
-const V1_ENTRY_POINT_PREFIX: &str = "entry-point-v1-";
+pub(crate) const V1_ENTRY_POINT_PREFIX: &str = "entry-point-v1-";
 
 /// Context of method execution
 ///
@@ -139,6 +139,20 @@ impl EntryPointPayment {
             EntryPointPayment::DirectInvocationOnly | EntryPointPayment::SelfOnward => true,
         }
     }
+
+    /// The caller covers the costs of invoking the entry point.
+    pub fn pays_caller(&self) -> bool {
+        match self {
+            EntryPointPayment::Caller => true,
+            EntryPointPayment::DirectInvocationOnly | EntryPointPayment::SelfOnward => false,
+        }
+    }
+
+    /// The contract covers the costs of invoking the entry point, at least when it is directly
+    /// invoked.
+    pub fn pays_self(&self) -> bool {
+        self.will_pay_direct_invocation()
+    }
 }
 
 impl ToBytes for EntryPointPayment {
@@ -266,6 +280,16 @@ impl EntityEntryPoint {
     pub fn entry_point_payment(&self) -> EntryPointPayment {
         self.entry_point_payment
     }
+
+    /// Returns `true` if the caller covers the costs of invoking this entry point.
+    pub fn pays_caller(&self) -> bool {
+        self.entry_point_payment.pays_caller()
+    }
+
+    /// Returns `true` if the contract covers the costs of invoking this entry point.
+    pub fn pays_self(&self) -> bool {
+        self.entry_point_payment.pays_self()
+    }
 }
 
 impl Default for EntityEntryPoint {
@@ -820,4 +844,37 @@ mod tests {
         };
         bytesrepr::test_serialization_roundtrip(&vm1);
     }
+
+    #[test]
+    fn entry_point_payment_predicates() {
+        let caller = EntryPointPayment::Caller;
+        assert!(caller.pays_caller());
+        assert!(!caller.pays_self());
+
+        let direct_invocation_only = EntryPointPayment::DirectInvocationOnly;
+        assert!(!direct_invocation_only.pays_caller());
+        assert!(direct_invocation_only.pays_self());
+
+        let self_onward = EntryPointPayment::SelfOnward;
+        assert!(!self_onward.pays_caller());
+        assert!(self_onward.pays_self());
+    }
+
+    #[test]
+    fn entity_entry_point_should_expose_payment_predicates() {
+        let entry_point = EntityEntryPoint::default_with_name("call");
+        assert!(entry_point.pays_caller());
+        assert!(!entry_point.pays_self());
+
+        let entry_point = EntityEntryPoint::new(
+            "call",
+            Parameters::new(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Called,
+            EntryPointPayment::SelfOnward,
+        );
+        assert!(!entry_point.pays_caller());
+        assert!(entry_point.pays_self());
+    }
 }
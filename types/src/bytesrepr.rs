@@ -225,6 +225,51 @@ pub fn deserialize_from_slice<I: AsRef<[u8]>, O: FromBytes>(bytes: I) -> Result<
     }
 }
 
+/// Decodes a sequence of concatenated values from a slice of bytes.
+///
+/// Each call to [`Deserializer::decode`] consumes the bytes of one value, leaving the rest for
+/// subsequent calls.
+#[derive(Clone, Copy, Debug)]
+pub struct Deserializer<'a> {
+    remainder: &'a [u8],
+}
+
+impl<'a> Deserializer<'a> {
+    /// Creates a new `Deserializer` reading from `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Deserializer { remainder: bytes }
+    }
+
+    /// Decodes the next value from the stream.
+    ///
+    /// On error the stream is left unchanged.
+    pub fn decode<T: FromBytes>(&mut self) -> Result<T, Error> {
+        let (t, remainder) = T::from_bytes(self.remainder)?;
+        self.remainder = remainder;
+        Ok(t)
+    }
+
+    /// Returns the bytes which have not been consumed yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.remainder
+    }
+
+    /// Returns `true` if all of the input bytes have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.remainder.is_empty()
+    }
+
+    /// Consumes the `Deserializer`, returning an error if not all of the input bytes have been
+    /// consumed.
+    pub fn finish(self) -> Result<(), Error> {
+        if self.remainder.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::LeftOverBytes)
+        }
+    }
+}
+
 /// Serializes `t` into a `Vec<u8>`.
 pub fn serialize(t: impl ToBytes) -> Result<Vec<u8>, Error> {
     t.into_bytes()
@@ -1506,6 +1551,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserializer_should_decode_concatenated_values() {
+        let mut bytes = 42u64.to_bytes().unwrap();
+        bytes.extend(String::from("hello").to_bytes().unwrap());
+
+        let mut deserializer = Deserializer::new(&bytes);
+        assert_eq!(deserializer.decode::<u64>().unwrap(), 42);
+        assert_eq!(deserializer.remaining().len(), "hello".serialized_length());
+        assert_eq!(deserializer.decode::<String>().unwrap(), "hello");
+        assert!(deserializer.is_empty());
+        assert_eq!(deserializer.finish(), Ok(()));
+    }
+
+    #[test]
+    fn deserializer_should_detect_trailing_garbage() {
+        let mut bytes = 42u64.to_bytes().unwrap();
+        bytes.extend([1, 2, 3]);
+
+        let mut deserializer = Deserializer::new(&bytes);
+        assert_eq!(deserializer.decode::<u64>().unwrap(), 42);
+        assert_eq!(deserializer.remaining(), [1, 2, 3]);
+        assert_eq!(
+            deserializer.decode::<u64>().unwrap_err(),
+            Error::EarlyEndOfStream
+        );
+        assert_eq!(deserializer.remaining(), [1, 2, 3]);
+        assert_eq!(deserializer.finish(), Err(Error::LeftOverBytes));
+    }
+
     #[test]
     fn deserializing_empty_vec_has_no_capacity() {
         let bytes = ToBytes::to_bytes(&(0u32, b"123")).unwrap();
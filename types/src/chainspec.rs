@@ -53,7 +53,9 @@ pub use core_config::{
 pub use fee_handling::FeeHandling;
 #[cfg(any(feature = "std", test))]
 pub use genesis_config::GenesisConfig;
-pub use global_state_update::{GlobalStateUpdate, GlobalStateUpdateConfig, GlobalStateUpdateError};
+pub use global_state_update::{
+    GlobalStateUpdate, GlobalStateUpdateBuilder, GlobalStateUpdateConfig, GlobalStateUpdateError,
+};
 pub use highway_config::HighwayConfig;
 pub use hold_balance_handling::HoldBalanceHandling;
 pub use network_config::NetworkConfig;
@@ -73,8 +75,8 @@ pub use vacancy_config::VacancyConfig;
 pub use vm_config::{
     AuctionCosts, BrTableCost, ChainspecRegistry, ControlFlowCosts, HandlePaymentCosts,
     HostFunction, HostFunctionCost, HostFunctionCostsV1, HostFunctionCostsV2, HostFunctionV2,
-    MessageLimits, MintCosts, OpcodeCosts, StandardPaymentCosts, StorageCosts, SystemConfig,
-    WasmConfig, WasmV1Config, WasmV2Config, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY,
+    MessageLimits, MintCosts, OpcodeCosts, RegistryError, StandardPaymentCosts, StorageCosts,
+    SystemConfig, WasmConfig, WasmV1Config, WasmV2Config, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY,
 };
 #[cfg(any(feature = "testing", test))]
 pub use vm_config::{
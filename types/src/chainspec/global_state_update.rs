@@ -10,7 +10,7 @@ use thiserror::Error;
 use crate::testing::TestRng;
 use crate::{
     bytesrepr::{self, Bytes, FromBytes, ToBytes},
-    AsymmetricType, Key, PublicKey, U512,
+    AsymmetricType, Key, PublicKey, StoredValue, U512,
 };
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
@@ -106,6 +106,60 @@ impl FromBytes for GlobalStateUpdate {
     }
 }
 
+/// Builder of global state updates from typed key/value entries.
+///
+/// Every entry is checked to be serializable when building, so that the resulting update can be
+/// stored in a chainspec and applied during a protocol upgrade.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct GlobalStateUpdateBuilder {
+    validators: Option<BTreeMap<PublicKey, U512>>,
+    entries: BTreeMap<Key, StoredValue>,
+}
+
+impl GlobalStateUpdateBuilder {
+    /// Returns a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the value stored under `key`.
+    pub fn with_entry(mut self, key: Key, value: StoredValue) -> Self {
+        let _ = self.entries.insert(key, value);
+        self
+    }
+
+    /// Sets the full set of post-upgrade validators.
+    pub fn with_validators(mut self, validators: BTreeMap<PublicKey, U512>) -> Self {
+        self.validators = Some(validators);
+        self
+    }
+
+    /// Builds a [`GlobalStateUpdate`] holding the serialized entries.
+    pub fn build(self) -> Result<GlobalStateUpdate, bytesrepr::Error> {
+        let mut entries = BTreeMap::new();
+        for (key, value) in self.entries {
+            let _ = key.to_bytes()?;
+            let _ = entries.insert(key, value.to_bytes()?.into());
+        }
+        Ok(GlobalStateUpdate {
+            validators: self.validators,
+            entries,
+        })
+    }
+
+    /// Builds the key/value mapping applied by a protocol upgrade.
+    ///
+    /// The entries go through the same serialization roundtrip as a [`GlobalStateUpdate`] read
+    /// from a chainspec.
+    pub fn build_update_mapping(self) -> Result<BTreeMap<Key, StoredValue>, bytesrepr::Error> {
+        let mut update_mapping = BTreeMap::new();
+        for (key, value_bytes) in self.build()?.entries {
+            let _ = update_mapping.insert(key, bytesrepr::deserialize(value_bytes.into())?);
+        }
+        Ok(update_mapping)
+    }
+}
+
 /// Error loading global state update file.
 #[derive(Debug, Error)]
 pub enum GlobalStateUpdateError {
@@ -172,6 +226,23 @@ mod tests {
     use super::*;
     use rand::SeedableRng;
 
+    #[test]
+    fn builder_should_serialize_entries() {
+        let key = Key::Hash([1; 32]);
+        let value = StoredValue::CLValue(crate::CLValue::from_t(42u64).unwrap());
+        let builder = GlobalStateUpdateBuilder::new().with_entry(key, value.clone());
+
+        let update = builder.clone().build().unwrap();
+        assert_eq!(update.validators, None);
+        assert_eq!(
+            update.entries,
+            BTreeMap::from([(key, value.to_bytes().unwrap().into())])
+        );
+
+        let update_mapping = builder.build_update_mapping().unwrap();
+        assert_eq!(update_mapping, BTreeMap::from([(key, value)]));
+    }
+
     #[test]
     fn global_state_update_bytesrepr_roundtrip() {
         let mut rng = TestRng::from_entropy();
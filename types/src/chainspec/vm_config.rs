@@ -16,7 +16,7 @@ mod wasm_v2_config;
 pub use auction_costs::AuctionCosts;
 #[cfg(any(feature = "testing", test))]
 pub use auction_costs::{DEFAULT_ADD_BID_COST, DEFAULT_DELEGATE_COST};
-pub use chainspec_registry::ChainspecRegistry;
+pub use chainspec_registry::{ChainspecRegistry, RegistryError};
 pub use handle_payment_costs::HandlePaymentCosts;
 #[cfg(any(feature = "testing", test))]
 pub use host_function_costs::DEFAULT_NEW_DICTIONARY_COST;
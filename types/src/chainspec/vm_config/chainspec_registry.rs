@@ -1,6 +1,11 @@
 //! The registry of chainspec hash digests.
 
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+};
 
 #[cfg(any(feature = "testing", test))]
 use rand::{
@@ -12,11 +17,51 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
-    CLType, CLTyped, Digest,
+    CLType, CLTyped, ChainspecRawBytes, Digest,
 };
 
 type BytesreprChainspecRegistry = BTreeMap<String, Digest>;
 
+/// Error returned when a [`ChainspecRegistry`] is inconsistent with the raw bytes of the files.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RegistryError {
+    /// The registry holds a hash for a file whose raw bytes were not provided.
+    MissingBytes {
+        /// The registry entry of the file.
+        entry: &'static str,
+    },
+    /// The hash held by the registry differs from the hash of the file's raw bytes.
+    HashMismatch {
+        /// The registry entry of the file.
+        entry: &'static str,
+        /// The hash held by the registry.
+        registered: Digest,
+        /// The hash of the raw bytes.
+        computed: Digest,
+    },
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            RegistryError::MissingBytes { entry } => {
+                write!(formatter, "no raw bytes provided for {}", entry)
+            }
+            RegistryError::HashMismatch {
+                entry,
+                registered,
+                computed,
+            } => write!(
+                formatter,
+                "hash mismatch for {}: registered {} but computed {}",
+                entry, registered, computed
+            ),
+        }
+    }
+}
+
+impl StdError for RegistryError {}
+
 /// The chainspec registry.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
 pub struct ChainspecRegistry {
@@ -69,6 +114,45 @@ impl ChainspecRegistry {
         self.global_state_raw_hash.as_ref()
     }
 
+    /// Checks that every hash held by the registry matches the hash of the corresponding file in
+    /// `raw_bytes`.
+    ///
+    /// Files without a hash in the registry are not checked.
+    pub fn validate(&self, raw_bytes: &ChainspecRawBytes) -> Result<(), RegistryError> {
+        let entries = [
+            (
+                Self::CHAINSPEC_RAW_MAP_KEY,
+                Some(self.chainspec_raw_hash),
+                Some(raw_bytes.chainspec_bytes()),
+            ),
+            (
+                Self::GENESIS_ACCOUNTS_RAW_MAP_KEY,
+                self.genesis_accounts_raw_hash,
+                raw_bytes.maybe_genesis_accounts_bytes(),
+            ),
+            (
+                Self::GLOBAL_STATE_RAW_MAP_KEY,
+                self.global_state_raw_hash,
+                raw_bytes.maybe_global_state_bytes(),
+            ),
+        ];
+        for (entry, maybe_registered, maybe_bytes) in entries {
+            let Some(registered) = maybe_registered else {
+                continue;
+            };
+            let bytes = maybe_bytes.ok_or(RegistryError::MissingBytes { entry })?;
+            let computed = Digest::hash(bytes);
+            if computed != registered {
+                return Err(RegistryError::HashMismatch {
+                    entry,
+                    registered,
+                    computed,
+                });
+            }
+        }
+        Ok(())
+    }
+
     fn as_map(&self) -> BytesreprChainspecRegistry {
         let mut map = BTreeMap::new();
         map.insert(
@@ -148,6 +232,7 @@ mod tests {
     use rand::Rng;
 
     use super::*;
+    use crate::bytesrepr::Bytes;
 
     #[test]
     fn bytesrepr_roundtrip() {
@@ -171,4 +256,47 @@ mod tests {
             ChainspecRegistry::new_with_optional_global_state(&chainspec_file_bytes, None);
         bytesrepr::test_serialization_roundtrip(&chainspec_registry);
     }
+
+    #[test]
+    fn should_validate_registry_against_raw_bytes() {
+        let raw_bytes = ChainspecRawBytes::new(
+            Bytes::from(vec![1, 2, 3]),
+            Some(Bytes::from(vec![4, 5, 6])),
+            None,
+        );
+        let chainspec_registry = ChainspecRegistry::new_with_genesis(&[1, 2, 3], &[4, 5, 6]);
+        assert_eq!(chainspec_registry.validate(&raw_bytes), Ok(()));
+
+        let chainspec_registry =
+            ChainspecRegistry::new_with_optional_global_state(&[1, 2, 3], None);
+        assert_eq!(chainspec_registry.validate(&raw_bytes), Ok(()));
+    }
+
+    #[test]
+    fn should_reject_tampered_registry() {
+        let raw_bytes = ChainspecRawBytes::new(
+            Bytes::from(vec![1, 2, 3]),
+            Some(Bytes::from(vec![4, 5, 6])),
+            None,
+        );
+
+        let tampered_registry = ChainspecRegistry::new_with_genesis(&[1, 2, 3], &[4, 5, 7]);
+        assert_eq!(
+            tampered_registry.validate(&raw_bytes),
+            Err(RegistryError::HashMismatch {
+                entry: ChainspecRegistry::GENESIS_ACCOUNTS_RAW_MAP_KEY,
+                registered: Digest::hash([4, 5, 7]),
+                computed: Digest::hash([4, 5, 6]),
+            })
+        );
+
+        let tampered_registry =
+            ChainspecRegistry::new_with_optional_global_state(&[1, 2, 3], Some(&[7, 8, 9]));
+        assert_eq!(
+            tampered_registry.validate(&raw_bytes),
+            Err(RegistryError::MissingBytes {
+                entry: ChainspecRegistry::GLOBAL_STATE_RAW_MAP_KEY,
+            })
+        );
+    }
 }
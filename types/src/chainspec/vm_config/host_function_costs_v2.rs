@@ -197,6 +197,24 @@ const DEFAULT_EMIT_PAYLOAD_SIZE_HEIGHT: Cost = 100;
 
 const DEFAULT_ENV_INFO_COST: Cost = 10_000;
 
+const DEFAULT_REGISTER_TOPIC_COST: Cost = 200;
+const DEFAULT_REGISTER_TOPIC_SIZE_WEIGHT: Cost = 100;
+
+const DEFAULT_MESSAGE_COUNT_COST: Cost = 200;
+const DEFAULT_MESSAGE_COUNT_TOPIC_SIZE_WEIGHT: Cost = 100;
+
+const DEFAULT_EMIT_WITH_INDEX_COST: Cost = 200;
+
+const DEFAULT_TRY_CREATE_COST: Cost = 0;
+
+const DEFAULT_SELF_CODE_HASH_COST: Cost = 10_000;
+
+const DEFAULT_ABORT_COST: Cost = 300;
+const DEFAULT_ABORT_MESSAGE_SIZE_WEIGHT: Cost = 100;
+
+const DEFAULT_RANDOM_BYTES_COST: Cost = 10_000;
+const DEFAULT_RANDOM_BYTES_SIZE_WEIGHT: Cost = 100;
+
 /// Definition of a host function cost table.
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -228,6 +246,20 @@ pub struct HostFunctionCostsV2 {
     pub emit: HostFunctionV2<[Cost; 4]>,
     /// Cost of calling the `env_info` host function.
     pub env_info: HostFunctionV2<[Cost; 2]>,
+    /// Cost of calling the `register_topic` host function.
+    pub register_topic: HostFunctionV2<[Cost; 2]>,
+    /// Cost of calling the `message_count` host function.
+    pub message_count: HostFunctionV2<[Cost; 3]>,
+    /// Cost of calling the `emit_with_index` host function.
+    pub emit_with_index: HostFunctionV2<[Cost; 5]>,
+    /// Cost of calling the `try_create` host function.
+    pub try_create: HostFunctionV2<[Cost; 12]>,
+    /// Cost of calling the `self_code_hash` host function.
+    pub self_code_hash: HostFunctionV2<[Cost; 2]>,
+    /// Cost of calling the `abort` host function.
+    pub abort: HostFunctionV2<[Cost; 2]>,
+    /// Cost of calling the `random_bytes` host function.
+    pub random_bytes: HostFunctionV2<[Cost; 2]>,
 }
 
 impl HostFunctionCostsV2 {
@@ -246,6 +278,13 @@ impl HostFunctionCostsV2 {
             print: HostFunctionV2::zero(),
             emit: HostFunctionV2::zero(),
             env_info: HostFunctionV2::zero(),
+            register_topic: HostFunctionV2::zero(),
+            message_count: HostFunctionV2::zero(),
+            emit_with_index: HostFunctionV2::zero(),
+            try_create: HostFunctionV2::zero(),
+            self_code_hash: HostFunctionV2::zero(),
+            abort: HostFunctionV2::zero(),
+            random_bytes: HostFunctionV2::zero(),
         }
     }
 }
@@ -319,6 +358,50 @@ impl Default for HostFunctionCostsV2 {
                 ],
             ),
             env_info: HostFunctionV2::new(DEFAULT_ENV_INFO_COST, [NOT_USED, NOT_USED]),
+            register_topic: HostFunctionV2::new(
+                DEFAULT_REGISTER_TOPIC_COST,
+                [NOT_USED, DEFAULT_REGISTER_TOPIC_SIZE_WEIGHT],
+            ),
+            message_count: HostFunctionV2::new(
+                DEFAULT_MESSAGE_COUNT_COST,
+                [NOT_USED, DEFAULT_MESSAGE_COUNT_TOPIC_SIZE_WEIGHT, NOT_USED],
+            ),
+            emit_with_index: HostFunctionV2::new(
+                DEFAULT_EMIT_WITH_INDEX_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_EMIT_TOPIC_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_EMIT_PAYLOAD_SIZE_HEIGHT,
+                    NOT_USED,
+                ],
+            ),
+            try_create: HostFunctionV2::new(
+                DEFAULT_TRY_CREATE_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_CREATE_CODE_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    DEFAULT_CREATE_ENTRYPOINT_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_CREATE_INPUT_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_CREATE_SEED_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            self_code_hash: HostFunctionV2::new(DEFAULT_SELF_CODE_HASH_COST, [NOT_USED, NOT_USED]),
+            abort: HostFunctionV2::new(
+                DEFAULT_ABORT_COST,
+                [NOT_USED, DEFAULT_ABORT_MESSAGE_SIZE_WEIGHT],
+            ),
+            random_bytes: HostFunctionV2::new(
+                DEFAULT_RANDOM_BYTES_COST,
+                [NOT_USED, DEFAULT_RANDOM_BYTES_SIZE_WEIGHT],
+            ),
         }
     }
 }
@@ -339,6 +422,13 @@ impl ToBytes for HostFunctionCostsV2 {
         ret.append(&mut self.print.to_bytes()?);
         ret.append(&mut self.emit.to_bytes()?);
         ret.append(&mut self.env_info.to_bytes()?);
+        ret.append(&mut self.register_topic.to_bytes()?);
+        ret.append(&mut self.message_count.to_bytes()?);
+        ret.append(&mut self.emit_with_index.to_bytes()?);
+        ret.append(&mut self.try_create.to_bytes()?);
+        ret.append(&mut self.self_code_hash.to_bytes()?);
+        ret.append(&mut self.abort.to_bytes()?);
+        ret.append(&mut self.random_bytes.to_bytes()?);
         Ok(ret)
     }
 
@@ -356,6 +446,13 @@ impl ToBytes for HostFunctionCostsV2 {
             + self.print.serialized_length()
             + self.emit.serialized_length()
             + self.env_info.serialized_length()
+            + self.register_topic.serialized_length()
+            + self.message_count.serialized_length()
+            + self.emit_with_index.serialized_length()
+            + self.try_create.serialized_length()
+            + self.self_code_hash.serialized_length()
+            + self.abort.serialized_length()
+            + self.random_bytes.serialized_length()
     }
 }
 
@@ -374,6 +471,13 @@ impl FromBytes for HostFunctionCostsV2 {
         let (print, rem) = FromBytes::from_bytes(rem)?;
         let (emit, rem) = FromBytes::from_bytes(rem)?;
         let (env_info, rem) = FromBytes::from_bytes(rem)?;
+        let (register_topic, rem) = FromBytes::from_bytes(rem)?;
+        let (message_count, rem) = FromBytes::from_bytes(rem)?;
+        let (emit_with_index, rem) = FromBytes::from_bytes(rem)?;
+        let (try_create, rem) = FromBytes::from_bytes(rem)?;
+        let (self_code_hash, rem) = FromBytes::from_bytes(rem)?;
+        let (abort, rem) = FromBytes::from_bytes(rem)?;
+        let (random_bytes, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             HostFunctionCostsV2 {
                 read,
@@ -389,6 +493,13 @@ impl FromBytes for HostFunctionCostsV2 {
                 print,
                 emit,
                 env_info,
+                register_topic,
+                message_count,
+                emit_with_index,
+                try_create,
+                self_code_hash,
+                abort,
+                random_bytes,
             },
             rem,
         ))
@@ -412,6 +523,13 @@ impl Distribution<HostFunctionCostsV2> for Standard {
             print: rng.gen(),
             emit: rng.gen(),
             env_info: rng.gen(),
+            register_topic: rng.gen(),
+            message_count: rng.gen(),
+            emit_with_index: rng.gen(),
+            try_create: rng.gen(),
+            self_code_hash: rng.gen(),
+            abort: rng.gen(),
+            random_bytes: rng.gen(),
         }
     }
 }
@@ -445,6 +563,13 @@ pub mod gens {
             print in host_function_cost_v2_arb(),
             emit in host_function_cost_v2_arb(),
             env_info in host_function_cost_v2_arb(),
+            register_topic in host_function_cost_v2_arb(),
+            message_count in host_function_cost_v2_arb(),
+            emit_with_index in host_function_cost_v2_arb(),
+            try_create in host_function_cost_v2_arb(),
+            self_code_hash in host_function_cost_v2_arb(),
+            abort in host_function_cost_v2_arb(),
+            random_bytes in host_function_cost_v2_arb(),
         ) -> HostFunctionCostsV2 {
             HostFunctionCostsV2 {
                 read,
@@ -459,7 +584,14 @@ pub mod gens {
                 call,
                 print,
                 emit,
-                env_info
+                env_info,
+                register_topic,
+                message_count,
+                emit_with_index,
+                try_create,
+                self_code_hash,
+                abort,
+                random_bytes
             }
         }
     }
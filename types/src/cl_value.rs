@@ -38,11 +38,7 @@ pub struct CLTypeMismatch {
 
 impl Display for CLTypeMismatch {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Expected {:?} but found {:?}.",
-            self.expected, self.found
-        )
+        write!(f, "Expected {} but found {}.", self.expected, self.found)
     }
 }
 
@@ -271,6 +267,27 @@ mod tests {
         U128, U256, U512, UREF_ADDR_LENGTH,
     };
 
+    #[test]
+    fn should_display_type_mismatch_with_type_names() {
+        let error = CLValue::from_t("hello".to_string())
+            .unwrap()
+            .into_t::<U512>()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Type mismatch: Expected u512 but found string."
+        );
+
+        let mismatch = CLTypeMismatch {
+            expected: CLType::Option(Box::new(CLType::U512)),
+            found: CLType::List(Box::new(CLType::String)),
+        };
+        assert_eq!(
+            mismatch.to_string(),
+            "Expected option<u512> but found list<string>."
+        );
+    }
+
     #[cfg(feature = "json-schema")]
     #[test]
     fn json_schema() {
@@ -0,0 +1,132 @@
+//! The `dictionary_item_key` module provides a length-checked dictionary item key.
+
+use alloc::string::String;
+use core::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+
+use crate::{Key, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH};
+
+/// Error returned when constructing a [`DictionaryItemKey`] from a string that is too long.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DictionaryItemKeyError {
+    length: usize,
+}
+
+impl DictionaryItemKeyError {
+    /// Returns the length of the rejected dictionary item key.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+}
+
+impl Display for DictionaryItemKeyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "dictionary item key length {} exceeds the maximum of {}",
+            self.length, DICTIONARY_ITEM_KEY_MAX_LENGTH
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for DictionaryItemKeyError {}
+
+/// A dictionary item key which is guaranteed to be no longer than
+/// [`DICTIONARY_ITEM_KEY_MAX_LENGTH`] bytes.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+pub struct DictionaryItemKey(String);
+
+impl DictionaryItemKey {
+    /// Constructs a new `DictionaryItemKey`.
+    ///
+    /// Returns an error if `item_key` is longer than [`DICTIONARY_ITEM_KEY_MAX_LENGTH`] bytes.
+    pub fn new<T: Into<String>>(item_key: T) -> Result<Self, DictionaryItemKeyError> {
+        let item_key = item_key.into();
+        if item_key.len() > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+            return Err(DictionaryItemKeyError {
+                length: item_key.len(),
+            });
+        }
+        Ok(DictionaryItemKey(item_key))
+    }
+
+    /// Returns the item key as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the [`Key::Dictionary`] under which this item is stored in the dictionary seeded by
+    /// `seed_uref`.
+    pub fn to_key(&self, seed_uref: URef) -> Key {
+        Key::dictionary(seed_uref, self.0.as_bytes())
+    }
+}
+
+impl TryFrom<&str> for DictionaryItemKey {
+    type Error = DictionaryItemKeyError;
+
+    fn try_from(item_key: &str) -> Result<Self, Self::Error> {
+        DictionaryItemKey::new(item_key)
+    }
+}
+
+impl TryFrom<String> for DictionaryItemKey {
+    type Error = DictionaryItemKeyError;
+
+    fn try_from(item_key: String) -> Result<Self, Self::Error> {
+        DictionaryItemKey::new(item_key)
+    }
+}
+
+impl AsRef<str> for DictionaryItemKey {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for DictionaryItemKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::AccessRights;
+
+    #[test]
+    fn should_accept_key_up_to_max_length() {
+        let item_key = "a".repeat(DICTIONARY_ITEM_KEY_MAX_LENGTH);
+        let dictionary_item_key = DictionaryItemKey::new(item_key.clone()).unwrap();
+        assert_eq!(dictionary_item_key.as_str(), item_key);
+
+        let seed_uref = URef::new([7; 32], AccessRights::READ_ADD_WRITE);
+        assert_eq!(
+            dictionary_item_key.to_key(seed_uref),
+            Key::dictionary(seed_uref, item_key.as_bytes())
+        );
+    }
+
+    #[test]
+    fn should_reject_over_length_key() {
+        let item_key = "a".repeat(DICTIONARY_ITEM_KEY_MAX_LENGTH + 1);
+        let error = DictionaryItemKey::try_from(item_key.as_str()).unwrap_err();
+        assert_eq!(error.length(), DICTIONARY_ITEM_KEY_MAX_LENGTH + 1);
+        assert_eq!(
+            error.to_string(),
+            "dictionary item key length 129 exceeds the maximum of 128"
+        );
+    }
+}
@@ -122,6 +122,19 @@ impl ChunkWithProof {
         }
         Ok(())
     }
+
+    /// Verify the integrity of this chunk with indexed Merkle proof, and that the proof leads to
+    /// the given Merkle root of the whole data.
+    pub fn verify_against_root(
+        &self,
+        merkle_root: &Digest,
+    ) -> Result<(), ChunkWithProofVerificationError> {
+        self.verify()?;
+        if self.proof().root_hash() != *merkle_root {
+            return Err(ChunkWithProofVerificationError::UnexpectedRootHash);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -135,7 +148,7 @@ mod tests {
 
     use crate::{
         bytesrepr::{self, FromBytes, ToBytes},
-        ChunkWithProof, Digest, MerkleConstructionError,
+        ChunkWithProof, ChunkWithProofVerificationError, Digest, MerkleConstructionError,
     };
 
     fn prepare_bytes(length: usize) -> Vec<u8> {
@@ -290,6 +303,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verifies_each_chunk_against_merkle_root() {
+        // Spans several chunks, the last of which is partial.
+        let data = prepare_bytes(ChunkWithProof::CHUNK_SIZE_BYTES * 12 + 5);
+        let merkle_root = Digest::hash_merkle_tree(
+            data.chunks(ChunkWithProof::CHUNK_SIZE_BYTES)
+                .map(Digest::hash),
+        );
+
+        let chunk_count = data.chunks(ChunkWithProof::CHUNK_SIZE_BYTES).len() as u64;
+        for index in 0..chunk_count {
+            let chunk_with_proof = ChunkWithProof::new(&data, index).unwrap();
+            chunk_with_proof
+                .verify_against_root(&merkle_root)
+                .expect("should verify against merkle root");
+
+            let other_root = Digest::hash(merkle_root);
+            assert!(matches!(
+                chunk_with_proof.verify_against_root(&other_root),
+                Err(ChunkWithProofVerificationError::UnexpectedRootHash)
+            ));
+
+            let chunk_with_incorrect_proof = chunk_with_proof.replace_first_proof();
+            assert!(chunk_with_incorrect_proof
+                .verify_against_root(&merkle_root)
+                .is_err());
+        }
+    }
+
     #[test]
     fn returns_error_on_incorrect_index() {
         // This test needs specific data sizes, hence it doesn't use the proptest
@@ -50,6 +50,14 @@ impl Effects {
         self.0.len()
     }
 
+    /// Sorts the transforms by key.
+    ///
+    /// The sort is stable, so transforms recorded under the same key keep their relative order and
+    /// applying the effects yields the same state as before sorting.
+    pub fn order_by_key(&mut self) {
+        self.0.sort_by(|lhs, rhs| lhs.key().cmp(rhs.key()));
+    }
+
     /// Consumes `self`, returning the wrapped vec.
     pub fn value(self) -> Vec<TransformV2> {
         self.0
@@ -96,6 +104,37 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn should_order_by_key_keeping_order_within_key() {
+        use crate::{Key, StoredValue, U512};
+
+        let write = |key, value: u64| {
+            TransformV2::new(
+                key,
+                TransformKindV2::Write(StoredValue::CLValue(
+                    crate::CLValue::from_t(U512::from(value)).unwrap(),
+                )),
+            )
+        };
+        let first = Key::Hash([1; 32]);
+        let second = Key::Hash([2; 32]);
+
+        let mut effects = Effects::new();
+        effects.push(write(second, 1));
+        effects.push(write(first, 2));
+        effects.push(write(second, 3));
+        effects.push(write(first, 4));
+        effects.order_by_key();
+
+        let expected = [
+            write(first, 2),
+            write(first, 4),
+            write(second, 1),
+            write(second, 3),
+        ];
+        assert_eq!(effects.transforms(), expected);
+    }
+
     #[test]
     fn bytesrepr_roundtrip() {
         let rng = &mut TestRng::new();
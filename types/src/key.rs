@@ -37,6 +37,7 @@ use crate::{
     account::{AccountHash, ACCOUNT_HASH_LENGTH},
     addressable_entity::{
         self, AddressableEntityHash, EntityAddr, EntityKindTag, EntryPointAddr, NamedKeyAddr,
+        V1_ENTRY_POINT_PREFIX,
     },
     block::BlockGlobalAddr,
     byte_code,
@@ -555,6 +556,11 @@ impl Key {
     }
 
     /// Returns a human-readable version of `self`, with the inner bytes encoded to Base16.
+    ///
+    /// Each variant is rendered with a distinct prefix followed by its payload, e.g.
+    /// `dictionary-{hex addr}` for [`Key::Dictionary`] and
+    /// `entry-point-v1-{formatted entity addr}-{hex name}` for [`Key::EntryPoint`].  The output
+    /// can be parsed back into an identical `Key` via [`Key::from_formatted_str`].
     pub fn to_formatted_string(self) -> String {
         match self {
             Key::Account(account_hash) => account_hash.to_formatted_string(),
@@ -965,10 +971,10 @@ impl Key {
             return Ok(BlockGlobalAddr::AddressableEntity.into());
         }
 
-        match EntryPointAddr::from_formatted_str(input) {
-            Ok(entry_point_addr) => return Ok(Key::EntryPoint(entry_point_addr)),
-            Err(addressable_entity::FromStrError::InvalidPrefix) => {}
-            Err(error) => return Err(FromStrError::EntryPoint(error.to_string())),
+        if input.starts_with(V1_ENTRY_POINT_PREFIX) {
+            return EntryPointAddr::from_formatted_str(input)
+                .map(Key::EntryPoint)
+                .map_err(|error| FromStrError::EntryPoint(error.to_string()));
         }
 
         if let Some(entity_addr_formatted) = input.strip_prefix(STATE_PREFIX) {
@@ -1368,6 +1374,27 @@ impl Key {
             _ => None,
         }
     }
+
+    /// Returns the [`EntityAddr`] of the entity addressed by `self`, if any.
+    ///
+    /// * [`Key::AddressableEntity`] returns its entity addr.
+    /// * [`Key::Account`] is the account entity with the same account hash.
+    /// * [`Key::Hash`] is a legacy contract hash, see [`EntityAddr::from_legacy_contract_hash`].
+    /// * [`Key::SmartContract`] is a smart contract entity addressed by its package address, as
+    ///   done by the Wasm V2 executor.
+    ///
+    /// All other keys return `None`.
+    pub fn as_entity_addr(&self) -> Option<EntityAddr> {
+        match self {
+            Key::AddressableEntity(entity_addr) => Some(*entity_addr),
+            Key::Account(account_hash) => Some(EntityAddr::new_account(account_hash.value())),
+            Key::Hash(hash_addr) => Some(EntityAddr::from_legacy_contract_hash(ContractHash::new(
+                *hash_addr,
+            ))),
+            Key::SmartContract(package_addr) => Some(EntityAddr::new_smart_contract(*package_addr)),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Key {
@@ -2614,6 +2641,17 @@ mod tests {
             "unknown prefix for key"
         );
 
+        for invalid_entry_point in [V1_ENTRY_POINT_PREFIX, "entry-point-v1-entity-contract-2a"] {
+            let error_string = Key::from_formatted_str(invalid_entry_point)
+                .unwrap_err()
+                .to_string();
+            assert!(
+                error_string.starts_with("entry-point from string error: "),
+                "{}",
+                error_string
+            );
+        }
+
         let balance_hold_err = Key::from_formatted_str(BALANCE_HOLD_PREFIX)
             .unwrap_err()
             .to_string();
@@ -2747,6 +2785,50 @@ mod tests {
         round_trip(&state_key);
     }
 
+    #[test]
+    fn dictionary_formatted_string_round_trip() {
+        let mut test_rng = TestRng::new();
+        let key = Key::Dictionary(test_rng.gen());
+        let formatted = key.to_formatted_string();
+        assert!(formatted.starts_with(DICTIONARY_PREFIX));
+        assert_eq!(Key::from_formatted_str(&formatted).unwrap(), key);
+        round_trip(&key);
+
+        let error_string = Key::from_formatted_str(&format!("{}{}", DICTIONARY_PREFIX, "00"))
+            .unwrap_err()
+            .to_string();
+        assert!(error_string.starts_with("dictionary-key from string error: "));
+    }
+
+    #[test]
+    fn entry_point_formatted_string_round_trip() {
+        let mut test_rng = TestRng::new();
+        for entity_addr in [
+            EntityAddr::new_system(test_rng.gen()),
+            EntityAddr::new_account(test_rng.gen()),
+            EntityAddr::new_smart_contract(test_rng.gen()),
+        ] {
+            let entry_point_addr =
+                EntryPointAddr::new_v1_entry_point_addr(entity_addr, "entry_point").unwrap();
+            let key = Key::EntryPoint(entry_point_addr);
+            let formatted = key.to_formatted_string();
+            assert!(formatted.starts_with(V1_ENTRY_POINT_PREFIX));
+            assert_eq!(formatted, entry_point_addr.to_formatted_string());
+            assert_eq!(Key::from_formatted_str(&formatted).unwrap(), key);
+            assert_eq!(
+                format!("{}", key),
+                format!("Key::EntryPointAddr({})", entry_point_addr)
+            );
+            round_trip(&key);
+        }
+
+        let random_key = Key::EntryPoint(test_rng.gen());
+        assert_eq!(
+            Key::from_formatted_str(&random_key.to_formatted_string()).unwrap(),
+            random_key
+        );
+    }
+
     #[test]
     fn roundtrip() {
         bytesrepr::test_serialization_roundtrip(&ACCOUNT_KEY);
@@ -2778,6 +2860,31 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&NAMED_KEY);
     }
 
+    #[test]
+    fn should_convert_keys_to_entity_addr() {
+        assert_eq!(
+            ACCOUNT_KEY.as_entity_addr(),
+            Some(EntityAddr::Account([42; 32]))
+        );
+        assert_eq!(
+            HASH_KEY.as_entity_addr(),
+            Some(EntityAddr::SmartContract([42; 32]))
+        );
+        assert_eq!(
+            PACKAGE_KEY.as_entity_addr(),
+            Some(EntityAddr::SmartContract([42; 32]))
+        );
+        for key in [
+            ADDRESSABLE_ENTITY_SYSTEM_KEY,
+            ADDRESSABLE_ENTITY_ACCOUNT_KEY,
+            ADDRESSABLE_ENTITY_SMART_CONTRACT_KEY,
+        ] {
+            assert_eq!(key.as_entity_addr(), key.into_entity_addr());
+        }
+        assert_eq!(UREF_KEY.as_entity_addr(), None);
+        assert_eq!(BALANCE_KEY.as_entity_addr(), None);
+    }
+
     fn round_trip(key: &Key) {
         let encoded = serde_json::to_value(key).unwrap();
         let decoded = serde_json::from_value(encoded.clone())
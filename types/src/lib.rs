@@ -41,6 +41,7 @@ mod contract_wasm;
 pub mod contracts;
 pub mod crypto;
 mod deploy_info;
+mod dictionary_item_key;
 mod digest;
 mod display_iter;
 mod era_id;
@@ -83,7 +84,8 @@ use once_cell::sync::Lazy;
 pub use crate::uint::{UIntParseError, U128, U256, U512};
 
 pub use access_rights::{
-    AccessRights, ContextAccessRights, GrantedAccess, ACCESS_RIGHTS_SERIALIZED_LENGTH,
+    AccessRights, ContextAccessRights, FromCharsError as AccessRightsFromCharsError, GrantedAccess,
+    ACCESS_RIGHTS_SERIALIZED_LENGTH,
 };
 pub use account::Account;
 #[doc(inline)]
@@ -126,15 +128,15 @@ pub use chainspec::{
     AccountConfig, AccountsConfig, ActivationPoint, AdministratorAccount, AuctionCosts,
     BrTableCost, Chainspec, ChainspecRawBytes, ChainspecRegistry, ConsensusProtocolName,
     ControlFlowCosts, CoreConfig, DelegatorConfig, DeployConfig, FeeHandling, GenesisAccount,
-    GenesisConfig, GenesisValidator, GlobalStateUpdate, GlobalStateUpdateConfig,
-    GlobalStateUpdateError, HandlePaymentCosts, HighwayConfig, HoldBalanceHandling, HostFunction,
-    HostFunctionCost, HostFunctionCostsV1, HostFunctionCostsV2, HostFunctionV2,
-    LegacyRequiredFinality, MessageLimits, MintCosts, NetworkConfig, NextUpgrade, OpcodeCosts,
-    PricingHandling, ProtocolConfig, ProtocolUpgradeConfig, RefundHandling, StandardPaymentCosts,
-    StorageCosts, SystemConfig, TransactionConfig, TransactionLaneDefinition, TransactionV1Config,
-    VacancyConfig, ValidatorConfig, WasmConfig, WasmV1Config, WasmV2Config,
-    DEFAULT_BASELINE_MOTES_AMOUNT, DEFAULT_GAS_HOLD_INTERVAL, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY,
-    DEFAULT_MINIMUM_BID_AMOUNT, DEFAULT_REFUND_HANDLING,
+    GenesisConfig, GenesisValidator, GlobalStateUpdate, GlobalStateUpdateBuilder,
+    GlobalStateUpdateConfig, GlobalStateUpdateError, HandlePaymentCosts, HighwayConfig,
+    HoldBalanceHandling, HostFunction, HostFunctionCost, HostFunctionCostsV1, HostFunctionCostsV2,
+    HostFunctionV2, LegacyRequiredFinality, MessageLimits, MintCosts, NetworkConfig, NextUpgrade,
+    OpcodeCosts, PricingHandling, ProtocolConfig, ProtocolUpgradeConfig, RefundHandling,
+    RegistryError, StandardPaymentCosts, StorageCosts, SystemConfig, TransactionConfig,
+    TransactionLaneDefinition, TransactionV1Config, VacancyConfig, ValidatorConfig, WasmConfig,
+    WasmV1Config, WasmV2Config, DEFAULT_BASELINE_MOTES_AMOUNT, DEFAULT_GAS_HOLD_INTERVAL,
+    DEFAULT_HOST_FUNCTION_NEW_DICTIONARY, DEFAULT_MINIMUM_BID_AMOUNT, DEFAULT_REFUND_HANDLING,
 };
 #[cfg(any(all(feature = "std", feature = "testing"), test))]
 pub use chainspec::{
@@ -158,6 +160,7 @@ pub use contract_wasm::{ContractWasm, ContractWasmHash};
 pub use contracts::{Contract, NamedKeys};
 pub use crypto::*;
 pub use deploy_info::DeployInfo;
+pub use dictionary_item_key::{DictionaryItemKey, DictionaryItemKeyError};
 pub use digest::{
     ChunkWithProof, ChunkWithProofVerificationError, Digest, DigestError, IndexedMerkleProof,
     MerkleConstructionError, MerkleVerificationError,
@@ -198,11 +201,12 @@ pub use transaction::{
     DeployError, DeployExcessiveSizeError, DeployHash, DeployHeader, DeployId,
     ExecutableDeployItem, ExecutableDeployItemIdentifier, ExecutionInfo, InitiatorAddr,
     InvalidDeploy, InvalidTransaction, InvalidTransactionV1, NamedArg, PackageIdentifier,
-    PricingMode, PricingModeError, RuntimeArgs, Transaction, TransactionArgs,
-    TransactionEntryPoint, TransactionHash, TransactionId, TransactionInvocationTarget,
-    TransactionRuntimeParams, TransactionScheduling, TransactionTarget, TransactionV1,
-    TransactionV1DecodeFromJsonError, TransactionV1Error, TransactionV1ExcessiveSizeError,
-    TransactionV1Hash, TransactionV1Payload, TransferTarget,
+    PricingMode, PricingModeError, RuntimeArgs, RuntimeArgsBuilder, RuntimeArgsBuilderError,
+    Transaction, TransactionArgs, TransactionEntryPoint, TransactionHash, TransactionId,
+    TransactionInvocationTarget, TransactionRuntimeParams, TransactionScheduling,
+    TransactionTarget, TransactionV1, TransactionV1DecodeFromJsonError, TransactionV1Error,
+    TransactionV1ExcessiveSizeError, TransactionV1Hash, TransactionV1Payload, TransferTarget,
+    TransferTargetError,
 };
 pub use transfer::{
     Transfer, TransferAddr, TransferFromStrError, TransferV1, TransferV2, TRANSFER_ADDR_LENGTH,
@@ -1,6 +1,10 @@
 //! The `motes` module is used for working with Motes.
 
-use alloc::vec::Vec;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::fmt;
 
 #[cfg(feature = "datasize")]
@@ -12,6 +16,9 @@ use crate::{
     Gas, U512,
 };
 
+/// The number of decimal places of a CSPR amount expressed in motes.
+const CSPR_DECIMALS: usize = 9;
+
 /// A struct representing a number of `Motes`.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -72,6 +79,24 @@ impl Motes {
     pub fn from_price(amount: U512, price: u8) -> Option<Self> {
         amount.checked_mul(U512::from(price)).map(Self::new)
     }
+
+    /// Formats the amount in CSPR, with thousands separators and all nine decimal places, e.g.
+    /// `Motes::new(1_234_500_000_000u64)` is formatted as `"1,234.500000000 CSPR"`.
+    pub fn to_cspr_string(&self) -> String {
+        let digits = self.0.to_string();
+        let padded = format!("{:0>width$}", digits, width = CSPR_DECIMALS + 1);
+        let (whole, fraction) = padded.split_at(padded.len() - CSPR_DECIMALS);
+
+        let mut separated = String::with_capacity(whole.len() + whole.len() / 3);
+        for (index, digit) in whole.chars().enumerate() {
+            if index > 0 && (whole.len() - index) % 3 == 0 {
+                separated.push(',');
+            }
+            separated.push(digit);
+        }
+
+        format!("{}.{} CSPR", separated, fraction)
+    }
 }
 
 impl fmt::Display for Motes {
@@ -114,6 +139,36 @@ mod tests {
         )
     }
 
+    #[test]
+    fn should_format_small_amounts_as_cspr() {
+        assert_eq!(Motes::zero().to_cspr_string(), "0.000000000 CSPR");
+        assert_eq!(Motes::new(1).to_cspr_string(), "0.000000001 CSPR");
+        assert_eq!(
+            Motes::new(2_500_000_000u64).to_cspr_string(),
+            "2.500000000 CSPR"
+        );
+        assert_eq!(
+            Motes::new(999_999_999_999u64).to_cspr_string(),
+            "999.999999999 CSPR"
+        );
+    }
+
+    #[test]
+    fn should_format_large_amounts_as_cspr_with_thousands_separators() {
+        assert_eq!(
+            Motes::new(1_234_500_000_000u64).to_cspr_string(),
+            "1,234.500000000 CSPR"
+        );
+        assert_eq!(
+            Motes::new(U512::from(10u64).pow(U512::from(19u64))).to_cspr_string(),
+            "10,000,000,000.000000000 CSPR"
+        );
+        assert_eq!(
+            Motes::new(100_000_000_000_000_001u64).to_cspr_string(),
+            "100,000,000.000000001 CSPR"
+        );
+    }
+
     #[test]
     fn should_be_able_to_compare_two_instances_of_motes() {
         let left_motes = Motes::new(1);
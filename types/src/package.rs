@@ -214,6 +214,11 @@ impl EntityVersions {
         self.0.values()
     }
 
+    /// Returns an iterator over the `EntityVersionKey`s (i.e. the map's keys).
+    pub fn keys(&self) -> impl Iterator<Item = &EntityVersionKey> {
+        self.0.keys()
+    }
+
     /// Returns the `AddressableEntityHash` under the key
     pub fn get(&self, key: &EntityVersionKey) -> Option<&EntityAddr> {
         self.0.get(key)
@@ -129,6 +129,19 @@ impl ProtocolVersion {
     pub fn is_compatible_with(&self, version: &ProtocolVersion) -> bool {
         self.0.major == version.0.major
     }
+
+    /// Checks if this protocol version is equal to or newer than `other`.
+    ///
+    /// Useful for gating behavior on a minimum protocol version.
+    pub fn is_at_least(&self, other: ProtocolVersion) -> bool {
+        *self >= other
+    }
+
+    /// Checks if a network running `other` needs to be upgraded to reach this protocol version,
+    /// i.e. if this version is strictly newer than `other`.
+    pub fn requires_upgrade_from(&self, other: ProtocolVersion) -> bool {
+        *self > other
+    }
 }
 
 impl ToBytes for ProtocolVersion {
@@ -536,6 +549,34 @@ mod tests {
         assert!(current.is_compatible_with(&other));
     }
 
+    #[test]
+    fn should_check_minimum_version_across_boundaries() {
+        let version = ProtocolVersion::from_parts(2, 1, 3);
+
+        assert!(version.is_at_least(version));
+        assert!(version.is_at_least(ProtocolVersion::from_parts(2, 1, 2)));
+        assert!(version.is_at_least(ProtocolVersion::from_parts(2, 0, 99)));
+        assert!(version.is_at_least(ProtocolVersion::from_parts(1, 99, 99)));
+
+        assert!(!version.is_at_least(ProtocolVersion::from_parts(2, 1, 4)));
+        assert!(!version.is_at_least(ProtocolVersion::from_parts(2, 2, 0)));
+        assert!(!version.is_at_least(ProtocolVersion::from_parts(3, 0, 0)));
+    }
+
+    #[test]
+    fn should_require_upgrade_only_from_older_versions() {
+        let version = ProtocolVersion::from_parts(2, 1, 3);
+
+        assert!(!version.requires_upgrade_from(version));
+        assert!(version.requires_upgrade_from(ProtocolVersion::from_parts(2, 1, 2)));
+        assert!(version.requires_upgrade_from(ProtocolVersion::from_parts(2, 0, 99)));
+        assert!(version.requires_upgrade_from(ProtocolVersion::from_parts(1, 99, 99)));
+
+        assert!(!version.requires_upgrade_from(ProtocolVersion::from_parts(2, 1, 4)));
+        assert!(!version.requires_upgrade_from(ProtocolVersion::from_parts(2, 2, 0)));
+        assert!(!version.requires_upgrade_from(ProtocolVersion::from_parts(3, 0, 0)));
+    }
+
     #[test]
     fn should_serialize_to_json_properly() {
         let protocol_version = ProtocolVersion::from_parts(1, 1, 1);
@@ -6,7 +6,10 @@ use alloc::{
     string::{String, ToString},
     vec::Vec,
 };
-use core::{convert::TryFrom, fmt::Debug};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+};
 
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
@@ -80,6 +83,41 @@ pub enum StoredValueTag {
     RawBytes = 20,
 }
 
+impl StoredValueTag {
+    /// Returns the name of the tag, suitable for logs and metrics labels.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            StoredValueTag::CLValue => "CLValue",
+            StoredValueTag::Account => "Account",
+            StoredValueTag::ContractWasm => "ContractWasm",
+            StoredValueTag::Contract => "Contract",
+            StoredValueTag::ContractPackage => "ContractPackage",
+            StoredValueTag::Transfer => "Transfer",
+            StoredValueTag::DeployInfo => "DeployInfo",
+            StoredValueTag::EraInfo => "EraInfo",
+            StoredValueTag::Bid => "Bid",
+            StoredValueTag::Withdraw => "Withdraw",
+            StoredValueTag::Unbonding => "Unbonding",
+            StoredValueTag::BidKind => "BidKind",
+            StoredValueTag::Package => "Package",
+            StoredValueTag::AddressableEntity => "AddressableEntity",
+            StoredValueTag::ByteCode => "ByteCode",
+            StoredValueTag::MessageTopic => "MessageTopic",
+            StoredValueTag::Message => "Message",
+            StoredValueTag::NamedKey => "NamedKey",
+            StoredValueTag::Prepayment => "Prepayment",
+            StoredValueTag::EntryPoint => "EntryPoint",
+            StoredValueTag::RawBytes => "RawBytes",
+        }
+    }
+}
+
+impl Display for StoredValueTag {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
 /// A value stored in Global State.
 #[allow(clippy::large_enum_variant)]
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -1118,7 +1156,12 @@ impl<'de> Deserialize<'de> for StoredValue {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bytesrepr, gens, StoredValue};
+    use alloc::collections::BTreeSet;
+
+    use crate::{
+        bytesrepr::{self, ToBytes},
+        gens, CLValue, Package, StoredValue, StoredValueTag,
+    };
     use proptest::proptest;
     use serde_json::Value;
 
@@ -1279,6 +1322,53 @@ mod tests {
             .contains("duplicate contract version: ContractVersionKey(1, 1)"));
     }
 
+    #[test]
+    fn tag_names_should_be_unique() {
+        let tags = [
+            StoredValueTag::CLValue,
+            StoredValueTag::Account,
+            StoredValueTag::ContractWasm,
+            StoredValueTag::Contract,
+            StoredValueTag::ContractPackage,
+            StoredValueTag::Transfer,
+            StoredValueTag::DeployInfo,
+            StoredValueTag::EraInfo,
+            StoredValueTag::Bid,
+            StoredValueTag::Withdraw,
+            StoredValueTag::Unbonding,
+            StoredValueTag::BidKind,
+            StoredValueTag::Package,
+            StoredValueTag::AddressableEntity,
+            StoredValueTag::ByteCode,
+            StoredValueTag::MessageTopic,
+            StoredValueTag::Message,
+            StoredValueTag::NamedKey,
+            StoredValueTag::Prepayment,
+            StoredValueTag::EntryPoint,
+            StoredValueTag::RawBytes,
+        ];
+        let names: BTreeSet<_> = tags.iter().map(StoredValueTag::name).collect();
+        assert_eq!(names.len(), tags.len());
+        assert_eq!(StoredValueTag::Package.name(), "Package");
+        assert_eq!(StoredValueTag::RawBytes.to_string(), "RawBytes");
+    }
+
+    #[test]
+    fn should_tag_each_variant() {
+        assert_eq!(
+            StoredValue::CLValue(CLValue::unit()).tag(),
+            StoredValueTag::CLValue
+        );
+        assert_eq!(
+            StoredValue::SmartContract(Package::default()).tag(),
+            StoredValueTag::Package
+        );
+        assert_eq!(
+            StoredValue::RawBytes(vec![]).tag(),
+            StoredValueTag::RawBytes
+        );
+    }
+
     #[test]
     fn json_serialization_of_raw_bytes() {
         let stored_value = StoredValue::RawBytes(vec![1, 2, 3, 4]);
@@ -1301,5 +1391,11 @@ mod tests {
         fn serialization_roundtrip(v in gens::stored_value_arb()) {
             bytesrepr::test_serialization_roundtrip(&v);
         }
+
+        #[test]
+        fn tag_should_match_serialized_tag(v in gens::stored_value_arb()) {
+            let bytes = v.to_bytes().unwrap();
+            assert_eq!(bytes[0], v.tag() as u8);
+        }
     }
 }
@@ -77,7 +77,7 @@ pub use initiator_addr::InitiatorAddr;
 pub(crate) use initiator_addr_and_secret_key::InitiatorAddrAndSecretKey;
 pub use package_identifier::PackageIdentifier;
 pub use pricing_mode::{PricingMode, PricingModeError};
-pub use runtime_args::{NamedArg, RuntimeArgs};
+pub use runtime_args::{NamedArg, RuntimeArgs, RuntimeArgsBuilder, RuntimeArgsBuilderError};
 pub use transaction_entry_point::TransactionEntryPoint;
 pub use transaction_hash::TransactionHash;
 pub use transaction_id::TransactionId;
@@ -92,7 +92,7 @@ pub use transaction_v1::{
     InvalidTransactionV1, TransactionArgs, TransactionV1, TransactionV1DecodeFromJsonError,
     TransactionV1Error, TransactionV1ExcessiveSizeError, TransactionV1Hash, TransactionV1Payload,
 };
-pub use transfer_target::TransferTarget;
+pub use transfer_target::{TransferTarget, TransferTargetError};
 
 const DEPLOY_TAG: u8 = 0;
 const V1_TAG: u8 = 1;
@@ -104,6 +104,53 @@ impl PricingMode {
         }
     }
 
+    /// Returns `true` if this is a `PaymentLimited` variant.
+    pub fn is_payment_limited(&self) -> bool {
+        matches!(self, PricingMode::PaymentLimited { .. })
+    }
+
+    /// Returns `true` if this is a `Fixed` variant.
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, PricingMode::Fixed { .. })
+    }
+
+    /// Returns `true` if this is a `Prepaid` variant.
+    pub fn is_prepaid(&self) -> bool {
+        matches!(self, PricingMode::Prepaid { .. })
+    }
+
+    /// Returns the user-specified gas price tolerance, if it is a `PaymentLimited` or `Fixed`
+    /// variant.
+    pub fn gas_price_tolerance(&self) -> Option<u8> {
+        match self {
+            PricingMode::PaymentLimited {
+                gas_price_tolerance,
+                ..
+            }
+            | PricingMode::Fixed {
+                gas_price_tolerance,
+                ..
+            } => Some(*gas_price_tolerance),
+            PricingMode::Prepaid { .. } => None,
+        }
+    }
+
+    /// Returns the user-specified payment amount, if it is a `PaymentLimited` variant.
+    pub fn payment_amount(&self) -> Option<u64> {
+        match self {
+            PricingMode::PaymentLimited { payment_amount, .. } => Some(*payment_amount),
+            PricingMode::Fixed { .. } | PricingMode::Prepaid { .. } => None,
+        }
+    }
+
+    /// Returns the pre-paid receipt, if it is a `Prepaid` variant.
+    pub fn receipt(&self) -> Option<&Digest> {
+        match self {
+            PricingMode::Prepaid { receipt } => Some(receipt),
+            PricingMode::PaymentLimited { .. } | PricingMode::Fixed { .. } => None,
+        }
+    }
+
     fn serialized_field_lengths(&self) -> Vec<usize> {
         match self {
             PricingMode::PaymentLimited {
@@ -419,6 +466,53 @@ mod tests {
         });
     }
 
+    #[test]
+    fn payment_limited_accessors() {
+        let pricing_mode = PricingMode::PaymentLimited {
+            payment_amount: 100,
+            gas_price_tolerance: 3,
+            standard_payment: false,
+        };
+        assert!(pricing_mode.is_payment_limited());
+        assert!(!pricing_mode.is_fixed());
+        assert!(!pricing_mode.is_prepaid());
+        assert_eq!(pricing_mode.gas_price_tolerance(), Some(3));
+        assert_eq!(pricing_mode.payment_amount(), Some(100));
+        assert_eq!(pricing_mode.receipt(), None);
+        assert_eq!(pricing_mode.additional_computation_factor(), 0);
+        assert!(!pricing_mode.is_standard_payment());
+    }
+
+    #[test]
+    fn fixed_accessors() {
+        let pricing_mode = PricingMode::Fixed {
+            additional_computation_factor: 2,
+            gas_price_tolerance: 5,
+        };
+        assert!(!pricing_mode.is_payment_limited());
+        assert!(pricing_mode.is_fixed());
+        assert!(!pricing_mode.is_prepaid());
+        assert_eq!(pricing_mode.gas_price_tolerance(), Some(5));
+        assert_eq!(pricing_mode.payment_amount(), None);
+        assert_eq!(pricing_mode.receipt(), None);
+        assert_eq!(pricing_mode.additional_computation_factor(), 2);
+        assert!(pricing_mode.is_standard_payment());
+    }
+
+    #[test]
+    fn prepaid_accessors() {
+        let receipt = Digest::hash(b"prepaid");
+        let pricing_mode = PricingMode::Prepaid { receipt };
+        assert!(!pricing_mode.is_payment_limited());
+        assert!(!pricing_mode.is_fixed());
+        assert!(pricing_mode.is_prepaid());
+        assert_eq!(pricing_mode.gas_price_tolerance(), None);
+        assert_eq!(pricing_mode.payment_amount(), None);
+        assert_eq!(pricing_mode.receipt(), Some(&receipt));
+        assert_eq!(pricing_mode.additional_computation_factor(), 0);
+        assert!(pricing_mode.is_standard_payment());
+    }
+
     use crate::gens::pricing_mode_arb;
     use proptest::prelude::*;
     proptest! {
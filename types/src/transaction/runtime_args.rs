@@ -1,6 +1,13 @@
 //! Home of RuntimeArgs for calling contracts
 
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
 
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
@@ -231,6 +238,108 @@ impl FromBytes for RuntimeArgs {
     }
 }
 
+/// Error returned while building [`RuntimeArgs`] with a [`RuntimeArgsBuilder`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum RuntimeArgsBuilderError {
+    /// An argument with the given name has already been added.
+    DuplicateName(String),
+    /// The argument value could not be converted into a [`CLValue`].
+    CLValue(CLValueError),
+}
+
+impl From<CLValueError> for RuntimeArgsBuilderError {
+    fn from(error: CLValueError) -> Self {
+        RuntimeArgsBuilderError::CLValue(error)
+    }
+}
+
+impl Display for RuntimeArgsBuilderError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            RuntimeArgsBuilderError::DuplicateName(name) => {
+                write!(formatter, "duplicate runtime arg name: {}", name)
+            }
+            RuntimeArgsBuilderError::CLValue(error) => {
+                write!(formatter, "invalid runtime arg value: {}", error)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for RuntimeArgsBuilderError {}
+
+/// Builder for [`RuntimeArgs`] which rejects duplicate argument names.
+///
+/// Unlike [`RuntimeArgs::insert`], which appends unconditionally, every insertion is checked
+/// against the names already present.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RuntimeArgsBuilder {
+    args: Vec<NamedArg>,
+}
+
+impl RuntimeArgsBuilder {
+    /// Returns a new, empty `RuntimeArgsBuilder`.
+    pub fn new() -> Self {
+        RuntimeArgsBuilder::default()
+    }
+
+    /// Adds a named argument.
+    ///
+    /// Returns an error if an argument with the same name has already been added, or if the
+    /// value cannot be converted into a [`CLValue`].
+    pub fn insert<K, V>(self, key: K, value: V) -> Result<Self, RuntimeArgsBuilderError>
+    where
+        K: Into<String>,
+        V: CLTyped + ToBytes,
+    {
+        let cl_value = CLValue::from_t(value)?;
+        self.insert_cl_value(key, cl_value)
+    }
+
+    /// Adds a named argument holding an already constructed [`CLValue`].
+    ///
+    /// Returns an error if an argument with the same name has already been added.
+    pub fn insert_cl_value<K>(
+        mut self,
+        key: K,
+        cl_value: CLValue,
+    ) -> Result<Self, RuntimeArgsBuilderError>
+    where
+        K: Into<String>,
+    {
+        let name = key.into();
+        if self.args.iter().any(|arg| arg.name() == name) {
+            return Err(RuntimeArgsBuilderError::DuplicateName(name));
+        }
+        self.args.push(NamedArg(name, cl_value));
+        Ok(self)
+    }
+
+    /// Returns the built [`RuntimeArgs`].
+    ///
+    /// Returns an error if any argument name appears more than once, which can only happen if
+    /// the builder was seeded with existing [`RuntimeArgs`].
+    pub fn build(self) -> Result<RuntimeArgs, RuntimeArgsBuilderError> {
+        let mut names = BTreeSet::new();
+        for arg in &self.args {
+            if !names.insert(arg.name()) {
+                return Err(RuntimeArgsBuilderError::DuplicateName(
+                    arg.name().to_string(),
+                ));
+            }
+        }
+        Ok(RuntimeArgs(self.args))
+    }
+}
+
+impl From<RuntimeArgs> for RuntimeArgsBuilder {
+    fn from(args: RuntimeArgs) -> Self {
+        RuntimeArgsBuilder { args: args.0 }
+    }
+}
+
 /// Macro that makes it easier to construct named arguments.
 ///
 /// NOTE: This macro does not propagate possible errors that could occur while creating a
@@ -385,4 +494,38 @@ mod tests {
             U512::zero()
         );
     }
+
+    #[test]
+    fn builder_should_build_runtime_args() {
+        let args = RuntimeArgsBuilder::new()
+            .insert("foo", 1u64)
+            .and_then(|builder| builder.insert("bar", "baz"))
+            .and_then(|builder| builder.build())
+            .expect("should build");
+        assert_eq!(args.len(), 2);
+        assert_eq!(args.get("foo"), Some(&CLValue::from_t(1u64).unwrap()));
+        assert_eq!(args.get("bar"), Some(&CLValue::from_t("baz").unwrap()));
+    }
+
+    #[test]
+    fn builder_should_reject_duplicate_name() {
+        let result = RuntimeArgsBuilder::new()
+            .insert("foo", 1u64)
+            .and_then(|builder| builder.insert("foo", 2u64));
+        assert_eq!(
+            result,
+            Err(RuntimeArgsBuilderError::DuplicateName("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn builder_should_reject_duplicates_in_seeded_args() {
+        let mut args = RuntimeArgs::new();
+        args.insert("foo", 1u64).unwrap();
+        args.insert("foo", 2u64).unwrap();
+        assert_eq!(
+            RuntimeArgsBuilder::from(args).build(),
+            Err(RuntimeArgsBuilderError::DuplicateName("foo".to_string()))
+        );
+    }
 }
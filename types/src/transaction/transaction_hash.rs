@@ -65,6 +65,20 @@ impl TransactionHash {
     pub const fn from_raw(raw_digest: [u8; TransactionV1Hash::LENGTH]) -> Self {
         TransactionHash::V1(TransactionV1Hash::from_raw(raw_digest))
     }
+
+    /// Returns a new `TransactionHash::Deploy` directly initialized with the provided bytes; no
+    /// hashing is done.
+    pub const fn deploy_from_raw(raw_digest: [u8; DeployHash::LENGTH]) -> Self {
+        TransactionHash::Deploy(DeployHash::new(Digest::from_raw(raw_digest)))
+    }
+
+    /// Returns the tag identifying the variant of the hash in its `bytesrepr` encoding.
+    pub const fn tag(&self) -> u8 {
+        match self {
+            TransactionHash::Deploy(_) => DEPLOY_TAG,
+            TransactionHash::V1(_) => V1_TAG,
+        }
+    }
 }
 
 impl From<DeployHash> for TransactionHash {
@@ -175,4 +189,28 @@ mod tests {
         let hash = TransactionHash::from(TransactionV1Hash::random(rng));
         bytesrepr::test_serialization_roundtrip(&hash);
     }
+
+    #[test]
+    fn should_construct_v1_hash_from_raw_bytes() {
+        let hash = TransactionHash::from_raw([7; 32]);
+        assert_eq!(
+            hash,
+            TransactionHash::V1(TransactionV1Hash::new(Digest::from_raw([7; 32])))
+        );
+        assert_eq!(hash.tag(), V1_TAG);
+        assert_eq!(hash.to_bytes().unwrap()[0], V1_TAG);
+        bytesrepr::test_serialization_roundtrip(&hash);
+    }
+
+    #[test]
+    fn should_construct_deploy_hash_from_raw_bytes() {
+        let hash = TransactionHash::deploy_from_raw([7; 32]);
+        assert_eq!(
+            hash,
+            TransactionHash::Deploy(DeployHash::new(Digest::from_raw([7; 32])))
+        );
+        assert_eq!(hash.tag(), DEPLOY_TAG);
+        assert_eq!(hash.to_bytes().unwrap()[0], DEPLOY_TAG);
+        bytesrepr::test_serialization_roundtrip(&hash);
+    }
 }
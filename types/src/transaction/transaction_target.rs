@@ -35,7 +35,9 @@ const SEED_VALUE_INDEX: u16 = 2;
 )]
 #[serde(deny_unknown_fields)]
 pub enum TransactionRuntimeParams {
+    /// The target is executed by the Casper VM 1.0 runtime.
     VmCasperV1,
+    /// The target is executed by the Casper VM 2.0 runtime.
     VmCasperV2 {
         /// The amount of motes to transfer before code is executed.
         ///
@@ -446,4 +448,73 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&val);
         }
     }
+
+    /// Returns the name and the sorted field names of each variant in a `oneOf` enum schema.
+    #[cfg(feature = "json-schema")]
+    fn variant_fields(one_of: &serde_json::Value) -> Vec<(String, Vec<String>)> {
+        one_of
+            .as_array()
+            .expect("should have oneOf")
+            .iter()
+            .map(|variant| {
+                if let Some(names) = variant["enum"].as_array() {
+                    return (names[0].as_str().unwrap().to_string(), vec![]);
+                }
+                let (name, body) = variant["properties"]
+                    .as_object()
+                    .and_then(|properties| properties.iter().next())
+                    .expect("should have a single property");
+                let mut fields: Vec<String> = body["properties"]
+                    .as_object()
+                    .map(|fields| fields.keys().cloned().collect())
+                    .unwrap_or_default();
+                fields.sort();
+                (name.clone(), fields)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "json-schema")]
+    #[test]
+    fn json_schema_should_enumerate_each_target_variant() {
+        let schema = serde_json::to_value(schemars::schema_for!(super::TransactionTarget)).unwrap();
+        let fields = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+        assert_eq!(
+            variant_fields(&schema["oneOf"]),
+            vec![
+                ("Native".to_string(), vec![]),
+                ("Stored".to_string(), fields(&["id", "runtime"])),
+                (
+                    "Session".to_string(),
+                    fields(&["is_install_upgrade", "module_bytes", "runtime"])
+                ),
+            ]
+        );
+        assert_eq!(
+            variant_fields(&schema["definitions"]["TransactionInvocationTarget"]["oneOf"]),
+            vec![
+                ("ByHash".to_string(), vec![]),
+                ("ByName".to_string(), vec![]),
+                (
+                    "ByPackageHash".to_string(),
+                    fields(&["addr", "version", "version_key"])
+                ),
+                (
+                    "ByPackageName".to_string(),
+                    fields(&["name", "version", "version_key"])
+                ),
+            ]
+        );
+        assert_eq!(
+            variant_fields(&schema["definitions"]["TransactionRuntimeParams"]["oneOf"]),
+            vec![
+                ("VmCasperV1".to_string(), vec![]),
+                (
+                    "VmCasperV2".to_string(),
+                    fields(&["seed", "transferred_value"])
+                ),
+            ]
+        );
+    }
 }
@@ -1,3 +1,7 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
 #[cfg(any(feature = "testing", test))]
 use rand::Rng;
 
@@ -6,6 +10,13 @@ use crate::testing::TestRng;
 use crate::{account::AccountHash, PublicKey, URef};
 
 /// The various types which can be used as the `target` runtime argument of a native transfer.
+///
+/// When a transfer is executed the target is resolved to a purse as follows:
+///
+/// * [`TransferTarget::PublicKey`] and [`TransferTarget::AccountHash`] resolve to the main purse of
+///   the matching account, which is created if it does not exist yet.
+/// * [`TransferTarget::URef`] is used directly as the target purse, and therefore must carry
+///   [`AccessRights::ADD`](crate::AccessRights::ADD).
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 pub enum TransferTarget {
     /// A public key.
@@ -17,6 +28,42 @@ pub enum TransferTarget {
 }
 
 impl TransferTarget {
+    /// Returns a new `TransferTarget` pointing at the account identified by `public_key`.
+    pub fn from_public_key(public_key: PublicKey) -> Self {
+        TransferTarget::PublicKey(public_key)
+    }
+
+    /// Returns a new `TransferTarget` pointing at the account identified by `account_hash`.
+    pub fn from_account_hash(account_hash: AccountHash) -> Self {
+        TransferTarget::AccountHash(account_hash)
+    }
+
+    /// Returns a new `TransferTarget` pointing at the purse `uref`.
+    ///
+    /// Returns an error if `uref` does not carry [`AccessRights::ADD`](crate::AccessRights::ADD).
+    pub fn from_uref(uref: URef) -> Result<Self, TransferTargetError> {
+        let target = TransferTarget::URef(uref);
+        target.validate()?;
+        Ok(target)
+    }
+
+    /// Checks that this target can receive a transfer.
+    ///
+    /// Only [`TransferTarget::URef`] can fail validation, when it lacks
+    /// [`AccessRights::ADD`](crate::AccessRights::ADD).
+    pub fn validate(&self) -> Result<(), TransferTargetError> {
+        match self {
+            TransferTarget::PublicKey(_) | TransferTarget::AccountHash(_) => Ok(()),
+            TransferTarget::URef(uref) => {
+                if uref.is_addable() {
+                    Ok(())
+                } else {
+                    Err(TransferTargetError::MissingAddAccess(*uref))
+                }
+            }
+        }
+    }
+
     /// Returns a random `TransferTarget`.
     #[cfg(any(feature = "testing", test))]
     pub fn random(rng: &mut TestRng) -> Self {
@@ -46,3 +93,84 @@ impl From<URef> for TransferTarget {
         Self::URef(uref)
     }
 }
+
+/// Error returned when a [`TransferTarget`] is not a valid target of a native transfer.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum TransferTargetError {
+    /// The target purse does not carry [`AccessRights::ADD`](crate::AccessRights::ADD).
+    MissingAddAccess(URef),
+}
+
+impl Display for TransferTargetError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            TransferTargetError::MissingAddAccess(uref) => write!(
+                formatter,
+                "transfer target {} does not have add access rights",
+                uref.to_formatted_string()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for TransferTargetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccessRights, SecretKey};
+
+    #[test]
+    fn should_construct_from_public_key() {
+        let secret_key = SecretKey::ed25519_from_bytes([1; 32]).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let target = TransferTarget::from_public_key(public_key.clone());
+        assert_eq!(target, TransferTarget::PublicKey(public_key));
+        assert!(target.validate().is_ok());
+    }
+
+    #[test]
+    fn should_construct_from_account_hash() {
+        let account_hash = AccountHash::new([2; 32]);
+        let target = TransferTarget::from_account_hash(account_hash);
+        assert_eq!(target, TransferTarget::AccountHash(account_hash));
+        assert!(target.validate().is_ok());
+    }
+
+    #[test]
+    fn should_construct_from_addable_uref() {
+        let uref = URef::new([3; 32], AccessRights::ADD);
+        assert_eq!(
+            TransferTarget::from_uref(uref),
+            Ok(TransferTarget::URef(uref))
+        );
+
+        let uref = URef::new([3; 32], AccessRights::READ_ADD_WRITE);
+        assert_eq!(
+            TransferTarget::from_uref(uref),
+            Ok(TransferTarget::URef(uref))
+        );
+    }
+
+    #[test]
+    fn should_reject_uref_without_add_access() {
+        for access_rights in [
+            AccessRights::NONE,
+            AccessRights::READ,
+            AccessRights::WRITE,
+            AccessRights::READ_WRITE,
+        ] {
+            let uref = URef::new([4; 32], access_rights);
+            assert_eq!(
+                TransferTarget::from_uref(uref),
+                Err(TransferTargetError::MissingAddAccess(uref))
+            );
+            assert_eq!(
+                TransferTarget::URef(uref).validate(),
+                Err(TransferTargetError::MissingAddAccess(uref))
+            );
+        }
+    }
+}
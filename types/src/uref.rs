@@ -101,6 +101,21 @@ impl URef {
         URef(address, access_rights)
     }
 
+    /// Constructs a [`URef`] from an address with [`AccessRights::READ`] permission.
+    pub const fn new_readable(address: URefAddr) -> Self {
+        URef(address, AccessRights::READ)
+    }
+
+    /// Constructs a [`URef`] from an address with [`AccessRights::WRITE`] permission.
+    pub const fn new_writeable(address: URefAddr) -> Self {
+        URef(address, AccessRights::WRITE)
+    }
+
+    /// Constructs a [`URef`] from an address with [`AccessRights::READ_ADD_WRITE`] permission.
+    pub const fn new_read_add_write(address: URefAddr) -> Self {
+        URef(address, AccessRights::READ_ADD_WRITE)
+    }
+
     /// Returns the address of this [`URef`].
     pub fn addr(&self) -> URefAddr {
         self.0
@@ -423,4 +438,24 @@ mod tests {
         uref.disable_access_rights(AccessRights::NONE);
         assert_eq!(uref.access_rights(), AccessRights::NONE);
     }
+
+    #[test]
+    fn should_construct_with_access_rights() {
+        let address = [7; 32];
+
+        let readable = URef::new_readable(address);
+        assert_eq!(readable, URef::new(address, AccessRights::READ));
+        assert_eq!(readable.access_rights().bits(), 0b001);
+
+        let writeable = URef::new_writeable(address);
+        assert_eq!(writeable, URef::new(address, AccessRights::WRITE));
+        assert_eq!(writeable.access_rights().bits(), 0b010);
+
+        let read_add_write = URef::new_read_add_write(address);
+        assert_eq!(
+            read_add_write,
+            URef::new(address, AccessRights::READ_ADD_WRITE)
+        );
+        assert_eq!(read_add_write.access_rights().bits(), 0b111);
+    }
 }